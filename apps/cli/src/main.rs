@@ -173,6 +173,9 @@ impl Export {
             fps: 60,
             resolution_base: XY::new(1920, 1080),
             compression: cap_export::mp4::ExportCompression::Minimal,
+            output_resolution: None,
+            quality: None,
+            encoder: Default::default(),
         }
         .export(exporter_base, move |f| {
             // print!("\rrendered frame {f}");
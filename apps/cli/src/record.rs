@@ -70,9 +70,12 @@ impl RecordStart {
             cap_recording::RecordingBaseInputs {
                 capture_target: target_info,
                 capture_system_audio: self.system_audio,
+                app_audio: None,
                 mic_feed: &None,
+                start_time: None,
             },
             camera.map(|c| Arc::new(Mutex::new(c))),
+            vec![],
             false,
         )
         .await
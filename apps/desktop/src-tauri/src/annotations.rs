@@ -0,0 +1,378 @@
+use std::path::PathBuf;
+
+use cap_project::{RecordingMeta, XY};
+use glyphon::{Attrs, Buffer, Color, FontSystem, Metrics, Shaping, SwashCache};
+use image::{Rgba, RgbaImage};
+use serde::Deserialize;
+use specta::Type;
+use tauri::AppHandle;
+use tauri_specta::Event;
+
+use crate::{
+    screenshots::{screenshots_path, set_screenshot_image},
+    NewScreenshotAdded,
+};
+
+// Highlights are drawn see-through so the content underneath stays readable
+const HIGHLIGHT_OPACITY: f32 = 0.4;
+
+/// Markup drawn onto a screenshot. Positions and sizes are in image pixels,
+/// and colors are `#RRGGBB` or `#RRGGBBAA` hex strings.
+#[derive(Deserialize, Type, Debug, Clone)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum Annotation {
+    #[serde(rename_all = "camelCase")]
+    Rectangle {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        color: String,
+        stroke_width: f32,
+    },
+    #[serde(rename_all = "camelCase")]
+    Arrow {
+        from: XY<f32>,
+        to: XY<f32>,
+        color: String,
+        stroke_width: f32,
+    },
+    Highlight {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        color: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Text {
+        x: f32,
+        y: f32,
+        text: String,
+        color: String,
+        font_size: f32,
+    },
+}
+
+/// Draw `annotations` onto the screenshot at `path` and save the result as a PNG.
+/// The annotated copy is added as a new screenshot unless `overwrite` is set,
+/// in which case it replaces the original. Returns the annotated image's path.
+#[tauri::command]
+#[specta::specta]
+pub async fn annotate_screenshot(
+    app: AppHandle,
+    path: PathBuf,
+    annotations: Vec<Annotation>,
+    overwrite: bool,
+) -> Result<PathBuf, String> {
+    let Some(screenshot_dir) = path.parent().map(PathBuf::from) else {
+        return Err("Invalid screenshot path".to_string());
+    };
+    let mut meta = RecordingMeta::load_for_project(&screenshot_dir)
+        .map_err(|e| format!("Failed to load screenshot metadata: {e}"))?;
+
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let output = if overwrite {
+        path.with_extension("png")
+    } else {
+        let output_dir = screenshots_path(&app).join(format!("{}.cap", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&output_dir).map_err(|e| e.to_string())?;
+        output_dir.join(format!("{stem} (annotated).png"))
+    };
+
+    tokio::task::spawn_blocking({
+        let path = path.clone();
+        let output = output.clone();
+        move || -> Result<(), String> {
+            let mut image = image::open(&path)
+                .map_err(|e| format!("Failed to open screenshot: {e}"))?
+                .to_rgba8();
+
+            let mut text_renderer = None;
+            for annotation in &annotations {
+                draw_annotation(&mut image, annotation, &mut text_renderer)?;
+            }
+
+            image
+                .save_with_format(&output, image::ImageFormat::Png)
+                .map_err(|e| format!("Failed to save annotated screenshot: {e}"))
+        }
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??;
+
+    // Overwriting a JPEG or WebP leaves the PNG in its place
+    if overwrite && output != path {
+        std::fs::remove_file(&path).map_err(|e| e.to_string())?;
+    }
+
+    set_screenshot_image(&mut meta, &output);
+    meta.save_for_project()
+        .map_err(|e| format!("Failed to save screenshot metadata: {e:?}"))?;
+
+    if !overwrite {
+        NewScreenshotAdded {
+            path: output.clone(),
+        }
+        .emit(&app)
+        .ok();
+    }
+
+    Ok(output)
+}
+
+/// Fonts are only loaded once the first text annotation needs them
+struct TextRenderer {
+    font_system: FontSystem,
+    swash_cache: SwashCache,
+}
+
+fn draw_annotation(
+    image: &mut RgbaImage,
+    annotation: &Annotation,
+    text_renderer: &mut Option<TextRenderer>,
+) -> Result<(), String> {
+    match annotation {
+        Annotation::Rectangle {
+            x,
+            y,
+            width,
+            height,
+            color,
+            stroke_width,
+        } => {
+            let color = parse_hex_color(color)?;
+            let (right, bottom) = (x + width, y + height);
+            let stroke = stroke_width.max(1.0);
+
+            fill_rect(image, *x, *y, right, y + stroke, color);
+            fill_rect(image, *x, bottom - stroke, right, bottom, color);
+            fill_rect(image, *x, y + stroke, x + stroke, bottom - stroke, color);
+            fill_rect(
+                image,
+                right - stroke,
+                y + stroke,
+                right,
+                bottom - stroke,
+                color,
+            );
+        }
+        Annotation::Arrow {
+            from,
+            to,
+            color,
+            stroke_width,
+        } => {
+            let color = parse_hex_color(color)?;
+            let stroke = stroke_width.max(1.0);
+
+            let (dx, dy) = (to.x - from.x, to.y - from.y);
+            let length = (dx * dx + dy * dy).sqrt();
+            if length == 0.0 {
+                return Ok(());
+            }
+
+            let head_length = (stroke * 4.0).max(10.0).min(length);
+            let angle = dy.atan2(dx);
+            let head = |offset: f32| {
+                XY::new(
+                    to.x - head_length * (angle + offset).cos(),
+                    to.y - head_length * (angle + offset).sin(),
+                )
+            };
+
+            let segments = [
+                (*from, *to),
+                (head(std::f32::consts::FRAC_PI_6), *to),
+                (head(-std::f32::consts::FRAC_PI_6), *to),
+            ];
+            draw_lines(image, &segments, stroke, color);
+        }
+        Annotation::Highlight {
+            x,
+            y,
+            width,
+            height,
+            color,
+        } => {
+            let mut color = parse_hex_color(color)?;
+            color.0[3] = (color.0[3] as f32 * HIGHLIGHT_OPACITY) as u8;
+
+            fill_rect(image, *x, *y, x + width, y + height, color);
+        }
+        Annotation::Text {
+            x,
+            y,
+            text,
+            color,
+            font_size,
+        } => {
+            let color = parse_hex_color(color)?;
+            let TextRenderer {
+                font_system,
+                swash_cache,
+            } = text_renderer.get_or_insert_with(|| TextRenderer {
+                font_system: FontSystem::new(),
+                swash_cache: SwashCache::new(),
+            });
+
+            let font_size = font_size.max(1.0);
+            let mut buffer = Buffer::new(font_system, Metrics::new(font_size, font_size * 1.2));
+            buffer.set_size(font_system, Some(image.width() as f32 - x), None);
+            buffer.set_text(font_system, text, &Attrs::new(), Shaping::Advanced);
+            buffer.shape_until_scroll(font_system, false);
+
+            let [r, g, b, a] = color.0;
+            buffer.draw(
+                font_system,
+                swash_cache,
+                Color::rgba(r, g, b, a),
+                |glyph_x, glyph_y, width, height, color| {
+                    let left = x + glyph_x as f32;
+                    let top = y + glyph_y as f32;
+                    fill_rect(
+                        image,
+                        left,
+                        top,
+                        left + width as f32,
+                        top + height as f32,
+                        Rgba([color.r(), color.g(), color.b(), color.a()]),
+                    );
+                },
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_hex_color(color: &str) -> Result<Rgba<u8>, String> {
+    let hex = color.trim_start_matches('#');
+    let component = |i: usize| {
+        hex.get(i..i + 2)
+            .and_then(|c| u8::from_str_radix(c, 16).ok())
+            .ok_or_else(|| format!("Invalid color: {color}"))
+    };
+
+    match hex.len() {
+        6 => Ok(Rgba([component(0)?, component(2)?, component(4)?, 255])),
+        8 => Ok(Rgba([
+            component(0)?,
+            component(2)?,
+            component(4)?,
+            component(6)?,
+        ])),
+        _ => Err(format!("Invalid color: {color}")),
+    }
+}
+
+/// Alpha blend `color` over the pixels between the corners, clamped to the image
+fn fill_rect(image: &mut RgbaImage, left: f32, top: f32, right: f32, bottom: f32, color: Rgba<u8>) {
+    let (width, height) = (image.width() as f32, image.height() as f32);
+    let left = left.round().clamp(0.0, width) as u32;
+    let right = right.round().clamp(0.0, width) as u32;
+    let top = top.round().clamp(0.0, height) as u32;
+    let bottom = bottom.round().clamp(0.0, height) as u32;
+
+    for y in top..bottom {
+        for x in left..right {
+            blend_pixel(image.get_pixel_mut(x, y), color);
+        }
+    }
+}
+
+/// Draw line segments `stroke` pixels thick, blending each pixel only once
+/// so translucent colors don't darken where the segments meet
+fn draw_lines(
+    image: &mut RgbaImage,
+    segments: &[(XY<f32>, XY<f32>)],
+    stroke: f32,
+    color: Rgba<u8>,
+) {
+    let radius = stroke / 2.0;
+    let (min_x, min_y, max_x, max_y) = segments.iter().flat_map(|(a, b)| [a, b]).fold(
+        (f32::MAX, f32::MAX, f32::MIN, f32::MIN),
+        |(min_x, min_y, max_x, max_y), point| {
+            (
+                min_x.min(point.x),
+                min_y.min(point.y),
+                max_x.max(point.x),
+                max_y.max(point.y),
+            )
+        },
+    );
+
+    let clamp = |value: f32, max: u32| value.clamp(0.0, max as f32) as u32;
+    for y in clamp(min_y - radius, image.height())..clamp(max_y + radius + 1.0, image.height()) {
+        for x in clamp(min_x - radius, image.width())..clamp(max_x + radius + 1.0, image.width()) {
+            let point = XY::new(x as f32 + 0.5, y as f32 + 0.5);
+            if segments
+                .iter()
+                .any(|(a, b)| distance_to_segment(point, *a, *b) <= radius)
+            {
+                blend_pixel(image.get_pixel_mut(x, y), color);
+            }
+        }
+    }
+}
+
+fn distance_to_segment(point: XY<f32>, a: XY<f32>, b: XY<f32>) -> f32 {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let length_squared = dx * dx + dy * dy;
+    let t = if length_squared == 0.0 {
+        0.0
+    } else {
+        (((point.x - a.x) * dx + (point.y - a.y) * dy) / length_squared).clamp(0.0, 1.0)
+    };
+
+    let (x, y) = (a.x + t * dx - point.x, a.y + t * dy - point.y);
+    (x * x + y * y).sqrt()
+}
+
+fn blend_pixel(pixel: &mut Rgba<u8>, color: Rgba<u8>) {
+    let alpha = color.0[3] as f32 / 255.0;
+    for i in 0..3 {
+        pixel.0[i] = (color.0[i] as f32 * alpha + pixel.0[i] as f32 * (1.0 - alpha)).round() as u8;
+    }
+    pixel.0[3] = pixel.0[3].max(color.0[3]);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_hex_colors() {
+        assert_eq!(
+            parse_hex_color("#ff8000").unwrap(),
+            Rgba([255, 128, 0, 255])
+        );
+        assert_eq!(parse_hex_color("00000080").unwrap(), Rgba([0, 0, 0, 128]));
+        assert!(parse_hex_color("#fff").is_err());
+        assert!(parse_hex_color("#gg0000").is_err());
+    }
+
+    #[test]
+    fn draws_rectangle_outline() {
+        let mut image = RgbaImage::from_pixel(10, 10, Rgba([0, 0, 0, 255]));
+        let rectangle = Annotation::Rectangle {
+            x: 2.0,
+            y: 2.0,
+            width: 6.0,
+            height: 6.0,
+            color: "#ffffff".to_string(),
+            stroke_width: 1.0,
+        };
+
+        draw_annotation(&mut image, &rectangle, &mut None).unwrap();
+
+        assert_eq!(image.get_pixel(2, 2), &Rgba([255, 255, 255, 255]));
+        assert_eq!(image.get_pixel(7, 5), &Rgba([255, 255, 255, 255]));
+        assert_eq!(image.get_pixel(5, 5), &Rgba([0, 0, 0, 255]));
+        assert_eq!(image.get_pixel(1, 1), &Rgba([0, 0, 0, 255]));
+    }
+}
@@ -38,6 +38,9 @@ impl AppSounds {
     }
 }
 
+/// Silence is shown at -60dBFS instead of -∞ for practical use
+const SILENCE_DBFS: f32 = -60.0;
+
 pub fn get_waveform(audio: &AudioData) -> Vec<f32> {
     const CHUNK_SIZE: usize = (cap_audio::AudioData::SAMPLE_RATE as usize) / 10; // ~100ms
 
@@ -60,9 +63,10 @@ pub fn get_waveform(audio: &AudioData) -> Vec<f32> {
     // Convert to absolute dBFS (0 dBFS = digital full scale)
     for v in waveform.iter_mut() {
         *v = if *v > 0.0 {
-            20.0 * v.log10() // Absolute dBFS relative to 1.0
+            // Quiet room tone can't read lower than silence, eg. where push-to-talk gated the mic
+            (20.0 * v.log10()).max(SILENCE_DBFS) // Absolute dBFS relative to 1.0
         } else {
-            -60.0 // Set silence to -60dBFS instead of -∞ for practical use
+            SILENCE_DBFS
         };
     }
 
@@ -1,19 +1,51 @@
-use cap_media::{feeds::RawCameraFrame, frame_ws::WSFrame};
-use flume::Sender;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, SystemTime, SystemTimeError},
+};
 
-pub async fn create_camera_preview_ws() -> (
+use cap_media::{
+    feeds::{mirror_frame, RawCameraFrame},
+    frame_ws::{FrameTimings, WSFrame},
+};
+use flume::{Receiver, Sender};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::AppHandle;
+
+use crate::{
+    general_settings::{CameraMirrorSettings, GeneralSettingsStore},
+    App, MutableState,
+};
+
+/// How long `measure_camera_latency` samples preview frames for
+const LATENCY_SAMPLE_DURATION: Duration = Duration::from_secs(3);
+
+/// Preview frames that have been sent, with when sending finished
+pub type CameraLatencySamples = Receiver<(FrameTimings, SystemTime)>;
+
+/// `mirrored` flips the preview horizontally, and can be changed while it's running
+pub async fn create_camera_preview_ws(
+    mirrored: Arc<AtomicBool>,
+) -> (
     Sender<RawCameraFrame>,
     u16,
     tauri::async_runtime::Sender<()>,
+    CameraLatencySamples,
 ) {
     let (camera_tx, mut _camera_rx) = flume::bounded::<RawCameraFrame>(4);
     let (_camera_tx, camera_rx) = flume::bounded::<WSFrame>(4);
+    // Only drained while measuring, otherwise it fills up and new samples are dropped
+    let (latency_tx, latency_rx) = flume::bounded(256);
     std::thread::spawn(move || {
         use ffmpeg::format::Pixel;
 
         let mut converter: Option<(Pixel, ffmpeg::software::scaling::Context)> = None;
 
         while let Ok(raw_frame) = _camera_rx.recv() {
+            let received_at = SystemTime::now();
             let mut frame = raw_frame.frame;
 
             if frame.format() != Pixel::RGBA || frame.width() > 1280 || frame.height() > 720 {
@@ -56,18 +88,181 @@ pub async fn create_camera_preview_ws() -> (
                 frame = new_frame;
             }
 
+            // Flipped after scaling, as there are fewer pixels to move
+            if mirrored.load(Ordering::Relaxed) {
+                mirror_frame(&mut frame);
+            }
+
             _camera_tx
                 .send(WSFrame {
                     data: frame.data(0).to_vec(),
                     width: frame.width(),
                     height: frame.height(),
                     stride: frame.stride(0) as u32,
+                    timings: Some(FrameTimings {
+                        captured_at: raw_frame.captured_at,
+                        received_at,
+                        converted_at: SystemTime::now(),
+                    }),
                 })
                 .ok();
         }
     });
     // _shutdown needs to be kept alive to keep the camera ws running
-    let (camera_ws_port, _shutdown) = cap_media::frame_ws::create_frame_ws(camera_rx.clone()).await;
+    let (camera_ws_port, _shutdown) =
+        cap_media::frame_ws::create_frame_ws(camera_rx.clone(), Some(latency_tx)).await;
+
+    (camera_tx, camera_ws_port, _shutdown, latency_rx)
+}
+
+pub(crate) fn camera_mirror(app: &AppHandle) -> CameraMirrorSettings {
+    GeneralSettingsStore::get(app)
+        .ok()
+        .flatten()
+        .map(|settings| settings.camera_mirror)
+        .unwrap_or_default()
+}
+
+#[derive(Deserialize, Type, Debug, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum CameraMirrorTarget {
+    Preview,
+    Recording,
+}
+
+/// Flip the camera horizontally in the preview or in recordings, which are set separately.
+/// By default the preview is mirrored, as that's what people expect of self-view, and recordings aren't.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_camera_mirror(
+    state: MutableState<'_, App>,
+    enabled: bool,
+    target: CameraMirrorTarget,
+) -> Result<(), String> {
+    let app = state.read().await;
+
+    match target {
+        CameraMirrorTarget::Preview => app
+            .camera_preview_mirrored
+            .store(enabled, Ordering::Relaxed),
+        CameraMirrorTarget::Recording => {
+            for feed in app
+                .camera_feed
+                .iter()
+                .chain(app.additional_camera_feeds.values())
+            {
+                feed.lock().await.set_mirrored(enabled);
+            }
+        }
+    }
+
+    GeneralSettingsStore::update(&app.handle, |settings| match target {
+        CameraMirrorTarget::Preview => settings.camera_mirror.preview = enabled,
+        CameraMirrorTarget::Recording => settings.camera_mirror.recording = enabled,
+    })
+}
 
-    (camera_tx, camera_ws_port, _shutdown)
+/// Average delays of camera preview frames, in milliseconds
+#[derive(Serialize, Type, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyReport {
+    /// From capture until the frame was sent to the preview window.
+    /// The window's own drawing isn't included.
+    pub capture_to_display_ms: f64,
+    /// From capture until the preview picked the frame up, ie. the camera and its feed
+    pub capture_ms: f64,
+    /// Converting and scaling the frame to RGBA
+    pub conversion_ms: f64,
+    /// Waiting for, encoding as the preview asked and sending over its websocket
+    pub transport_ms: f64,
+    pub fps: f64,
+}
+
+impl LatencyReport {
+    fn from_samples(samples: &[(FrameTimings, SystemTime)], duration: Duration) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let average_ms =
+            |delay: fn(&(FrameTimings, SystemTime)) -> Result<Duration, SystemTimeError>| {
+                samples
+                    .iter()
+                    .map(|sample| delay(sample).unwrap_or_default().as_secs_f64() * 1000.0)
+                    .sum::<f64>()
+                    / samples.len() as f64
+            };
+
+        Some(Self {
+            capture_to_display_ms: average_ms(|(t, sent_at)| sent_at.duration_since(t.captured_at)),
+            capture_ms: average_ms(|(t, _)| t.received_at.duration_since(t.captured_at)),
+            conversion_ms: average_ms(|(t, _)| t.converted_at.duration_since(t.received_at)),
+            transport_ms: average_ms(|(t, sent_at)| sent_at.duration_since(t.converted_at)),
+            fps: samples.len() as f64 / duration.as_secs_f64(),
+        })
+    }
+}
+
+/// Sample the camera preview for a few seconds and report how far behind capture it is.
+/// The preview window needs to be open, as frames are only sent while it's connected.
+#[tauri::command]
+#[specta::specta]
+pub async fn measure_camera_latency(state: MutableState<'_, App>) -> Result<LatencyReport, String> {
+    let latency_rx = {
+        let app = state.read().await;
+        if app.camera_feed.is_none() {
+            return Err("No camera is active".to_string());
+        }
+        app.camera_latency_rx.clone()
+    };
+
+    // Anything already queued was sent before measuring started
+    latency_rx.drain();
+
+    let deadline = tokio::time::Instant::now() + LATENCY_SAMPLE_DURATION;
+    let mut samples = vec![];
+    while let Ok(Ok(sample)) = tokio::time::timeout_at(deadline, latency_rx.recv_async()).await {
+        samples.push(sample);
+    }
+
+    LatencyReport::from_samples(&samples, LATENCY_SAMPLE_DURATION)
+        .ok_or_else(|| "No frames reached the camera preview".to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn latency_report_averages_stages() {
+        let at = |ms| SystemTime::UNIX_EPOCH + Duration::from_millis(ms);
+        let sample = |captured, received, converted, sent| {
+            (
+                FrameTimings {
+                    captured_at: at(captured),
+                    received_at: at(received),
+                    converted_at: at(converted),
+                },
+                at(sent),
+            )
+        };
+
+        let report = LatencyReport::from_samples(
+            &[sample(0, 10, 15, 20), sample(100, 120, 125, 140)],
+            Duration::from_secs(1),
+        )
+        .unwrap();
+
+        assert_eq!(
+            report,
+            LatencyReport {
+                capture_to_display_ms: 30.0,
+                capture_ms: 15.0,
+                conversion_ms: 5.0,
+                transport_ms: 10.0,
+                fps: 2.0,
+            }
+        );
+        assert!(LatencyReport::from_samples(&[], Duration::from_secs(1)).is_none());
+    }
 }
@@ -10,18 +10,22 @@ use ffmpeg::{
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::Read;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Manager, Window};
+use tauri_specta::Event;
 use tempfile::tempdir;
 use tokio::io::AsyncWriteExt;
 use tokio::sync::Mutex;
-use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+use whisper_rs::{
+    FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters, WhisperState,
+};
 
 // Re-export caption types from cap_project
-pub use cap_project::{CaptionSegment, CaptionSettings};
+pub use cap_project::{CaptionSegment, CaptionSettings, CaptionWord};
 
 // Convert the project type's float precision from f32 to f64 for compatibility
 #[derive(Debug, Serialize, Deserialize, Type, Clone)]
@@ -62,7 +66,13 @@ pub async fn save_model_file(path: String, data: Vec<u8>) -> Result<(), String>
 }
 
 /// Extract audio from a video file and save it as a temporary WAV file
-async fn extract_audio_from_video(video_path: &str, output_path: &PathBuf) -> Result<(), String> {
+/// Extracts the audio Whisper transcribes, returning where each recording segment starts in it,
+/// in seconds. `max_secs` stops after that much audio, for when only the start is needed.
+async fn extract_audio_from_video(
+    video_path: &str,
+    output_path: &PathBuf,
+    max_secs: Option<f64>,
+) -> Result<Vec<f64>, String> {
     log::info!("Attempting to extract audio from: {}", video_path);
 
     // Check if this is a .cap directory
@@ -77,46 +87,57 @@ async fn extract_audio_from_video(video_path: &str, output_path: &PathBuf) -> Re
         let meta: serde_json::Value = serde_json::from_str(&meta_content)
             .map_err(|e| format!("Failed to parse recording metadata: {}", e))?;
 
-        // Get paths for both audio sources
+        // One audio source per recording segment, preferring the mic over system audio
         let base_path = std::path::Path::new(video_path);
         let mut audio_sources = Vec::new();
 
         if let Some(segments) = meta["segments"].as_array() {
             for segment in segments {
-                // Prioritize microphone audio over system audio
-                // Add microphone audio if available - check both "mic" and "audio" for compatibility
-                if let Some(mic) = segment["mic"]["path"].as_str() {
-                    let mic_path = base_path.join(mic);
-                    log::info!("Found microphone audio at: {:?}", mic_path);
-                    audio_sources.push(mic_path);
+                // Check both "mic" and the legacy "audio" field for compatibility
+                let source = if let Some(mic) = segment["mic"]["path"].as_str() {
+                    log::info!("Found microphone audio at: {:?}", base_path.join(mic));
+                    Some(base_path.join(mic))
                 } else if let Some(audio) = segment["audio"]["path"].as_str() {
-                    let audio_path = base_path.join(audio);
-                    log::info!("Found audio (legacy field) at: {:?}", audio_path);
-                    audio_sources.push(audio_path);
-                }
-                
-                // Only add system audio if we don't have microphone audio
-                if audio_sources.is_empty() {
-                    if let Some(system_audio) = segment["system_audio"]["path"].as_str() {
-                        let system_path = base_path.join(system_audio);
-                        log::warn!("Using system audio (no microphone found): {:?}", system_path);
-                        audio_sources.push(system_path);
-                    }
-                }
+                    log::info!("Found audio (legacy field) at: {:?}", base_path.join(audio));
+                    Some(base_path.join(audio))
+                } else if let Some(system_audio) = segment["system_audio"]["path"].as_str() {
+                    let system_path = base_path.join(system_audio);
+                    log::warn!(
+                        "Using system audio (no microphone found): {:?}",
+                        system_path
+                    );
+                    Some(system_path)
+                } else {
+                    None
+                };
+
+                audio_sources.push(source);
             }
         }
 
-        if audio_sources.is_empty() {
+        if audio_sources.iter().all(Option::is_none) {
             return Err("No audio sources found in the recording metadata".to_string());
         }
 
         log::info!("Found {} audio sources", audio_sources.len());
 
-        // Process each audio source using AudioData
+        // Segments are laid end to end, so each one's captions can be traced back to it
         let mut mixed_samples = Vec::new();
-        let mut channel_count = 0;
+        let mut segment_starts = Vec::with_capacity(audio_sources.len());
+        let channel_count = 1;
+        let max_samples = max_secs.map(|secs| (secs * AudioData::SAMPLE_RATE as f64) as usize);
 
         for source in audio_sources {
+            if max_samples.is_some_and(|max_samples| mixed_samples.len() >= max_samples) {
+                break;
+            }
+
+            segment_starts.push(mixed_samples.len() as f64 / AudioData::SAMPLE_RATE as f64);
+
+            let Some(source) = source else {
+                continue;
+            };
+
             match AudioData::from_file(&source) {
                 Ok(audio) => {
                     log::info!(
@@ -126,39 +147,9 @@ async fn extract_audio_from_video(video_path: &str, output_path: &PathBuf) -> Re
                         audio.sample_count()
                     );
 
-                    if mixed_samples.is_empty() {
-                        mixed_samples = audio.samples().to_vec();
-                        channel_count = audio.channels() as usize;
-                    } else {
-                        // Handle potential different channel counts by mixing to mono first if needed
-                        if audio.channels() as usize != channel_count {
-                            log::info!(
-                                "Channel count mismatch: {} vs {}, mixing to mono",
-                                channel_count,
-                                audio.channels()
-                            );
-
-                            // If we have mixed samples with multiple channels, convert to mono
-                            if channel_count > 1 {
-                                let mono_samples = convert_to_mono(&mixed_samples, channel_count);
-                                mixed_samples = mono_samples;
-                                channel_count = 1;
-                            }
-
-                            // Convert the new audio to mono too if it has multiple channels
-                            let samples = if audio.channels() > 1 {
-                                convert_to_mono(audio.samples(), audio.channels() as usize)
-                            } else {
-                                audio.samples().to_vec()
-                            };
-
-                            // Mix mono samples
-                            mix_samples(&mut mixed_samples, &samples);
-                        } else {
-                            // Same channel count, simple mix
-                            mix_samples(&mut mixed_samples, audio.samples());
-                        }
-                    }
+                    // Whisper needs mono audio
+                    mixed_samples
+                        .extend(convert_to_mono(audio.samples(), audio.channels() as usize));
                 }
                 Err(e) => {
                     log::warn!("Failed to process audio source {:?}: {}", source, e);
@@ -167,14 +158,8 @@ async fn extract_audio_from_video(video_path: &str, output_path: &PathBuf) -> Re
             }
         }
 
-        // No matter what, ensure we have mono audio for Whisper
-        if channel_count > 1 {
-            log::info!(
-                "Converting final mixed audio from {} channels to mono",
-                channel_count
-            );
-            mixed_samples = convert_to_mono(&mixed_samples, channel_count);
-            channel_count = 1;
+        if let Some(max_samples) = max_samples {
+            mixed_samples.truncate(max_samples);
         }
 
         if mixed_samples.is_empty() {
@@ -337,7 +322,7 @@ async fn extract_audio_from_video(video_path: &str, output_path: &PathBuf) -> Re
             .write_trailer()
             .map_err(|e| format!("Failed to write trailer: {}", e))?;
 
-        Ok(())
+        Ok(segment_starts)
     } else {
         // Handle regular video file
         let mut input = avformat::input(&video_path)
@@ -461,6 +446,7 @@ async fn extract_audio_from_video(video_path: &str, output_path: &PathBuf) -> Re
 
         // Save the stream index from the original stream (not the output stream)
         let input_stream_index = stream.index();
+        let time_base = f64::from(stream.time_base());
 
         // Process packets one at a time, cloning what we need from input packets
         let mut packet_queue = Vec::new();
@@ -470,6 +456,12 @@ async fn extract_audio_from_video(video_path: &str, output_path: &PathBuf) -> Re
             // Use a separate block to limit the immutable borrow lifetime
             for (stream_idx, packet) in input.packets() {
                 if stream_idx.index() == input_stream_index {
+                    if let (Some(max_secs), Some(pts)) = (max_secs, packet.pts()) {
+                        if pts as f64 * time_base >= max_secs {
+                            break;
+                        }
+                    }
+
                     // Clone the packet data to avoid borrowing input
                     if let Some(data) = packet.data() {
                         // Copy the packet data to a new packet
@@ -558,12 +550,13 @@ async fn extract_audio_from_video(video_path: &str, output_path: &PathBuf) -> Re
             .write_trailer()
             .map_err(|e| format!("Failed to write trailer: {}", e))?;
 
-        Ok(())
+        // A single file has just the one segment
+        Ok(vec![0.0])
     }
 }
 
 /// Load or initialize the WhisperContext
-async fn get_whisper_context(model_path: &str) -> Result<Arc<WhisperContext>, String> {
+pub(crate) async fn get_whisper_context(model_path: &str) -> Result<Arc<WhisperContext>, String> {
     // Always create a new context to avoid issues with multiple uses
     // Don't use the global WHISPER_CONTEXT as it causes ownership issues
     log::info!("Initializing Whisper context with model: {}", model_path);
@@ -574,11 +567,50 @@ async fn get_whisper_context(model_path: &str) -> Result<Arc<WhisperContext>, St
     Ok(Arc::new(ctx))
 }
 
+/// Group a segment's timestamped tokens into words. Whisper tokens that start
+/// with a space begin a new word, and special tokens like `[_BEG_]` are skipped.
+fn segment_words(state: &WhisperState, segment: i32) -> Vec<CaptionWord> {
+    let token_count = state.full_n_tokens(segment).unwrap_or(0);
+    let mut words: Vec<CaptionWord> = Vec::new();
+
+    for t in 0..token_count {
+        let (Ok(text), Ok(data)) = (
+            state.full_get_token_text(segment, t),
+            state.full_get_token_data(segment, t),
+        ) else {
+            continue;
+        };
+
+        if text.starts_with("[_") || text.starts_with("<|") {
+            continue;
+        }
+
+        let start = data.t0 as f32 / 100.0;
+        let end = data.t1 as f32 / 100.0;
+
+        match words.last_mut() {
+            Some(word) if !text.starts_with(' ') => {
+                word.text.push_str(&text);
+                word.end = end.max(word.end);
+            }
+            _ if text.trim().is_empty() => {}
+            _ => words.push(CaptionWord {
+                start,
+                end,
+                text: text.trim().to_string(),
+            }),
+        }
+    }
+
+    words
+}
+
 /// Process audio file with Whisper for transcription
 fn process_with_whisper(
     audio_path: &PathBuf,
     context: Arc<WhisperContext>,
     language: &str,
+    translate: bool,
 ) -> Result<CaptionData, String> {
     log::info!("Processing audio file: {:?}", audio_path);
 
@@ -586,7 +618,7 @@ fn process_with_whisper(
     let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
 
     // Configure parameters for better caption quality
-    params.set_translate(false);
+    params.set_translate(translate); // Output English text regardless of the spoken language
     params.set_print_special(false);
     params.set_print_progress(false);
     params.set_print_realtime(false);
@@ -680,11 +712,15 @@ fn process_with_whisper(
         );
 
         if !text.trim().is_empty() {
+            let words = segment_words(&state, i);
+
             segments.push(CaptionSegment {
                 id: format!("segment-{}", i),
                 start: start_time,
                 end: end_time,
                 text: text.trim().to_string(),
+                style_override: None,
+                words: (!words.is_empty()).then_some(words),
             });
         }
     }
@@ -697,14 +733,20 @@ fn process_with_whisper(
     })
 }
 
-/// Function to transcribe audio from a video file using Whisper
+/// Function to transcribe audio from a video file using Whisper.
+/// `language` is an ISO 639-1 hint, and the language is detected from the audio when it's `None`.
+/// With `translate` the captions are English, and `language` still records the source language.
 #[tauri::command]
 #[specta::specta]
 pub async fn transcribe_audio(
     video_path: String,
-    model_path: String,
-    language: String,
+    model: WhisperModel,
+    language: Option<String>,
+    translate: bool,
+    app: AppHandle,
 ) -> Result<CaptionData, String> {
+    let model_path = model.path(&app)?.to_string_lossy().to_string();
+
     // Check if files exist with detailed error messages
     if !std::path::Path::new(&video_path).exists() {
         return Err(format!("Video file not found at path: {}", video_path));
@@ -714,18 +756,26 @@ pub async fn transcribe_audio(
         return Err(format!("Model file not found at path: {}", model_path));
     }
 
+    // Check the model can handle the language before doing any expensive work
+    model.validate_language(language.as_deref().unwrap_or("auto"))?;
+    // English-only models can't detect the language, and there's only one they'd find
+    let language = language.or_else(|| (!model.multilingual()).then(|| "en".to_string()));
+
     // Create temp dir with better error handling
     let temp_dir = tempdir().map_err(|e| format!("Failed to create temporary directory: {}", e))?;
     let audio_path = temp_dir.path().join("audio.wav");
 
     // First try the ffmpeg implementation
-    match extract_audio_from_video(&video_path, &audio_path).await {
-        Ok(_) => log::info!("Successfully extracted audio to {:?}", audio_path),
+    let segment_starts = match extract_audio_from_video(&video_path, &audio_path, None).await {
+        Ok(segment_starts) => {
+            log::info!("Successfully extracted audio to {:?}", audio_path);
+            segment_starts
+        }
         Err(e) => {
             log::error!("Failed to extract audio: {}", e);
             return Err(format!("Failed to extract audio from video: {}", e));
         }
-    }
+    };
 
     // Verify the audio file was created
     if !audio_path.exists() {
@@ -743,13 +793,38 @@ pub async fn transcribe_audio(
         }
     };
 
+    // A hint skips detection, otherwise detect the language up front so the captions can be labelled
+    let language = match language {
+        Some(language) => Some(language),
+        None => detect_language_in_file(&context, &audio_path)?,
+    };
+
     // Process with Whisper and handle errors
-    match process_with_whisper(&audio_path, context, &language) {
-        Ok(captions) => {
+    match process_with_whisper(
+        &audio_path,
+        context,
+        language.as_deref().unwrap_or("auto"),
+        translate,
+    ) {
+        Ok(mut captions) => {
             if captions.segments.is_empty() {
                 log::warn!("No caption segments were generated");
                 return Err("No speech detected in the audio".to_string());
             }
+            if let Some(settings) = captions.settings.as_mut() {
+                settings.language = language;
+                settings.translated = translate;
+            }
+
+            // Whisper times the audio with cut sections still in, so line it up with the edits
+            if let Some(timeline) = cap_project::ProjectConfiguration::load(&video_path)
+                .ok()
+                .and_then(|config| config.timeline)
+            {
+                captions.segments =
+                    remap_captions_to_timeline(captions.segments, &timeline, &segment_starts);
+            }
+
             Ok(captions)
         }
         Err(e) => {
@@ -759,6 +834,103 @@ pub async fn transcribe_audio(
     }
 }
 
+// Seconds of audio sampled for language detection
+const LANGUAGE_DETECTION_SECS: usize = 30;
+
+/// Detect the primary spoken language of a recording from a short audio sample,
+/// returning `None` if the recording has no usable audio.
+/// English-only models can't tell languages apart, so they're rejected.
+#[tauri::command]
+#[specta::specta]
+pub async fn detect_audio_language(
+    project_path: String,
+    model: WhisperModel,
+    app: AppHandle,
+) -> Result<Option<String>, String> {
+    if !model.multilingual() {
+        return Err(format!(
+            "The '{}' model only supports English, so it can't detect languages. Download the '{}' model to detect them.",
+            model.name(),
+            model.name().trim_end_matches(".en")
+        ));
+    }
+
+    let model_path = model.path(&app)?.to_string_lossy().to_string();
+    if !std::path::Path::new(&model_path).exists() {
+        return Err(format!("Model file not found at path: {}", model_path));
+    }
+
+    let temp_dir = tempdir().map_err(|e| format!("Failed to create temporary directory: {}", e))?;
+    let audio_path = temp_dir.path().join("audio.wav");
+
+    if let Err(e) = extract_audio_from_video(
+        &project_path,
+        &audio_path,
+        Some(LANGUAGE_DETECTION_SECS as f64),
+    )
+    .await
+    {
+        log::info!("No audio available for language detection: {}", e);
+        return Ok(None);
+    }
+
+    let context = get_whisper_context(&model_path).await?;
+
+    tokio::task::spawn_blocking(move || detect_language_in_file(&context, &audio_path))
+        .await
+        .map_err(|e| format!("Language detection task failed: {}", e))?
+}
+
+/// Detect the most likely spoken language from the start of a 16-bit mono WAV file,
+/// returning `None` if it has no audio
+fn detect_language_in_file(
+    context: &WhisperContext,
+    audio_path: &std::path::Path,
+) -> Result<Option<String>, String> {
+    let audio_data =
+        std::fs::read(audio_path).map_err(|e| format!("Failed to read audio file: {}", e))?;
+
+    // Skip the WAV header and only take the first few seconds of 16-bit mono PCM
+    let samples = audio_data
+        .get(44..)
+        .unwrap_or_default()
+        .chunks_exact(2)
+        .take(WHISPER_SAMPLE_RATE as usize * LANGUAGE_DETECTION_SECS)
+        .map(|b| i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0)
+        .collect::<Vec<_>>();
+
+    if samples.is_empty() {
+        return Ok(None);
+    }
+
+    let mut state = context
+        .create_state()
+        .map_err(|e| format!("Failed to create Whisper state: {}", e))?;
+
+    let threads = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+
+    state
+        .pcm_to_mel(&samples, threads)
+        .map_err(|e| format!("Failed to compute audio spectrogram: {}", e))?;
+
+    let probabilities = state
+        .lang_detect(0, threads)
+        .map_err(|e| format!("Failed to detect language: {}", e))?;
+
+    let language = probabilities
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .and_then(|(id, _)| whisper_rs::get_lang_str(id as i32))
+        .map(|lang| lang.to_string());
+
+    log::info!("Detected language: {:?}", language);
+
+    Ok(language)
+}
+
 /// Function to save caption data to a file
 #[tauri::command]
 #[specta::specta]
@@ -813,6 +985,16 @@ pub async fn save_captions(
                     "text".to_string(),
                     serde_json::Value::String(seg.text.clone()),
                 );
+                if let Some(style_override) = &seg.style_override {
+                    if let Ok(value) = serde_json::to_value(style_override) {
+                        segment.insert("styleOverride".to_string(), value);
+                    }
+                }
+                if let Some(words) = &seg.words {
+                    if let Ok(value) = serde_json::to_value(words) {
+                        segment.insert("words".to_string(), value);
+                    }
+                }
                 segment
             })
             .collect::<Vec<_>>(),
@@ -823,6 +1005,11 @@ pub async fn save_captions(
     })?;
 
     json_obj.insert("segments".to_string(), segments_array);
+    json_obj.insert(
+        "timelineRelative".to_string(),
+        // Captions are always handled on the edited timeline, see `load_captions`
+        serde_json::Value::Bool(true),
+    );
 
     // Add settings object with camelCase naming
     let mut settings_obj = serde_json::Map::new();
@@ -854,6 +1041,13 @@ pub async fn save_captions(
         "position".to_string(),
         serde_json::Value::String(settings.position.clone()),
     );
+    settings_obj.insert(
+        "positionOffset".to_string(),
+        serde_json::Value::Number(
+            serde_json::Number::from_f64(settings.position_offset as f64)
+                .unwrap_or_else(|| serde_json::Number::from(0)),
+        ),
+    );
     settings_obj.insert("bold".to_string(), serde_json::Value::Bool(settings.bold));
     settings_obj.insert(
         "italic".to_string(),
@@ -867,10 +1061,50 @@ pub async fn save_captions(
         "outlineColor".to_string(),
         serde_json::Value::String(settings.outline_color.clone()),
     );
+    settings_obj.insert(
+        "outlineWidth".to_string(),
+        serde_json::Value::Number(
+            serde_json::Number::from_f64(settings.outline_width as f64)
+                .unwrap_or_else(|| serde_json::Number::from(1)),
+        ),
+    );
+    settings_obj.insert(
+        "highlightColor".to_string(),
+        serde_json::Value::String(settings.highlight_color.clone()),
+    );
     settings_obj.insert(
         "exportWithSubtitles".to_string(),
         serde_json::Value::Bool(settings.export_with_subtitles),
     );
+    settings_obj.insert(
+        "maxLines".to_string(),
+        serde_json::Value::Number(serde_json::Number::from(settings.max_lines)),
+    );
+    settings_obj.insert(
+        "truncateOverflow".to_string(),
+        serde_json::Value::Bool(settings.truncate_overflow),
+    );
+    settings_obj.insert(
+        "avoidCamera".to_string(),
+        serde_json::Value::Bool(settings.avoid_camera),
+    );
+    if let Some(language) = &settings.language {
+        settings_obj.insert(
+            "language".to_string(),
+            serde_json::Value::String(language.clone()),
+        );
+    }
+    settings_obj.insert(
+        "translated".to_string(),
+        serde_json::Value::Bool(settings.translated),
+    );
+    settings_obj.insert(
+        "fadeDuration".to_string(),
+        serde_json::Value::Number(
+            serde_json::Number::from_f64(settings.fade_duration as f64)
+                .unwrap_or_else(|| serde_json::Number::from(0)),
+        ),
+    );
 
     json_obj.insert(
         "settings".to_string(),
@@ -892,6 +1126,85 @@ pub async fn save_captions(
     Ok(())
 }
 
+/// Whether a saved captions file has already been mapped onto the edited timeline
+fn json_timeline_relative(json: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(json)
+        .ok()
+        .and_then(|value| value.get("timelineRelative")?.as_bool())
+        .unwrap_or(false)
+}
+
+/// Maps a span of recording time onto the edited timeline, clipped to the first
+/// timeline segment it overlaps. Returns `None` if the span was cut out entirely.
+/// `segment_starts` is where each recording segment starts in the span's time, with
+/// unlisted segments starting at 0 as they did when their audio was mixed together.
+fn recording_span_to_timeline(
+    timeline: &cap_project::TimelineConfiguration,
+    segment_starts: &[f64],
+    start: f32,
+    end: f32,
+) -> Option<(f32, f32)> {
+    let (start, end) = (f64::from(start), f64::from(end));
+    let mut offset = 0.0;
+
+    for segment in &timeline.segments {
+        let segment_start = segment_starts
+            .get(segment.recording_segment as usize)
+            .copied()
+            .unwrap_or(0.0);
+        let clipped_start = (start - segment_start).max(segment.start);
+        let clipped_end = (end - segment_start).min(segment.end);
+
+        if clipped_start < clipped_end {
+            return Some((
+                (offset + (clipped_start - segment.start) / segment.timescale) as f32,
+                (offset + (clipped_end - segment.start) / segment.timescale) as f32,
+            ));
+        }
+
+        offset += segment.duration();
+    }
+
+    None
+}
+
+/// Remaps caption and word times from the raw recording onto the edited timeline,
+/// dropping captions that fall entirely inside removed sections
+fn remap_captions_to_timeline(
+    segments: Vec<CaptionSegment>,
+    timeline: &cap_project::TimelineConfiguration,
+    segment_starts: &[f64],
+) -> Vec<CaptionSegment> {
+    segments
+        .into_iter()
+        .filter_map(|segment| {
+            let (start, end) =
+                recording_span_to_timeline(timeline, segment_starts, segment.start, segment.end)?;
+            let words = segment.words.map(|words| {
+                words
+                    .into_iter()
+                    .filter_map(|word| {
+                        let (start, end) = recording_span_to_timeline(
+                            timeline,
+                            segment_starts,
+                            word.start,
+                            word.end,
+                        )?;
+                        Some(CaptionWord { start, end, ..word })
+                    })
+                    .collect()
+            });
+
+            Some(CaptionSegment {
+                start,
+                end,
+                words,
+                ..segment
+            })
+        })
+        .collect()
+}
+
 /// Helper function to parse captions from a JSON string
 /// This can be used by other modules to parse captions without duplicating code
 pub fn parse_captions_json(json: &str) -> Result<cap_project::CaptionsData, String> {
@@ -909,11 +1222,22 @@ pub fn parse_captions_json(json: &str) -> Result<cap_project::CaptionsData, Stri
                         segment.get("end").and_then(|v| v.as_f64()),
                         segment.get("text").and_then(|v| v.as_str()),
                     ) {
+                        let style_override = segment
+                            .get("styleOverride")
+                            .or_else(|| segment.get("style_override"))
+                            .and_then(|v| serde_json::from_value(v.clone()).ok());
+                        // Older captions files have no word timings
+                        let words = segment
+                            .get("words")
+                            .and_then(|v| serde_json::from_value(v.clone()).ok());
+
                         segments.push(cap_project::CaptionSegment {
                             id: id.to_string(),
                             start: start as f32,
                             end: end as f32,
                             text: text.to_string(),
+                            style_override,
+                            words,
                         });
                     }
                 }
@@ -959,6 +1283,11 @@ pub fn parse_captions_json(json: &str) -> Result<cap_project::CaptionsData, Stri
                         .and_then(|v| v.as_str())
                         .unwrap_or("bottom")
                         .to_string();
+                    let position_offset = settings_obj
+                        .get("positionOffset")
+                        .or_else(|| settings_obj.get("position_offset"))
+                        .and_then(|v| v.as_f64())
+                        .unwrap_or(0.0) as f32;
                     let bold = settings_obj
                         .get("bold")
                         .and_then(|v| v.as_bool())
@@ -979,12 +1308,59 @@ pub fn parse_captions_json(json: &str) -> Result<cap_project::CaptionsData, Stri
                         .unwrap_or("#000000")
                         .to_string();
 
+                    let outline_width = settings_obj
+                        .get("outlineWidth")
+                        .or_else(|| settings_obj.get("outline_width"))
+                        .and_then(|v| v.as_f64())
+                        .unwrap_or(1.0) as f32;
+
+                    let highlight_color = settings_obj
+                        .get("highlightColor")
+                        .or_else(|| settings_obj.get("highlight_color"))
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("#FFFFFF")
+                        .to_string();
+
                     let export_with_subtitles = settings_obj
                         .get("exportWithSubtitles")
                         .or_else(|| settings_obj.get("export_with_subtitles"))
                         .and_then(|v| v.as_bool())
                         .unwrap_or(false);
 
+                    let max_lines = settings_obj
+                        .get("maxLines")
+                        .or_else(|| settings_obj.get("max_lines"))
+                        .and_then(|v| v.as_u64())
+                        .unwrap_or(0) as u32;
+
+                    let truncate_overflow = settings_obj
+                        .get("truncateOverflow")
+                        .or_else(|| settings_obj.get("truncate_overflow"))
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+
+                    let avoid_camera = settings_obj
+                        .get("avoidCamera")
+                        .or_else(|| settings_obj.get("avoid_camera"))
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+
+                    let language = settings_obj
+                        .get("language")
+                        .and_then(|v| v.as_str())
+                        .map(|language| language.to_string());
+
+                    let translated = settings_obj
+                        .get("translated")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+
+                    let fade_duration = settings_obj
+                        .get("fadeDuration")
+                        .or_else(|| settings_obj.get("fade_duration"))
+                        .and_then(|v| v.as_f64())
+                        .unwrap_or(0.0) as f32;
+
                     cap_project::CaptionSettings {
                         enabled,
                         font,
@@ -993,11 +1369,20 @@ pub fn parse_captions_json(json: &str) -> Result<cap_project::CaptionsData, Stri
                         background_color,
                         background_opacity,
                         position,
+                        position_offset,
                         bold,
                         italic,
                         outline,
                         outline_color,
+                        outline_width,
+                        highlight_color,
                         export_with_subtitles,
+                        max_lines,
+                        truncate_overflow,
+                        avoid_camera,
+                        fade_duration,
+                        language,
+                        translated,
                     }
                 } else {
                     // Use default settings if none provided
@@ -1045,9 +1430,22 @@ pub async fn load_captions(
                 project_captions.segments.len()
             );
 
+            // Older captions are timed against the raw recording, so put them on the timeline
+            // the way everything else handles them. They're saved that way from then on.
+            let mut segments = project_captions.segments;
+            if !json_timeline_relative(&json) {
+                let project_path = crate::recording_path(&app, video_id.trim_end_matches(".cap"));
+                if let Some(timeline) = cap_project::ProjectConfiguration::load(&project_path)
+                    .ok()
+                    .and_then(|config| config.timeline)
+                {
+                    segments = remap_captions_to_timeline(segments, &timeline, &[]);
+                }
+            }
+
             // Create the CaptionData structure
             let tauri_captions = CaptionData {
-                segments: project_captions.segments,
+                segments,
                 settings: Some(project_captions.settings),
             };
 
@@ -1061,7 +1459,7 @@ pub async fn load_captions(
 }
 
 /// Helper function to get the captions directory for a video
-fn app_captions_dir(app: &AppHandle, video_id: &str) -> Result<PathBuf, String> {
+pub(crate) fn app_captions_dir(app: &AppHandle, video_id: &str) -> Result<PathBuf, String> {
     tracing::info!("Getting captions directory for video_id: {}", video_id);
 
     // Get the app data directory
@@ -1079,139 +1477,993 @@ fn app_captions_dir(app: &AppHandle, video_id: &str) -> Result<PathBuf, String>
     Ok(captions_dir)
 }
 
-// Add new type for download progress
-#[derive(Debug, Serialize, Type, tauri_specta::Event, Clone)]
-pub struct DownloadProgress {
-    pub progress: f64,
-    pub message: String,
-}
-
-impl DownloadProgress {
-    const EVENT_NAME: &'static str = "download-progress";
+// Keeps searches of large libraries responsive, as there's only so much a user can look through
+const MAX_CAPTION_HITS: usize = 200;
+// Characters of caption text shown either side of a match
+const CAPTION_SNIPPET_CONTEXT: usize = 40;
+
+#[derive(Serialize, Type, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptionHit {
+    pub project_path: PathBuf,
+    pub segment_id: String,
+    /// Start of the matching caption on the project's edited timeline, in seconds
+    pub time: f64,
+    pub snippet: String,
 }
 
-/// Helper function to download a Whisper model from Hugging Face Hub
+/// Case-insensitively search the captions of every recording for `query`,
+/// returning the first caption match in each segment. Recordings without captions are skipped.
 #[tauri::command]
 #[specta::specta]
-pub async fn download_whisper_model(
-    window: Window,
-    model_name: String,
-    output_path: String,
-) -> Result<(), String> {
-    // Define model URLs based on model names
-    let model_url = match model_name.as_str() {
-        "tiny" => "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-tiny.bin",
-        "base" => "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-base.bin",
-        "small" => "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-small.bin",
-        "medium" => "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-medium.bin",
-        "large" => "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-large-v3.bin",
-        "large-v3" => "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-large-v3.bin",
-        _ => "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-tiny.bin", // Default to tiny
-    };
+pub async fn search_all_captions(app: AppHandle, query: String) -> Result<Vec<CaptionHit>, String> {
+    let query = query.trim().to_string();
+    if query.is_empty() {
+        return Ok(vec![]);
+    }
 
-    // Create the client and download the model
-    let client = Client::new();
-    let response = client
-        .get(model_url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to download model: {}", e))?;
+    tokio::task::spawn_blocking(move || {
+        let entries = std::fs::read_dir(crate::recordings_path(&app))
+            .map_err(|e| format!("Failed to read recordings directory: {}", e))?;
 
-    if !response.status().is_success() {
-        return Err(format!(
-            "Failed to download model: HTTP {}",
-            response.status()
-        ));
-    }
+        // JSON escapes these, so they can't be looked for in the raw file
+        let can_prefilter = !query
+            .chars()
+            .any(|c| c == '"' || c == '\\' || c.is_control());
+        let lowercase_query = query.to_lowercase();
 
-    // Get the total size for progress calculation
-    let total_size = response.content_length().unwrap_or(0);
+        let mut hits = vec![];
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let project_path = entry.path();
+            let Some(video_id) = project_path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
 
-    // Create a file to write to
-    if let Some(parent) = std::path::Path::new(&output_path).parent() {
-        std::fs::create_dir_all(parent)
-            .map_err(|e| format!("Failed to create parent directories: {}", e))?;
-    }
-    let mut file = tokio::fs::File::create(&output_path)
-        .await
-        .map_err(|e| format!("Failed to create file: {}", e))?;
+            let captions_path = app_captions_dir(&app, video_id)?.join("captions.json");
+            let Ok(json) = std::fs::read_to_string(&captions_path) else {
+                continue;
+            };
 
-    // Download and write in chunks
-    let mut downloaded = 0;
-    let mut bytes = response
-        .bytes()
-        .await
-        .map_err(|e| format!("Failed to get response bytes: {}", e))?;
+            // Most recordings won't mention the query, and this is much cheaper than parsing them
+            if can_prefilter && !json.to_lowercase().contains(&lowercase_query) {
+                continue;
+            }
+
+            let captions = match parse_captions_json(&json) {
+                Ok(captions) => captions,
+                Err(e) => {
+                    tracing::warn!("Skipping unreadable captions {:?}: {}", captions_path, e);
+                    continue;
+                }
+            };
 
-    // Write the bytes in chunks to show progress
-    const CHUNK_SIZE: usize = 1024 * 1024; // 1MB chunks
-    while !bytes.is_empty() {
-        let chunk_size = std::cmp::min(CHUNK_SIZE, bytes.len());
-        let chunk = bytes.split_to(chunk_size);
+            // Older captions are timed against the raw recording
+            let timeline = (!json_timeline_relative(&json))
+                .then(|| cap_project::ProjectConfiguration::load(&project_path).ok())
+                .flatten()
+                .and_then(|config| config.timeline);
 
-        file.write_all(&chunk)
-            .await
-            .map_err(|e| format!("Error while writing to file: {}", e))?;
+            for segment in &captions.segments {
+                let Some(range) = find_ignore_case(&segment.text, &query) else {
+                    continue;
+                };
 
-        downloaded += chunk_size as u64;
+                let time = match &timeline {
+                    Some(timeline) => {
+                        // Cut out of the edited recording, so there's nowhere to jump to
+                        let Some((start, _)) =
+                            recording_span_to_timeline(timeline, &[], segment.start, segment.end)
+                        else {
+                            continue;
+                        };
+                        start
+                    }
+                    None => segment.start,
+                };
 
-        // Calculate and emit progress
-        let progress = if total_size > 0 {
-            (downloaded as f64 / total_size as f64) * 100.0
-        } else {
-            0.0
-        };
+                hits.push(CaptionHit {
+                    project_path: project_path.clone(),
+                    segment_id: segment.id.clone(),
+                    time: f64::from(time),
+                    snippet: caption_snippet(&segment.text, range),
+                });
 
-        window
-            .emit(
-                DownloadProgress::EVENT_NAME,
-                DownloadProgress {
-                    message: format!("Downloading model: {:.1}%", progress),
-                    progress,
-                },
-            )
-            .map_err(|e| format!("Failed to emit progress: {}", e))?;
+                if hits.len() >= MAX_CAPTION_HITS {
+                    return Ok(hits);
+                }
+            }
+        }
+
+        Ok(hits)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// Byte range of the first match of `query` in `text`, ignoring case
+fn find_ignore_case(text: &str, query: &str) -> Option<std::ops::Range<usize>> {
+    if query.is_empty() {
+        return None;
     }
 
-    // Ensure file is properly written
-    file.flush()
-        .await
-        .map_err(|e| format!("Failed to flush file: {}", e))?;
+    text.char_indices().find_map(|(start, _)| {
+        let mut chars = text[start..].char_indices();
+        for query_char in query.chars() {
+            let (_, c) = chars.next()?;
+            if !c.to_lowercase().eq(query_char.to_lowercase()) {
+                return None;
+            }
+        }
 
-    Ok(())
+        let end = chars.next().map_or(text.len(), |(i, _)| start + i);
+        Some(start..end)
+    })
 }
 
-/// Function to check if a model file exists
-#[tauri::command]
-#[specta::specta]
-pub async fn check_model_exists(model_path: String) -> Result<bool, String> {
-    Ok(std::path::Path::new(&model_path).exists())
+/// The match in `range` with up to `CAPTION_SNIPPET_CONTEXT` characters either side, on one line
+fn caption_snippet(text: &str, range: std::ops::Range<usize>) -> String {
+    let start = text[..range.start]
+        .char_indices()
+        .rev()
+        .nth(CAPTION_SNIPPET_CONTEXT - 1)
+        .map_or(0, |(i, _)| i);
+    let end = text[range.end..]
+        .char_indices()
+        .nth(CAPTION_SNIPPET_CONTEXT)
+        .map_or(text.len(), |(i, _)| range.end + i);
+
+    let mut snippet = text[start..end].replace('\n', " ");
+    if start > 0 {
+        snippet.insert(0, '…');
+    }
+    if end < text.len() {
+        snippet.push('…');
+    }
+    snippet
 }
 
-/// Function to delete a downloaded model
+const SUMMARY_KEYWORDS: usize = 10;
+const SUMMARY_CAPTIONS: usize = 3;
+
+// Common English words and speech filler that say nothing about what a recording is about
+const STOPWORDS: &[&str] = &[
+    "about", "actually", "after", "again", "all", "also", "and", "any", "are", "because", "been",
+    "before", "being", "but", "can", "could", "did", "does", "doing", "don't", "down", "each",
+    "even", "for", "from", "get", "going", "gonna", "got", "had", "has", "have", "here", "how",
+    "i'm", "into", "it's", "its", "just", "kind", "know", "let's", "like", "look", "make", "more",
+    "much", "need", "not", "now", "okay", "one", "only", "other", "our", "out", "over", "really",
+    "right", "see", "should", "some", "that", "that's", "the", "their", "them", "then", "there",
+    "these", "they", "thing", "things", "think", "this", "those", "through", "too", "uh", "um",
+    "very", "want", "was", "way", "we're", "well", "were", "what", "when", "where", "which",
+    "while", "who", "why", "will", "with", "would", "yeah", "you", "you're", "your",
+];
+
+#[derive(Serialize, Type, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptionSummary {
+    /// Most distinctive words first
+    pub keywords: Vec<String>,
+    /// The most representative captions, in the order they're spoken
+    pub summary: String,
+}
+
+/// Pick out keywords and the most representative captions of a recording, entirely locally.
+/// Tuned for English, as other languages' common words aren't filtered out.
 #[tauri::command]
 #[specta::specta]
-pub async fn delete_whisper_model(model_path: String) -> Result<(), String> {
-    if !std::path::Path::new(&model_path).exists() {
-        return Err(format!("Model file not found: {}", model_path));
+pub async fn summarize_captions(
+    app: AppHandle,
+    project_path: PathBuf,
+) -> Result<CaptionSummary, String> {
+    let video_id = project_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| "Invalid project path".to_string())?;
+    let captions_path = app_captions_dir(&app, &video_id)?.join("captions.json");
+
+    tokio::task::spawn_blocking(move || {
+        let json = std::fs::read_to_string(&captions_path)
+            .map_err(|_| "This recording has no captions".to_string())?;
+        let captions = parse_captions_json(&json)?;
+
+        summarize_segments(&captions.segments)
+            .ok_or_else(|| "This recording's captions have nothing to summarize".to_string())
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+fn summary_terms(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .map(|word| word.trim_matches('\'').to_lowercase())
+        .filter(|word| word.chars().count() > 2 && !word.chars().all(|c| c.is_numeric()))
+        .filter(|word| !STOPWORDS.contains(&word.as_str()))
+        .collect()
+}
+
+/// Scores words by TF-IDF, with each caption as a document so words spread through the
+/// recording rank above ones repeated in a single sentence, then ranks captions by the
+/// scores of the words they contain
+fn summarize_segments(segments: &[CaptionSegment]) -> Option<CaptionSummary> {
+    let terms = segments
+        .iter()
+        .map(|segment| summary_terms(&segment.text))
+        .collect::<Vec<_>>();
+
+    let mut frequency = HashMap::<&str, usize>::new();
+    let mut document_frequency = HashMap::<&str, usize>::new();
+    for segment_terms in &terms {
+        for term in segment_terms {
+            *frequency.entry(term).or_default() += 1;
+        }
+        for term in segment_terms.iter().collect::<HashSet<_>>() {
+            *document_frequency.entry(term).or_default() += 1;
+        }
     }
 
-    tokio::fs::remove_file(&model_path)
-        .await
-        .map_err(|e| format!("Failed to delete model file: {}", e))?;
+    if frequency.is_empty() {
+        return None;
+    }
 
-    Ok(())
+    let documents = segments.len() as f64;
+    let scores = frequency
+        .iter()
+        .map(|(term, count)| {
+            let idf = 1.0 + (documents / document_frequency[term] as f64).ln();
+            (*term, *count as f64 * idf)
+        })
+        .collect::<HashMap<_, _>>();
+
+    let mut keywords = scores.iter().collect::<Vec<_>>();
+    keywords.sort_by(|a, b| b.1.total_cmp(a.1).then(a.0.cmp(b.0)));
+
+    // Normalized by length, so long rambling captions don't win just by having more words
+    let mut ranked = terms
+        .iter()
+        .enumerate()
+        .filter(|(_, segment_terms)| !segment_terms.is_empty())
+        .map(|(i, segment_terms)| {
+            let unique = segment_terms.iter().collect::<HashSet<_>>();
+            let score = unique.iter().map(|term| scores[term.as_str()]).sum::<f64>()
+                / (segment_terms.len() as f64).sqrt();
+            (i, score)
+        })
+        .collect::<Vec<_>>();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    let mut chosen = ranked
+        .into_iter()
+        .take(SUMMARY_CAPTIONS)
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    chosen.sort();
+
+    Some(CaptionSummary {
+        keywords: keywords
+            .into_iter()
+            .take(SUMMARY_KEYWORDS)
+            .map(|(term, _)| term.to_string())
+            .collect(),
+        summary: chosen
+            .into_iter()
+            .map(|i| segments[i].text.trim().replace('\n', " "))
+            .collect::<Vec<_>>()
+            .join(" "),
+    })
 }
 
-/// Convert caption segments to SRT format
-fn captions_to_srt(captions: &CaptionData) -> String {
-    let mut srt = String::new();
-    for (i, segment) in captions.segments.iter().enumerate() {
-        // Convert start and end times from seconds to HH:MM:SS,mmm format
-        let start_time = format_srt_time(f64::from(segment.start));
-        let end_time = format_srt_time(f64::from(segment.end));
+// Hesitations and verbal tics common in English speech
+pub(crate) const DEFAULT_FILLER_WORDS: &[&str] = &[
+    "um",
+    "uh",
+    "uhm",
+    "erm",
+    "er",
+    "ah",
+    "hmm",
+    "like",
+    "you know",
+    "i mean",
+    "basically",
+    "literally",
+];
+
+// Only hesitations are cut by default, as words like "like" are as often meant as not
+const DEFAULT_TRIMMED_FILLERS: &[&str] = &["um", "uh", "uhm", "erm", "er", "ah", "hmm"];
+
+// Cut slightly either side of a filler so none of it is left audible
+const FILLER_TRIM_PADDING: f32 = 0.05;
+
+#[derive(Serialize, Type, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FillerHit {
+    /// When the filler is spoken on the project's edited timeline, in seconds
+    pub time: f64,
+    pub word: String,
+}
 
-        // Write SRT entry
+/// Find filler words in a recording's captions, in the order they're spoken. `words` can include
+/// phrases such as "you know", and a default English list is used when it's empty.
+/// Word timings are used where the transcription has them, otherwise times are estimated
+/// from where the filler falls in its caption.
+#[tauri::command]
+#[specta::specta]
+pub async fn detect_filler_words(
+    app: AppHandle,
+    project_path: PathBuf,
+    words: Vec<String>,
+) -> Result<Vec<FillerHit>, String> {
+    let video_id = project_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| "Invalid project path".to_string())?;
+    let captions_path = app_captions_dir(&app, &video_id)?.join("captions.json");
+
+    let fillers = filler_phrases(words, DEFAULT_FILLER_WORDS);
+
+    tokio::task::spawn_blocking(move || {
+        let json = std::fs::read_to_string(&captions_path)
+            .map_err(|_| "This recording has no captions".to_string())?;
+        let captions = parse_captions_json(&json)?;
+
+        // Older captions are timed against the raw recording
+        let timeline = (!json_timeline_relative(&json))
+            .then(|| cap_project::ProjectConfiguration::load(&project_path).ok())
+            .flatten()
+            .and_then(|config| config.timeline);
+
+        let mut hits = find_filler_words(&captions.segments, &fillers)
+            .into_iter()
+            .filter_map(|(word, start, end)| {
+                let time = match &timeline {
+                    // Fillers that were cut out of the edited recording aren't heard any more
+                    Some(timeline) => recording_span_to_timeline(timeline, &[], start, end)?.0,
+                    None => start,
+                };
+
+                Some(FillerHit {
+                    time: f64::from(time),
+                    word,
+                })
+            })
+            .collect::<Vec<_>>();
+        hits.sort_by(|a, b| a.time.total_cmp(&b.time));
+
+        Ok(hits)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[derive(Serialize, Type, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FillerTrim {
+    /// The project with the fillers cut. It isn't saved, so the editor can apply it as an undoable change.
+    pub config: cap_project::ProjectConfiguration,
+    pub fillers_removed: u32,
+    /// Seconds taken off the edited timeline
+    pub removed_secs: f64,
+}
+
+/// Cut filler words out of the project's timeline and captions, using the captions' word timings,
+/// and pull later captions and lower thirds back to match.
+/// Captions without word timings are left alone, as fillers can't be placed precisely enough in them
+/// to cut. `words` works as in `detect_filler_words`, but defaults to hesitations such as "um".
+/// Only the project configuration changes, never the recorded media.
+#[tauri::command]
+#[specta::specta]
+pub async fn auto_trim_fillers(
+    project_path: PathBuf,
+    words: Vec<String>,
+) -> Result<FillerTrim, String> {
+    let mut config = cap_project::ProjectConfiguration::load(&project_path)
+        .map_err(|e| format!("Failed to load project: {}", e))?;
+    let Some(timeline) = config.timeline.as_mut() else {
+        return Err("This project has no timeline to trim".to_string());
+    };
+    let Some(captions) = config.captions.as_mut() else {
+        return Err("This project has no captions".to_string());
+    };
+
+    let timed_segments = captions
+        .segments
+        .iter()
+        .filter(|segment| {
+            segment
+                .words
+                .as_ref()
+                .is_some_and(|words| !words.is_empty())
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+    if timed_segments.is_empty() {
+        return Err("Auto-trim needs word timings, which these captions don't have".to_string());
+    }
+
+    let hits = find_filler_words(
+        &timed_segments,
+        &filler_phrases(words, DEFAULT_TRIMMED_FILLERS),
+    );
+    let spans = merge_spans(
+        hits.iter()
+            .map(|(_, start, end)| {
+                (
+                    (start - FILLER_TRIM_PADDING).max(0.0),
+                    end + FILLER_TRIM_PADDING,
+                )
+            })
+            .collect(),
+    );
+
+    remove_cut_words(&mut captions.segments, &spans);
+
+    // Latest first, so the positions of earlier spans are still valid
+    let mut removed_secs = 0.0;
+    for &(start, end) in spans.iter().rev() {
+        let removed = timeline.cut_timeline_span(f64::from(start), f64::from(end));
+        retime_after_cut(
+            &mut captions.segments,
+            &mut config.lower_thirds,
+            start,
+            start + removed as f32,
+        );
+        removed_secs += removed;
+    }
+
+    Ok(FillerTrim {
+        config,
+        fillers_removed: hits.len() as u32,
+        removed_secs,
+    })
+}
+
+/// `words` split into lowercase phrases for `find_filler_words`, or `defaults` if it's empty
+pub(crate) fn filler_phrases(words: Vec<String>, defaults: &[&str]) -> Vec<Vec<String>> {
+    let words = if words.is_empty() {
+        defaults.iter().map(|word| word.to_string()).collect()
+    } else {
+        words
+    };
+
+    words
+        .iter()
+        .map(|filler| {
+            spoken_words(filler)
+                .into_iter()
+                .map(|(word, _)| word)
+                .collect::<Vec<_>>()
+        })
+        .filter(|filler| !filler.is_empty())
+        .collect()
+}
+
+/// Sort `spans` and join any that overlap
+fn merge_spans(mut spans: Vec<(f32, f32)>) -> Vec<(f32, f32)> {
+    spans.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut merged: Vec<(f32, f32)> = vec![];
+    for (start, end) in spans {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+
+    merged
+}
+
+/// Drop words that lie inside the cut `spans` from captions with word timings, rebuilding
+/// their text from the words that are left. Captions with no words left are removed.
+fn remove_cut_words(segments: &mut Vec<CaptionSegment>, spans: &[(f32, f32)]) {
+    segments.retain_mut(|segment| {
+        let Some(words) = &mut segment.words else {
+            return true;
+        };
+
+        let count = words.len();
+        words.retain(|word| {
+            !spans
+                .iter()
+                .any(|(start, end)| word.start >= *start && word.end <= *end)
+        });
+        if words.len() == count {
+            return true;
+        }
+
+        segment.text = words
+            .iter()
+            .map(|word| word.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+        !words.is_empty()
+    });
+}
+
+/// Pull captions and lower thirds after `start` back by the `start` to `end` cut from the
+/// timeline, dropping any that were entirely inside it
+fn retime_after_cut(
+    segments: &mut Vec<CaptionSegment>,
+    lower_thirds: &mut Vec<cap_project::LowerThird>,
+    start: f32,
+    end: f32,
+) {
+    let shift = |time: f32| {
+        cap_project::time_after_cut(f64::from(time), f64::from(start), f64::from(end)) as f32
+    };
+
+    segments.retain_mut(|segment| {
+        segment.start = shift(segment.start);
+        segment.end = shift(segment.end);
+        if let Some(words) = &mut segment.words {
+            for word in words.iter_mut() {
+                word.start = shift(word.start);
+                word.end = shift(word.end);
+            }
+        }
+        segment.end > segment.start
+    });
+    lower_thirds.retain_mut(|lower_third| {
+        lower_third.start = shift(lower_third.start);
+        lower_third.end = shift(lower_third.end);
+        lower_third.end > lower_third.start
+    });
+}
+
+/// The lowercased words in `text` with their byte ranges, ignoring punctuation
+pub(crate) fn spoken_words(text: &str) -> Vec<(String, std::ops::Range<usize>)> {
+    let mut words = vec![];
+    let mut word_start = None;
+
+    for (i, c) in text.char_indices().chain([(text.len(), ' ')]) {
+        let in_word = c.is_alphanumeric() || c == '\'';
+        match word_start {
+            None if in_word => word_start = Some(i),
+            Some(start) if !in_word => {
+                let word = text[start..i].trim_matches('\'').to_lowercase();
+                if !word.is_empty() {
+                    words.push((word, start..i));
+                }
+                word_start = None;
+            }
+            _ => {}
+        }
+    }
+
+    words
+}
+
+/// Each occurrence of one of `fillers`, given as lists of lowercased words, with the
+/// filler's text and its start and end in seconds
+pub(crate) fn find_filler_words(
+    segments: &[CaptionSegment],
+    fillers: &[Vec<String>],
+) -> Vec<(String, f32, f32)> {
+    let mut hits = vec![];
+
+    for segment in segments {
+        let timed_words = match &segment.words {
+            Some(words) if !words.is_empty() => words
+                .iter()
+                .flat_map(|word| {
+                    spoken_words(&word.text)
+                        .into_iter()
+                        .map(|(text, _)| (text, word.start, word.end))
+                })
+                .collect::<Vec<_>>(),
+            _ => {
+                let duration = segment.end - segment.start;
+                let length = segment.text.len().max(1) as f32;
+                spoken_words(&segment.text)
+                    .into_iter()
+                    .map(|(text, range)| {
+                        (
+                            text,
+                            segment.start + duration * range.start as f32 / length,
+                            segment.start + duration * range.end as f32 / length,
+                        )
+                    })
+                    .collect()
+            }
+        };
+
+        let mut i = 0;
+        while i < timed_words.len() {
+            let filler = fillers.iter().find(|filler| {
+                timed_words[i..].len() >= filler.len()
+                    && filler
+                        .iter()
+                        .zip(&timed_words[i..])
+                        .all(|(filler_word, (word, _, _))| filler_word == word)
+            });
+
+            match filler {
+                Some(filler) => {
+                    let end = timed_words[i + filler.len() - 1].2;
+                    hits.push((filler.join(" "), timed_words[i].1, end));
+                    i += filler.len();
+                }
+                None => i += 1,
+            }
+        }
+    }
+
+    hits
+}
+
+/// Split a caption segment in two at `at` seconds, dividing its text between the
+/// halves in proportion to their durations
+#[tauri::command]
+#[specta::specta]
+pub async fn split_caption_segment(
+    project_path: PathBuf,
+    segment_id: String,
+    at: f32,
+    app: AppHandle,
+) -> Result<(), String> {
+    let video_id = project_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| "Invalid project path".to_string())?
+        .to_string();
+
+    let mut captions = load_captions(video_id.clone(), app.clone())
+        .await?
+        .ok_or_else(|| "This project has no captions".to_string())?;
+
+    let index = captions
+        .segments
+        .iter()
+        .position(|segment| segment.id == segment_id)
+        .ok_or_else(|| format!("Caption segment {} not found", segment_id))?;
+
+    let (first, second) = split_segment(&captions.segments[index], at)?;
+    captions.segments.splice(index..=index, [first, second]);
+
+    save_captions(video_id, captions, app).await
+}
+
+/// Split `segment` at `at`, which must be strictly inside it. The text is split by
+/// character count, moved to the nearest word boundary when there is one.
+fn split_segment(
+    segment: &CaptionSegment,
+    at: f32,
+) -> Result<(CaptionSegment, CaptionSegment), String> {
+    if at <= segment.start || at >= segment.end {
+        return Err(format!(
+            "Split point {}s is outside the caption ({}s to {}s)",
+            at, segment.start, segment.end
+        ));
+    }
+
+    let text = segment.text.trim();
+    let chars = text.chars().collect::<Vec<_>>();
+    let fraction = (at - segment.start) / (segment.end - segment.start);
+    let target = (chars.len() as f32 * fraction).round() as usize;
+
+    let split = chars
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| c.is_whitespace())
+        .map(|(i, _)| i)
+        .min_by_key(|i| i.abs_diff(target))
+        .unwrap_or(target);
+    let (first_text, second_text) = chars.split_at(split);
+
+    let words = |keep: fn(&CaptionWord, f32) -> bool| {
+        segment.words.as_ref().map(|words| {
+            words
+                .iter()
+                .filter(|word| keep(word, at))
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+    };
+
+    let first = CaptionSegment {
+        id: uuid::Uuid::new_v4().to_string(),
+        start: segment.start,
+        end: at,
+        text: first_text.iter().collect::<String>().trim().to_string(),
+        style_override: segment.style_override.clone(),
+        words: words(|word, at| word.start < at),
+    };
+    let second = CaptionSegment {
+        id: uuid::Uuid::new_v4().to_string(),
+        start: at,
+        end: segment.end,
+        text: second_text.iter().collect::<String>().trim().to_string(),
+        style_override: segment.style_override.clone(),
+        words: words(|word, at| word.start >= at),
+    };
+
+    Ok((first, second))
+}
+
+// Add new type for download progress
+#[derive(Debug, Serialize, Type, tauri_specta::Event, Clone)]
+pub struct DownloadProgress {
+    pub progress: f64,
+    pub message: String,
+    /// Bytes downloaded so far, including any resumed from an earlier attempt
+    pub downloaded_bytes: u64,
+    pub total_bytes: u64,
+}
+
+impl DownloadProgress {
+    const EVENT_NAME: &'static str = "download-progress";
+}
+
+/// Whisper model sizes that can be downloaded, trading transcription speed for accuracy
+#[derive(Debug, Serialize, Deserialize, Type, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum WhisperModel {
+    Tiny,
+    TinyEn,
+    Base,
+    BaseEn,
+    Small,
+    SmallEn,
+    Medium,
+    MediumEn,
+    LargeV3,
+}
+
+impl WhisperModel {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Tiny => "tiny",
+            Self::TinyEn => "tiny.en",
+            Self::Base => "base",
+            Self::BaseEn => "base.en",
+            Self::Small => "small",
+            Self::SmallEn => "small.en",
+            Self::Medium => "medium",
+            Self::MediumEn => "medium.en",
+            Self::LargeV3 => "large-v3",
+        }
+    }
+
+    /// English-only models are more accurate for their size, but can't transcribe anything else
+    fn multilingual(self) -> bool {
+        !matches!(
+            self,
+            Self::TinyEn | Self::BaseEn | Self::SmallEn | Self::MediumEn
+        )
+    }
+
+    /// Reject languages this model would produce garbage output for
+    fn validate_language(self, language: &str) -> Result<(), String> {
+        if self.multilingual() || language == "en" || language == "auto" {
+            return Ok(());
+        }
+
+        Err(format!(
+            "The '{}' model only supports English, but '{}' was requested. Download the '{}' model to transcribe other languages.",
+            self.name(),
+            language,
+            self.name().trim_end_matches(".en")
+        ))
+    }
+
+    fn url(self) -> String {
+        format!(
+            "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-{}.bin",
+            self.name()
+        )
+    }
+
+    /// Where this model is stored, each size under its own file so several can be installed
+    fn path(self, app: &AppHandle) -> Result<PathBuf, String> {
+        let models_dir = app
+            .path()
+            .app_local_data_dir()
+            .map_err(|_| "Failed to get app data directory".to_string())?
+            .join("transcription_models");
+
+        Ok(models_dir.join(format!("{}.bin", self.name())))
+    }
+}
+
+/// Helper function to download a Whisper model from Hugging Face Hub
+#[tauri::command]
+#[specta::specta]
+pub async fn download_whisper_model(window: Window, model: WhisperModel) -> Result<(), String> {
+    let output_path = model.path(window.app_handle())?;
+    let model_url = model.url();
+
+    if let Some(parent) = output_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create parent directories: {}", e))?;
+    }
+
+    // Download into a partial file first so an interrupted download can be resumed
+    let partial_path = output_path.with_extension("bin.download");
+    let client = Client::new();
+
+    let (mut response, mut downloaded) = loop {
+        let resume_from = tokio::fs::metadata(&partial_path)
+            .await
+            .map(|meta| meta.len())
+            .unwrap_or(0);
+
+        let mut request = client.get(&model_url);
+        if resume_from > 0 {
+            request = request.header(reqwest::header::RANGE, format!("bytes={}-", resume_from));
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("Failed to download model: {}", e))?;
+
+        match response.status() {
+            reqwest::StatusCode::PARTIAL_CONTENT => break (response, resume_from),
+            // The partial file is corrupt or from a different model, so start over
+            reqwest::StatusCode::RANGE_NOT_SATISFIABLE => {
+                tokio::fs::remove_file(&partial_path)
+                    .await
+                    .map_err(|e| format!("Failed to remove partial download: {}", e))?;
+            }
+            status if status.is_success() => {
+                if resume_from > 0 {
+                    log::info!("Server doesn't support resuming, restarting model download");
+                }
+                break (response, 0);
+            }
+            status => return Err(format!("Failed to download model: HTTP {}", status)),
+        }
+    };
+
+    // Get the total size for progress calculation
+    let total_size = response
+        .content_length()
+        .map(|length| length + downloaded)
+        .unwrap_or(0);
+
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(downloaded > 0)
+        .truncate(downloaded == 0)
+        .open(&partial_path)
+        .await
+        .map_err(|e| format!("Failed to create file: {}", e))?;
+
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("Failed to download model: {}", e))?
+    {
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| format!("Error while writing to file: {}", e))?;
+
+        downloaded += chunk.len() as u64;
+
+        // Calculate and emit progress
+        let progress = if total_size > 0 {
+            (downloaded as f64 / total_size as f64) * 100.0
+        } else {
+            0.0
+        };
+
+        window
+            .emit(
+                DownloadProgress::EVENT_NAME,
+                DownloadProgress {
+                    message: format!("Downloading model: {:.1}%", progress),
+                    progress,
+                    downloaded_bytes: downloaded,
+                    total_bytes: total_size,
+                },
+            )
+            .map_err(|e| format!("Failed to emit progress: {}", e))?;
+    }
+
+    // Ensure file is properly written
+    file.flush()
+        .await
+        .map_err(|e| format!("Failed to flush file: {}", e))?;
+    drop(file);
+
+    if total_size > 0 && downloaded != total_size {
+        return Err(format!(
+            "Model download was incomplete: got {} of {} bytes",
+            downloaded, total_size
+        ));
+    }
+
+    // Only a complete download ever appears at the model path
+    tokio::fs::rename(&partial_path, &output_path)
+        .await
+        .map_err(|e| format!("Failed to move downloaded model into place: {}", e))?;
+
+    // Record the complete size so partial or corrupted models can be spotted later
+    let meta = WhisperModelMeta {
+        name: model.name().to_string(),
+        size: Some(downloaded),
+    };
+    let meta_json = serde_json::to_string_pretty(&meta)
+        .map_err(|e| format!("Failed to serialize model metadata: {}", e))?;
+    tokio::fs::write(whisper_model_meta_path(&output_path), meta_json)
+        .await
+        .map_err(|e| format!("Failed to write model metadata: {}", e))?;
+
+    Ok(())
+}
+
+/// Details of a downloaded Whisper model, stored next to the model file
+#[derive(Debug, Serialize, Deserialize, Type, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WhisperModelMeta {
+    pub name: String,
+    /// Size of the complete model file in bytes
+    #[serde(default)]
+    pub size: Option<u64>,
+}
+
+/// Path of the metadata sidecar for a model, e.g. `ggml-base.bin` -> `ggml-base.meta.json`
+fn whisper_model_meta_path(model_path: impl AsRef<std::path::Path>) -> PathBuf {
+    model_path.as_ref().with_extension("meta.json")
+}
+
+/// Read a model's metadata sidecar, if it was downloaded since sidecars were written
+fn read_whisper_model_meta(model_path: &std::path::Path) -> Option<WhisperModelMeta> {
+    std::fs::read_to_string(whisper_model_meta_path(model_path))
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+}
+
+/// Function to check if a model file exists and is complete
+#[tauri::command]
+#[specta::specta]
+pub async fn check_model_exists(model: WhisperModel, app: AppHandle) -> Result<bool, String> {
+    let model_path = model.path(&app)?;
+    let Ok(file) = tokio::fs::metadata(&model_path).await else {
+        return Ok(false);
+    };
+
+    // Models downloaded before sizes were recorded can't be checked
+    Ok(
+        match read_whisper_model_meta(&model_path).and_then(|meta| meta.size) {
+            Some(size) => size == file.len(),
+            None => true,
+        },
+    )
+}
+
+/// Function to delete a downloaded model
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_whisper_model(model: WhisperModel, app: AppHandle) -> Result<(), String> {
+    let model_path = model.path(&app)?;
+    if !model_path.exists() {
+        return Err(format!("Model file not found: {:?}", model_path));
+    }
+
+    tokio::fs::remove_file(&model_path)
+        .await
+        .map_err(|e| format!("Failed to delete model file: {}", e))?;
+
+    let meta_path = whisper_model_meta_path(&model_path);
+    if meta_path.exists() {
+        tokio::fs::remove_file(&meta_path)
+            .await
+            .map_err(|e| format!("Failed to delete model metadata: {}", e))?;
+    }
+
+    Ok(())
+}
+
+/// Convert caption segments to SRT format
+fn captions_to_srt(captions: &CaptionData) -> String {
+    let mut srt = String::new();
+    for (i, segment) in captions.segments.iter().enumerate() {
+        // Convert start and end times from seconds to HH:MM:SS,mmm format
+        let start_time = format_srt_time(f64::from(segment.start));
+        let end_time = format_srt_time(f64::from(segment.end));
+
+        // Write SRT entry
         srt.push_str(&format!(
             "{}\n{} --> {}\n{}\n\n",
             i + 1,
@@ -1298,6 +2550,205 @@ pub async fn export_captions_srt(
     }
 }
 
+/// Format time in seconds to ASS time format (H:MM:SS.cc)
+fn format_ass_time(seconds: f64) -> String {
+    let centis = (seconds.max(0.0) * 100.0).round() as u64;
+    format!(
+        "{}:{:02}:{:02}.{:02}",
+        centis / 360_000,
+        (centis / 6000) % 60,
+        (centis / 100) % 60,
+        centis % 100
+    )
+}
+
+/// Convert a `#RRGGBB` color to ASS's `&HAABBGGRR`, where an alpha of 00 is opaque
+fn ass_color(hex: &str, opacity: u32) -> String {
+    let hex = hex.trim_start_matches('#');
+    let component = |i: usize| {
+        hex.get(i..i + 2)
+            .and_then(|c| u8::from_str_radix(c, 16).ok())
+            .unwrap_or(0)
+    };
+    let alpha = 255 - (opacity.min(100) * 255 / 100) as u8;
+
+    format!(
+        "&H{:02X}{:02X}{:02X}{:02X}",
+        alpha,
+        component(4),
+        component(2),
+        component(0)
+    )
+}
+
+/// Convert caption segments to an ASS script, with the caption settings as its default style
+fn captions_to_ass(captions: &cap_project::CaptionsData) -> String {
+    let settings = &captions.settings;
+
+    let font = match settings.font.as_str() {
+        "System Serif" => "Times New Roman",
+        "System Monospace" => "Courier New",
+        _ => "Arial",
+    };
+    // Numpad style alignment, matching where the caption layer anchors captions. As there,
+    // a positive offset moves captions down, so bottom-anchored ones get a smaller margin.
+    let offset = (settings.position_offset * 1080.0).round() as i32;
+    let (alignment, margin_v, position) = match settings.position.as_str() {
+        "top" => (8, 20 + offset, None),
+        // Middle alignment ignores MarginV, so the lines are positioned instead
+        "middle" => (
+            5,
+            20,
+            (offset != 0).then(|| format!("{{\\pos(960,{})}}", 540 + offset)),
+        ),
+        _ => (2, 20 - offset, None),
+    };
+    let margin_v = margin_v.max(0);
+
+    let mut ass = String::from(
+        "[Script Info]\nScriptType: v4.00+\nPlayResX: 1920\nPlayResY: 1080\nWrapStyle: 0\nScaledBorderAndShadow: yes\n\n",
+    );
+
+    ass.push_str("[V4+ Styles]\nFormat: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding\n");
+    ass.push_str(&format!(
+        "Style: Default,{font},{},{},&H000000FF,{},{},{},{},0,0,100,100,0,0,1,{},0,{alignment},20,20,{margin_v},1\n\n",
+        settings.size,
+        ass_color(&settings.color, 100),
+        ass_color(&settings.outline_color, 100),
+        ass_color(&settings.background_color, settings.background_opacity),
+        if settings.bold { -1 } else { 0 },
+        if settings.italic { -1 } else { 0 },
+        if settings.outline { settings.outline_width } else { 0.0 },
+    ));
+
+    ass.push_str("[Events]\nFormat: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\n");
+    for segment in &captions.segments {
+        // Backslashes and braces would start tags, so they're escaped, and newlines are written as \N
+        let text = segment
+            .text
+            .trim()
+            .replace('\\', "\\\\")
+            .replace('{', "\\{")
+            .replace('}', "\\}")
+            .replace("\r\n", "\\N")
+            .replace('\n', "\\N");
+
+        ass.push_str(&format!(
+            "Dialogue: 0,{},{},Default,,0,0,0,,{}{}\n",
+            format_ass_time(f64::from(segment.start)),
+            format_ass_time(f64::from(segment.end)),
+            position.as_deref().unwrap_or_default(),
+            text
+        ));
+    }
+
+    ass
+}
+
+/// Export the project's captions as an ASS subtitle script that keeps their style.
+/// Writes to `output` when given, and returns the script either way.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_captions_to_ass(
+    project_path: PathBuf,
+    output: Option<PathBuf>,
+) -> Result<String, String> {
+    let captions = cap_project::ProjectConfiguration::load(&project_path)
+        .map_err(|e| format!("Failed to load project: {}", e))?
+        .captions
+        .filter(|captions| !captions.segments.is_empty())
+        .ok_or_else(|| "This project has no captions".to_string())?;
+
+    let ass = captions_to_ass(&captions);
+
+    if let Some(output) = output {
+        std::fs::write(&output, &ass).map_err(|e| format!("Failed to write ASS file: {}", e))?;
+        tracing::info!("Wrote ASS captions to {:?}", output);
+    }
+
+    Ok(ass)
+}
+
+/// Format time in seconds to WebVTT time format (HH:MM:SS.mmm)
+fn format_vtt_time(seconds: f64) -> String {
+    let millis = (seconds.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        millis / 3_600_000,
+        (millis / 60_000) % 60,
+        (millis / 1000) % 60,
+        millis % 1000
+    )
+}
+
+/// Convert caption segments to WebVTT. Segments with word timings get inline
+/// timestamps and `<c>` spans so players can highlight each word as it's spoken.
+fn captions_to_vtt(captions: &cap_project::CaptionsData) -> String {
+    let mut vtt = String::from("WEBVTT\n\n");
+
+    for segment in &captions.segments {
+        // Cue text can't contain these, and a blank line would end the cue
+        let escape = |text: &str| {
+            text.trim()
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;")
+                .replace("\n\n", "\n")
+        };
+
+        let text = match segment.words.as_deref() {
+            Some(words) if !words.is_empty() => words
+                .iter()
+                .enumerate()
+                .map(|(i, word)| {
+                    let span = format!("<c>{}</c>", escape(&word.text));
+                    // The first word starts with the cue, so only later words need a timestamp
+                    if i == 0 {
+                        span
+                    } else {
+                        format!("<{}>{}", format_vtt_time(f64::from(word.start)), span)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
+            _ => escape(&segment.text),
+        };
+
+        vtt.push_str(&format!(
+            "{} --> {}\n{}\n\n",
+            format_vtt_time(f64::from(segment.start)),
+            format_vtt_time(f64::from(segment.end)),
+            text
+        ));
+    }
+
+    vtt
+}
+
+/// Export the project's captions as WebVTT, including word timings when available.
+/// Writes to `output` when given, and returns the file contents either way.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_captions_to_vtt(
+    project_path: PathBuf,
+    output: Option<PathBuf>,
+) -> Result<String, String> {
+    let captions = cap_project::ProjectConfiguration::load(&project_path)
+        .map_err(|e| format!("Failed to load project: {}", e))?
+        .captions
+        .filter(|captions| !captions.segments.is_empty())
+        .ok_or_else(|| "This project has no captions".to_string())?;
+
+    let vtt = captions_to_vtt(&captions);
+
+    if let Some(output) = output {
+        std::fs::write(&output, &vtt).map_err(|e| format!("Failed to write VTT file: {}", e))?;
+        tracing::info!("Wrote VTT captions to {:?}", output);
+    }
+
+    Ok(vtt)
+}
+
 // Helper function to convert multi-channel audio to mono
 fn convert_to_mono(samples: &[f32], channels: usize) -> Vec<f32> {
     if channels == 1 {
@@ -1318,12 +2769,551 @@ fn convert_to_mono(samples: &[f32], channels: usize) -> Vec<f32> {
     mono_samples
 }
 
-// Helper function to mix two sample arrays together
-fn mix_samples(dest: &mut Vec<f32>, source: &[f32]) -> usize {
-    let length = dest.len().min(source.len());
-    for i in 0..length {
-        // Simple mix with equal weight (0.5) to prevent clipping
-        dest[i] = (dest[i] + source[i]) * 0.5;
+/// Emitted when subtitles are imported for a recording so an open editor can reload them
+#[derive(Debug, Serialize, Deserialize, Type, tauri_specta::Event, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptionsImported {
+    pub video_id: String,
+}
+
+pub const IMPORTABLE_CAPTION_EXTENSIONS: &[&str] = &["srt", "vtt"];
+
+/// Parse a subtitle timestamp such as `00:01:02,500` (SRT) or `01:02.500` (WebVTT) into seconds
+fn parse_subtitle_time(time: &str) -> Result<f64, String> {
+    let invalid = || format!("Invalid subtitle timestamp '{}'", time);
+
+    let normalized = time.trim().replace(',', ".");
+    let parts = normalized.split(':').collect::<Vec<_>>();
+    let (hours, minutes, seconds) = match parts.as_slice() {
+        [hours, minutes, seconds] => (*hours, *minutes, *seconds),
+        [minutes, seconds] => ("0", *minutes, *seconds),
+        _ => return Err(invalid()),
+    };
+
+    let hours = hours.parse::<u32>().map_err(|_| invalid())?;
+    let minutes = minutes.parse::<u32>().map_err(|_| invalid())?;
+    let seconds = seconds.parse::<f64>().map_err(|_| invalid())?;
+
+    Ok(hours as f64 * 3600.0 + minutes as f64 * 60.0 + seconds)
+}
+
+/// Remove inline markup like `<i>` or `<v Speaker>` from cue text
+fn strip_subtitle_tags(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut in_tag = false;
+
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => result.push(c),
+            _ => {}
+        }
+    }
+
+    result
+}
+
+/// Parse SRT or WebVTT subtitles into caption segments.
+/// Blocks without a timing line (the WebVTT header, NOTE and STYLE blocks) are skipped.
+pub fn parse_subtitles(content: &str) -> Result<Vec<CaptionSegment>, String> {
+    let content = content.trim_start_matches('\u{feff}');
+
+    let mut blocks = vec![];
+    let mut current = vec![];
+    for line in content.lines() {
+        if line.trim().is_empty() {
+            if !current.is_empty() {
+                blocks.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(line.trim_end());
+        }
+    }
+    if !current.is_empty() {
+        blocks.push(current);
+    }
+
+    let mut segments = vec![];
+
+    for block in blocks {
+        let Some(timing_index) = block.iter().position(|line| line.contains("-->")) else {
+            continue;
+        };
+
+        let (start, end) = block[timing_index].split_once("-->").unwrap();
+        let start = parse_subtitle_time(start)?;
+        // WebVTT cue settings may follow the end time
+        let end = parse_subtitle_time(end.split_whitespace().next().unwrap_or_default())?;
+
+        let text = strip_subtitle_tags(&block[timing_index + 1..].join("\n"));
+        if text.trim().is_empty() || end <= start {
+            continue;
+        }
+
+        segments.push(CaptionSegment {
+            id: format!("segment-{}", segments.len()),
+            start: start as f32,
+            end: end as f32,
+            text: text.trim().to_string(),
+            style_override: None,
+            words: None,
+        });
+    }
+
+    if segments.is_empty() {
+        return Err("No captions found in subtitle file".to_string());
+    }
+
+    Ok(segments)
+}
+
+/// Import an SRT or WebVTT file as the captions for a video, replacing any existing segments
+/// while keeping the current caption settings
+#[tauri::command]
+#[specta::specta]
+pub async fn import_captions(
+    video_id: String,
+    path: PathBuf,
+    app: AppHandle,
+) -> Result<CaptionData, String> {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase())
+        .unwrap_or_default();
+    if !IMPORTABLE_CAPTION_EXTENSIONS.contains(&extension.as_str()) {
+        return Err("Only SRT and VTT subtitle files can be imported".to_string());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read subtitle file: {}", e))?;
+    let segments = parse_subtitles(&content)?;
+
+    let mut settings = load_captions(video_id.clone(), app.clone())
+        .await
+        .ok()
+        .flatten()
+        .and_then(|captions| captions.settings)
+        .unwrap_or_default();
+    settings.enabled = true;
+
+    let captions = CaptionData {
+        segments,
+        settings: Some(settings),
+    };
+
+    save_captions(video_id.clone(), captions.clone(), app.clone()).await?;
+
+    tracing::info!(
+        "Imported {} caption segments from {:?}",
+        captions.segments.len(),
+        path
+    );
+
+    CaptionsImported { video_id }.emit(&app).ok();
+
+    Ok(captions)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn summarizes_captions_by_distinctive_words() {
+        let segment = |id: &str, text: &str| CaptionSegment {
+            id: id.to_string(),
+            start: 0.0,
+            end: 1.0,
+            text: text.to_string(),
+            style_override: None,
+            words: None,
+        };
+        let segments = [
+            segment("1", "Okay so um let's get started."),
+            segment("2", "Today we're migrating the billing database."),
+            segment("3", "The billing database migration needs a maintenance window."),
+            segment("4", "Yeah that's it."),
+        ];
+
+        let summary = summarize_segments(&segments).unwrap();
+
+        assert_eq!(&summary.keywords[..2], ["billing", "database"]);
+        assert!(!summary.keywords.iter().any(|keyword| keyword == "okay"));
+        assert!(summary.summary.ends_with("needs a maintenance window."));
+        assert!(!summary.summary.contains("Yeah"));
+
+        assert_eq!(summarize_segments(&[segment("1", "Um, yeah.")]), None);
+    }
+
+    #[test]
+    fn finds_caption_matches_ignoring_case() {
+        let text = "So the Größe of the deploy matters";
+
+        let range = find_ignore_case(text, "GRÖßE");
+        assert_eq!(range.map(|range| &text[range]), Some("Größe"));
+        assert_eq!(find_ignore_case(text, "rollback"), None);
+
+        let long = format!("{}needle{}", "a".repeat(50), "b".repeat(50));
+        let range = find_ignore_case(&long, "Needle").unwrap();
+        assert_eq!(
+            caption_snippet(&long, range),
+            format!("…{}needle{}…", "a".repeat(40), "b".repeat(40))
+        );
+    }
+
+    #[test]
+    fn finds_filler_words() {
+        let word = |text: &str, start: f32, end: f32| CaptionWord {
+            start,
+            end,
+            text: text.to_string(),
+        };
+        let segments = [
+            CaptionSegment {
+                id: "1".to_string(),
+                start: 0.0,
+                end: 1.0,
+                text: "Um, so you know".to_string(),
+                style_override: None,
+                words: Some(vec![
+                    word("Um,", 0.0, 0.3),
+                    word(" so", 0.3, 0.5),
+                    word(" you", 0.5, 0.7),
+                    word(" know", 0.7, 0.9),
+                ]),
+            },
+            CaptionSegment {
+                id: "2".to_string(),
+                start: 2.0,
+                end: 3.0,
+                text: "I LIKE it".to_string(),
+                style_override: None,
+                words: None,
+            },
+        ];
+        let fillers = ["um", "you know", "like"]
+            .map(|filler| filler.split(' ').map(str::to_string).collect::<Vec<_>>());
+
+        let hits = find_filler_words(&segments, &fillers);
+
+        assert_eq!(hits.len(), 3);
+        assert_eq!(hits[0], ("um".to_string(), 0.0, 0.3));
+        assert_eq!(hits[1], ("you know".to_string(), 0.5, 0.9));
+        assert_eq!(hits[2].0, "like");
+        assert!((hits[2].1 - 2.0 - 2.0 / 9.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn removes_cut_words_from_captions() {
+        let word = |text: &str, start: f32, end: f32| CaptionWord {
+            start,
+            end,
+            text: text.to_string(),
+        };
+        let segment = |id: &str, words: Vec<CaptionWord>| CaptionSegment {
+            id: id.to_string(),
+            start: words[0].start,
+            end: words[words.len() - 1].end,
+            text: String::new(),
+            style_override: None,
+            words: Some(words),
+        };
+        let mut segments = vec![
+            segment(
+                "1",
+                vec![
+                    word("Um,", 0.0, 0.3),
+                    word("we", 0.3, 0.5),
+                    word("ship", 0.5, 0.9),
+                ],
+            ),
+            segment("2", vec![word("uh", 1.0, 1.2)]),
+        ];
+
+        let spans = merge_spans(vec![(0.95, 1.25), (0.0, 0.35), (0.3, 0.32)]);
+        assert_eq!(spans, [(0.0, 0.35), (0.95, 1.25)]);
+
+        remove_cut_words(&mut segments, &spans);
+
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].text, "we ship");
+        assert_eq!(segments[0].words.as_ref().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn retimes_captions_after_cut() {
+        let mut segments = vec![
+            CaptionSegment {
+                id: "1".to_string(),
+                start: 1.0,
+                end: 3.0,
+                text: "so we ship".to_string(),
+                style_override: None,
+                words: Some(vec![
+                    CaptionWord {
+                        start: 1.0,
+                        end: 1.5,
+                        text: "so".to_string(),
+                    },
+                    CaptionWord {
+                        start: 2.5,
+                        end: 3.0,
+                        text: "ship".to_string(),
+                    },
+                ]),
+            },
+            CaptionSegment {
+                id: "2".to_string(),
+                start: 1.75,
+                end: 2.25,
+                text: "um".to_string(),
+                style_override: None,
+                words: None,
+            },
+        ];
+        let mut lower_thirds = vec![cap_project::LowerThird {
+            start: 4.0,
+            end: 6.0,
+            ..Default::default()
+        }];
+
+        retime_after_cut(&mut segments, &mut lower_thirds, 1.5, 2.5);
+
+        assert_eq!(segments.len(), 1);
+        assert_eq!((segments[0].start, segments[0].end), (1.0, 2.0));
+        let words = segments[0].words.as_ref().unwrap();
+        assert_eq!((words[1].start, words[1].end), (1.5, 2.0));
+        assert_eq!((lower_thirds[0].start, lower_thirds[0].end), (3.0, 5.0));
+    }
+
+    #[test]
+    fn parses_srt() {
+        let srt = "1\n00:00:01,000 --> 00:00:02,500\nHello\n\n2\n00:00:03,000 --> 00:00:04,000\n<i>World</i>\nagain\n";
+
+        let segments = parse_subtitles(srt).unwrap();
+
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].start, 1.0);
+        assert_eq!(segments[0].end, 2.5);
+        assert_eq!(segments[0].text, "Hello");
+        assert_eq!(segments[1].text, "World\nagain");
+    }
+
+    #[test]
+    fn parses_vtt() {
+        let vtt = "WEBVTT\n\nNOTE a comment\n\n00:01.000 --> 00:02.000 align:start\nHi there\n\nintro\n01:00:00.000 --> 01:00:01.000\nLater\n";
+
+        let segments = parse_subtitles(vtt).unwrap();
+
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].start, 1.0);
+        assert_eq!(segments[0].end, 2.0);
+        assert_eq!(segments[1].start, 3600.0);
+        assert_eq!(segments[1].text, "Later");
+    }
+
+    #[test]
+    fn formats_ass() {
+        assert_eq!(format_ass_time(3723.456), "1:02:03.46");
+        assert_eq!(ass_color("#FF8000", 100), "&H000080FF");
+
+        let ass = captions_to_ass(&cap_project::CaptionsData {
+            segments: vec![CaptionSegment {
+                start: 1.0,
+                end: 2.5,
+                text: "Hello\nworld".to_string(),
+                ..Default::default()
+            }],
+            settings: CaptionSettings::default(),
+        });
+
+        assert!(ass.contains("[V4+ Styles]"));
+        assert!(ass.contains("Dialogue: 0,0:00:01.00,0:00:02.50,Default,,0,0,0,,Hello\\Nworld"));
+
+        let ass = captions_to_ass(&cap_project::CaptionsData {
+            segments: vec![CaptionSegment {
+                start: 1.0,
+                end: 2.5,
+                text: "{\\b1}C:\\path".to_string(),
+                ..Default::default()
+            }],
+            settings: CaptionSettings::default(),
+        });
+
+        assert!(ass.contains(",,\\{\\\\b1\\}C:\\\\path\n"));
+    }
+
+    #[test]
+    fn offsets_ass_captions_like_the_caption_layer() {
+        let ass = |position: &str, position_offset: f32| {
+            captions_to_ass(&cap_project::CaptionsData {
+                segments: vec![CaptionSegment {
+                    start: 1.0,
+                    end: 2.0,
+                    text: "Hi".to_string(),
+                    ..Default::default()
+                }],
+                settings: CaptionSettings {
+                    position: position.to_string(),
+                    position_offset,
+                    ..Default::default()
+                },
+            })
+        };
+
+        // Moving bottom captions up takes a bigger margin from the bottom edge
+        assert!(ass("bottom", -0.1).contains(",2,20,20,128,1\n"));
+        assert!(ass("bottom", 0.1).contains(",2,20,20,0,1\n"));
+        assert!(ass("top", 0.1).contains(",8,20,20,128,1\n"));
+        assert!(ass("middle", 0.1).contains(",,{\\pos(960,648)}Hi\n"));
+        assert!(ass("middle", 0.0).contains(",,Hi\n"));
+    }
+
+    #[test]
+    fn formats_vtt_word_timings() {
+        let word = |start: f32, end: f32, text: &str| CaptionWord {
+            start,
+            end,
+            text: text.to_string(),
+        };
+
+        let vtt = captions_to_vtt(&cap_project::CaptionsData {
+            segments: vec![
+                CaptionSegment {
+                    start: 1.0,
+                    end: 2.0,
+                    text: "Hello world".to_string(),
+                    words: Some(vec![word(1.0, 1.4, "Hello"), word(1.5, 2.0, "world")]),
+                    ..Default::default()
+                },
+                CaptionSegment {
+                    start: 3.0,
+                    end: 4.0,
+                    text: "a < b".to_string(),
+                    ..Default::default()
+                },
+            ],
+            settings: CaptionSettings::default(),
+        });
+
+        assert!(vtt.starts_with("WEBVTT\n\n"));
+        assert!(vtt
+            .contains("00:00:01.000 --> 00:00:02.000\n<c>Hello</c> <00:00:01.500><c>world</c>\n"));
+        assert!(vtt.contains("00:00:03.000 --> 00:00:04.000\na &lt; b\n"));
+    }
+
+    #[test]
+    fn splits_segment_at_word_boundary() {
+        let segment = CaptionSegment {
+            id: "a".to_string(),
+            start: 0.0,
+            end: 4.0,
+            text: "one two three four".to_string(),
+            ..Default::default()
+        };
+
+        let (first, second) = split_segment(&segment, 2.0).unwrap();
+        assert_eq!((first.start, first.end), (0.0, 2.0));
+        assert_eq!((second.start, second.end), (2.0, 4.0));
+        assert_eq!(first.text, "one two");
+        assert_eq!(second.text, "three four");
+        assert_ne!(first.id, second.id);
+
+        assert!(split_segment(&segment, 0.0).is_err());
+        assert!(split_segment(&segment, 4.0).is_err());
+    }
+
+    #[test]
+    fn remaps_captions_through_cuts() {
+        let timeline = cap_project::TimelineConfiguration {
+            segments: vec![
+                cap_project::TimelineSegment {
+                    recording_segment: 0,
+                    timescale: 1.0,
+                    start: 0.0,
+                    end: 5.0,
+                    interpolate: false,
+                },
+                cap_project::TimelineSegment {
+                    recording_segment: 0,
+                    timescale: 1.0,
+                    start: 10.0,
+                    end: 20.0,
+                    interpolate: false,
+                },
+            ],
+            zoom_segments: vec![],
+            scene_segments: None,
+        };
+        let segment = |start: f32, end: f32| CaptionSegment {
+            start,
+            end,
+            ..Default::default()
+        };
+
+        let remapped = remap_captions_to_timeline(
+            vec![segment(1.0, 2.0), segment(6.0, 9.0), segment(12.0, 14.0)],
+            &timeline,
+            &[],
+        );
+
+        assert_eq!(remapped.len(), 2);
+        assert_eq!((remapped[0].start, remapped[0].end), (1.0, 2.0));
+        assert_eq!((remapped[1].start, remapped[1].end), (7.0, 9.0));
+    }
+
+    #[test]
+    fn remaps_captions_from_later_recording_segments() {
+        let timeline = cap_project::TimelineConfiguration {
+            segments: vec![
+                cap_project::TimelineSegment {
+                    recording_segment: 0,
+                    timescale: 1.0,
+                    start: 2.0,
+                    end: 8.0,
+                    interpolate: false,
+                },
+                cap_project::TimelineSegment {
+                    recording_segment: 1,
+                    timescale: 1.0,
+                    start: 0.0,
+                    end: 5.0,
+                    interpolate: false,
+                },
+            ],
+            zoom_segments: vec![],
+            scene_segments: None,
+        };
+        let segment = |start: f32, end: f32| CaptionSegment {
+            start,
+            end,
+            ..Default::default()
+        };
+
+        // The second recording segment's audio follows the first's 8 seconds
+        let remapped = remap_captions_to_timeline(
+            vec![segment(1.0, 1.5), segment(9.0, 10.0)],
+            &timeline,
+            &[0.0, 8.0],
+        );
+
+        assert_eq!(remapped.len(), 1);
+        assert_eq!((remapped[0].start, remapped[0].end), (7.0, 8.0));
+    }
+
+    #[test]
+    fn rejects_files_without_cues() {
+        assert!(parse_subtitles("WEBVTT\n\n").is_err());
+        assert!(parse_subtitles("00:00:01,000 --> nonsense\nHi").is_err());
+    }
+
+    #[test]
+    fn english_only_models_reject_other_languages() {
+        assert!(WhisperModel::BaseEn.validate_language("en").is_ok());
+        assert!(WhisperModel::BaseEn.validate_language("auto").is_ok());
+        assert!(WhisperModel::BaseEn.validate_language("fr").is_err());
+        assert!(WhisperModel::Base.validate_language("fr").is_ok());
     }
-    length
 }
@@ -127,6 +127,10 @@ impl DeepLinkAction {
                     capture_target,
                     capture_system_audio,
                     mode,
+                    countdown: None,
+                    audio_only: false,
+                    app_audio: None,
+                    push_to_talk: false,
                 };
 
                 crate::recording::start_recording(app.clone(), state, inputs).await
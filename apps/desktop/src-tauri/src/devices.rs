@@ -0,0 +1,200 @@
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use cap_media::{
+    data::AudioInfo,
+    feeds::{AudioInputFeed, CameraFeed},
+};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Manager};
+use tauri_specta::Event;
+use tokio::sync::{Mutex, RwLock};
+use tracing::{info, warn};
+
+use crate::{App, MutableState};
+
+const WAKE_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+// Ticks don't fire while the system sleeps, so a tick arriving this late means it just woke
+const WAKE_THRESHOLD: Duration = Duration::from_secs(30);
+
+#[derive(Serialize, Deserialize, Type, Clone, Copy, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum InputDeviceKind {
+    Microphone,
+    Camera,
+}
+
+/// Emitted when a selected input device has disappeared, so the user can pick another one
+#[derive(Serialize, Deserialize, Type, Clone, Debug, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct InputDeviceLost {
+    pub kind: InputDeviceKind,
+    pub label: String,
+}
+
+/// Revalidates input devices whenever the system wakes from sleep
+pub fn init(app: &AppHandle) {
+    let app = app.clone();
+
+    tauri::async_runtime::spawn(async move {
+        let mut last_tick = SystemTime::now();
+
+        loop {
+            tokio::time::sleep(WAKE_CHECK_INTERVAL).await;
+
+            let now = SystemTime::now();
+            let elapsed = now.duration_since(last_tick).unwrap_or_default();
+            last_tick = now;
+
+            if elapsed < WAKE_THRESHOLD {
+                continue;
+            }
+
+            info!(
+                "System woke after {}s, revalidating input devices",
+                elapsed.as_secs()
+            );
+
+            let state = app.state::<Arc<RwLock<App>>>();
+            if let Err(e) = revalidate(&app, &state).await {
+                warn!("Failed to revalidate input devices: {e}");
+            }
+        }
+    });
+}
+
+/// Reopens the selected mic and cameras if they've changed identity, eg. been re-enumerated
+/// while the system slept. Devices that are no longer available are deselected and reported
+/// with `InputDeviceLost`. Live feeds are only replaced once their replacement is open.
+async fn revalidate(app: &AppHandle, state: &RwLock<App>) -> Result<(), String> {
+    let mut state = state.write().await;
+
+    // Reopening devices mid-recording would cut out the recording's audio and camera
+    if state.current_recording.is_some() {
+        return Ok(());
+    }
+
+    if let Some(label) = state.mic_label.clone() {
+        let listed = AudioInputFeed::list_devices()
+            .get(&label)
+            .map(|(_, config)| AudioInfo::from_stream_config(config));
+        let current = state.mic_feed.as_ref().map(|feed| feed.audio_info());
+
+        match listed {
+            Some(info) if Some(info) == current => {}
+            Some(_) => match AudioInputFeed::init(&label).await {
+                Ok(feed) => {
+                    feed.add_sender(state.mic_samples_tx.clone())
+                        .await
+                        .map_err(|e| e.to_string())?;
+                    crate::apply_mic_settings(app, &feed, &label)
+                        .await
+                        .map_err(|e| e.to_string())?;
+                    state.mic_feed = Some(feed);
+                }
+                Err(e) => warn!("Failed to reopen microphone '{label}': {e}"),
+            },
+            None => {
+                warn!("Microphone '{label}' is no longer available");
+                state.mic_feed = None;
+                state.mic_label = None;
+                InputDeviceLost {
+                    kind: InputDeviceKind::Microphone,
+                    label,
+                }
+                .emit(app)
+                .ok();
+            }
+        }
+    }
+
+    if let Some(camera_feed) = state.camera_feed.clone() {
+        match revalidate_camera(app, &camera_feed).await {
+            CameraCheck::Unchanged => {}
+            CameraCheck::Reopened(feed) => {
+                feed.attach(state.camera_tx.clone());
+                state.camera_feed = Some(Arc::new(Mutex::new(feed)));
+            }
+            CameraCheck::Lost(label) => {
+                warn!("Camera '{label}' is no longer available");
+                state.camera_feed = None;
+                InputDeviceLost {
+                    kind: InputDeviceKind::Camera,
+                    label,
+                }
+                .emit(app)
+                .ok();
+            }
+        }
+    }
+
+    let slots = state
+        .additional_camera_feeds
+        .iter()
+        .map(|(slot, feed)| (*slot, feed.clone()))
+        .collect::<Vec<_>>();
+    for (slot, camera_feed) in slots {
+        match revalidate_camera(app, &camera_feed).await {
+            CameraCheck::Unchanged => {}
+            CameraCheck::Reopened(feed) => {
+                state
+                    .additional_camera_feeds
+                    .insert(slot, Arc::new(Mutex::new(feed)));
+            }
+            CameraCheck::Lost(label) => {
+                warn!("Camera '{label}' in slot {slot} is no longer available");
+                state.additional_camera_feeds.remove(&slot);
+                InputDeviceLost {
+                    kind: InputDeviceKind::Camera,
+                    label,
+                }
+                .emit(app)
+                .ok();
+            }
+        }
+    }
+
+    Ok(())
+}
+
+enum CameraCheck {
+    Unchanged,
+    Reopened(CameraFeed),
+    Lost(String),
+}
+
+async fn revalidate_camera(app: &AppHandle, camera_feed: &Mutex<CameraFeed>) -> CameraCheck {
+    let camera_feed = camera_feed.lock().await;
+    if camera_feed.is_connected() {
+        return CameraCheck::Unchanged;
+    }
+
+    let label = camera_feed.camera_info.human_name();
+    if !CameraFeed::list_cameras().contains(&label) {
+        return CameraCheck::Lost(label);
+    }
+
+    match CameraFeed::init(&label).await {
+        Ok(feed) => {
+            feed.set_mirrored(crate::camera::camera_mirror(app).recording);
+            CameraCheck::Reopened(feed)
+        }
+        Err(e) => {
+            warn!("Failed to reopen camera '{label}': {e}");
+            CameraCheck::Unchanged
+        }
+    }
+}
+
+/// Check the selected mic and camera are still usable, reopening them if needed
+#[tauri::command]
+#[specta::specta]
+pub async fn revalidate_devices(
+    app: AppHandle,
+    state: MutableState<'_, App>,
+) -> Result<(), String> {
+    revalidate(&app, &state).await
+}
@@ -3,7 +3,7 @@ use std::path::PathBuf;
 use std::str::FromStr;
 
 use cap_editor::EditorState;
-use cap_project::{ProjectConfiguration, RecordingMeta, XY};
+use cap_project::{Crop, ProjectConfiguration, RecordingMeta, Rotation, XY};
 use clipboard_rs::Clipboard;
 use cap_rendering::ProjectRecordingsMeta;
 use serde::Serialize;
@@ -142,6 +142,84 @@ pub async fn get_mic_waveforms(editor_instance: WindowEditorInstance) -> Result<
     Ok(out)
 }
 
+/// Reports whether the editor is rendering on the GPU or the software fallback
+#[tauri::command]
+#[specta::specta]
+pub async fn get_render_backend(
+    editor_instance: WindowEditorInstance,
+) -> Result<cap_rendering::RenderBackend, String> {
+    Ok(editor_instance.render_constants.backend)
+}
+
+/// Crop the recording to a region of the display, or remove the crop with `None`.
+/// Camera, captions, cursor and zoom are all laid out over the cropped frame.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_project_crop(
+    editor_instance: WindowEditorInstance,
+    crop: Option<Crop>,
+) -> Result<(), String> {
+    let mut config = editor_instance.project_config.1.borrow().clone();
+    // Crops are in rotated coordinates
+    let screen_size = config.oriented_size(editor_instance.render_constants.options.screen_size);
+
+    if let Some(crop) = &crop {
+        if crop.size.x == 0 || crop.size.y == 0 {
+            return Err("Crop must have a non-zero size".to_string());
+        }
+
+        if crop.position.x + crop.size.x > screen_size.x
+            || crop.position.y + crop.size.y > screen_size.y
+        {
+            return Err(format!(
+                "Crop extends outside the {}x{} recording",
+                screen_size.x, screen_size.y
+            ));
+        }
+    }
+
+    config.background.crop = crop;
+
+    config
+        .write(&editor_instance.project_path)
+        .map_err(|e| e.to_string())?;
+    editor_instance.project_config.0.send(config).ok();
+
+    Ok(())
+}
+
+/// Rotate and flip the recording, for captures that came in the wrong way round.
+/// Any crop is removed, as it no longer covers the same region once the recording is reoriented.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_project_orientation(
+    editor_instance: WindowEditorInstance,
+    rotation: Rotation,
+    flip_horizontal: bool,
+    flip_vertical: bool,
+) -> Result<(), String> {
+    let mut config = editor_instance.project_config.1.borrow().clone();
+
+    if config.rotation == rotation
+        && config.flip_horizontal == flip_horizontal
+        && config.flip_vertical == flip_vertical
+    {
+        return Ok(());
+    }
+
+    config.rotation = rotation;
+    config.flip_horizontal = flip_horizontal;
+    config.flip_vertical = flip_vertical;
+    config.background.crop = None;
+
+    config
+        .write(&editor_instance.project_path)
+        .map_err(|e| e.to_string())?;
+    editor_instance.project_config.0.send(config).ok();
+
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn copy_video_to_clipboard(
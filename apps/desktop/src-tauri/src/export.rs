@@ -1,12 +1,28 @@
 use crate::{system::get_video_metadata, FramesRendered};
-use cap_export::ExporterBase;
-use cap_project::{RecordingMeta, XY};
-use serde::Deserialize;
+use cap_export::{
+    gif::GifExportSettings, metadata::ExportMetadata, mp4::ExportQuality,
+    resolution::OutputResolution, resolve_time_range, ExportControl, ExporterBase,
+};
+use cap_media::encoders::VideoEncoder;
+use cap_project::{
+    timeline_to_edl, timeline_to_fcpxml, AspectRatio, CaptionSettings, RecordingMeta,
+    StudioRecordingMeta, TimelineConfiguration, TimelineFormat, TimelineSegment, TimelineSource,
+    XY,
+};
+use cap_rendering::{lut::Lut3d, ProjectRecordingsMeta};
+use serde::{Deserialize, Serialize};
 use specta::Type;
-use std::path::PathBuf;
-use tracing::info;
+use std::{
+    collections::{HashMap, VecDeque},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Instant,
+};
+use tauri::{AppHandle, Manager};
+use tauri_specta::Event;
+use tracing::{error, info, warn};
 
-#[derive(Deserialize, Clone, Copy, Debug, Type)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Type)]
 #[serde(tag = "format")]
 pub enum ExportSettings {
     Mp4(cap_export::mp4::Mp4ExportSettings),
@@ -22,60 +38,447 @@ impl ExportSettings {
     }
 }
 
+/// Frames an export to a different aspect ratio than the project's, such as 9:16 for
+/// social media, with the project's background filling the extra space
+#[derive(Deserialize, Type, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportAspect {
+    pub aspect_ratio: AspectRatio,
+    /// Where the content sits horizontally when there's space beside it,
+    /// from -1 for the left edge to 1 for the right. Centered by default.
+    #[serde(default)]
+    pub offset_x: f64,
+}
+
+/// `start` and `end` limit the export to that part of the timeline, in seconds
 #[tauri::command]
 #[specta::specta]
+#[allow(clippy::too_many_arguments)]
 pub async fn export_video(
+    app: AppHandle,
     project_path: PathBuf,
     progress: tauri::ipc::Channel<FramesRendered>,
     settings: ExportSettings,
-) -> Result<PathBuf, String> {
+    export_metadata: Option<ExportMetadata>,
+    lut: Option<PathBuf>,
+    burn_captions: Option<bool>,
+    start: Option<f64>,
+    end: Option<f64>,
+    aspect: Option<ExportAspect>,
+) -> Result<ExportResult, String> {
+    let control = track_export(&app, &project_path);
+
+    let result = run_export(
+        project_path.clone(),
+        settings,
+        None,
+        export_metadata.unwrap_or_default(),
+        lut,
+        burn_captions,
+        aspect,
+        (start, end),
+        control.clone(),
+        move |frames| {
+            let _ = progress.send(frames);
+        },
+    )
+    .await;
+
+    untrack_export(&app, project_path, &control);
+
+    result
+}
+
+#[derive(Deserialize, Type, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchExportJob {
+    pub project_path: PathBuf,
+    /// Defaults to the project's usual export location
+    pub output_path: Option<PathBuf>,
+    pub settings: ExportSettings,
+}
+
+#[derive(Serialize, Type, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchExportProgress {
+    pub project_path: PathBuf,
+    pub frames: FramesRendered,
+}
+
+#[derive(Serialize, Type, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchExportResult {
+    pub project_path: PathBuf,
+    /// The exported file, if the export succeeded
+    pub output_path: Option<PathBuf>,
+    pub error: Option<String>,
+}
+
+/// Export each job in turn, so only one project's renderer is in memory at a time.
+/// Every job is attempted, with failures reported per job. Each can be stopped with
+/// `cancel_export` while it's running.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_videos_batch(
+    app: AppHandle,
+    jobs: Vec<BatchExportJob>,
+    progress: tauri::ipc::Channel<BatchExportProgress>,
+) -> Vec<BatchExportResult> {
+    let mut results = Vec::with_capacity(jobs.len());
+
+    for job in jobs {
+        let control = track_export(&app, &job.project_path);
+
+        let result = run_export(
+            job.project_path.clone(),
+            job.settings,
+            job.output_path,
+            Default::default(),
+            None,
+            None,
+            None,
+            (None, None),
+            control.clone(),
+            {
+                let progress = progress.clone();
+                let project_path = job.project_path.clone();
+                move |frames| {
+                    let _ = progress.send(BatchExportProgress {
+                        project_path: project_path.clone(),
+                        frames,
+                    });
+                }
+            },
+        )
+        .await;
+
+        untrack_export(&app, job.project_path.clone(), &control);
+
+        let (output_path, error) = match result {
+            Ok(result) => (Some(result.path), None),
+            Err(e) => {
+                error!("Batch export of {} failed: {e}", job.project_path.display());
+                (None, Some(e))
+            }
+        };
+        results.push(BatchExportResult {
+            project_path: job.project_path,
+            output_path,
+            error,
+        });
+    }
+
+    results
+}
+
+/// Exports started from the UI, by project path, so they can be cancelled
+pub type ActiveExports = Mutex<HashMap<PathBuf, ExportControl>>;
+
+fn track_export(app: &AppHandle, project_path: &Path) -> ExportControl {
+    let control = app.state::<ExportControl>().for_export();
+    app.state::<ActiveExports>()
+        .lock()
+        .unwrap()
+        .insert(project_path.to_path_buf(), control.clone());
+    control
+}
+
+fn untrack_export(app: &AppHandle, project_path: PathBuf, control: &ExportControl) {
+    {
+        // Another export of the project may have replaced this one since
+        let state = app.state::<ActiveExports>();
+        let mut active = state.lock().unwrap();
+        if active.get(&project_path) == Some(control) {
+            active.remove(&project_path);
+        }
+    }
+
+    if control.is_cancelled() {
+        let _ = ExportCancelled { project_path }.emit(app);
+    }
+}
+
+#[derive(Serialize, Deserialize, Type, Clone, Debug, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportCancelled {
+    pub project_path: PathBuf,
+}
+
+/// Stop the project's running export at the next frame and delete its partial output.
+/// `ExportCancelled` is emitted once it has stopped.
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_export(app: AppHandle, project_path: PathBuf) -> Result<(), String> {
+    let state = app.state::<ActiveExports>();
+    let active = state.lock().unwrap();
+    let control = active
+        .get(&project_path)
+        .ok_or_else(|| "This project isn't being exported".to_string())?;
+
+    control.cancel();
+    info!("Cancelling export of {}", project_path.display());
+
+    Ok(())
+}
+
+// Keeps GIFs to a sensible size, as every frame is stored with little compression
+const MAX_GIF_DIMENSION: u32 = 1280;
+// Browsers slow down GIFs with frame delays under 20ms
+const MAX_GIF_FPS: u32 = 50;
+
+/// Renders the part of the timeline between `start` and `end` seconds as a looping GIF.
+/// `width` and `fps` are capped so long or high resolution projects don't produce huge files.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_gif(
+    app: AppHandle,
+    project_path: PathBuf,
+    output: PathBuf,
+    fps: u32,
+    width: u32,
+    start: f64,
+    end: f64,
+) -> Result<(), String> {
+    let control = app.state::<ExportControl>().inner().clone();
+
     let exporter_base = ExporterBase::builder(project_path)
+        .with_control(control)
+        .with_output_path(output)
+        .with_time_range(Some(start), Some(end))
         .build()
         .await
-        .map_err(|e| {
-            sentry::capture_message(&e.to_string(), sentry::Level::Error);
-            e.to_string()
-        })?;
+        .map_err(|e| e.to_string())?;
 
-    let total_frames = exporter_base.total_frames(settings.fps());
+    let settings = GifExportSettings {
+        fps: fps.clamp(1, MAX_GIF_FPS),
+        // Capped on both sides so tall outputs stay small too
+        resolution_base: XY::new(width.clamp(2, MAX_GIF_DIMENSION), MAX_GIF_DIMENSION),
+        output_resolution: None,
+    };
 
-    let _ = progress.send(FramesRendered {
-        rendered_count: 0,
-        total_frames,
-    });
-
-    let output_path = match settings {
-        ExportSettings::Mp4(settings) => {
-            settings
-                .export(exporter_base, move |frame_index| {
-                    // Ensure progress never exceeds total frames
-                    let _ = progress.send(FramesRendered {
-                        rendered_count: (frame_index + 1).min(total_frames),
-                        total_frames,
-                    });
-                })
-                .await
+    let output_path = settings.export(exporter_base, |_| {}).await?;
+
+    info!("Exported GIF to {}", output_path.display());
+
+    Ok(())
+}
+
+// Enough frames to smooth out per-frame jitter while still following changes in encoding speed
+const PROGRESS_RATE_WINDOW: usize = 30;
+
+/// Times an export's progress, with the frame rate taken over the last `PROGRESS_RATE_WINDOW`
+/// frames rather than since the start, so ETAs react when encoding speeds up or slows down.
+struct ProgressTimer {
+    started: Instant,
+    recent: VecDeque<(u32, Instant)>,
+}
+
+impl ProgressTimer {
+    fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            recent: VecDeque::with_capacity(PROGRESS_RATE_WINDOW + 1),
         }
-        ExportSettings::Gif(settings) => {
-            settings
-                .export(exporter_base, move |frame_index| {
-                    // Ensure progress never exceeds total frames
-                    let _ = progress.send(FramesRendered {
-                        rendered_count: (frame_index + 1).min(total_frames),
-                        total_frames,
-                    });
-                })
+    }
+
+    fn progress(&mut self, rendered_count: u32, total_frames: u32) -> FramesRendered {
+        let now = Instant::now();
+
+        FramesRendered {
+            rendered_count,
+            total_frames,
+            elapsed_secs: now.duration_since(self.started).as_secs_f64(),
+            fps: self.record(rendered_count, now),
+        }
+    }
+
+    fn record(&mut self, rendered_count: u32, at: Instant) -> f64 {
+        self.recent.push_back((rendered_count, at));
+        if self.recent.len() > PROGRESS_RATE_WINDOW + 1 {
+            self.recent.pop_front();
+        }
+
+        let (Some((first_count, first_at)), Some((last_count, last_at))) =
+            (self.recent.front(), self.recent.back())
+        else {
+            return 0.0;
+        };
+
+        let secs = last_at.duration_since(*first_at).as_secs_f64();
+        if secs <= 0.0 {
+            return 0.0;
+        }
+
+        last_count.saturating_sub(*first_count) as f64 / secs
+    }
+}
+
+#[derive(Serialize, Type, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportResult {
+    pub path: PathBuf,
+    /// The encoder MP4s were encoded with, after falling back from unavailable hardware encoders
+    pub encoder: Option<VideoEncoder>,
+}
+
+/// Exports a project, reporting progress as frames are rendered.
+/// `output_path` overrides where the export is written.
+/// `metadata` is written into MP4 containers, with the title defaulting to the recording's name.
+/// `lut` replaces the project's color LUT for this export.
+/// `burn_captions` overrides whether the project's captions are rendered into the video.
+/// `aspect` frames the output to another aspect ratio, with the resolution turned to match.
+/// `time_range` is the start and end of the part of the timeline to export, defaulting to all of it.
+#[allow(clippy::too_many_arguments)]
+pub async fn run_export(
+    project_path: PathBuf,
+    settings: ExportSettings,
+    output_path: Option<PathBuf>,
+    metadata: ExportMetadata,
+    lut: Option<PathBuf>,
+    burn_captions: Option<bool>,
+    aspect: Option<ExportAspect>,
+    (start, end): (Option<f64>, Option<f64>),
+    control: ExportControl,
+    on_progress: impl Fn(FramesRendered) + Send + 'static,
+) -> Result<ExportResult, String> {
+    // Probed up front so the result reports the encoder that's actually used
+    let (settings, encoder) = match settings {
+        ExportSettings::Mp4(mut settings) => {
+            let encoder = settings.encoder;
+            settings.encoder = tokio::task::spawn_blocking(move || encoder.resolve())
                 .await
+                .map_err(|e| e.to_string())?;
+            (ExportSettings::Mp4(settings), Some(settings.encoder))
+        }
+        settings => (settings, None),
+    };
+
+    let mut builder = ExporterBase::builder(project_path)
+        .with_control(control.clone())
+        .with_metadata(metadata)
+        .with_time_range(start, end);
+
+    if let Some(output_path) = output_path {
+        builder = builder.with_output_path(output_path);
+    }
+
+    if let Some(lut) = lut {
+        // Fail up front rather than silently exporting without the requested look
+        Lut3d::load(&lut)?;
+        builder = builder.with_lut(lut);
+    }
+
+    if let Some(burn_captions) = burn_captions {
+        builder = builder.with_burned_captions(burn_captions);
+    }
+
+    let mut settings = settings;
+    if let Some(aspect) = aspect {
+        // A 1920x1080 resolution should give a 1080x1920 vertical video, not a 608x1080 one
+        match &mut settings {
+            ExportSettings::Mp4(settings) => {
+                settings.resolution_base =
+                    orient_resolution_base(settings.resolution_base, &aspect.aspect_ratio)
+            }
+            ExportSettings::Gif(settings) => {
+                settings.resolution_base =
+                    orient_resolution_base(settings.resolution_base, &aspect.aspect_ratio)
+            }
         }
+        builder = builder.with_aspect_ratio(aspect.aspect_ratio, aspect.offset_x);
     }
-    .map_err(|e| {
+
+    let exporter_base = builder.build().await.map_err(|e| {
+        sentry::capture_message(&e.to_string(), sentry::Level::Error);
+        e.to_string()
+    })?;
+
+    let total_frames = exporter_base.total_frames(settings.fps());
+    let partial_output = match settings {
+        ExportSettings::Mp4(_) => exporter_base.output_path().to_path_buf(),
+        ExportSettings::Gif(_) => exporter_base.output_path().with_extension("gif"),
+    };
+
+    let mut timer = ProgressTimer::new();
+    on_progress(timer.progress(0, total_frames));
+
+    let on_frame = move |frame_index: u32| {
+        // Ensure progress never exceeds total frames
+        on_progress(timer.progress((frame_index + 1).min(total_frames), total_frames));
+    };
+
+    let result = match settings {
+        ExportSettings::Mp4(settings) => settings.export(exporter_base, on_frame).await,
+        ExportSettings::Gif(settings) => settings.export(exporter_base, on_frame).await,
+    };
+
+    // Whatever was written before the export stopped isn't worth keeping
+    if control.is_cancelled() {
+        if let Err(e) = std::fs::remove_file(&partial_output) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!("Failed to delete cancelled export: {e}");
+            }
+        }
+
+        info!("Export to {} cancelled", partial_output.display());
+        return Err(cap_export::ExportError::Cancelled.to_string());
+    }
+
+    let output_path = result.map_err(|e| {
         sentry::capture_message(&e.to_string(), sentry::Level::Error);
         e.to_string()
     })?;
 
     info!("Exported to {} completed", output_path.display());
 
-    Ok(output_path)
+    Ok(ExportResult {
+        path: output_path,
+        encoder,
+    })
+}
+
+/// Swap the resolution's width and height if it's oriented differently to `aspect_ratio`,
+/// so its long edge stays the long edge of the output
+fn orient_resolution_base(resolution_base: XY<u32>, aspect_ratio: &AspectRatio) -> XY<u32> {
+    let portrait = aspect_ratio.ratio() < 1.0;
+    let landscape = aspect_ratio.ratio() > 1.0;
+
+    if (portrait && resolution_base.x > resolution_base.y)
+        || (landscape && resolution_base.x < resolution_base.y)
+    {
+        XY::new(resolution_base.y, resolution_base.x)
+    } else {
+        resolution_base
+    }
+}
+
+#[derive(Serialize, Deserialize, Type, Clone, Debug, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportPauseChanged {
+    pub paused: bool,
+}
+
+/// Pause all running exports at the next frame boundary, freeing up the GPU until `resume_export`
+#[tauri::command]
+#[specta::specta]
+pub fn pause_export(app: AppHandle) {
+    app.state::<ExportControl>().pause();
+    info!("Exports paused");
+    let _ = ExportPauseChanged { paused: true }.emit(&app);
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn resume_export(app: AppHandle) {
+    app.state::<ExportControl>().resume();
+    info!("Exports resumed");
+    let _ = ExportPauseChanged { paused: false }.emit(&app);
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn is_export_paused(app: AppHandle) -> bool {
+    app.state::<ExportControl>().is_paused()
 }
 
 #[derive(Debug, serde::Serialize, specta::Type)]
@@ -92,7 +495,15 @@ pub async fn get_export_estimates(
     path: PathBuf,
     resolution: XY<u32>,
     fps: u32,
+    output_resolution: Option<OutputResolution>,
+    quality: Option<ExportQuality>,
+    start: Option<f64>,
+    end: Option<f64>,
 ) -> Result<ExportEstimates, String> {
+    if let Some(quality) = &quality {
+        quality.validate()?;
+    }
+
     let screen_metadata = get_video_metadata(path.clone()).await?;
     let camera_metadata = get_video_metadata(path.clone()).await.ok();
 
@@ -113,8 +524,13 @@ pub async fn get_export_estimates(
     } else {
         raw_duration
     };
+    let (start, end) = resolve_time_range(start, end, duration_seconds)?;
+    let duration_seconds = end - start;
 
-    let (width, height) = (resolution.x, resolution.y);
+    let (width, height) = match output_resolution {
+        Some(output_resolution) => output_resolution.size(),
+        None => (resolution.x, resolution.y),
+    };
 
     let base_bitrate = if width <= 1280 && height <= 720 {
         4_000_000.0
@@ -127,7 +543,8 @@ pub async fn get_export_estimates(
     };
 
     let fps_factor = (fps as f64) / 30.0;
-    let video_bitrate = base_bitrate * fps_factor;
+    let quality_factor = quality.map_or(1.0, |quality| quality.size_factor());
+    let video_bitrate = base_bitrate * fps_factor * quality_factor;
 
     let audio_bitrate = 192_000.0;
 
@@ -153,3 +570,177 @@ pub async fn get_export_estimates(
         estimated_size_mb,
     })
 }
+
+/// Write the project's edits as an EDL or FCPXML referencing the original recording files,
+/// for finishing in another editor. Returns the path of the written file.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_timeline(
+    project_path: PathBuf,
+    format: TimelineFormat,
+) -> Result<PathBuf, String> {
+    let meta = RecordingMeta::load_for_project(&project_path).map_err(|e| e.to_string())?;
+    let Some(studio_meta) = meta.studio_meta() else {
+        return Err("Only studio recordings have a timeline to export".to_string());
+    };
+
+    let display_paths = match studio_meta {
+        StudioRecordingMeta::SingleSegment { segment } => vec![meta.path(&segment.display.path)],
+        StudioRecordingMeta::MultipleSegments { inner } => inner
+            .segments
+            .iter()
+            .map(|segment| Ok(meta.path(&segment.display()?.path)))
+            .collect::<Result<_, String>>()?,
+    };
+
+    let recordings = ProjectRecordingsMeta::new(&project_path, studio_meta)?;
+    let sources = display_paths
+        .into_iter()
+        .zip(&recordings.segments)
+        .enumerate()
+        .map(|(i, (path, segment))| TimelineSource {
+            name: format!("{} {}", meta.pretty_name, i + 1),
+            path,
+            duration: segment.display.duration,
+        })
+        .collect::<Vec<_>>();
+
+    // Projects that were never edited play every segment in full
+    let timeline = meta
+        .project_config()
+        .timeline
+        .unwrap_or_else(|| TimelineConfiguration {
+            segments: recordings
+                .segments
+                .iter()
+                .enumerate()
+                .map(|(i, segment)| TimelineSegment {
+                    recording_segment: i as u32,
+                    timescale: 1.0,
+                    start: 0.0,
+                    end: segment.duration(),
+                    interpolate: false,
+                })
+                .collect(),
+            zoom_segments: vec![],
+            scene_segments: None,
+        });
+
+    let fps = studio_meta.max_fps();
+    let contents = match format {
+        TimelineFormat::Edl => timeline_to_edl(&meta.pretty_name, &timeline, &sources, fps)?,
+        TimelineFormat::Fcpxml => timeline_to_fcpxml(&meta.pretty_name, &timeline, &sources, fps)?,
+    };
+
+    let output_dir = project_path.join("output");
+    std::fs::create_dir_all(&output_dir)
+        .map_err(|e| format!("Failed to create output directory: {}", e))?;
+
+    let output_path = output_dir.join(format!("timeline.{}", format.extension()));
+    std::fs::write(&output_path, contents)
+        .map_err(|e| format!("Failed to write timeline: {}", e))?;
+
+    info!("Exported timeline to {}", output_path.display());
+
+    Ok(output_path)
+}
+
+/// Render a title card, eg. for an intro or outro, styled like captions.
+/// Returns the card as PNG bytes.
+#[tauri::command]
+#[specta::specta]
+pub async fn generate_title_card(
+    text: String,
+    subtitle: Option<String>,
+    size: XY<u32>,
+    background: String,
+    style: CaptionSettings,
+) -> Result<Vec<u8>, String> {
+    cap_rendering::generate_title_card(&text, subtitle.as_deref(), size, &background, &style)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[derive(Serialize, Type, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LutFile {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// `.cube` LUTs in the app data `luts` folder, sorted by name
+#[tauri::command]
+#[specta::specta]
+pub async fn list_luts(app: AppHandle) -> Result<Vec<LutFile>, String> {
+    let luts_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join("luts");
+
+    let Ok(entries) = std::fs::read_dir(&luts_dir) else {
+        return Ok(vec![]);
+    };
+
+    let mut luts = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("cube"))
+        })
+        .map(|path| LutFile {
+            name: path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            path,
+        })
+        .collect::<Vec<_>>();
+
+    luts.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+
+    Ok(luts)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn orients_resolution_to_aspect_ratio() {
+        let base = XY::new(1920, 1080);
+
+        assert_eq!(
+            orient_resolution_base(base, &AspectRatio::Vertical),
+            XY::new(1080, 1920)
+        );
+        assert_eq!(orient_resolution_base(base, &AspectRatio::Wide), base);
+        assert_eq!(orient_resolution_base(base, &AspectRatio::Square), base);
+        assert_eq!(
+            orient_resolution_base(XY::new(1080, 1920), &AspectRatio::Classic),
+            base
+        );
+    }
+
+    #[test]
+    fn progress_fps_follows_recent_frames() {
+        let mut timer = ProgressTimer::new();
+        let start = timer.started;
+
+        // A slow start at 10fps...
+        for frame in 0..=20 {
+            timer.record(frame, start + Duration::from_millis(frame as u64 * 100));
+        }
+
+        // ...then a full window at 60fps, which the rate shouldn't be dragged down from
+        let fast_start = start + Duration::from_secs(2);
+        let mut fps = 0.0;
+        for frame in 1..=PROGRESS_RATE_WINDOW as u32 {
+            let at = fast_start + Duration::from_secs_f64(frame as f64 / 60.0);
+            fps = timer.record(20 + frame, at);
+        }
+
+        assert!((fps - 60.0).abs() < 0.01, "{fps}");
+    }
+}
@@ -0,0 +1,394 @@
+use std::{cmp::Reverse, collections::HashMap, path::PathBuf, sync::Mutex};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use specta::Type;
+use tauri::{AppHandle, Manager, Wry};
+use tauri_plugin_store::StoreExt;
+use tauri_specta::Event;
+use tracing::{error, info};
+
+use cap_export::ExportControl;
+
+use crate::export::{run_export, ExportSettings};
+
+#[derive(Serialize, Deserialize, Type, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ExportJobStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Serialize, Deserialize, Type, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportJob {
+    pub id: String,
+    pub project_path: PathBuf,
+    pub settings: ExportSettings,
+    /// Queued jobs with a higher priority start first, and equal ones in the order they were added
+    #[serde(default)]
+    pub priority: i32,
+    pub status: ExportJobStatus,
+    pub rendered_count: u32,
+    pub total_frames: u32,
+    pub output_path: Option<PathBuf>,
+    pub error: Option<String>,
+}
+
+#[derive(Deserialize, Type, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct NewExportJob {
+    pub project_path: PathBuf,
+    pub settings: ExportSettings,
+    #[serde(default)]
+    pub priority: i32,
+}
+
+#[derive(Serialize, Deserialize, Type, Clone, Debug, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportQueueChanged {
+    pub jobs: Vec<ExportJob>,
+}
+
+#[derive(Serialize, Deserialize, Type, Clone, Debug, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportJobProgress {
+    pub id: String,
+    pub rendered_count: u32,
+    pub total_frames: u32,
+}
+
+#[derive(Serialize, Deserialize, Type, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportQueueStore {
+    jobs: Vec<ExportJob>,
+    /// Exports running at once. Defaults to 1 as concurrent exports contend for the GPU.
+    #[serde(default = "default_concurrency")]
+    concurrency: u32,
+}
+
+fn default_concurrency() -> u32 {
+    1
+}
+
+impl Default for ExportQueueStore {
+    fn default() -> Self {
+        Self {
+            jobs: vec![],
+            concurrency: default_concurrency(),
+        }
+    }
+}
+
+impl ExportQueueStore {
+    fn get(app: &AppHandle<Wry>) -> Option<Self> {
+        let value = app.store("store").ok()?.get("export_queue")?;
+
+        match serde_json::from_value(value) {
+            Ok(store) => Some(store),
+            Err(e) => {
+                error!("Failed to deserialize export queue store: {e}");
+                None
+            }
+        }
+    }
+
+    fn save(&self, app: &AppHandle) -> Result<(), String> {
+        let Ok(store) = app.store("store") else {
+            return Err("Store not found".to_string());
+        };
+
+        store.set("export_queue", json!(self));
+        store.save().map_err(|e| e.to_string())
+    }
+}
+
+pub struct ExportQueue {
+    store: ExportQueueStore,
+    /// Controls for the running jobs, by job id, so they can be cancelled individually
+    running: HashMap<String, ExportControl>,
+}
+
+pub type ExportQueueState = Mutex<ExportQueue>;
+
+/// Loads the persisted queue and resumes any jobs left unfinished when the app last quit
+pub fn init(app: &AppHandle) {
+    let mut store = ExportQueueStore::get(app).unwrap_or_default();
+
+    for job in store.jobs.iter_mut() {
+        if job.status == ExportJobStatus::Running {
+            job.status = ExportJobStatus::Queued;
+            job.rendered_count = 0;
+        }
+    }
+
+    let pending = store
+        .jobs
+        .iter()
+        .filter(|job| job.status == ExportJobStatus::Queued)
+        .count();
+    if pending > 0 {
+        info!("Resuming {pending} queued exports");
+    }
+
+    app.manage::<ExportQueueState>(Mutex::new(ExportQueue {
+        store,
+        running: HashMap::new(),
+    }));
+
+    start_pending_jobs(app);
+}
+
+/// Starts queued jobs by priority until the concurrency limit is reached
+fn start_pending_jobs(app: &AppHandle) {
+    let state = app.state::<ExportQueueState>();
+    let mut queue = state.lock().unwrap();
+
+    let concurrency = queue.store.concurrency.max(1) as usize;
+    let mut started = false;
+
+    while queue.running.len() < concurrency {
+        // The first of the highest priority jobs, so ties keep their queue order
+        let Some(job) = queue
+            .store
+            .jobs
+            .iter_mut()
+            .filter(|job| job.status == ExportJobStatus::Queued)
+            .min_by_key(|job| Reverse(job.priority))
+        else {
+            break;
+        };
+
+        job.status = ExportJobStatus::Running;
+        job.rendered_count = 0;
+        job.error = None;
+
+        let job = job.clone();
+        let control = app.state::<ExportControl>().for_export();
+        queue.running.insert(job.id.clone(), control.clone());
+        tauri::async_runtime::spawn(run_job(app.clone(), job, control));
+        started = true;
+    }
+
+    if started {
+        persist_and_emit(app, &queue);
+    }
+}
+
+async fn run_job(app: AppHandle, job: ExportJob, control: ExportControl) {
+    info!("Starting export job {}", job.id);
+
+    let result = run_export(
+        job.project_path.clone(),
+        job.settings,
+        None,
+        Default::default(),
+        None,
+        None,
+        None,
+        (None, None),
+        control,
+        {
+            let app = app.clone();
+            let id = job.id.clone();
+            move |frames| {
+                if let Some(job) = app
+                    .state::<ExportQueueState>()
+                    .lock()
+                    .unwrap()
+                    .store
+                    .jobs
+                    .iter_mut()
+                    .find(|job| job.id == id)
+                {
+                    job.rendered_count = frames.rendered_count;
+                    job.total_frames = frames.total_frames;
+                }
+
+                let _ = ExportJobProgress {
+                    id: id.clone(),
+                    rendered_count: frames.rendered_count,
+                    total_frames: frames.total_frames,
+                }
+                .emit(&app);
+            }
+        },
+    )
+    .await;
+
+    {
+        let state = app.state::<ExportQueueState>();
+        let mut queue = state.lock().unwrap();
+        queue.running.remove(&job.id);
+
+        // A job cancelled right as it finished keeps its cancelled status
+        if let Some(queued_job) = queue
+            .store
+            .jobs
+            .iter_mut()
+            .find(|j| j.id == job.id && j.status == ExportJobStatus::Running)
+        {
+            match result {
+                Ok(result) => {
+                    queued_job.status = ExportJobStatus::Completed;
+                    queued_job.output_path = Some(result.path);
+                }
+                Err(e) => {
+                    error!("Export job {} failed: {e}", job.id);
+                    queued_job.status = ExportJobStatus::Failed;
+                    queued_job.error = Some(e);
+                }
+            }
+        }
+
+        persist_and_emit(&app, &queue);
+    }
+
+    start_pending_jobs(&app);
+}
+
+fn persist_and_emit(app: &AppHandle, queue: &ExportQueue) {
+    if let Err(e) = queue.store.save(app) {
+        error!("Failed to save export queue: {e}");
+    }
+
+    let _ = ExportQueueChanged {
+        jobs: queue.store.jobs.clone(),
+    }
+    .emit(app);
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn enqueue_export(app: AppHandle, job: NewExportJob) -> Result<ExportJob, String> {
+    let job = ExportJob {
+        id: uuid::Uuid::new_v4().to_string(),
+        project_path: job.project_path,
+        settings: job.settings,
+        priority: job.priority,
+        status: ExportJobStatus::Queued,
+        rendered_count: 0,
+        total_frames: 0,
+        output_path: None,
+        error: None,
+    };
+
+    {
+        let state = app.state::<ExportQueueState>();
+        let mut queue = state.lock().unwrap();
+        queue.store.jobs.push(job.clone());
+        persist_and_emit(&app, &queue);
+    }
+
+    start_pending_jobs(&app);
+
+    Ok(job)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn list_export_queue(app: AppHandle) -> Vec<ExportJob> {
+    app.state::<ExportQueueState>()
+        .lock()
+        .unwrap()
+        .store
+        .jobs
+        .clone()
+}
+
+/// Cancel a queued or running export. Running exports stop at the next frame and their
+/// partial output is deleted.
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_export_job(app: AppHandle, id: String) -> Result<(), String> {
+    {
+        let state = app.state::<ExportQueueState>();
+        let mut queue = state.lock().unwrap();
+
+        let job = queue
+            .store
+            .jobs
+            .iter_mut()
+            .find(|job| job.id == id)
+            .ok_or_else(|| format!("Export job {id} not found"))?;
+
+        if !matches!(
+            job.status,
+            ExportJobStatus::Queued | ExportJobStatus::Running
+        ) {
+            return Ok(());
+        }
+
+        job.status = ExportJobStatus::Cancelled;
+
+        // The job frees its slot in the queue once it has stopped
+        if let Some(control) = queue.running.get(&id) {
+            control.cancel();
+        }
+
+        persist_and_emit(&app, &queue);
+    }
+
+    start_pending_jobs(&app);
+
+    Ok(())
+}
+
+/// Change the priority of a queued job, which takes effect when the next job starts
+#[tauri::command]
+#[specta::specta]
+pub fn set_export_job_priority(app: AppHandle, id: String, priority: i32) -> Result<(), String> {
+    let state = app.state::<ExportQueueState>();
+    let mut queue = state.lock().unwrap();
+
+    let job = queue
+        .store
+        .jobs
+        .iter_mut()
+        .find(|job| job.id == id)
+        .ok_or_else(|| format!("Export job {id} not found"))?;
+    job.priority = priority;
+
+    persist_and_emit(&app, &queue);
+
+    Ok(())
+}
+
+/// Remove completed, failed and cancelled jobs from the queue
+#[tauri::command]
+#[specta::specta]
+pub fn clear_finished_export_jobs(app: AppHandle) {
+    let state = app.state::<ExportQueueState>();
+    let mut queue = state.lock().unwrap();
+
+    queue.store.jobs.retain(|job| {
+        matches!(
+            job.status,
+            ExportJobStatus::Queued | ExportJobStatus::Running
+        )
+    });
+
+    persist_and_emit(&app, &queue);
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn set_export_concurrency(app: AppHandle, limit: u32) -> Result<(), String> {
+    if limit == 0 {
+        return Err("Concurrency limit must be at least 1".to_string());
+    }
+
+    {
+        let state = app.state::<ExportQueueState>();
+        let mut queue = state.lock().unwrap();
+        queue.store.concurrency = limit;
+        persist_and_emit(&app, &queue);
+    }
+
+    start_pending_jobs(&app);
+
+    Ok(())
+}
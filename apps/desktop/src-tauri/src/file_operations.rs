@@ -5,6 +5,7 @@ use std::process::Command;
 
 use mp4::Mp4Reader;
 use tauri::{AppHandle, State};
+use tauri_plugin_opener::OpenerExt;
 
 use crate::{notifications, MutableState};
 
@@ -168,4 +169,24 @@ pub async fn open_file_path(_app: AppHandle, path: PathBuf) -> Result<(), String
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+fn open_folder(app: &AppHandle, path: &Path) -> Result<(), String> {
+    app.opener()
+        .open_path(path.to_str().ok_or("Invalid path")?, None::<String>)
+        .map_err(|e| format!("Failed to open folder: {}", e))
+}
+
+/// Open the recordings directory in the OS file manager, creating it if needed
+#[tauri::command]
+#[specta::specta]
+pub async fn open_recordings_folder(app: AppHandle) -> Result<(), String> {
+    open_folder(&app, &crate::recordings_path(&app))
+}
+
+/// Open the screenshots directory in the OS file manager, creating it if needed
+#[tauri::command]
+#[specta::specta]
+pub async fn open_screenshots_folder(app: AppHandle) -> Result<(), String> {
+    open_folder(&app, &crate::screenshots::screenshots_path(&app))
+}
@@ -1,10 +1,16 @@
+use std::{collections::HashMap, path::PathBuf};
+
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use specta::Type;
-use tauri::{AppHandle, Wry};
+use tauri::{AppHandle, LogicalPosition, LogicalSize, Manager, Wry};
 use tauri_plugin_store::StoreExt;
+use tauri_specta::Event;
+use tracing::error;
 use uuid::Uuid;
 
+use crate::{notifications, windows::CapWindowId};
+
 #[derive(Default, Serialize, Deserialize, Type, Debug)]
 #[serde(rename_all = "camelCase")]
 pub enum PostStudioRecordingBehaviour {
@@ -21,6 +27,93 @@ pub enum MainWindowRecordingStartBehaviour {
     Minimise,
 }
 
+#[derive(Default, Serialize, Deserialize, Type, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum RecordingIndicatorSize {
+    Small,
+    #[default]
+    Medium,
+    Large,
+}
+
+impl RecordingIndicatorSize {
+    /// Logical size of the in-progress recording window
+    pub fn window_size(&self) -> (f64, f64) {
+        match self {
+            Self::Small => (200.0, 32.0),
+            Self::Medium => (244.0, 40.0),
+            Self::Large => (300.0, 50.0),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Type, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingIndicatorSettings {
+    /// Hex color of the recording dot, eg. `#E5484D`
+    #[serde(default = "default_indicator_color")]
+    pub color: String,
+    #[serde(default)]
+    pub size: RecordingIndicatorSize,
+    #[serde(default = "true_b")]
+    pub show_elapsed_time: bool,
+    #[serde(default = "true_b")]
+    pub show_stop_button: bool,
+    /// Logical position of the window, bottom center of the primary monitor when unset
+    #[serde(default)]
+    pub position: Option<(f64, f64)>,
+}
+
+fn default_indicator_color() -> String {
+    "#E5484D".to_string()
+}
+
+impl Default for RecordingIndicatorSettings {
+    fn default() -> Self {
+        Self {
+            color: default_indicator_color(),
+            size: RecordingIndicatorSize::default(),
+            show_elapsed_time: true,
+            show_stop_button: true,
+            position: None,
+        }
+    }
+}
+
+/// Silences the mic while it's quieter than `threshold_db`, eg. to cut keyboard noise
+#[derive(Serialize, Deserialize, Type, Debug, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct NoiseGateSettings {
+    pub enabled: bool,
+    pub threshold_db: f32,
+}
+
+impl Default for NoiseGateSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold_db: -50.0,
+        }
+    }
+}
+
+/// Whether the camera is flipped horizontally, set separately for self-view and recordings
+#[derive(Serialize, Deserialize, Type, Debug, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct CameraMirrorSettings {
+    pub preview: bool,
+    pub recording: bool,
+}
+
+impl Default for CameraMirrorSettings {
+    fn default() -> Self {
+        Self {
+            preview: true,
+            recording: false,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Type, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct GeneralSettingsStore {
@@ -62,6 +155,36 @@ pub struct GeneralSettingsStore {
     _open_editor_after_recording: bool,
     #[serde(default)]
     pub instant_mode_save_path: Option<String>,
+    #[serde(default)]
+    pub live_captions_enabled: bool,
+    #[serde(default)]
+    pub live_captions_model_path: Option<String>,
+    #[serde(default)]
+    pub recording_indicator: RecordingIndicatorSettings,
+    /// Recordings older than this many days are removed on startup, except pinned ones.
+    /// Disabled when unset or 0.
+    #[serde(default)]
+    pub auto_delete_after_days: Option<u32>,
+    #[serde(default = "true_b")]
+    pub auto_delete_keep_shared: bool,
+    /// Seconds to count down before recordings start, used when a recording doesn't set its own
+    #[serde(default)]
+    pub recording_countdown: Option<u32>,
+    /// Footage from before a countdown finishes that's kept at the start of recordings,
+    /// so the moment the countdown ends isn't lost while capture ramps up
+    #[serde(default = "default_pre_roll_ms")]
+    pub recording_pre_roll_ms: u32,
+    /// Input gain in decibels for each mic that's had it changed, keyed by device name
+    #[serde(default)]
+    pub mic_gains: HashMap<String, f32>,
+    #[serde(default)]
+    pub mic_noise_gate: NoiseGateSettings,
+    #[serde(default)]
+    pub camera_mirror: CameraMirrorSettings,
+}
+
+fn default_pre_roll_ms() -> u32 {
+    500
 }
 
 fn default_server_url() -> String {
@@ -100,6 +223,16 @@ impl Default for GeneralSettingsStore {
             server_url: default_server_url(),
             _open_editor_after_recording: false,
             instant_mode_save_path: None,
+            live_captions_enabled: false,
+            live_captions_model_path: None,
+            recording_indicator: RecordingIndicatorSettings::default(),
+            auto_delete_after_days: None,
+            auto_delete_keep_shared: true,
+            recording_countdown: None,
+            recording_pre_roll_ms: default_pre_roll_ms(),
+            mic_gains: HashMap::new(),
+            mic_noise_gate: NoiseGateSettings::default(),
+            camera_mirror: CameraMirrorSettings::default(),
         }
     }
 }
@@ -153,15 +286,93 @@ impl GeneralSettingsStore {
     }
 }
 
+/// Emitted when stored settings couldn't be read and were reset to their defaults
+#[derive(Serialize, Type, tauri_specta::Event, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsReset {
+    /// The store key that was reset, or `None` if the whole store was unreadable
+    key: Option<String>,
+    /// Copy of the store from before the reset, if one could be made
+    backup_path: Option<PathBuf>,
+}
+
+fn store_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path().app_data_dir().ok().map(|dir| dir.join("store"))
+}
+
+fn back_up_store(app: &AppHandle) -> Option<PathBuf> {
+    let path = store_path(app)?;
+    let backup_path = path.with_file_name(format!(
+        "store.corrupt-{}",
+        chrono::Local::now().format("%Y%m%d%H%M%S")
+    ));
+
+    match std::fs::copy(&path, &backup_path) {
+        Ok(_) => Some(backup_path),
+        Err(e) => {
+            error!("Failed to back up settings store: {e}");
+            None
+        }
+    }
+}
+
+fn notify_settings_reset(app: &AppHandle, key: Option<&str>, backup_path: Option<PathBuf>) {
+    SettingsReset {
+        key: key.map(str::to_string),
+        backup_path,
+    }
+    .emit(app)
+    .ok();
+
+    notifications::send_notification(app, notifications::NotificationType::SettingsReset);
+}
+
+/// Move the settings store aside if it can't be parsed at all, so the app starts from
+/// defaults rather than every settings read failing
+pub fn ensure_store_loads(app: &AppHandle) {
+    let Err(e) = app.store("store") else {
+        return;
+    };
+
+    error!("Failed to load settings store, resetting it: {e}");
+
+    let backup_path = back_up_store(app);
+    if let Some(path) = store_path(app) {
+        std::fs::remove_file(path).ok();
+    }
+
+    if let Err(e) = app.store("store") {
+        error!("Failed to recreate settings store: {e}");
+    }
+
+    notify_settings_reset(app, None, backup_path);
+}
+
+/// Replace a store key that can't be deserialized with `default`, keeping a backup of the store
+pub fn reset_corrupt_setting(app: &AppHandle, key: &str, default: serde_json::Value) {
+    let backup_path = back_up_store(app);
+
+    if let Ok(store) = app.store("store") {
+        store.set(key, default);
+        if let Err(e) = store.save() {
+            error!("Failed to save reset {key}: {e}");
+        }
+    }
+
+    notify_settings_reset(app, Some(key), backup_path);
+}
+
 pub fn init(app: &AppHandle) {
     println!("Initializing GeneralSettingsStore");
 
     let store = match GeneralSettingsStore::get(app) {
         Ok(Some(store)) => store,
         Ok(None) => GeneralSettingsStore::default(),
-        e => {
-            e.unwrap();
-            return;
+        Err(e) => {
+            error!("{e}");
+            let store = GeneralSettingsStore::default();
+            reset_corrupt_setting(app, "general_settings", json!(store));
+            store
         }
     };
 
@@ -184,3 +395,42 @@ pub fn get_instant_save_path(app: AppHandle) -> Result<Option<String>, String> {
     Ok(GeneralSettingsStore::get(&app)?
         .and_then(|s| s.instant_mode_save_path))
 }
+
+fn is_hex_color(color: &str) -> bool {
+    color
+        .strip_prefix('#')
+        .is_some_and(|hex| hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Save the recording indicator's appearance and apply it to the indicator window if it's open
+#[tauri::command]
+#[specta::specta]
+pub fn set_recording_indicator_settings(
+    app: AppHandle,
+    settings: RecordingIndicatorSettings,
+) -> Result<(), String> {
+    if !is_hex_color(&settings.color) {
+        return Err(format!("Invalid indicator color: {}", settings.color));
+    }
+
+    GeneralSettingsStore::update(&app, |s| {
+        s.recording_indicator = settings.clone();
+    })?;
+
+    if let Some(window) = CapWindowId::InProgressRecording.get(&app) {
+        let (width, height) = settings.size.window_size();
+        window
+            .set_size(LogicalSize::new(width, height))
+            .map_err(|e| e.to_string())?;
+
+        if let Some((x, y)) = settings.position {
+            window
+                .set_position(LogicalPosition::new(x, y))
+                .map_err(|e| e.to_string())?;
+        }
+
+        window.eval("window.location.reload()").ok();
+    }
+
+    Ok(())
+}
@@ -1,16 +1,18 @@
-use crate::{recording, App};
-use cap_media::sources::{list_screens, ScreenCaptureTarget};
+use crate::{recording, App, RequestNewScreenshot};
+use cap_media::sources::{list_screens, list_windows, ScreenCaptureTarget};
 use cap_recording::RecordingMode;
 use serde::{Deserialize, Serialize};
 use specta::Type;
 use std::collections::HashMap;
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Manager};
 use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut, ShortcutState};
 use tauri_plugin_store::StoreExt;
 use tauri_specta::Event;
 
 #[derive(Serialize, Deserialize, Type, PartialEq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
 pub struct Hotkey {
     #[specta(type = String)]
     code: Code,
@@ -18,8 +20,15 @@ pub struct Hotkey {
     ctrl: bool,
     alt: bool,
     shift: bool,
+    /// Only trigger when pressed twice within `DOUBLE_TAP_WINDOW`, so bare keys can be used
+    /// without firing whenever they're typed
+    #[serde(default)]
+    double_tap: bool,
 }
 
+// How quickly the second press of a double tap has to follow the first
+const DOUBLE_TAP_WINDOW: Duration = Duration::from_millis(400);
+
 impl Hotkey {
     fn to_shortcut(&self) -> Shortcut {
         let mut modifiers = Modifiers::empty();
@@ -47,7 +56,35 @@ pub enum HotkeyAction {
     StartRecording,
     StopRecording,
     RestartRecording,
-    // TakeScreenshot,
+    CycleCaptureTarget,
+    TakeScreenshot,
+    PauseRecording,
+    ResumeRecording,
+    TogglePause,
+    /// Held to record the mic in a push-to-talk recording
+    PushToTalk,
+}
+
+impl HotkeyAction {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::StartRecording => "Start Recording",
+            Self::StopRecording => "Stop Recording",
+            Self::RestartRecording => "Restart Recording",
+            Self::CycleCaptureTarget => "Cycle Capture Target",
+            Self::TakeScreenshot => "Take Screenshot",
+            Self::PauseRecording => "Pause Recording",
+            Self::ResumeRecording => "Resume Recording",
+            Self::TogglePause => "Pause or Resume Recording",
+            Self::PushToTalk => "Push to Talk",
+        }
+    }
+}
+
+/// Emitted when a hotkey changes which screen, window or area will be recorded
+#[derive(Serialize, Type, tauri_specta::Event, Debug, Clone)]
+pub struct CaptureTargetSelected {
+    target: ScreenCaptureTarget,
 }
 
 #[derive(Serialize, Deserialize, Type, Default)]
@@ -63,49 +100,110 @@ impl HotkeysStore {
 
         serde_json::from_value(store).map_err(|e| e.to_string())
     }
+
+    /// The action bound to `shortcut`. Shortcuts are matched by their id,
+    /// which is derived from the modifiers and key alone.
+    fn action_for(&self, shortcut: &Shortcut) -> Option<HotkeyAction> {
+        self.hotkeys
+            .iter()
+            .find(|(_, hotkey)| hotkey.to_shortcut().id() == shortcut.id())
+            .map(|(action, _)| *action)
+    }
+
+    /// Another action that `hotkey` is already bound to
+    fn conflicting_action(&self, action: HotkeyAction, hotkey: &Hotkey) -> Option<HotkeyAction> {
+        self.action_for(&hotkey.to_shortcut())
+            .filter(|existing| *existing != action)
+    }
+}
+
+/// When each double tap hotkey was last pressed, if it's waiting on a second press
+#[derive(Default)]
+struct DoubleTaps {
+    pending: HashMap<u32, Instant>,
+}
+
+impl DoubleTaps {
+    /// Record a press of `shortcut`, returning whether it completes a double tap
+    fn press(&mut self, shortcut: u32, at: Instant) -> bool {
+        match self.pending.remove(&shortcut) {
+            Some(first) if at.duration_since(first) <= DOUBLE_TAP_WINDOW => true,
+            _ => {
+                self.pending.insert(shortcut, at);
+                false
+            }
+        }
+    }
 }
 
 pub type HotkeysState = Mutex<HotkeysStore>;
 pub fn init(app: &AppHandle) {
+    let double_taps = Mutex::new(DoubleTaps::default());
+
+    // Push-to-talk presses and releases are applied one at a time in the order they happened,
+    // so a quick tap's release can't be applied before its press and leave the mic open
+    let (push_to_talk_tx, push_to_talk_rx) = flume::unbounded::<bool>();
+    tauri::async_runtime::spawn({
+        let app = app.clone();
+        async move {
+            while let Ok(held) = push_to_talk_rx.recv_async().await {
+                if let Err(e) = recording::set_push_to_talk(app.state(), held).await {
+                    tracing::error!("Failed to apply push-to-talk: {e}");
+                }
+            }
+        }
+    });
+
     app.plugin(
         tauri_plugin_global_shortcut::Builder::new()
-            .with_handler(|app, shortcut, event| {
+            .with_handler(move |app, shortcut, event| {
                 println!("Hotkey event received: {:?} - State: {:?}", shortcut, event.state());
-                if !matches!(event.state(), ShortcutState::Pressed) {
+                let state = app.state::<HotkeysState>();
+                let store = state.lock().unwrap();
+                let action = store.action_for(shortcut);
+
+                // Push-to-talk follows the key being held, so it's the one action releases matter to
+                if action == Some(HotkeyAction::PushToTalk) {
+                    let held = matches!(event.state(), ShortcutState::Pressed);
+                    push_to_talk_tx.send(held).ok();
                     return;
                 }
 
-                let state = app.state::<HotkeysState>();
-                let store = state.lock().unwrap();
+                if !matches!(event.state(), ShortcutState::Pressed) {
+                    return;
+                }
 
-                for (action, hotkey) in &store.hotkeys {
-                    // Compare shortcuts by converting to debug strings
-                    // This is more reliable than the previous string splitting approach
-                    let test_shortcut = hotkey.to_shortcut();
-                    
-                    // Format both shortcuts as strings for comparison
-                    let test_str = format!("{:?}", test_shortcut);
-                    let received_str = format!("{:?}", shortcut);
-                    
-                    // Extract the key and modifiers part, ignoring the ID which is unique per instance
-                    // The format is like: Shortcut { modifiers: META | SHIFT, key: KeyP, id: ... }
-                    let test_parts: Vec<&str> = test_str.split(", id:").collect();
-                    let received_parts: Vec<&str> = received_str.split(", id:").collect();
-                    
-                    if !test_parts.is_empty() && !received_parts.is_empty() {
-                        if test_parts[0] == received_parts[0] {
-                            println!("Triggering hotkey action: {:?}", action);
-                            tokio::spawn(handle_hotkey(app.clone(), *action));
-                            break; // Only trigger one action per hotkey press
-                        }
+                if let Some(action) = action {
+                    let double_tap = store.hotkeys.get(&action).is_some_and(|h| h.double_tap);
+                    if double_tap
+                        && !double_taps
+                            .lock()
+                            .unwrap()
+                            .press(shortcut.id(), Instant::now())
+                    {
+                        return;
                     }
+
+                    println!("Triggering hotkey action: {:?}", action);
+                    tokio::spawn(handle_hotkey(app.clone(), action));
                 }
             })
             .build(),
     )
     .unwrap();
 
-    let store = HotkeysStore::get(app).unwrap().unwrap_or_default();
+    let store = match HotkeysStore::get(app) {
+        Ok(store) => store.unwrap_or_default(),
+        Err(e) => {
+            tracing::error!("Failed to deserialize hotkeys: {e}");
+            crate::general_settings::reset_corrupt_setting(
+                app,
+                "hotkeys",
+                serde_json::json!(HotkeysStore::default()),
+            );
+            HotkeysStore::default()
+        }
+    };
 
     let global_shortcut = app.global_shortcut();
 
@@ -125,23 +223,30 @@ async fn handle_hotkey(app: AppHandle, action: HotkeyAction) -> Result<(), Strin
             let state = app.state::<crate::ArcLock<App>>();
             let app_state = state.read().await;
             let has_recording = app_state.current_recording.is_some();
+            let selected_target = app_state.selected_capture_target;
             drop(app_state);
             
             if has_recording {
                 // Stop the recording if one exists
                 recording::stop_recording(app.clone(), app.state()).await
             } else {
-                // Start a new recording
-                let primary_screen = list_screens()
-                    .into_iter()
-                    .next()
-                    .map(|(s, _)| s);
-                
-                if let Some(screen) = primary_screen {
+                // Start a new recording, of the primary screen unless another target was picked
+                let capture_target = selected_target.or_else(|| {
+                    list_screens()
+                        .into_iter()
+                        .next()
+                        .map(|(s, _)| ScreenCaptureTarget::Screen { id: s.id })
+                });
+
+                if let Some(capture_target) = capture_target {
                     let inputs = recording::StartRecordingInputs {
-                        capture_target: ScreenCaptureTarget::Screen { id: screen.id },
+                        capture_target,
                         mode: RecordingMode::Studio,
                         capture_system_audio: true,
+                        countdown: None,
+                        audio_only: false,
+                        app_audio: None,
+                        push_to_talk: false,
                     };
                     
                     recording::start_recording(app.clone(), app.state(), inputs).await
@@ -154,16 +259,139 @@ async fn handle_hotkey(app: AppHandle, action: HotkeyAction) -> Result<(), Strin
         HotkeyAction::RestartRecording => {
             recording::restart_recording(app.clone(), app.state()).await
         }
+        HotkeyAction::CycleCaptureTarget => {
+            let state = app.state::<crate::ArcLock<App>>();
+            let mut app_state = state.write().await;
+
+            // Switching targets mid-recording would have no effect
+            if app_state.current_recording.is_some() {
+                return Ok(());
+            }
+
+            let targets = list_screens()
+                .into_iter()
+                .map(|(screen, _)| ScreenCaptureTarget::Screen { id: screen.id })
+                .chain(
+                    list_windows()
+                        .into_iter()
+                        .map(|(window, _)| ScreenCaptureTarget::Window { id: window.id }),
+                )
+                .collect::<Vec<_>>();
+
+            let Some(target) =
+                next_capture_target(app_state.selected_capture_target.as_ref(), &targets)
+            else {
+                return Err("No capture targets available".to_string());
+            };
+
+            app_state.selected_capture_target = Some(target);
+            drop(app_state);
+
+            CaptureTargetSelected { target }
+                .emit(&app)
+                .map_err(|e| e.to_string())
+        }
+        // Handled by the same listener as the tray's screenshot item
+        HotkeyAction::TakeScreenshot => RequestNewScreenshot.emit(&app).map_err(|e| e.to_string()),
+        HotkeyAction::PauseRecording => recording::pause_recording(app.state()).await,
+        HotkeyAction::ResumeRecording => recording::resume_recording(app.state()).await,
+        // Handled on both press and release in `init`
+        HotkeyAction::PushToTalk => Ok(()),
+        HotkeyAction::TogglePause => {
+            let paused = app
+                .state::<crate::ArcLock<App>>()
+                .read()
+                .await
+                .recording_paused;
+
+            if paused {
+                recording::resume_recording(app.state()).await
+            } else {
+                recording::pause_recording(app.state()).await
+            }
+        }
+    }
+}
+
+/// The target after `current` in `targets`, wrapping around at the end.
+/// A selected area is kept in the cycle, straight after the screen it's on.
+fn next_capture_target(
+    current: Option<&ScreenCaptureTarget>,
+    targets: &[ScreenCaptureTarget],
+) -> Option<ScreenCaptureTarget> {
+    let mut cycle = targets.to_vec();
+
+    if let Some(area @ ScreenCaptureTarget::Area { screen, .. }) = current {
+        let position = cycle
+            .iter()
+            .position(|target| matches!(target, ScreenCaptureTarget::Screen { id } if id == screen))
+            .map(|index| index + 1)
+            .unwrap_or(cycle.len());
+        cycle.insert(position, *area);
+    }
+
+    let index = current
+        .and_then(|current| cycle.iter().position(|target| same_target(target, current)))
+        .map(|index| (index + 1) % cycle.len())
+        .unwrap_or(0);
+
+    cycle.get(index).copied()
+}
+
+fn same_target(a: &ScreenCaptureTarget, b: &ScreenCaptureTarget) -> bool {
+    match (a, b) {
+        (ScreenCaptureTarget::Screen { id: a }, ScreenCaptureTarget::Screen { id: b }) => a == b,
+        (ScreenCaptureTarget::Window { id: a }, ScreenCaptureTarget::Window { id: b }) => a == b,
+        (ScreenCaptureTarget::Area { .. }, ScreenCaptureTarget::Area { .. }) => true,
+        _ => false,
+    }
+}
+
+/// Swap in a whole new set of hotkeys, re-registering the global shortcuts
+pub fn replace_all(app: &AppHandle, hotkeys: HotkeysStore) {
+    let global_shortcut = app.global_shortcut();
+    let state = app.state::<HotkeysState>();
+    let mut store = state.lock().unwrap();
+
+    for hotkey in store.hotkeys.values() {
+        global_shortcut.unregister(hotkey.to_shortcut()).ok();
+    }
+
+    for hotkey in hotkeys.hotkeys.values() {
+        global_shortcut.register(hotkey.to_shortcut()).ok();
     }
+
+    *store = hotkeys;
+}
+
+/// The current bindings, in the form `set_hotkey` takes them
+#[tauri::command(async)]
+#[specta::specta]
+pub fn get_hotkeys(app: AppHandle) -> Result<HashMap<HotkeyAction, Hotkey>, String> {
+    let state = app.state::<HotkeysState>();
+    let store = state.lock().map_err(|e| e.to_string())?;
+
+    Ok(store.hotkeys.clone())
 }
 
 #[tauri::command(async)]
 #[specta::specta]
-pub fn set_hotkey(app: AppHandle, action: HotkeyAction, hotkey: Option<Hotkey>) -> Result<(), ()> {
+pub fn set_hotkey(
+    app: AppHandle,
+    action: HotkeyAction,
+    hotkey: Option<Hotkey>,
+) -> Result<(), String> {
     let global_shortcut = app.global_shortcut();
     let state = app.state::<HotkeysState>();
     let mut store = state.lock().unwrap();
 
+    if let Some(existing) = hotkey.and_then(|hotkey| store.conflicting_action(action, &hotkey)) {
+        return Err(format!(
+            "This shortcut is already used for {}",
+            existing.label()
+        ));
+    }
+
     let prev = store.hotkeys.get(&action).cloned();
 
     if let Some(hotkey) = hotkey {
@@ -11,6 +11,7 @@ mod tests {
             ctrl: false,
             alt: false,
             shift: true,
+            double_tap: false,
         };
         
         let shortcut = hotkey.to_shortcut();
@@ -31,6 +32,7 @@ mod tests {
             ctrl: true,
             alt: true,
             shift: true,
+            double_tap: false,
         };
         
         let shortcut = hotkey.to_shortcut();
@@ -51,6 +53,7 @@ mod tests {
             ctrl: false,
             alt: false,
             shift: false,
+            double_tap: false,
         };
         
         let shortcut = hotkey.to_shortcut();
@@ -75,6 +78,36 @@ mod tests {
         let action = HotkeyAction::StopRecording;
         let serialized = serde_json::to_string(&action).unwrap();
         assert_eq!(serialized, "\"stopRecording\"");
+
+        let action = HotkeyAction::CycleCaptureTarget;
+        let serialized = serde_json::to_string(&action).unwrap();
+        assert_eq!(serialized, "\"cycleCaptureTarget\"");
+
+        let action = HotkeyAction::TakeScreenshot;
+        let serialized = serde_json::to_string(&action).unwrap();
+        assert_eq!(serialized, "\"takeScreenshot\"");
+
+        let action = HotkeyAction::PushToTalk;
+        let serialized = serde_json::to_string(&action).unwrap();
+        assert_eq!(serialized, "\"pushToTalk\"");
+    }
+
+    #[test]
+    fn test_hotkey_round_trips_through_json() {
+        let hotkey = Hotkey {
+            code: Code::F8,
+            meta: false,
+            ctrl: true,
+            alt: false,
+            shift: false,
+            double_tap: true,
+        };
+
+        let value = serde_json::to_value(hotkey).unwrap();
+        assert_eq!(value["code"], "F8");
+        assert_eq!(value["doubleTap"], true);
+
+        assert!(serde_json::from_value::<Hotkey>(value).unwrap() == hotkey);
     }
 
     #[test]
@@ -91,6 +124,7 @@ mod tests {
             ctrl: false,
             alt: false,
             shift: false,
+            double_tap: false,
         };
         
         // Test insertion
@@ -102,4 +136,102 @@ mod tests {
         store.hotkeys.remove(&HotkeyAction::StartRecording);
         assert_eq!(store.hotkeys.len(), 0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_cycle_capture_target_wraps() {
+        use cap_media::sources::ScreenCaptureTarget;
+
+        let targets = [
+            ScreenCaptureTarget::Screen { id: 1 },
+            ScreenCaptureTarget::Screen { id: 2 },
+            ScreenCaptureTarget::Window { id: 7 },
+        ];
+
+        let next = next_capture_target(None, &targets);
+        assert!(matches!(next, Some(ScreenCaptureTarget::Screen { id: 1 })));
+
+        let next = next_capture_target(Some(&targets[1]), &targets);
+        assert!(matches!(next, Some(ScreenCaptureTarget::Window { id: 7 })));
+
+        let next = next_capture_target(Some(&targets[2]), &targets);
+        assert!(matches!(next, Some(ScreenCaptureTarget::Screen { id: 1 })));
+
+        assert!(next_capture_target(None, &[]).is_none());
+    }
+
+    #[test]
+    fn test_cycle_capture_target_keeps_area() {
+        use cap_media::{platform::Bounds, sources::ScreenCaptureTarget};
+
+        let targets = [
+            ScreenCaptureTarget::Screen { id: 1 },
+            ScreenCaptureTarget::Screen { id: 2 },
+        ];
+        let area = ScreenCaptureTarget::Area {
+            screen: 1,
+            bounds: Bounds::default(),
+        };
+
+        let next = next_capture_target(Some(&area), &targets);
+        assert!(matches!(next, Some(ScreenCaptureTarget::Screen { id: 2 })));
+    }
+
+    #[test]
+    fn test_double_tap_needs_quick_second_press() {
+        let mut double_taps = DoubleTaps::default();
+        let start = std::time::Instant::now();
+        let ms = std::time::Duration::from_millis;
+
+        assert!(!double_taps.press(1, start));
+        assert!(double_taps.press(1, start + ms(200)));
+
+        // A completed double tap starts over
+        assert!(!double_taps.press(1, start + ms(300)));
+
+        // Too slow, so the late press becomes the first of a new double tap
+        assert!(!double_taps.press(2, start));
+        assert!(!double_taps.press(2, start + ms(1000)));
+        assert!(double_taps.press(2, start + ms(1200)));
+    }
+
+    #[test]
+    fn test_hotkey_conflicts_with_other_actions() {
+        use std::collections::HashMap;
+
+        let hotkey = Hotkey {
+            code: Code::KeyR,
+            meta: true,
+            ctrl: false,
+            alt: false,
+            shift: true,
+            double_tap: false,
+        };
+        let store = HotkeysStore {
+            hotkeys: HashMap::from([(HotkeyAction::StartRecording, hotkey)]),
+        };
+
+        assert_eq!(
+            store.conflicting_action(HotkeyAction::TakeScreenshot, &hotkey),
+            Some(HotkeyAction::StartRecording)
+        );
+        // Rebinding an action to its own hotkey isn't a conflict
+        assert_eq!(
+            store.conflicting_action(HotkeyAction::StartRecording, &hotkey),
+            None
+        );
+
+        let other = Hotkey {
+            shift: false,
+            ..hotkey
+        };
+        assert_eq!(
+            store.conflicting_action(HotkeyAction::TakeScreenshot, &other),
+            None
+        );
+        assert_eq!(
+            store.action_for(&hotkey.to_shortcut()),
+            Some(HotkeyAction::StartRecording)
+        );
+        assert_eq!(store.action_for(&other.to_shortcut()), None);
+    }
+}
@@ -0,0 +1,174 @@
+use std::path::{Path, PathBuf};
+
+use cap_project::{
+    AudioMeta, Platform, RecordingMeta, RecordingMetaInner, SingleSegment, StudioRecordingMeta,
+    VideoMeta,
+};
+use ffmpeg::{codec as avcodec, format as avformat};
+use relative_path::RelativePathBuf;
+use tauri::AppHandle;
+use tauri_specta::Event;
+use tracing::info;
+
+use crate::{optimize::transcode_video, recording_path, NewStudioRecordingAdded};
+
+// Near-lossless quality for videos that have to be re-encoded on import
+const TRANSCODE_CRF: u32 = 18;
+
+struct ProbedVideo {
+    fps: u32,
+    width: u32,
+    height: u32,
+    duration: f64,
+    has_audio: bool,
+    /// Whether the video must be re-encoded to H.264 MP4 for the editor to decode it
+    needs_transcode: bool,
+}
+
+/// Checks the file's contents rather than its extension. The editor decodes H.264/HEVC in an
+/// MP4 or QuickTime container directly, while WebM/Matroska videos are re-encoded on import.
+fn probe_video(path: &Path) -> Result<ProbedVideo, String> {
+    let input = avformat::input(&path).map_err(|_| "File is not a readable video".to_string())?;
+
+    let format_name = input.format().name().to_string();
+    let formats = format_name.split(',').collect::<Vec<_>>();
+    let is_mp4 = formats.iter().any(|name| *name == "mp4" || *name == "mov");
+    if !is_mp4 && !formats.iter().any(|name| *name == "matroska" || *name == "webm") {
+        return Err(format!(
+            "Unsupported video format '{format_name}', only MP4, MOV and WebM files can be imported"
+        ));
+    }
+
+    let stream = input
+        .streams()
+        .best(ffmpeg::media::Type::Video)
+        .ok_or_else(|| "File has no video stream".to_string())?;
+
+    let codec_id = stream.parameters().id();
+    let needs_transcode = !is_mp4 || !matches!(codec_id, avcodec::Id::H264 | avcodec::Id::HEVC);
+
+    let decoder = avcodec::Context::from_parameters(stream.parameters())
+        .and_then(|c| c.decoder().video())
+        .map_err(|e| format!("Failed to read video stream: {e}"))?;
+
+    let frame_rate = stream.avg_frame_rate();
+    let fps = if frame_rate.denominator() > 0 {
+        (frame_rate.numerator() as f64 / frame_rate.denominator() as f64).round() as u32
+    } else {
+        0
+    };
+    let duration = input.duration() as f64 / 1_000_000.0;
+
+    if fps == 0 || duration <= 0.0 || decoder.width() == 0 || decoder.height() == 0 {
+        return Err("Video has no frames".to_string());
+    }
+
+    Ok(ProbedVideo {
+        fps,
+        width: decoder.width(),
+        height: decoder.height(),
+        duration,
+        has_audio: input.streams().best(ffmpeg::media::Type::Audio).is_some(),
+        needs_transcode,
+    })
+}
+
+/// Copy an external MP4, MOV or WebM into a new studio recording so it can be opened in the editor.
+/// Returns the new project's path.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_video(app: AppHandle, source: PathBuf) -> Result<PathBuf, String> {
+    if !source.is_file() {
+        return Err(format!("{} does not exist", source.display()));
+    }
+
+    let video = tokio::task::spawn_blocking({
+        let source = source.clone();
+        move || probe_video(&source)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let project_path = recording_path(&app, &uuid::Uuid::new_v4().to_string());
+    let content_dir = project_path.join("content");
+    tokio::fs::create_dir_all(&content_dir)
+        .await
+        .map_err(|e| format!("Failed to create project directory: {e}"))?;
+
+    let display_path = RelativePathBuf::from("content/display.mp4");
+
+    let imported = if video.needs_transcode {
+        let source = source.clone();
+        let output = display_path.to_path(&project_path);
+        tokio::task::spawn_blocking(move || transcode_video(&source, &output, TRANSCODE_CRF))
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|r| r.map_err(|e| format!("Failed to convert video: {e}")))
+    } else {
+        tokio::fs::copy(&source, display_path.to_path(&project_path))
+            .await
+            .map(|_| ())
+            .map_err(|e| format!("Failed to copy video: {e}"))
+    };
+
+    if let Err(e) = imported {
+        tokio::fs::remove_dir_all(&project_path).await.ok();
+        return Err(e);
+    }
+
+    let pretty_name = source
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Imported Video".to_string());
+
+    let meta = RecordingMeta {
+        platform: Some(Platform::default()),
+        project_path: project_path.clone(),
+        sharing: None,
+        capture_target: None,
+        system_audio_source: None,
+        pinned: false,
+        optimization: None,
+        pretty_name,
+        inner: RecordingMetaInner::Studio(StudioRecordingMeta::SingleSegment {
+            segment: SingleSegment {
+                display: VideoMeta {
+                    path: display_path.clone(),
+                    fps: video.fps,
+                    start_time: None,
+                    frame_timestamps: None,
+                },
+                camera: None,
+                // The audio track is decoded straight from the video file
+                audio: video.has_audio.then(|| AudioMeta {
+                    path: display_path,
+                    start_time: None,
+                }),
+                cursor: None,
+            },
+        }),
+    };
+
+    if let Err(e) = meta.save_for_project() {
+        tokio::fs::remove_dir_all(&project_path).await.ok();
+        return Err(format!("Failed to save recording meta: {e}"));
+    }
+
+    info!(
+        "Imported {} ({}x{} @ {}fps, {:.2}s) to {}",
+        source.display(),
+        video.width,
+        video.height,
+        video.fps,
+        video.duration,
+        project_path.display()
+    );
+
+    NewStudioRecordingAdded {
+        path: project_path.clone(),
+    }
+    .emit(&app)
+    .ok();
+
+    Ok(project_path)
+}
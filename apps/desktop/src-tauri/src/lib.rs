@@ -1,23 +1,34 @@
+mod annotations;
 mod audio;
 mod audio_meter;
 mod auth;
 mod camera;
 mod captions;
 mod deeplink_actions;
+mod devices;
 mod editor;
 mod editor_window;
 mod export;
+mod export_queue;
 mod fake_window;
 mod file_operations;
 mod flags;
 mod general_settings;
 mod hotkeys;
+mod import;
+mod live_captions;
 mod notifications;
+mod optimize;
 mod permissions;
 mod platform;
 mod screenshots;
 mod presets;
 mod recording;
+mod replay_buffer;
+mod retention;
+mod scene_detection;
+mod settings_transfer;
+mod speaking_stats;
 mod system;
 mod tray;
 mod upload;
@@ -26,13 +37,17 @@ mod windows;
 
 use audio::AppSounds;
 use auth::{AuthStore, AuthenticationInvalid, Plan};
-use camera::create_camera_preview_ws;
+use camera::{create_camera_preview_ws, CameraLatencySamples};
 use cap_editor::EditorInstance;
 use cap_editor::EditorState;
 use cap_media::feeds::RawCameraFrame;
-use cap_media::feeds::{AudioInputFeed, AudioInputSamplesSender};
+use cap_media::feeds::{AudioInputFeed, AudioInputSamplesSender, AudioMonitor, MAX_GAIN_DB};
 use cap_media::platform::Bounds;
-use cap_media::{feeds::CameraFeed, sources::ScreenCaptureTarget};
+use cap_media::{
+    feeds::CameraFeed,
+    sources::{AppAudioTarget, ScreenCaptureTarget},
+    MediaError,
+};
 use cap_project::RecordingMetaInner;
 use cap_project::XY;
 use cap_project::{ProjectConfiguration, RecordingMeta, SharingMeta, StudioRecordingMeta};
@@ -41,7 +56,7 @@ use clipboard_rs::common::RustImage;
 use clipboard_rs::{Clipboard, ClipboardContext};
 use editor_window::EditorInstances;
 use editor_window::WindowEditorInstance;
-use general_settings::GeneralSettingsStore;
+use general_settings::{GeneralSettingsStore, NoiseGateSettings};
 use mp4::Mp4Reader;
 use notifications::NotificationType;
 use png::{ColorType, Encoder};
@@ -63,7 +78,10 @@ use std::{
     path::PathBuf,
     process::Command,
     str::FromStr,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
 };
 use tauri::Window;
 use tauri::{AppHandle, Manager, State, WindowEvent};
@@ -76,6 +94,7 @@ use tauri_specta::Event;
 use tokio::sync::{Mutex, RwLock};
 use tracing::debug;
 use tracing::error;
+use tracing::warn;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::Layer;
@@ -92,16 +111,42 @@ pub struct App {
     camera_tx: flume::Sender<RawCameraFrame>,
     camera_ws_port: u16,
     #[serde(skip)]
+    camera_latency_rx: CameraLatencySamples,
+    #[serde(skip)]
+    camera_preview_mirrored: Arc<AtomicBool>,
+    #[serde(skip)]
     camera_feed: Option<Arc<Mutex<CameraFeed>>>,
+    /// Cameras recorded alongside the primary one, keyed by slot (1 and up)
+    #[serde(skip)]
+    additional_camera_feeds: BTreeMap<u32, Arc<Mutex<CameraFeed>>>,
     #[serde(skip)]
     mic_feed: Option<AudioInputFeed>,
     #[serde(skip)]
+    mic_label: Option<String>,
+    #[serde(skip)]
     mic_samples_tx: AudioInputSamplesSender,
+    /// Plays the mic back while monitoring is on
+    #[serde(skip)]
+    mic_monitor: Option<AudioMonitor>,
+    #[serde(skip)]
+    app_audio: Option<AppAudioTarget>,
     #[serde(skip)]
     handle: AppHandle,
     #[serde(skip)]
     current_recording: Option<InProgressRecording>,
     #[serde(skip)]
+    recording_paused: bool,
+    /// Whether the mic is muted in the current recording
+    #[serde(skip)]
+    mic_muted: bool,
+    /// What the next recording will capture, as last picked in the UI or by hotkey
+    #[serde(skip)]
+    selected_capture_target: Option<ScreenCaptureTarget>,
+    #[serde(skip)]
+    live_transcription: Option<live_captions::LiveTranscription>,
+    #[serde(skip)]
+    replay_buffer: Option<cap_recording::replay_buffer::ReplayBufferHandle>,
+    #[serde(skip)]
     recording_logging_handle: LoggingHandle,
     server_url: String,
 }
@@ -137,17 +182,47 @@ pub struct VideoUploadInfo {
 
 impl App {
     pub fn set_current_recording(&mut self, actor: InProgressRecording) {
+        if captures_monitored_audio(&actor) && self.mic_monitor.take().is_some() {
+            warn!("Stopped mic monitoring so system audio doesn't record it");
+        }
+
         self.current_recording = Some(actor);
+        self.recording_paused = false;
 
         CurrentRecordingChanged.emit(&self.handle).ok();
     }
 
     pub fn clear_current_recording(&mut self) -> Option<InProgressRecording> {
         self.close_occluder_windows();
+        self.recording_paused = false;
+
+        // Muting only lasts for the recording
+        if std::mem::take(&mut self.mic_muted) {
+            if let Some(feed) = self.mic_feed.clone() {
+                tokio::spawn(async move {
+                    if let Err(e) = feed.set_muted(false).await {
+                        error!("Failed to unmute mic: {e}");
+                    }
+                });
+            }
+        }
 
         self.current_recording.take()
     }
 
+    /// Additional camera feeds indexed by slot starting at slot 1, with gaps left as `None`
+    /// so recordings keep the slot numbers the cameras were assigned
+    pub fn additional_camera_slots(&self) -> Vec<Option<Arc<Mutex<CameraFeed>>>> {
+        let last_slot = self
+            .additional_camera_feeds
+            .last_key_value()
+            .map_or(0, |(slot, _)| *slot);
+
+        (1..=last_slot)
+            .map(|slot| self.additional_camera_feeds.get(&slot).cloned())
+            .collect()
+    }
+
     fn close_occluder_windows(&self) {
         for window in self.handle.webview_windows() {
             if window.0.starts_with("window-capture-occluder-") {
@@ -162,7 +237,7 @@ impl App {
 async fn set_mic_input(state: MutableState<'_, App>, label: Option<String>) -> Result<(), String> {
     let mut app = state.write().await;
 
-    match (label, &mut app.mic_feed) {
+    let result = match (label.clone(), &mut app.mic_feed) {
         (Some(label), None) => {
             AudioInputFeed::init(&label)
                 .await
@@ -180,7 +255,153 @@ async fn set_mic_input(state: MutableState<'_, App>, label: Option<String>) -> R
             app.mic_feed.take();
             Ok(())
         }
+    };
+
+    if result.is_ok() {
+        // Gain is remembered per device, so switching mics restores the new one's
+        if let (Some(feed), Some(label)) = (&app.mic_feed, &label) {
+            if let Err(e) = apply_mic_settings(&app.handle, feed, label).await {
+                error!("Failed to apply mic settings: {e}");
+            }
+        }
+
+        app.mic_label = label;
+
+        // The monitor is tied to the feed it started with, so it follows the switch
+        if app.mic_monitor.take().is_some() {
+            if let Some(feed) = &app.mic_feed {
+                match AudioMonitor::start(feed).await {
+                    Ok(monitor) => app.mic_monitor = Some(monitor),
+                    Err(e) => error!("Failed to restart mic monitoring: {e}"),
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// Where system audio capture can't keep our own playback out, a recording of
+/// system audio would pick the monitored mic up a second time
+fn captures_monitored_audio(recording: &InProgressRecording) -> bool {
+    !cap_media::sources::excludes_own_audio() && recording.inputs().capture_system_audio
+}
+
+/// Play the selected mic on the default output device, eg. to hear yourself during a voiceover.
+/// Where system audio capture can't leave it out, it can't be on while recording system audio.
+#[tauri::command]
+#[specta::specta]
+async fn set_mic_monitoring(state: MutableState<'_, App>, enabled: bool) -> Result<(), String> {
+    let mut app = state.write().await;
+
+    if !enabled {
+        app.mic_monitor.take();
+        return Ok(());
+    }
+
+    if app.mic_monitor.is_some() {
+        return Ok(());
+    }
+
+    if app
+        .current_recording
+        .as_ref()
+        .is_some_and(captures_monitored_audio)
+    {
+        return Err("Mic monitoring can't be used while recording system audio".to_string());
+    }
+
+    let Some(feed) = &app.mic_feed else {
+        return Err("No microphone selected".to_string());
+    };
+
+    let monitor = AudioMonitor::start(feed).await.map_err(|e| e.to_string())?;
+    app.mic_monitor = Some(monitor);
+
+    Ok(())
+}
+
+/// Saved gain for the mic called `label`, in decibels
+pub(crate) fn mic_gain(app: &AppHandle, label: &str) -> f32 {
+    GeneralSettingsStore::get(app)
+        .ok()
+        .flatten()
+        .and_then(|settings| settings.mic_gains.get(label).copied())
+        .unwrap_or(0.0)
+}
+
+/// Apply the saved gain for the mic called `label`, and the noise gate, to `feed`
+pub(crate) async fn apply_mic_settings(
+    app: &AppHandle,
+    feed: &AudioInputFeed,
+    label: &str,
+) -> Result<(), MediaError> {
+    feed.set_gain(mic_gain(app, label)).await?;
+
+    let noise_gate = GeneralSettingsStore::get(app)
+        .ok()
+        .flatten()
+        .map(|settings| settings.mic_noise_gate)
+        .unwrap_or_default();
+    feed.set_noise_gate(noise_gate.enabled.then_some(noise_gate.threshold_db))
+        .await
+}
+
+/// Boost or cut the selected mic by `gain_db` decibels, up to `MAX_GAIN_DB` either way.
+/// Recordings and the level meter both get the adjusted samples.
+#[tauri::command]
+#[specta::specta]
+async fn set_mic_gain(state: MutableState<'_, App>, gain_db: f32) -> Result<(), String> {
+    let app = state.read().await;
+
+    let (Some(feed), Some(label)) = (&app.mic_feed, &app.mic_label) else {
+        return Err("No microphone selected".to_string());
+    };
+
+    let gain_db = gain_db.clamp(-MAX_GAIN_DB, MAX_GAIN_DB);
+    feed.set_gain(gain_db).await.map_err(|e| e.to_string())?;
+
+    GeneralSettingsStore::update(&app.handle, |settings| {
+        settings.mic_gains.insert(label.clone(), gain_db);
+    })
+}
+
+/// Silence the mic while it's quieter than `threshold_db`, eg. to cut keyboard clatter
+/// between sentences. Unlike gain, this applies to every mic.
+#[tauri::command]
+#[specta::specta]
+async fn set_mic_noise_gate(
+    state: MutableState<'_, App>,
+    threshold_db: f32,
+    enabled: bool,
+) -> Result<(), String> {
+    let app = state.read().await;
+    let threshold_db = threshold_db.clamp(-96.0, 0.0);
+
+    if let Some(feed) = &app.mic_feed {
+        feed.set_noise_gate(enabled.then_some(threshold_db))
+            .await
+            .map_err(|e| e.to_string())?;
     }
+
+    GeneralSettingsStore::update(&app.handle, |settings| {
+        settings.mic_noise_gate = NoiseGateSettings {
+            enabled,
+            threshold_db,
+        };
+    })
+}
+
+/// Gain of the selected mic in decibels, 0 when none is selected
+#[tauri::command]
+#[specta::specta]
+async fn get_mic_gain(state: MutableState<'_, App>) -> Result<f32, String> {
+    let app = state.read().await;
+
+    Ok(app
+        .mic_label
+        .as_deref()
+        .map_or(0.0, |label| mic_gain(&app.handle, label)))
 }
 
 #[tauri::command]
@@ -188,7 +409,13 @@ async fn set_mic_input(state: MutableState<'_, App>, label: Option<String>) -> R
 async fn set_camera_input(
     state: MutableState<'_, App>,
     label: Option<String>,
+    slot: Option<u32>,
 ) -> Result<bool, String> {
+    match slot {
+        None | Some(0) => {}
+        Some(slot) => return set_additional_camera_input(&state, label, slot).await,
+    }
+
     let mut app = state.write().await;
 
     match (&label, app.camera_feed.as_ref()) {
@@ -214,6 +441,7 @@ async fn set_camera_input(
                             Ok(Ok(feed)) => {
                                 let mut app = state.write().await;
                                 if app.camera_feed.is_none() {
+                                    feed.set_mirrored(camera::camera_mirror(&app.handle).recording);
                                     feed.attach(camera_tx);
                                     app.camera_feed = Some(Arc::new(Mutex::new(feed)));
                                     return Ok(true);
@@ -242,6 +470,51 @@ async fn set_camera_input(
     }
 }
 
+/// Additional cameras are only recorded, so unlike the primary camera they don't feed the preview window
+async fn set_additional_camera_input(
+    state: &RwLock<App>,
+    label: Option<String>,
+    slot: u32,
+) -> Result<bool, String> {
+    let mut app = state.write().await;
+
+    let Some(label) = label else {
+        app.additional_camera_feeds.remove(&slot);
+        return Ok(true);
+    };
+
+    // A camera can only be opened once, so it can't fill two slots
+    let other_feeds = app.camera_feed.iter().chain(
+        app.additional_camera_feeds
+            .iter()
+            .filter(|(s, _)| **s != slot)
+            .map(|(_, feed)| feed),
+    );
+    for feed in other_feeds {
+        if feed.lock().await.camera_info.human_name() == label {
+            return Err(format!(
+                "'{label}' is already in use by another camera slot"
+            ));
+        }
+    }
+
+    if let Some(feed) = app.additional_camera_feeds.get(&slot) {
+        feed.lock()
+            .await
+            .switch_cameras(&label)
+            .await
+            .map_err(|e| e.to_string())?;
+        return Ok(true);
+    }
+
+    let feed = CameraFeed::init(&label).await.map_err(|e| e.to_string())?;
+    feed.set_mirrored(camera::camera_mirror(&app.handle).recording);
+    app.additional_camera_feeds
+        .insert(slot, Arc::new(Mutex::new(feed)));
+
+    Ok(true)
+}
+
 #[derive(specta::Type, Serialize, tauri_specta::Event, Clone)]
 pub struct RecordingOptionsChanged;
 
@@ -348,6 +621,9 @@ async fn get_current_recording(
             target,
             r#type: match r {
                 InProgressRecording::Instant { .. } => RecordingType::Instant,
+                InProgressRecording::Studio { inputs, .. } if inputs.audio_only => {
+                    RecordingType::Audio
+                }
                 InProgressRecording::Studio { .. } => RecordingType::Studio,
             },
         }
@@ -373,6 +649,10 @@ struct RenderFrameEvent {
 pub struct FramesRendered {
     rendered_count: u32,
     total_frames: u32,
+    /// Seconds since the export started
+    elapsed_secs: f64,
+    /// Frames per second over the most recent frames, for estimating the time remaining
+    fps: f64,
 }
 
 #[tauri::command]
@@ -602,6 +882,7 @@ impl RecordingMetaWithType {
     fn new(inner: RecordingMeta) -> Self {
         Self {
             r#type: match &inner.inner {
+                RecordingMetaInner::Studio(meta) if meta.is_audio_only() => RecordingType::Audio,
                 RecordingMetaInner::Studio(_) => RecordingType::Studio,
                 RecordingMetaInner::Instant(_) => RecordingType::Instant,
             },
@@ -615,6 +896,8 @@ impl RecordingMetaWithType {
 pub enum RecordingType {
     Studio,
     Instant,
+    /// A studio recording of just the microphone and system audio
+    Audio,
 }
 
 #[tauri::command(async)]
@@ -669,12 +952,28 @@ fn list_recordings(app: AppHandle) -> Result<Vec<(PathBuf, RecordingMetaWithType
                 .and_then(|m| m.created())
                 .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
 
-        b_time.cmp(&a_time)
+        // Pinned first, each group newest first
+        b.1.inner
+            .pinned
+            .cmp(&a.1.inner.pinned)
+            .then(b_time.cmp(&a_time))
     });
 
     Ok(result)
 }
 
+/// Pin a recording or screenshot so it's listed before the rest
+#[tauri::command]
+#[specta::specta]
+fn set_recording_pinned(path: PathBuf, pinned: bool) -> Result<(), String> {
+    let mut meta = RecordingMeta::load_for_project(&path)
+        .map_err(|e| format!("Failed to load recording meta: {}", e))?;
+
+    meta.pinned = pinned;
+    meta.save_for_project()
+        .map_err(|e| format!("Failed to save recording meta: {e:?}"))
+}
+
 #[tauri::command]
 #[specta::specta]
 async fn check_upgraded_and_update(app: AppHandle) -> Result<bool, String> {
@@ -877,6 +1176,16 @@ async fn set_server_url(app: MutableState<'_, App>, server_url: String) -> Resul
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+async fn set_capture_target(
+    app: MutableState<'_, App>,
+    target: Option<ScreenCaptureTarget>,
+) -> Result<(), ()> {
+    app.write().await.selected_capture_target = target;
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 async fn update_auth_plan(app: AppHandle) {
@@ -898,17 +1207,33 @@ pub async fn run(recording_logging_handle: LoggingHandle) {
     let specta_builder = tauri_specta::Builder::new()
         .commands(tauri_specta::collect_commands![
             set_mic_input,
+            set_mic_gain,
+            get_mic_gain,
+            set_mic_noise_gate,
+            set_mic_monitoring,
             set_camera_input,
+            camera::measure_camera_latency,
+            camera::set_camera_mirror,
+            devices::revalidate_devices,
             recording::start_recording,
             recording::stop_recording,
             recording::pause_recording,
             recording::resume_recording,
+            recording::set_mic_muted,
             recording::restart_recording,
             recording::delete_recording,
             recording::list_cameras,
+            recording::get_capture_capabilities,
+            recording::list_audio_applications,
+            recording::set_app_audio_capture,
+            recording::set_system_audio_source,
             recording::list_capture_windows,
             recording::list_capture_screens,
             screenshots::take_screenshot,
+            screenshots::take_delayed_screenshot,
+            screenshots::cancel_delayed_screenshot,
+            screenshots::capture_scrolling_screenshot,
+            screenshots::stop_scrolling_screenshot,
             list_audio_devices,
             system::close_recordings_overlay_window,
             fake_window::set_fake_window_bounds,
@@ -916,14 +1241,25 @@ pub async fn run(recording_logging_handle: LoggingHandle) {
             system::focus_captures_panel,
             get_current_recording,
             export::export_video,
+            export::export_videos_batch,
+            export::export_gif,
             export::get_export_estimates,
             file_operations::copy_file_to_path,
             editor::copy_video_to_clipboard,
             screenshots::copy_screenshot_to_clipboard,
+            screenshots::convert_screenshots,
+            screenshots::read_screenshot_metadata,
+            annotations::annotate_screenshot,
             file_operations::open_file_path,
+            file_operations::open_recordings_folder,
+            file_operations::open_screenshots_folder,
+            import::import_video,
             system::get_video_metadata,
             editor::create_editor_instance,
             editor::get_mic_waveforms,
+            editor::get_render_backend,
+            editor::set_project_crop,
+            editor::set_project_orientation,
             system::get_system_audio_waveforms,
             editor::start_playback,
             editor::stop_playback,
@@ -938,15 +1274,18 @@ pub async fn run(recording_logging_handle: LoggingHandle) {
             save_file_dialog,
             delete_wallpaper,
             list_recordings,
+            set_recording_pinned,
             screenshots::list_screenshots,
             check_upgraded_and_update,
             open_external_link,
+            hotkeys::get_hotkeys,
             hotkeys::set_hotkey,
             reset_camera_permissions,
             reset_microphone_permissions,
             system::is_camera_window_open,
             editor::seek_to,
             windows::position_traffic_lights,
+            windows::preview_recording_indicator,
             windows::set_theme,
             global_message_dialog,
             system::show_window,
@@ -958,17 +1297,54 @@ pub async fn run(recording_logging_handle: LoggingHandle) {
             set_window_transparent,
             editor::get_editor_meta,
             set_server_url,
+            set_capture_target,
             captions::create_dir,
             captions::save_model_file,
             captions::transcribe_audio,
+            captions::detect_audio_language,
             captions::save_captions,
             captions::load_captions,
+            captions::search_all_captions,
+            captions::summarize_captions,
+            captions::detect_filler_words,
+            captions::auto_trim_fillers,
+            speaking_stats::get_speaking_stats,
             captions::download_whisper_model,
             captions::check_model_exists,
             captions::delete_whisper_model,
             captions::export_captions_srt,
+            captions::export_captions_to_ass,
+            captions::export_captions_to_vtt,
+            captions::split_caption_segment,
+            captions::import_captions,
             general_settings::set_instant_save_path,
-            general_settings::get_instant_save_path
+            general_settings::get_instant_save_path,
+            general_settings::set_recording_indicator_settings,
+            settings_transfer::export_settings,
+            settings_transfer::import_settings,
+            settings_transfer::reset_settings,
+            notifications::send_test_notification,
+            retention::apply_retention_policy,
+            live_captions::set_live_captions,
+            replay_buffer::start_replay_buffer,
+            replay_buffer::save_replay,
+            replay_buffer::stop_replay_buffer,
+            optimize::optimize_recording,
+            optimize::restore_recording_backup,
+            scene_detection::detect_scene_changes,
+            export_queue::enqueue_export,
+            export_queue::list_export_queue,
+            export_queue::cancel_export_job,
+            export_queue::set_export_job_priority,
+            export_queue::clear_finished_export_jobs,
+            export_queue::set_export_concurrency,
+            export::pause_export,
+            export::resume_export,
+            export::is_export_paused,
+            export::cancel_export,
+            export::list_luts,
+            export::export_timeline,
+            export::generate_title_card
         ])
         .events(tauri_specta::collect_events![
             RecordingOptionsChanged,
@@ -987,6 +1363,19 @@ pub async fn run(recording_logging_handle: LoggingHandle) {
             audio_meter::AudioInputLevelChange,
             UploadProgress,
             captions::DownloadProgress,
+            captions::CaptionsImported,
+            live_captions::LiveCaptionsUpdate,
+            export_queue::ExportQueueChanged,
+            export_queue::ExportJobProgress,
+            export::ExportPauseChanged,
+            export::ExportCancelled,
+            recording::RecordingPauseChanged,
+            recording::MicMuteChanged,
+            devices::InputDeviceLost,
+            hotkeys::CaptureTargetSelected,
+            general_settings::SettingsReset,
+            screenshots::ScreenshotCountdown,
+            screenshots::ScrollingScreenshotProgress,
         ])
         .error_handling(tauri_specta::ErrorHandlingMode::Throw)
         .typ::<ProjectConfiguration>()
@@ -1004,7 +1393,10 @@ pub async fn run(recording_logging_handle: LoggingHandle) {
         )
         .expect("Failed to export typescript bindings");
 
-    let (camera_tx, camera_ws_port, _shutdown) = create_camera_preview_ws().await;
+    // Updated from the settings once the app has started
+    let camera_preview_mirrored = Arc::new(AtomicBool::new(true));
+    let (camera_tx, camera_ws_port, _shutdown, camera_latency_rx) =
+        create_camera_preview_ws(camera_preview_mirrored.clone()).await;
 
     let (audio_input_tx, audio_input_rx) = AudioInputFeed::create_channel();
 
@@ -1075,8 +1467,24 @@ pub async fn run(recording_logging_handle: LoggingHandle) {
         .setup(move |app| {
             let app = app.handle().clone();
             specta_builder.mount_events(&app);
+            general_settings::ensure_store_loads(&app);
             hotkeys::init(&app);
             general_settings::init(&app);
+
+            tauri::async_runtime::spawn({
+                let app = app.clone();
+                async move {
+                    if let Err(e) = retention::apply_retention_policy(app).await {
+                        error!("Failed to apply retention policy: {e}");
+                    }
+                }
+            });
+
+            app.manage(cap_export::ExportControl::default());
+            app.manage(export::ActiveExports::default());
+            app.manage(screenshots::PendingScreenshotState::default());
+            app.manage(screenshots::ScrollingScreenshotState::default());
+            export_queue::init(&app);
             fake_window::init(&app);
             app.manage(EditorWindowIds::default());
 
@@ -1089,15 +1497,28 @@ pub async fn run(recording_logging_handle: LoggingHandle) {
                 });
             }
 
+            camera_preview_mirrored.store(camera::camera_mirror(&app).preview, Ordering::Relaxed);
+
             {
                 app.manage(Arc::new(RwLock::new(App {
                     handle: app.clone(),
                     camera_tx,
                     camera_ws_port,
+                    camera_latency_rx,
+                    camera_preview_mirrored,
                     camera_feed: None,
+                    additional_camera_feeds: BTreeMap::new(),
                     mic_samples_tx: audio_input_tx,
                     mic_feed: None,
+                    mic_label: None,
+                    mic_monitor: None,
+                    app_audio: None,
                     current_recording: None,
+                    recording_paused: false,
+                    mic_muted: false,
+                    selected_capture_target: None,
+                    live_transcription: None,
+                    replay_buffer: None,
                     recording_logging_handle,
                     server_url: GeneralSettingsStore::get(&app)
                         .ok()
@@ -1115,6 +1536,8 @@ pub async fn run(recording_logging_handle: LoggingHandle) {
                 )));
             }
 
+            devices::init(&app);
+
             tokio::spawn(check_notification_permissions(app.clone()));
 
             println!("Checking startup completion and permissions...");
@@ -1124,8 +1547,8 @@ pub async fn run(recording_logging_handle: LoggingHandle) {
             tokio::spawn({
                 let app = app.clone();
                 async move {
+                    // Accessibility is optional, so missing it only degrades window focusing
                     if !permissions.screen_recording.permitted()
-                        || !permissions.accessibility.permitted()
                         || GeneralSettingsStore::get(&app)
                             .ok()
                             .flatten()
@@ -1146,7 +1569,17 @@ pub async fn run(recording_logging_handle: LoggingHandle) {
             tray::create_tray(&app).unwrap();
 
             RequestNewScreenshot::listen_any_spawn(&app, |_, app| async move {
-                if let Err(e) = screenshots::take_screenshot(app.clone(), app.state()).await {
+                if let Err(e) = screenshots::take_screenshot(
+                    app.clone(),
+                    app.state(),
+                    None,
+                    screenshots::ScreenshotFormat::Png,
+                    0,
+                    None,
+                    false,
+                )
+                .await
+                {
                     eprintln!("Failed to take screenshot: {}", e);
                 }
             });
@@ -1183,6 +1616,7 @@ pub async fn run(recording_logging_handle: LoggingHandle) {
                                     if app_state.current_recording.is_none() {
                                         app_state.mic_feed.take();
                                         app_state.camera_feed.take();
+                                        app_state.additional_camera_feeds.clear();
 
                                         if let Some(camera) = CapWindowId::Camera.get(&app) {
                                             let _ = camera.close();
@@ -1232,7 +1666,7 @@ pub async fn run(recording_logging_handle: LoggingHandle) {
                 WindowEvent::DragDrop(event) => {
                     if let tauri::DragDropEvent::Drop { paths, .. } = event {
                         for path in paths {
-                            let _ = open_project_from_path(path, app.clone());
+                            handle_dropped_path(path.clone(), label.to_string(), app.clone());
                         }
                     }
                 }
@@ -1409,6 +1843,74 @@ fn open_project_from_path(path: &PathBuf, app: AppHandle) -> Result<(), String>
     Ok(())
 }
 
+const IMPORTABLE_VIDEO_EXTENSIONS: &[&str] = &["mp4", "mov", "webm"];
+
+/// Opens dropped projects, imports dropped videos into new projects, and imports dropped
+/// subtitles into the project open in the editor window they were dropped on
+fn handle_dropped_path(path: PathBuf, window_label: String, app: AppHandle) {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase())
+        .unwrap_or_default();
+
+    if extension == "cap" {
+        if let Err(e) = open_project_from_path(&path, app.clone()) {
+            global_message_dialog(app, format!("Failed to open project: {e}"));
+        }
+    } else if IMPORTABLE_VIDEO_EXTENSIONS.contains(&extension.as_str()) {
+        tokio::spawn(async move {
+            let project_path = match import::import_video(app.clone(), path).await {
+                Ok(project_path) => project_path,
+                Err(e) => {
+                    global_message_dialog(app, format!("Failed to import video: {e}"));
+                    return;
+                }
+            };
+
+            if let Err(e) = open_project_from_path(&project_path, app.clone()) {
+                global_message_dialog(app, format!("Failed to open imported video: {e}"));
+            }
+        });
+    } else if captions::IMPORTABLE_CAPTION_EXTENSIONS.contains(&extension.as_str()) {
+        let project_path = match CapWindowId::from_str(&window_label) {
+            Ok(CapWindowId::Editor { id }) => EditorWindowIds::get(&app)
+                .ids
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|(_, window_id)| *window_id == id)
+                .map(|(path, _)| path.clone()),
+            _ => None,
+        };
+
+        let Some(video_id) = project_path
+            .as_ref()
+            .and_then(|path| path.file_stem())
+            .map(|stem| stem.to_string_lossy().to_string())
+        else {
+            global_message_dialog(
+                app,
+                "Drop subtitle files onto an open editor to import them as captions.".to_string(),
+            );
+            return;
+        };
+
+        tokio::spawn(async move {
+            if let Err(e) = captions::import_captions(video_id, path, app.clone()).await {
+                global_message_dialog(app, format!("Failed to import captions: {e}"));
+            }
+        });
+    } else {
+        global_message_dialog(
+            app,
+            "Unsupported file type. Drop a Klip project (.cap), a video (.mp4, .mov, .webm), \
+             or subtitles (.srt, .vtt) onto an editor."
+                .to_string(),
+        );
+    }
+}
+
 #[tauri::command]
 #[specta::specta]
 async fn delete_wallpaper(app: AppHandle, file_path: String) -> Result<(), String> {
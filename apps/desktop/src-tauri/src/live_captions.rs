@@ -0,0 +1,373 @@
+use std::{sync::Arc, time::Duration};
+
+use cap_media::feeds::{AudioInputFeed, AudioInputSamplesReceiver};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::AppHandle;
+use tauri_specta::Event;
+use tokio::sync::oneshot;
+use tracing::{error, info, warn};
+use whisper_rs::{FullParams, SamplingStrategy, WhisperContext};
+
+use crate::{
+    audio_meter::samples_to_f64,
+    captions::{self, CaptionData, CaptionSegment, CaptionSettings},
+    general_settings::GeneralSettingsStore,
+};
+
+const WHISPER_SAMPLE_RATE: u32 = 16000;
+// Seconds of audio collected before each incremental Whisper pass
+const CHUNK_SECS: usize = 5;
+
+/// Partial captions produced while recording, or the reconciled result once it stops
+#[derive(Debug, Clone, Serialize, Deserialize, Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveCaptionsUpdate {
+    pub segments: Vec<CaptionSegment>,
+    pub is_final: bool,
+}
+
+/// Feeds microphone audio to Whisper in small chunks while a recording is in progress
+pub struct LiveTranscription {
+    stop_tx: flume::Sender<oneshot::Sender<Vec<CaptionSegment>>>,
+}
+
+impl LiveTranscription {
+    /// Starts live transcription if it's enabled in the general settings and a mic is attached
+    pub async fn start_if_enabled(
+        app: &AppHandle,
+        mic_feed: Option<&AudioInputFeed>,
+    ) -> Option<Self> {
+        let settings = GeneralSettingsStore::get(app).ok().flatten()?;
+        if !settings.live_captions_enabled {
+            return None;
+        }
+
+        let Some(model_path) = settings.live_captions_model_path else {
+            warn!("Live captions are enabled but no model is selected");
+            return None;
+        };
+
+        let mic_feed = mic_feed?;
+
+        match Self::start(app.clone(), mic_feed, &model_path).await {
+            Ok(transcription) => Some(transcription),
+            Err(e) => {
+                error!("Failed to start live captions: {e}");
+                None
+            }
+        }
+    }
+
+    pub async fn start(
+        app: AppHandle,
+        mic_feed: &AudioInputFeed,
+        model_path: &str,
+    ) -> Result<Self, String> {
+        if !std::path::Path::new(model_path).exists() {
+            return Err(format!("Model file not found at path: {}", model_path));
+        }
+
+        let context = captions::get_whisper_context(model_path).await?;
+
+        let (samples_tx, samples_rx) = AudioInputFeed::create_channel();
+        mic_feed
+            .add_sender(samples_tx)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let audio_info = mic_feed.audio_info();
+        let (stop_tx, stop_rx) = flume::bounded(1);
+
+        std::thread::spawn(move || {
+            transcribe_loop(
+                app,
+                context,
+                samples_rx,
+                stop_rx,
+                audio_info.sample_rate,
+                audio_info.channels,
+            )
+        });
+
+        info!("Started live captions with model {}", model_path);
+
+        Ok(Self { stop_tx })
+    }
+
+    /// Transcribes any remaining audio and returns every segment, ordered and renumbered
+    pub async fn finish(self) -> Vec<CaptionSegment> {
+        let (done_tx, done_rx) = oneshot::channel();
+
+        if self.stop_tx.send_async(done_tx).await.is_err() {
+            return vec![];
+        }
+
+        done_rx.await.unwrap_or_default()
+    }
+}
+
+/// Finalizes live transcription for a completed recording and saves it as the project's captions
+pub async fn finish_and_save(
+    app: &AppHandle,
+    transcription: LiveTranscription,
+    project_path: &std::path::Path,
+) -> Result<(), String> {
+    let segments = transcription.finish().await;
+
+    LiveCaptionsUpdate {
+        segments: segments.clone(),
+        is_final: true,
+    }
+    .emit(app)
+    .ok();
+
+    if segments.is_empty() {
+        return Ok(());
+    }
+
+    let video_id = project_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .ok_or_else(|| "Invalid recording path".to_string())?
+        .to_string();
+
+    captions::save_captions(
+        video_id,
+        CaptionData {
+            segments,
+            settings: Some(CaptionSettings::default()),
+        },
+        app.clone(),
+    )
+    .await
+}
+
+/// A chunk of mono 16kHz audio, with where it starts in the recording
+struct PendingChunk {
+    samples: Vec<f32>,
+    offset: f32,
+}
+
+/// Drains the mic channel and hands full chunks to a separate inference thread,
+/// so the channel never backs up (and drops audio) while Whisper is running
+fn transcribe_loop(
+    app: AppHandle,
+    context: Arc<WhisperContext>,
+    samples_rx: AudioInputSamplesReceiver,
+    stop_rx: flume::Receiver<oneshot::Sender<Vec<CaptionSegment>>>,
+    input_rate: u32,
+    channels: usize,
+) {
+    let chunk_len = WHISPER_SAMPLE_RATE as usize * CHUNK_SECS;
+    let (chunk_tx, chunk_rx) = flume::unbounded::<PendingChunk>();
+    let worker = std::thread::spawn(move || inference_loop(app, context, chunk_rx));
+
+    // Timestamps come from the number of input frames received rather than from
+    // the resampled length, so rounding in each resample doesn't add up over time
+    let mut input_frames = 0u64;
+    let mut pending = PendingChunk {
+        samples: Vec::with_capacity(chunk_len),
+        offset: 0.0,
+    };
+
+    let done_tx = loop {
+        match stop_rx.try_recv() {
+            Ok(done_tx) => break Some(done_tx),
+            Err(flume::TryRecvError::Disconnected) => break None,
+            Err(flume::TryRecvError::Empty) => {}
+        }
+
+        match samples_rx.recv_timeout(Duration::from_millis(100)) {
+            Ok(samples) => {
+                let interleaved = samples_to_f64(&samples)
+                    .map(|s| s as f32)
+                    .collect::<Vec<_>>();
+                let mono = downmix(&interleaved, channels);
+                input_frames += mono.len() as u64;
+                pending
+                    .samples
+                    .extend(resample_linear(&mono, input_rate, WHISPER_SAMPLE_RATE));
+            }
+            Err(flume::RecvTimeoutError::Timeout) => {}
+            Err(flume::RecvTimeoutError::Disconnected) => break stop_rx.recv().ok(),
+        }
+
+        if pending.samples.len() >= chunk_len {
+            let next = PendingChunk {
+                samples: Vec::with_capacity(chunk_len),
+                offset: input_frames as f32 / input_rate as f32,
+            };
+            chunk_tx.send(std::mem::replace(&mut pending, next)).ok();
+        }
+    };
+
+    // Dropped without finishing, e.g. the recording was cancelled
+    let Some(done_tx) = done_tx else {
+        return;
+    };
+
+    if !pending.samples.is_empty() {
+        chunk_tx.send(pending).ok();
+    }
+    drop(chunk_tx);
+
+    let segments = worker.join().unwrap_or_default();
+    done_tx.send(reconcile(segments)).ok();
+}
+
+/// Runs Whisper over each chunk as it arrives, until the drain side hangs up
+fn inference_loop(
+    app: AppHandle,
+    context: Arc<WhisperContext>,
+    chunk_rx: flume::Receiver<PendingChunk>,
+) -> Vec<CaptionSegment> {
+    let mut segments = Vec::<CaptionSegment>::new();
+
+    while let Ok(chunk) = chunk_rx.recv() {
+        let partial = match transcribe_chunk(&context, &chunk.samples, chunk.offset) {
+            Ok(partial) => partial,
+            Err(e) => {
+                warn!("Live caption chunk failed: {e}");
+                continue;
+            }
+        };
+
+        if partial.is_empty() {
+            continue;
+        }
+
+        segments.extend(partial.iter().cloned());
+
+        LiveCaptionsUpdate {
+            segments: partial,
+            is_final: false,
+        }
+        .emit(&app)
+        .ok();
+    }
+
+    segments
+}
+
+fn transcribe_chunk(
+    context: &WhisperContext,
+    samples: &[f32],
+    offset: f32,
+) -> Result<Vec<CaptionSegment>, String> {
+    let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+    params.set_translate(false);
+    params.set_print_special(false);
+    params.set_print_progress(false);
+    params.set_print_realtime(false);
+    params.set_single_segment(false);
+    params.set_language(Some("auto"));
+
+    let mut state = context
+        .create_state()
+        .map_err(|e| format!("Failed to create Whisper state: {}", e))?;
+
+    state
+        .full(params, samples)
+        .map_err(|e| format!("Failed to run Whisper transcription: {}", e))?;
+
+    let num_segments = state
+        .full_n_segments()
+        .map_err(|e| format!("Failed to get number of segments: {}", e))?;
+
+    let mut segments = Vec::new();
+
+    for i in 0..num_segments {
+        let text = state
+            .full_get_segment_text(i)
+            .map_err(|e| format!("Failed to get segment text: {}", e))?;
+        let start = state
+            .full_get_segment_t0(i)
+            .map_err(|e| format!("Failed to get segment start time: {}", e))?;
+        let end = state
+            .full_get_segment_t1(i)
+            .map_err(|e| format!("Failed to get segment end time: {}", e))?;
+
+        if text.trim().is_empty() {
+            continue;
+        }
+
+        segments.push(CaptionSegment {
+            id: String::new(),
+            start: offset + start as f32 / 100.0,
+            end: offset + end as f32 / 100.0,
+            text: text.trim().to_string(),
+            style_override: None,
+            words: None,
+        });
+    }
+
+    Ok(segments)
+}
+
+/// Orders the chunked results, clamps overlaps at chunk boundaries and assigns final ids
+fn reconcile(mut segments: Vec<CaptionSegment>) -> Vec<CaptionSegment> {
+    segments.sort_by(|a, b| a.start.total_cmp(&b.start));
+
+    for i in 1..segments.len() {
+        let previous_end = segments[i - 1].end;
+        if segments[i].start < previous_end {
+            segments[i - 1].end = segments[i].start;
+        }
+    }
+
+    segments.retain(|s| s.end > s.start);
+
+    for (i, segment) in segments.iter_mut().enumerate() {
+        segment.id = format!("segment-{}", i);
+    }
+
+    segments
+}
+
+fn downmix(samples: &[f32], channels: usize) -> Vec<f32> {
+    if channels <= 1 {
+        return samples.to_vec();
+    }
+
+    samples
+        .chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+        .collect()
+}
+
+fn resample_linear(samples: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
+    if from_rate == to_rate || samples.is_empty() {
+        return samples.to_vec();
+    }
+
+    let ratio = from_rate as f64 / to_rate as f64;
+    let out_len = (samples.len() as f64 / ratio) as usize;
+
+    (0..out_len)
+        .map(|i| {
+            let position = i as f64 * ratio;
+            let index = position as usize;
+            let fraction = (position - index as f64) as f32;
+            let current = samples[index.min(samples.len() - 1)];
+            let next = samples[(index + 1).min(samples.len() - 1)];
+            current + (next - current) * fraction
+        })
+        .collect()
+}
+
+/// Enable or disable live captions during recording, and pick the (ideally tiny/base) model to use
+#[tauri::command]
+#[specta::specta]
+pub fn set_live_captions(
+    app: AppHandle,
+    enabled: bool,
+    model_path: Option<String>,
+) -> Result<(), String> {
+    GeneralSettingsStore::update(&app, |settings| {
+        settings.live_captions_enabled = enabled;
+        if model_path.is_some() {
+            settings.live_captions_model_path = model_path;
+        }
+    })
+}
@@ -1,5 +1,8 @@
 use crate::{general_settings::GeneralSettingsStore, AppSounds};
-use tauri_plugin_notification::NotificationExt;
+use serde::Serialize;
+use specta::Type;
+use tauri::AppHandle;
+use tauri_plugin_notification::{NotificationExt, PermissionState};
 
 pub enum NotificationType {
     VideoSaved,
@@ -13,6 +16,18 @@ pub enum NotificationType {
     ScreenshotCopiedToClipboard,
     ScreenshotSaveFailed,
     ScreenshotCopyFailed,
+    SettingsReset,
+    Test,
+}
+
+/// Whether a notification was shown, or why it wasn't
+#[derive(Serialize, Type, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum NotificationDelivery {
+    Delivered,
+    DisabledInSettings,
+    PermissionDenied,
+    Failed,
 }
 
 impl NotificationType {
@@ -61,6 +76,16 @@ impl NotificationType {
                 "Unable to copy screenshot to clipboard. Please try again",
                 true,
             ),
+            NotificationType::SettingsReset => (
+                "Settings Reset",
+                "Some settings couldn't be read and were reset. A backup was kept",
+                true,
+            ),
+            NotificationType::Test => (
+                "Notifications Enabled",
+                "This is how notifications from Cap will look",
+                false,
+            ),
         }
     }
 
@@ -85,24 +110,48 @@ impl NotificationType {
     }
 }
 
-pub fn send_notification(app: &tauri::AppHandle, notification_type: NotificationType) {
+pub fn send_notification(
+    app: &tauri::AppHandle,
+    notification_type: NotificationType,
+) -> NotificationDelivery {
     // Check if notifications are enabled in settings
     let enable_notifications = GeneralSettingsStore::get(app)
         .map(|settings| settings.map_or(false, |s| s.enable_notifications))
         .unwrap_or(false);
 
     if !enable_notifications {
-        return;
+        return NotificationDelivery::DisabledInSettings;
+    }
+
+    if !matches!(
+        app.notification().permission_state(),
+        Ok(PermissionState::Granted)
+    ) {
+        return NotificationDelivery::PermissionDenied;
     }
 
     let (title, body, is_error) = notification_type.details();
 
-    app.notification()
+    if app
+        .notification()
         .builder()
         .title(title)
         .body(body)
         .show()
-        .ok();
+        .is_err()
+    {
+        return NotificationDelivery::Failed;
+    }
 
     AppSounds::Notification.play();
+
+    NotificationDelivery::Delivered
+}
+
+/// Send a sample notification, reporting whether it could be shown, to help troubleshoot
+/// missing notifications
+#[tauri::command]
+#[specta::specta]
+pub fn send_test_notification(app: AppHandle) -> NotificationDelivery {
+    send_notification(&app, NotificationType::Test)
 }
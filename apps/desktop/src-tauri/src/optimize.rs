@@ -0,0 +1,466 @@
+use std::path::{Path, PathBuf};
+
+use cap_project::{OptimizationMeta, RecordingMeta, RecordingMetaInner, StudioRecordingMeta};
+use ffmpeg::{codec as avcodec, format as avformat, software::scaling, Dictionary};
+use relative_path::RelativePathBuf;
+use serde::Serialize;
+use specta::Type;
+use tracing::{info, warn};
+
+// Originals replaced by the last optimization, mirroring their paths within the project
+const BACKUP_DIR: &str = ".optimize-backup";
+const STAGING_BACKUP_DIR: &str = ".optimize-backup-staging";
+
+#[derive(Serialize, Type, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OptimizeRecordingResult {
+    /// Size of the recording's videos in MB before optimizing
+    pub size_before: f64,
+    /// Size of the recording's videos in MB after optimizing
+    pub size_after: f64,
+    pub optimized_files: u32,
+}
+
+/// Re-encode a recording's videos in place at the given CRF (0-51, lower is higher quality).
+/// Replaced originals are kept as a single backup level that `restore_recording_backup` can put back.
+#[tauri::command]
+#[specta::specta]
+pub async fn optimize_recording(
+    project_path: PathBuf,
+    quality: u32,
+) -> Result<OptimizeRecordingResult, String> {
+    if quality > 51 {
+        return Err("Quality must be a CRF value between 0 and 51".to_string());
+    }
+
+    let mut meta = RecordingMeta::load_for_project(&project_path).map_err(|e| e.to_string())?;
+    let videos = recording_video_paths(&meta);
+
+    let (result, replaced) =
+        tokio::task::spawn_blocking(move || optimize_videos(&project_path, &videos, quality))
+            .await
+            .map_err(|e| format!("Optimization task failed: {}", e))??;
+
+    // A new backup replaces any previous one, along with its record
+    if !replaced.is_empty() {
+        meta.optimization = Some(OptimizationMeta {
+            backup: RelativePathBuf::from(BACKUP_DIR),
+            files: replaced,
+            size_before: result.size_before,
+            size_after: result.size_after,
+        });
+        meta.save_for_project()
+            .map_err(|e| format!("Failed to save recording meta: {e:?}"))?;
+    }
+
+    Ok(result)
+}
+
+/// Put back the originals saved by the last `optimize_recording`
+#[tauri::command]
+#[specta::specta]
+pub async fn restore_recording_backup(project_path: PathBuf) -> Result<(), String> {
+    let backup_dir = project_path.join(BACKUP_DIR);
+    if !backup_dir.exists() {
+        return Err("This recording has no backup to restore".to_string());
+    }
+
+    move_files(&backup_dir, &project_path)
+        .map_err(|e| format!("Failed to restore backup: {}", e))?;
+    std::fs::remove_dir_all(&backup_dir).map_err(|e| format!("Failed to remove backup: {}", e))?;
+
+    let mut meta = RecordingMeta::load_for_project(&project_path).map_err(|e| e.to_string())?;
+    if meta.optimization.take().is_some() {
+        meta.save_for_project()
+            .map_err(|e| format!("Failed to save recording meta: {e:?}"))?;
+    }
+
+    info!("Restored recording backup for {}", project_path.display());
+
+    Ok(())
+}
+
+fn recording_video_paths(meta: &RecordingMeta) -> Vec<PathBuf> {
+    match &meta.inner {
+        RecordingMetaInner::Instant(_) => vec![meta.project_path.join("content/output.mp4")],
+        RecordingMetaInner::Studio(StudioRecordingMeta::SingleSegment { segment }) => {
+            std::iter::once(&segment.display)
+                .chain(segment.camera.as_ref())
+                .map(|video| meta.path(&video.path))
+                .collect()
+        }
+        RecordingMetaInner::Studio(StudioRecordingMeta::MultipleSegments { inner }) => inner
+            .segments
+            .iter()
+            .flat_map(|segment| {
+                segment
+                    .display
+                    .iter()
+                    .chain(segment.camera.as_ref())
+                    .chain(segment.additional_cameras.iter().flatten())
+            })
+            .map(|video| meta.path(&video.path))
+            .collect(),
+    }
+}
+
+/// Returns the totals for every video, and the paths of the ones that were replaced
+fn optimize_videos(
+    project_path: &Path,
+    videos: &[PathBuf],
+    crf: u32,
+) -> Result<(OptimizeRecordingResult, Vec<RelativePathBuf>), String> {
+    let staging_dir = project_path.join(STAGING_BACKUP_DIR);
+    if staging_dir.exists() {
+        std::fs::remove_dir_all(&staging_dir).map_err(|e| e.to_string())?;
+    }
+
+    let mut result = OptimizeRecordingResult {
+        size_before: 0.0,
+        size_after: 0.0,
+        optimized_files: 0,
+    };
+    let mut replaced = vec![];
+
+    for video in videos.iter().filter(|v| v.exists()) {
+        match optimize_video(project_path, video, &staging_dir, crf) {
+            Ok((before, after)) => {
+                result.size_before += bytes_to_mb(before);
+                result.size_after += bytes_to_mb(after);
+                if after < before {
+                    result.optimized_files += 1;
+                    if let Ok(relative) = RelativePathBuf::from_path(
+                        video.strip_prefix(project_path).unwrap_or(video),
+                    ) {
+                        replaced.push(relative);
+                    }
+                }
+            }
+            Err(e) => {
+                // Leave the recording exactly as it was
+                if staging_dir.exists() {
+                    if let Err(restore_err) = move_files(&staging_dir, project_path) {
+                        warn!("Failed to roll back optimization: {}", restore_err);
+                    }
+                    std::fs::remove_dir_all(&staging_dir).ok();
+                }
+                return Err(e);
+            }
+        }
+    }
+
+    if result.optimized_files > 0 {
+        // Only one level of backup is kept, so the new one replaces any previous backup
+        let backup_dir = project_path.join(BACKUP_DIR);
+        if backup_dir.exists() {
+            std::fs::remove_dir_all(&backup_dir).map_err(|e| e.to_string())?;
+        }
+        std::fs::rename(&staging_dir, &backup_dir).map_err(|e| e.to_string())?;
+    } else if staging_dir.exists() {
+        std::fs::remove_dir_all(&staging_dir).ok();
+    }
+
+    info!(
+        "Optimized {} files in {}: {:.2}MB -> {:.2}MB",
+        result.optimized_files,
+        project_path.display(),
+        result.size_before,
+        result.size_after
+    );
+
+    Ok((result, replaced))
+}
+
+/// Re-encodes a single video, replacing it only if the result is valid and smaller.
+/// Returns the size in bytes before and after.
+fn optimize_video(
+    project_path: &Path,
+    video: &Path,
+    staging_dir: &Path,
+    crf: u32,
+) -> Result<(u64, u64), String> {
+    let size_before = file_size(video)?;
+    let optimized = video.with_extension("optimized.mp4");
+
+    let transcoded =
+        transcode_video(video, &optimized, crf).and_then(|_| verify_transcode(video, &optimized));
+    if let Err(e) = transcoded {
+        std::fs::remove_file(&optimized).ok();
+        return Err(format!("Failed to optimize {}: {}", video.display(), e));
+    }
+
+    let size_after = file_size(&optimized)?;
+    if size_after >= size_before {
+        std::fs::remove_file(&optimized).ok();
+        return Ok((size_before, size_before));
+    }
+
+    let relative_path = video
+        .strip_prefix(project_path)
+        .map_err(|e| e.to_string())?;
+    let backup_path = staging_dir.join(relative_path);
+    if let Some(parent) = backup_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    std::fs::rename(video, &backup_path).map_err(|e| format!("Failed to back up video: {}", e))?;
+    std::fs::rename(&optimized, video).map_err(|e| format!("Failed to replace video: {}", e))?;
+
+    Ok((size_before, size_after))
+}
+
+pub(crate) fn transcode_video(
+    input_path: &Path,
+    output_path: &Path,
+    crf: u32,
+) -> Result<(), String> {
+    let mut input =
+        avformat::input(&input_path).map_err(|e| format!("Failed to open video file: {}", e))?;
+    let mut output = avformat::output(&output_path)
+        .map_err(|e| format!("Failed to create output file: {}", e))?;
+
+    let video_stream = input
+        .streams()
+        .best(ffmpeg::media::Type::Video)
+        .ok_or_else(|| "No video stream found".to_string())?;
+    let video_index = video_stream.index();
+    let input_time_base = video_stream.time_base();
+
+    let mut decoder = avcodec::Context::from_parameters(video_stream.parameters())
+        .map_err(|e| format!("Failed to create decoder context: {}", e))?
+        .decoder()
+        .video()
+        .map_err(|e| format!("Failed to create decoder: {}", e))?;
+
+    let codec = avcodec::encoder::find_by_name("libx264")
+        .or_else(|| avcodec::encoder::find(avcodec::Id::H264))
+        .ok_or_else(|| "H264 encoder not found".to_string())?;
+
+    let mut encoder = avcodec::Context::new_with_codec(codec)
+        .encoder()
+        .video()
+        .map_err(|e| format!("Failed to create encoder: {}", e))?;
+
+    encoder.set_width(decoder.width());
+    encoder.set_height(decoder.height());
+    encoder.set_format(avformat::Pixel::YUV420P);
+    encoder.set_time_base(input_time_base);
+    encoder.set_frame_rate(Some(video_stream.avg_frame_rate()));
+    if output
+        .format()
+        .flags()
+        .contains(avformat::Flags::GLOBAL_HEADER)
+    {
+        encoder.set_flags(avcodec::Flags::GLOBAL_HEADER);
+    }
+
+    let mut options = Dictionary::new();
+    options.set("crf", &crf.to_string());
+    options.set("preset", "medium");
+
+    let mut encoder = encoder
+        .open_with(options)
+        .map_err(|e| format!("Failed to open encoder: {}", e))?;
+
+    let mut stream_mapping = vec![None; input.nb_streams() as usize];
+
+    let mut output_video = output
+        .add_stream(codec)
+        .map_err(|e| format!("Failed to add video stream: {}", e))?;
+    output_video.set_parameters(&encoder);
+    stream_mapping[video_index] = Some(output_video.index());
+
+    // Audio is already compressed, so it's copied through untouched
+    for stream in input.streams() {
+        if stream.parameters().medium() != ffmpeg::media::Type::Audio {
+            continue;
+        }
+
+        let mut output_stream = output
+            .add_stream(avcodec::encoder::find(avcodec::Id::None))
+            .map_err(|e| format!("Failed to add audio stream: {}", e))?;
+        output_stream.set_parameters(stream.parameters());
+        unsafe {
+            (*output_stream.parameters().as_mut_ptr()).codec_tag = 0;
+        }
+        stream_mapping[stream.index()] = Some(output_stream.index());
+    }
+
+    output
+        .write_header()
+        .map_err(|e| format!("Failed to write header: {}", e))?;
+
+    let output_video_index = stream_mapping[video_index].unwrap_or_default();
+    let output_time_base = output.stream(output_video_index).unwrap().time_base();
+
+    let mut scaler: Option<scaling::Context> = None;
+    let mut decoded = ffmpeg::frame::Video::empty();
+    let mut packet = ffmpeg::Packet::empty();
+
+    let mut encode_decoded = |decoder: &mut ffmpeg::decoder::Video,
+                              encoder: &mut ffmpeg::encoder::Video,
+                              output: &mut avformat::context::Output|
+     -> Result<(), String> {
+        while decoder.receive_frame(&mut decoded).is_ok() {
+            let mut frame = if decoded.format() == avformat::Pixel::YUV420P {
+                decoded.clone()
+            } else {
+                if scaler.is_none() {
+                    scaler = Some(
+                        scaling::Context::get(
+                            decoded.format(),
+                            decoded.width(),
+                            decoded.height(),
+                            avformat::Pixel::YUV420P,
+                            decoded.width(),
+                            decoded.height(),
+                            scaling::Flags::BILINEAR,
+                        )
+                        .map_err(|e| format!("Failed to create scaler: {}", e))?,
+                    );
+                }
+
+                let mut converted = ffmpeg::frame::Video::empty();
+                scaler
+                    .as_mut()
+                    .unwrap()
+                    .run(&decoded, &mut converted)
+                    .map_err(|e| format!("Failed to convert frame: {}", e))?;
+                converted
+            };
+
+            frame.set_pts(decoded.timestamp());
+            encoder
+                .send_frame(&frame)
+                .map_err(|e| format!("Failed to encode frame: {}", e))?;
+
+            write_encoded(
+                encoder,
+                &mut packet,
+                output,
+                output_video_index,
+                input_time_base,
+                output_time_base,
+            )?;
+        }
+
+        Ok(())
+    };
+
+    for (stream, mut input_packet) in input.packets() {
+        let Some(output_index) = stream_mapping[stream.index()] else {
+            continue;
+        };
+
+        if stream.index() == video_index {
+            decoder
+                .send_packet(&input_packet)
+                .map_err(|e| format!("Failed to decode packet: {}", e))?;
+            encode_decoded(&mut decoder, &mut encoder, &mut output)?;
+        } else {
+            input_packet.rescale_ts(
+                stream.time_base(),
+                output.stream(output_index).unwrap().time_base(),
+            );
+            input_packet.set_position(-1);
+            input_packet.set_stream(output_index);
+            input_packet
+                .write_interleaved(&mut output)
+                .map_err(|e| format!("Failed to write packet: {}", e))?;
+        }
+    }
+
+    decoder
+        .send_eof()
+        .map_err(|e| format!("Failed to flush decoder: {}", e))?;
+    encode_decoded(&mut decoder, &mut encoder, &mut output)?;
+
+    encoder
+        .send_eof()
+        .map_err(|e| format!("Failed to flush encoder: {}", e))?;
+    write_encoded(
+        &mut encoder,
+        &mut ffmpeg::Packet::empty(),
+        &mut output,
+        output_video_index,
+        input_time_base,
+        output_time_base,
+    )?;
+
+    output
+        .write_trailer()
+        .map_err(|e| format!("Failed to write trailer: {}", e))?;
+
+    Ok(())
+}
+
+fn write_encoded(
+    encoder: &mut ffmpeg::encoder::Video,
+    packet: &mut ffmpeg::Packet,
+    output: &mut avformat::context::Output,
+    stream_index: usize,
+    encoder_time_base: ffmpeg::Rational,
+    output_time_base: ffmpeg::Rational,
+) -> Result<(), String> {
+    while encoder.receive_packet(packet).is_ok() {
+        packet.set_stream(stream_index);
+        packet.rescale_ts(encoder_time_base, output_time_base);
+        packet
+            .write_interleaved(output)
+            .map_err(|e| format!("Failed to write packet: {}", e))?;
+    }
+
+    Ok(())
+}
+
+/// Makes sure the optimized file opens, has video, and covers the same duration as the original
+fn verify_transcode(original: &Path, optimized: &Path) -> Result<(), String> {
+    let duration = |path: &Path| -> Result<f64, String> {
+        let input =
+            avformat::input(&path).map_err(|e| format!("Failed to open video file: {}", e))?;
+        input
+            .streams()
+            .best(ffmpeg::media::Type::Video)
+            .ok_or_else(|| "No video stream found".to_string())?;
+        Ok(input.duration() as f64 / 1_000_000.0)
+    };
+
+    let original_duration = duration(original)?;
+    let optimized_duration = duration(optimized)?;
+
+    if (original_duration - optimized_duration).abs() > (original_duration * 0.02).max(0.5) {
+        return Err(format!(
+            "Optimized video is {:.2}s long but the original is {:.2}s",
+            optimized_duration, original_duration
+        ));
+    }
+
+    Ok(())
+}
+
+/// Moves every file under `from` to the same relative path under `to`, overwriting existing files
+fn move_files(from: &Path, to: &Path) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+
+        if entry.file_type()?.is_dir() {
+            std::fs::create_dir_all(&target)?;
+            move_files(&entry.path(), &target)?;
+        } else {
+            std::fs::rename(entry.path(), &target)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn file_size(path: &Path) -> Result<u64, String> {
+    path.metadata()
+        .map(|m| m.len())
+        .map_err(|e| format!("Failed to get file metadata: {}", e))
+}
+
+fn bytes_to_mb(bytes: u64) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0)
+}
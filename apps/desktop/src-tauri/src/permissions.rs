@@ -133,6 +133,14 @@ impl OSPermissionStatus {
     }
 }
 
+/// A feature that still works, but less well, because an optional permission is missing
+#[derive(Serialize, Deserialize, Debug, specta::Type, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum DegradedFeature {
+    // Cap's windows are raised with Tauri's window APIs, which may not take focus from other apps
+    WindowFocus,
+}
+
 #[derive(Serialize, Deserialize, Debug, specta::Type)]
 #[serde(rename_all = "camelCase")]
 pub struct OSPermissionsCheck {
@@ -140,6 +148,7 @@ pub struct OSPermissionsCheck {
     pub microphone: OSPermissionStatus,
     pub camera: OSPermissionStatus,
     pub accessibility: OSPermissionStatus,
+    pub degraded_features: Vec<DegradedFeature>,
 }
 
 impl OSPermissionsCheck {
@@ -199,6 +208,7 @@ pub fn do_permissions_check(initial_check: bool) -> OSPermissionsCheck {
             microphone: check_av_permission(AVMediaType::Audio),
             camera: check_av_permission(AVMediaType::Video),
             accessibility: { check_accessibility_permission() },
+            degraded_features: degraded_features(has_accessibility_permission()),
         }
     }
 
@@ -209,10 +219,22 @@ pub fn do_permissions_check(initial_check: bool) -> OSPermissionsCheck {
             microphone: OSPermissionStatus::NotNeeded,
             camera: OSPermissionStatus::NotNeeded,
             accessibility: OSPermissionStatus::NotNeeded,
+            degraded_features: vec![],
         }
     }
 }
 
+#[cfg(target_os = "macos")]
+fn degraded_features(accessibility: bool) -> Vec<DegradedFeature> {
+    let mut degraded = vec![];
+
+    if !accessibility {
+        degraded.push(DegradedFeature::WindowFocus);
+    }
+
+    degraded
+}
+
 #[cfg(target_os = "macos")]
 fn check_screen_recording_permission_via_window_list() -> bool {
     use core_foundation::array::CFArray;
@@ -276,6 +298,18 @@ pub fn check_accessibility_permission() -> OSPermissionStatus {
     }
 }
 
+/// Like `check_accessibility_permission`, but never prompts the user
+pub fn has_accessibility_permission() -> bool {
+    #[cfg(target_os = "macos")]
+    {
+        unsafe { AXIsProcessTrusted() }
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        true
+    }
+}
+
 pub fn request_accessibility_permission() {
     #[cfg(target_os = "macos")]
     {
@@ -1,4 +1,9 @@
-use std::{collections::HashMap, path::PathBuf, sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 
 use chrono::Local;
 
@@ -9,6 +14,7 @@ use crate::{
     general_settings::{
         GeneralSettingsStore, MainWindowRecordingStartBehaviour, PostStudioRecordingBehaviour,
     },
+    live_captions::{self, LiveTranscription},
     open_external_link,
     presets::PresetsStore,
     upload::{
@@ -23,11 +29,15 @@ use cap_fail::fail;
 use cap_media::{feeds::CameraFeed, platform::display_for_window, sources::ScreenCaptureTarget};
 use cap_media::{
     platform::Bounds,
-    sources::{CaptureScreen, CaptureWindow},
+    sources::{
+        capture_capabilities, supports_app_audio_capture, AppAudioTarget, AudioApplication,
+        CaptureCapabilities, CaptureScreen, CaptureWindow,
+    },
 };
 use cap_project::{
     Platform, ProjectConfiguration, RecordingMeta, RecordingMetaInner, SharingMeta,
-    StudioRecordingMeta, TimelineConfiguration, TimelineSegment, ZoomSegment,
+    StudioRecordingMeta, SystemAudioSourceMeta, TimelineConfiguration, TimelineSegment,
+    ZoomSegment,
 };
 use cap_recording::{
     instant_recording::{CompletedInstantRecording, InstantRecordingHandle},
@@ -35,12 +45,12 @@ use cap_recording::{
 };
 use cap_rendering::ProjectRecordingsMeta;
 use cap_utils::{ensure_dir, spawn_actor};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use specta::Type;
 use tauri::{AppHandle, Manager};
 use tauri_plugin_dialog::{DialogExt, MessageDialogBuilder};
 use tauri_specta::Event;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 pub enum InProgressRecording {
     Instant {
@@ -102,20 +112,24 @@ impl InProgressRecording {
                 progressive_upload,
                 video_upload_info,
                 target_name,
+                inputs,
                 ..
             } => CompletedRecording::Instant {
                 recording: handle.stop().await?,
                 progressive_upload,
                 video_upload_info,
                 target_name,
+                app_audio: inputs.app_audio,
             },
             Self::Studio {
                 handle,
                 target_name,
+                inputs,
                 ..
             } => CompletedRecording::Studio {
                 recording: handle.stop().await?,
                 target_name,
+                app_audio: inputs.app_audio,
             },
         })
     }
@@ -141,10 +155,12 @@ pub enum CompletedRecording {
         target_name: String,
         progressive_upload: Option<InstantMultipartUpload>,
         video_upload_info: VideoUploadInfo,
+        app_audio: Option<AppAudioTarget>,
     },
     Studio {
         recording: CompletedStudioRecording,
         target_name: String,
+        app_audio: Option<AppAudioTarget>,
     },
 }
 
@@ -169,6 +185,24 @@ impl CompletedRecording {
             Self::Studio { target_name, .. } => target_name,
         }
     }
+
+    /// The application system audio was limited to, if the platform could isolate it
+    pub fn system_audio_source(&self) -> Option<SystemAudioSourceMeta> {
+        let app_audio = match self {
+            Self::Instant { app_audio, .. } => app_audio,
+            Self::Studio { app_audio, .. } => app_audio,
+        };
+
+        app_audio
+            .as_ref()
+            .filter(|_| supports_app_audio_capture())
+            .map(|target| match target {
+                AppAudioTarget::Pid { pid } => SystemAudioSourceMeta::Pid { pid: *pid },
+                AppAudioTarget::BundleId { bundle_id } => SystemAudioSourceMeta::BundleId {
+                    bundle_id: bundle_id.clone(),
+                },
+            })
+    }
 }
 
 #[tauri::command(async)]
@@ -195,12 +229,90 @@ pub fn list_cameras() -> Vec<String> {
     CameraFeed::list_cameras()
 }
 
+/// Which capture features this platform supports, so the UI can hide the ones it doesn't
+#[tauri::command(async)]
+#[specta::specta]
+pub fn get_capture_capabilities() -> CaptureCapabilities {
+    capture_capabilities()
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_audio_applications() -> Vec<AudioApplication> {
+    cap_media::sources::list_audio_applications().await
+}
+
+/// Record only this application's audio instead of all system audio, or clear the selection with `None`.
+/// Returns false when the platform can't capture a single application's audio, in which case
+/// recordings fall back to capturing all system audio.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_app_audio_capture(
+    state: MutableState<'_, App>,
+    pid_or_bundle: Option<String>,
+) -> Result<bool, String> {
+    let target = pid_or_bundle
+        .filter(|v| !v.trim().is_empty())
+        .map(|v| AppAudioTarget::parse(&v));
+
+    let supported = supports_app_audio_capture();
+    if target.is_some() && !supported {
+        warn!(
+            "Per-app audio capture isn't supported on this platform, all system audio will be recorded"
+        );
+    }
+
+    state.write().await.app_audio = target;
+
+    Ok(supported)
+}
+
+/// Record only the application with `process_id` as system audio, or all system audio with `None`.
+/// Unlike `set_app_audio_capture`, this fails where the platform can't isolate an application
+/// rather than falling back to capturing everything.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_system_audio_source(
+    state: MutableState<'_, App>,
+    process_id: Option<u32>,
+) -> Result<(), String> {
+    let target = match process_id {
+        Some(_) if !supports_app_audio_capture() => {
+            return Err(
+                "Not implemented: this platform can't capture a single application's audio"
+                    .to_string(),
+            );
+        }
+        Some(pid) => Some(AppAudioTarget::Pid {
+            pid: i32::try_from(pid).map_err(|_| format!("Invalid process id {pid}"))?,
+        }),
+        None => None,
+    };
+
+    state.write().await.app_audio = target;
+
+    Ok(())
+}
+
 #[derive(Deserialize, Type, Clone)]
 pub struct StartRecordingInputs {
     pub capture_target: ScreenCaptureTarget,
     #[serde(default)]
     pub capture_system_audio: bool,
     pub mode: RecordingMode,
+    /// Seconds until the recording officially starts, the `recording_countdown` setting when unset.
+    /// Capture begins as soon as this is called so the pre-roll can be buffered during the countdown.
+    #[serde(default)]
+    pub countdown: Option<u32>,
+    /// Records only the microphone and system audio, as a studio recording with no video
+    #[serde(default)]
+    pub audio_only: bool,
+    /// The application system audio is limited to, taken from the selection at the start
+    #[serde(skip)]
+    pub app_audio: Option<AppAudioTarget>,
+    /// Only records the mic while the push-to-talk hotkey is held, with silence in between
+    #[serde(default)]
+    pub push_to_talk: bool,
 }
 
 #[tauri::command]
@@ -257,7 +369,9 @@ pub async fn start_recording(
         ) as DynLoggingLayer))
         .map_err(|e| format!("Failed to reload logging layer: {e}"))?;
 
-    let target_name = {
+    let target_name = if inputs.audio_only {
+        "Audio".to_string()
+    } else {
         let title = inputs.capture_target.get_title();
 
         match inputs.capture_target {
@@ -326,6 +440,8 @@ pub async fn start_recording(
     };
 
     match &inputs.capture_target {
+        // Nothing is shown in audio-only recordings, so there's nothing to occlude
+        _ if inputs.audio_only => {}
         ScreenCaptureTarget::Window { id } => {
             #[cfg(target_os = "macos")]
             let display = display_for_window(*id).unwrap().id;
@@ -355,6 +471,22 @@ pub async fn start_recording(
     // AUTH BYPASS: Since we're bypassing auth, instant upload is disabled
     let progressive_upload: Option<InstantMultipartUpload> = None;
 
+    let general_settings = GeneralSettingsStore::get(&app).ok().flatten();
+    let countdown = Duration::from_secs(
+        inputs
+            .countdown
+            .or_else(|| general_settings.as_ref()?.recording_countdown)
+            .unwrap_or_default() as u64,
+    );
+    let pre_roll = Duration::from_millis(
+        general_settings
+            .map(|s| s.recording_pre_roll_ms)
+            .unwrap_or_default() as u64,
+    );
+    let official_start = SystemTime::now() + countdown;
+    // Capture can't begin any earlier than now, so the pre-roll is limited by the countdown
+    let start_time = (official_start - pre_roll).max(SystemTime::now());
+
     println!("spawning actor");
 
     // done in spawn to catch panics just in case
@@ -365,19 +497,57 @@ pub async fn start_recording(
             fail!("recording::spawn_actor");
             let mut state = state_mtx.write().await;
 
+            let inputs = StartRecordingInputs {
+                app_audio: state.app_audio.clone(),
+                ..inputs
+            };
+
+            // Push-to-talk starts out silent until the hotkey is first held
+            if inputs.push_to_talk {
+                if let Some(feed) = &state.mic_feed {
+                    feed.set_muted(true).await.map_err(|e| e.to_string())?;
+                    state.mic_muted = true;
+                }
+            }
+
             let base_inputs = cap_recording::RecordingBaseInputs {
                 capture_target: inputs.capture_target,
                 capture_system_audio: inputs.capture_system_audio,
+                app_audio: inputs.app_audio.clone(),
                 mic_feed: &state.mic_feed,
+                start_time: Some(start_time),
             };
 
             let (actor, actor_done_rx) = match inputs.mode {
+                _ if inputs.audio_only => {
+                    let (handle, actor_done_rx) = cap_recording::spawn_audio_recording_actor(
+                        id.clone(),
+                        recording_dir.clone(),
+                        base_inputs,
+                    )
+                    .await
+                    .map_err(|e| {
+                        error!("Failed to spawn audio recording actor: {e}");
+                        e.to_string()
+                    })?;
+
+                    (
+                        InProgressRecording::Studio {
+                            handle,
+                            target_name,
+                            inputs,
+                            recording_dir: recording_dir.clone(),
+                        },
+                        actor_done_rx,
+                    )
+                }
                 RecordingMode::Studio => {
                     let (handle, actor_done_rx) = cap_recording::spawn_studio_recording_actor(
                         id.clone(),
                         recording_dir.clone(),
                         base_inputs,
                         state.camera_feed.clone(),
+                        state.additional_camera_slots(),
                         GeneralSettingsStore::get(&app)
                             .ok()
                             .flatten()
@@ -409,6 +579,7 @@ pub async fn start_recording(
                             recording_dir.clone(),
                             base_inputs,
                             state.camera_feed.clone(),
+                            state.additional_camera_slots(),
                             GeneralSettingsStore::get(&app)
                                 .ok()
                                 .flatten()
@@ -436,11 +607,24 @@ pub async fn start_recording(
 
             state.set_current_recording(actor);
 
+            state.live_transcription =
+                LiveTranscription::start_if_enabled(&app, state.mic_feed.as_ref()).await;
+
             Ok::<_, String>(actor_done_rx)
         }
     })
     .await
-    .map_err(|e| format!("Failed to spawn recording actor: {}", e))??;
+    .map_err(|e| format!("Failed to spawn recording actor: {}", e))
+    .and_then(|spawned| spawned);
+
+    let actor_done_rx = match actor_done_rx {
+        Ok(actor_done_rx) => actor_done_rx,
+        Err(e) => {
+            // Unmutes the mic if push-to-talk muted it for a recording that never started
+            state_mtx.write().await.clear_current_recording();
+            return Err(e);
+        }
+    };
 
     spawn_actor({
         let app = app.clone();
@@ -492,6 +676,11 @@ pub async fn start_recording(
         }
     }
 
+    // The recording is already capturing, but only counts as started once the countdown ends
+    if let Ok(remaining) = official_start.duration_since(SystemTime::now()) {
+        tokio::time::sleep(remaining).await;
+    }
+
     if let Some(window) = CapWindowId::InProgressRecording.get(&app) {
         window.eval("window.location.reload()").ok();
     } else {
@@ -507,6 +696,12 @@ pub async fn start_recording(
     Ok(())
 }
 
+#[derive(Serialize, Type, Clone, Debug, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingPauseChanged {
+    pub paused: bool,
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn pause_recording(state: MutableState<'_, App>) -> Result<(), String> {
@@ -514,6 +709,10 @@ pub async fn pause_recording(state: MutableState<'_, App>) -> Result<(), String>
 
     if let Some(recording) = state.current_recording.as_mut() {
         recording.pause().await.map_err(|e| e.to_string())?;
+        state.recording_paused = true;
+        RecordingPauseChanged { paused: true }
+            .emit(&state.handle)
+            .ok();
     }
 
     Ok(())
@@ -526,11 +725,73 @@ pub async fn resume_recording(state: MutableState<'_, App>) -> Result<(), String
 
     if let Some(recording) = state.current_recording.as_mut() {
         recording.resume().await.map_err(|e| e.to_string())?;
+        state.recording_paused = false;
+        RecordingPauseChanged { paused: false }
+            .emit(&state.handle)
+            .ok();
     }
 
     Ok(())
 }
 
+#[derive(Serialize, Type, Clone, Debug, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct MicMuteChanged {
+    pub muted: bool,
+}
+
+/// Mute or unmute the mic in the current recording, eg. to cough. Muted audio is recorded
+/// as silence so everything stays in sync, and the level meter shows it.
+/// The mic is unmuted again when the recording ends.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_mic_muted(state: MutableState<'_, App>, muted: bool) -> Result<(), String> {
+    let mut state = state.write().await;
+
+    let Some(recording) = &state.current_recording else {
+        return Err("Recording not in progress".to_string());
+    };
+
+    if recording.inputs().push_to_talk {
+        return Err("The mic is controlled by push-to-talk in this recording".to_string());
+    }
+
+    let Some(feed) = &state.mic_feed else {
+        return Err("No microphone selected".to_string());
+    };
+
+    feed.set_muted(muted).await.map_err(|e| e.to_string())?;
+    state.mic_muted = muted;
+    MicMuteChanged { muted }.emit(&state.handle).ok();
+
+    Ok(())
+}
+
+/// Gate the mic in a push-to-talk recording, recording it only while `held`.
+/// Does nothing when there's no push-to-talk recording in progress.
+pub async fn set_push_to_talk(state: MutableState<'_, App>, held: bool) -> Result<(), String> {
+    let mut state = state.write().await;
+
+    let push_to_talk = state
+        .current_recording
+        .as_ref()
+        .is_some_and(|recording| recording.inputs().push_to_talk);
+    let muted = !held;
+    if !push_to_talk || state.mic_muted == muted {
+        return Ok(());
+    }
+
+    let Some(feed) = &state.mic_feed else {
+        return Ok(());
+    };
+
+    feed.set_muted(muted).await.map_err(|e| e.to_string())?;
+    state.mic_muted = muted;
+    MicMuteChanged { muted }.emit(&state.handle).ok();
+
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn stop_recording(app: AppHandle, state: MutableState<'_, App>) -> Result<(), String> {
@@ -538,9 +799,22 @@ pub async fn stop_recording(app: AppHandle, state: MutableState<'_, App>) -> Res
     let Some(current_recording) = state.clear_current_recording() else {
         return Err("Recording not in progress".to_string())?;
     };
+    let live_transcription = state.live_transcription.take();
 
     let completed_recording = current_recording.stop().await.map_err(|e| e.to_string())?;
 
+    if let Some(live_transcription) = live_transcription {
+        if let Err(e) = live_captions::finish_and_save(
+            &app,
+            live_transcription,
+            completed_recording.project_path(),
+        )
+        .await
+        {
+            error!("Failed to save live captions: {e}");
+        }
+    }
+
     handle_recording_end(app, Some(completed_recording), &mut state).await?;
 
     Ok(())
@@ -555,7 +829,11 @@ pub async fn restart_recording(app: AppHandle, state: MutableState<'_, App>) ->
 
     let _ = CurrentRecordingChanged.emit(&app);
 
-    let inputs = recording.inputs().clone();
+    let inputs = StartRecordingInputs {
+        // Restarts begin straight away
+        countdown: Some(0),
+        ..recording.inputs().clone()
+    };
 
     let _ = recording.cancel().await;
 
@@ -569,6 +847,7 @@ pub async fn restart_recording(app: AppHandle, state: MutableState<'_, App>) ->
 pub async fn delete_recording(app: AppHandle, state: MutableState<'_, App>) -> Result<(), String> {
     let recording_data = {
         let mut app_state = state.write().await;
+        app_state.live_transcription.take();
         if let Some(recording) = app_state.clear_current_recording() {
             let recording_dir = recording.recording_dir().clone();
             let video_id = match &recording {
@@ -611,7 +890,8 @@ async fn handle_recording_end(
     app: &mut App,
 ) -> Result<(), String> {
     // Clear current recording, just in case :)
-    app.current_recording.take();
+    app.clear_current_recording();
+    app.live_transcription.take();
 
     if let Some(recording) = recording {
         handle_recording_finish(&handle, recording).await?;
@@ -632,6 +912,7 @@ async fn handle_recording_end(
             let _ = v.close();
         });
         app.camera_feed.take();
+        app.additional_camera_feeds.clear();
         app.mic_feed.take();
     }
 
@@ -650,30 +931,39 @@ async fn handle_recording_finish(
     let screenshots_dir = recording_dir.join("screenshots");
     std::fs::create_dir_all(&screenshots_dir).ok();
 
+    // Audio-only recordings have no video to take a screenshot from
     let display_output_path = match &completed_recording {
         CompletedRecording::Studio { recording, .. } => match &recording.meta {
             StudioRecordingMeta::SingleSegment { segment } => {
-                segment.display.path.to_path(&recording_dir)
-            }
-            StudioRecordingMeta::MultipleSegments { inner, .. } => {
-                inner.segments[0].display.path.to_path(&recording_dir)
+                Some(segment.display.path.to_path(&recording_dir))
             }
+            StudioRecordingMeta::MultipleSegments { inner, .. } => inner.segments[0]
+                .display
+                .as_ref()
+                .map(|display| display.path.to_path(&recording_dir)),
         },
         CompletedRecording::Instant { recording, .. } => {
-            recording.project_path.join("./content/output.mp4")
+            Some(recording.project_path.join("./content/output.mp4"))
         }
     };
 
     let display_screenshot = screenshots_dir.join("display.jpg");
-    let screenshot_task = tokio::spawn(create_screenshot_from_video(
-        display_output_path,
-        display_screenshot.clone(),
-        None,
-    ));
+    let screenshot_task = display_output_path.map(|display_output_path| {
+        tokio::spawn(create_screenshot_from_video(
+            display_output_path,
+            display_screenshot.clone(),
+            None,
+        ))
+    });
 
     let target_name = completed_recording.target_name().clone();
+    let system_audio_source = completed_recording.system_audio_source();
 
     let (meta_inner, sharing) = match completed_recording {
+        // Audio-only recordings keep the default config, as there's no video to set up
+        CompletedRecording::Studio { recording, .. } if recording.meta.is_audio_only() => {
+            (RecordingMetaInner::Studio(recording.meta), None)
+        }
         CompletedRecording::Studio { recording, .. } => {
             let recordings = ProjectRecordingsMeta::new(&recording_dir, &recording.meta)?;
 
@@ -720,7 +1010,9 @@ async fn handle_recording_finish(
                             }
                         };
 
-                        let _ = screenshot_task.await;
+                        if let Some(screenshot_task) = screenshot_task {
+                            let _ = screenshot_task.await;
+                        }
 
                         if video_upload_succeeded {
                             let resp = prepare_screenshot_upload(
@@ -782,6 +1074,10 @@ async fn handle_recording_finish(
         platform: Some(Platform::default()),
         project_path: recording_dir.clone(),
         sharing,
+        capture_target: None,
+        system_audio_source,
+        pinned: false,
+        optimization: None,
         pretty_name: format!(
             "{target_name} {}",
             chrono::Local::now().format("%Y-%m-%d %H:%M:%S")
@@ -792,7 +1088,12 @@ async fn handle_recording_finish(
     meta.save_for_project()
         .map_err(|e| format!("Failed to save recording meta: {e}"))?;
 
-    if let RecordingMetaInner::Studio(_) = meta.inner {
+    // Audio-only recordings can't be opened in the editor, so they're left in the recordings list
+    let audio_only = meta
+        .studio_meta()
+        .is_some_and(StudioRecordingMeta::is_audio_only);
+
+    if matches!(meta.inner, RecordingMetaInner::Studio(_)) && !audio_only {
         // Check if this was originally an instant mode recording with custom save path
         let general_settings = GeneralSettingsStore::get(&app).ok().flatten();
         let is_instant_with_custom_path = general_settings
@@ -919,6 +1220,7 @@ fn project_config_from_recording(
                     start: 0.0,
                     end: segment.duration(),
                     timescale: 1.0,
+                    interpolate: false,
                 })
                 .collect(),
             zoom_segments: generate_zoom_segments_from_clicks(&completed_recording, &recordings),
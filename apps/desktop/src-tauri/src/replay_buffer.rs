@@ -0,0 +1,143 @@
+use std::{path::PathBuf, time::Duration};
+
+use cap_media::sources::ScreenCaptureTarget;
+use cap_project::{
+    Platform, ProjectConfiguration, RecordingMeta, RecordingMetaInner, TimelineConfiguration,
+    TimelineSegment,
+};
+use cap_recording::{replay_buffer::spawn_replay_buffer_actor, RecordingBaseInputs};
+use cap_rendering::ProjectRecordingsMeta;
+use tauri::{AppHandle, Manager};
+use tauri_specta::Event;
+use tracing::info;
+
+use crate::{recording_path, App, MutableState, NewStudioRecordingAdded};
+
+/// Start continuously capturing `target`, keeping the last `duration_secs` seconds available to `save_replay`
+#[tauri::command]
+#[specta::specta]
+pub async fn start_replay_buffer(
+    app: AppHandle,
+    state: MutableState<'_, App>,
+    target: ScreenCaptureTarget,
+    duration_secs: u32,
+) -> Result<(), String> {
+    if duration_secs == 0 {
+        return Err("Replay duration must be greater than zero".to_string());
+    }
+
+    let mut state = state.write().await;
+
+    if state.replay_buffer.is_some() {
+        return Err("Replay buffer is already running".to_string());
+    }
+
+    let buffer_dir = app
+        .path()
+        .app_cache_dir()
+        .map_err(|e| e.to_string())?
+        .join("replay-buffer");
+
+    let handle = spawn_replay_buffer_actor(
+        buffer_dir,
+        RecordingBaseInputs {
+            capture_target: target,
+            capture_system_audio: true,
+            app_audio: None,
+            mic_feed: &state.mic_feed,
+            start_time: None,
+        },
+        Duration::from_secs(duration_secs as u64),
+    )
+    .await
+    .map_err(|e| format!("Failed to start replay buffer: {e}"))?;
+
+    info!("Replay buffer started for the last {duration_secs}s");
+
+    state.replay_buffer = Some(handle);
+
+    Ok(())
+}
+
+/// Save the buffered footage as a new studio recording and return its project path
+#[tauri::command]
+#[specta::specta]
+pub async fn save_replay(app: AppHandle, state: MutableState<'_, App>) -> Result<PathBuf, String> {
+    let Some(handle) = state.read().await.replay_buffer.clone() else {
+        return Err("Replay buffer is not running".to_string());
+    };
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let replay = handle
+        .save(recording_path(&app, &id))
+        .await
+        .map_err(|e| format!("Failed to save replay: {e}"))?;
+
+    let recordings = ProjectRecordingsMeta::new(&replay.project_path, &replay.meta)?;
+
+    // Chunks are saved whole, so trim whatever precedes the requested window
+    let mut excess = (recordings.duration() - handle.duration.as_secs_f64()).max(0.0);
+    let segments = recordings
+        .segments
+        .iter()
+        .enumerate()
+        .map(|(i, segment)| {
+            let end = segment.duration();
+            let start = excess.min(end);
+            excess -= start;
+
+            TimelineSegment {
+                recording_segment: i as u32,
+                timescale: 1.0,
+                start,
+                end,
+                interpolate: false,
+            }
+        })
+        .filter(|segment| segment.end > segment.start)
+        .collect();
+
+    ProjectConfiguration {
+        timeline: Some(TimelineConfiguration {
+            segments,
+            zoom_segments: vec![],
+            scene_segments: None,
+        }),
+        ..Default::default()
+    }
+    .write(&replay.project_path)
+    .map_err(|e| e.to_string())?;
+
+    RecordingMeta {
+        platform: Some(Platform::default()),
+        project_path: replay.project_path.clone(),
+        sharing: None,
+        capture_target: None,
+        system_audio_source: None,
+        pinned: false,
+        optimization: None,
+        pretty_name: format!("Replay {}", chrono::Local::now().format("%Y-%m-%d %H:%M:%S")),
+        inner: RecordingMetaInner::Studio(replay.meta),
+    }
+    .save_for_project()
+    .map_err(|e| format!("Failed to save recording meta: {e}"))?;
+
+    NewStudioRecordingAdded {
+        path: replay.project_path.clone(),
+    }
+    .emit(&app)
+    .ok();
+
+    Ok(replay.project_path)
+}
+
+/// Stop the replay buffer and discard the footage it was holding
+#[tauri::command]
+#[specta::specta]
+pub async fn stop_replay_buffer(state: MutableState<'_, App>) -> Result<(), String> {
+    let Some(handle) = state.write().await.replay_buffer.take() else {
+        return Ok(());
+    };
+
+    handle.stop().await.map_err(|e| e.to_string())
+}
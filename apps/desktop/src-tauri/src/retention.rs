@@ -0,0 +1,108 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use cap_project::RecordingMeta;
+use serde::Serialize;
+use specta::Type;
+use tauri::AppHandle;
+use tracing::{error, info, warn};
+
+use crate::{general_settings::GeneralSettingsStore, recordings_path};
+
+#[derive(Serialize, Type, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RemovedRecording {
+    pub path: PathBuf,
+    pub pretty_name: String,
+    /// Whether the recording was moved to the trash rather than deleted permanently
+    pub trashed: bool,
+}
+
+#[derive(Serialize, Type, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionPolicyResult {
+    pub removed: Vec<RemovedRecording>,
+}
+
+fn recording_age(path: &Path, now: SystemTime) -> Option<Duration> {
+    let metadata = path.metadata().ok()?;
+    let created = metadata.created().or_else(|_| metadata.modified()).ok()?;
+    now.duration_since(created).ok()
+}
+
+fn remove_recording(path: &Path) -> Result<bool, String> {
+    match trash::delete(path) {
+        Ok(()) => Ok(true),
+        Err(e) => {
+            warn!(
+                "Failed to move {} to trash, deleting instead: {e}",
+                path.display()
+            );
+            std::fs::remove_dir_all(path).map_err(|e| e.to_string())?;
+            Ok(false)
+        }
+    }
+}
+
+/// Remove recordings older than `auto_delete_after_days`, sparing shared ones if `auto_delete_keep_shared` is set.
+/// Pinned recordings are always kept. Does nothing unless a threshold of at least a day has been configured.
+#[tauri::command]
+#[specta::specta]
+pub async fn apply_retention_policy(app: AppHandle) -> Result<RetentionPolicyResult, String> {
+    let settings = GeneralSettingsStore::get(&app)?.unwrap_or_default();
+
+    // 0 would make every recording old enough, so it's treated as unset
+    let Some(days) = settings.auto_delete_after_days.filter(|days| *days > 0) else {
+        return Ok(RetentionPolicyResult::default());
+    };
+
+    let max_age = Duration::from_secs(days as u64 * 24 * 60 * 60);
+    let now = SystemTime::now();
+
+    let entries = std::fs::read_dir(recordings_path(&app))
+        .map_err(|e| format!("Failed to read recordings directory: {}", e))?;
+
+    let mut result = RetentionPolicyResult::default();
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        if !path.is_dir() {
+            continue;
+        }
+
+        // Anything that isn't a readable project is left alone
+        let Ok(meta) = RecordingMeta::load_for_project(&path) else {
+            continue;
+        };
+
+        // Pinned recordings have been marked as worth keeping
+        if meta.pinned || (settings.auto_delete_keep_shared && meta.sharing.is_some()) {
+            continue;
+        }
+
+        if !recording_age(&path, now).is_some_and(|age| age > max_age) {
+            continue;
+        }
+
+        match remove_recording(&path) {
+            Ok(trashed) => result.removed.push(RemovedRecording {
+                path,
+                pretty_name: meta.pretty_name,
+                trashed,
+            }),
+            Err(e) => error!("Failed to remove recording {}: {e}", path.display()),
+        }
+    }
+
+    if !result.removed.is_empty() {
+        info!(
+            "Retention policy removed {} recordings older than {days} days",
+            result.removed.len()
+        );
+    }
+
+    Ok(result)
+}
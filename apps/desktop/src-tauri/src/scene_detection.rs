@@ -0,0 +1,291 @@
+use std::path::{Path, PathBuf};
+
+use cap_project::{ProjectConfiguration, RecordingMeta, RecordingMetaInner, StudioRecordingMeta};
+use ffmpeg::{codec as avcodec, format as avformat, software::scaling};
+use serde::Serialize;
+use specta::Type;
+use tracing::info;
+
+// Scene changes don't need to be frame accurate, so keyframes at least this far apart are compared
+const SAMPLE_INTERVAL: f64 = 0.5;
+// Changes closer together are reported once, eg. for a window animating open
+const MIN_SCENE_GAP: f64 = 2.0;
+// Samples are compared at a small size, which also smooths out noise and cursor movement
+const SAMPLE_WIDTH: u32 = 64;
+const SAMPLE_HEIGHT: u32 = 36;
+const HISTOGRAM_BINS: usize = 32;
+
+#[derive(Serialize, Type, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneDetectionProgress {
+    pub analyzed_secs: f64,
+    pub total_secs: f64,
+}
+
+/// Find times on the project's timeline where the screen changes significantly, as candidates for chapters or markers.
+/// `threshold` ranges from 0 to 1, with lower values also reporting smaller changes.
+#[tauri::command]
+#[specta::specta]
+pub async fn detect_scene_changes(
+    project_path: PathBuf,
+    threshold: f32,
+    progress: tauri::ipc::Channel<SceneDetectionProgress>,
+) -> Result<Vec<f64>, String> {
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err("Threshold must be between 0 and 1".to_string());
+    }
+
+    let meta = RecordingMeta::load_for_project(&project_path).map_err(|e| e.to_string())?;
+    let videos = screen_video_paths(&meta);
+    let timeline = match &meta.inner {
+        RecordingMetaInner::Studio(_) => ProjectConfiguration::load(&project_path)
+            .ok()
+            .and_then(|config| config.timeline),
+        RecordingMetaInner::Instant(_) => None,
+    };
+
+    let changes = tokio::task::spawn_blocking(move || -> Result<Vec<f64>, String> {
+        let durations = videos
+            .iter()
+            .map(|video| video_duration(video))
+            .collect::<Result<Vec<_>, _>>()?;
+        let total_secs = durations.iter().sum::<f64>();
+
+        let mut changes = vec![];
+        let mut offset = 0.0;
+
+        for (i, (video, duration)) in videos.iter().zip(&durations).enumerate() {
+            let video_changes = detect_in_video(video, threshold, |time| {
+                let _ = progress.send(SceneDetectionProgress {
+                    analyzed_secs: (offset + time).min(total_secs),
+                    total_secs,
+                });
+            })?;
+
+            changes.extend(
+                video_changes
+                    .into_iter()
+                    .filter_map(|time| match &timeline {
+                        Some(timeline) => timeline.recording_time_to_timeline(i as u32, time),
+                        None => Some(offset + time),
+                    }),
+            );
+
+            offset += duration;
+        }
+
+        Ok(changes)
+    })
+    .await
+    .map_err(|e| format!("Scene detection task failed: {}", e))??;
+
+    let changes = merge_nearby(changes);
+    info!(
+        "Found {} scene changes in {}",
+        changes.len(),
+        project_path.display()
+    );
+
+    Ok(changes)
+}
+
+fn screen_video_paths(meta: &RecordingMeta) -> Vec<PathBuf> {
+    match &meta.inner {
+        RecordingMetaInner::Instant(_) => vec![meta.project_path.join("content/output.mp4")],
+        RecordingMetaInner::Studio(StudioRecordingMeta::SingleSegment { segment }) => {
+            vec![meta.path(&segment.display.path)]
+        }
+        RecordingMetaInner::Studio(StudioRecordingMeta::MultipleSegments { inner }) => inner
+            .segments
+            .iter()
+            .filter_map(|segment| segment.display.as_ref())
+            .map(|display| meta.path(&display.path))
+            .collect(),
+    }
+}
+
+fn video_duration(path: &Path) -> Result<f64, String> {
+    let input = avformat::input(&path).map_err(|e| format!("Failed to open video file: {}", e))?;
+
+    Ok(input.duration().max(0) as f64 / ffmpeg::ffi::AV_TIME_BASE as f64)
+}
+
+/// Times in the video where a sample differs from the previous one by at least `threshold`
+fn detect_in_video(
+    path: &Path,
+    threshold: f32,
+    mut on_progress: impl FnMut(f64),
+) -> Result<Vec<f64>, String> {
+    let mut input =
+        avformat::input(&path).map_err(|e| format!("Failed to open video file: {}", e))?;
+
+    let video_stream = input
+        .streams()
+        .best(ffmpeg::media::Type::Video)
+        .ok_or_else(|| "No video stream found".to_string())?;
+    let video_index = video_stream.index();
+    let time_base = f64::from(video_stream.time_base());
+
+    let mut decoder = avcodec::Context::from_parameters(video_stream.parameters())
+        .map_err(|e| format!("Failed to create decoder context: {}", e))?
+        .decoder()
+        .video()
+        .map_err(|e| format!("Failed to create decoder: {}", e))?;
+
+    // Only keyframes are decoded, as they don't depend on any other frame
+    unsafe {
+        (*decoder.as_mut_ptr()).skip_frame = ffmpeg::ffi::AVDiscard::AVDISCARD_NONKEY;
+    }
+
+    let mut scaler: Option<scaling::Context> = None;
+    let mut decoded = ffmpeg::frame::Video::empty();
+    let mut previous: Option<Vec<u8>> = None;
+    let mut next_sample = 0.0;
+    let mut changes = vec![];
+
+    // Rather than reading every packet, seek to the first keyframe at or after each sample time
+    loop {
+        let target = (next_sample * ffmpeg::ffi::AV_TIME_BASE as f64) as i64;
+        if input.seek(target, target..).is_err() {
+            break;
+        }
+
+        let Some(packet) = input
+            .packets()
+            .find(|(stream, packet)| stream.index() == video_index && packet.is_key())
+            .map(|(_, packet)| packet)
+        else {
+            break;
+        };
+
+        // Draining after the one packet makes the decoder output it straight away
+        decoder.flush();
+        decoder
+            .send_packet(&packet)
+            .map_err(|e| format!("Failed to decode packet: {}", e))?;
+        decoder.send_eof().ok();
+        if decoder.receive_frame(&mut decoded).is_err() {
+            break;
+        }
+        decoder.flush();
+
+        let time = decoded
+            .timestamp()
+            .or(packet.pts())
+            .map_or(next_sample, |ts| ts as f64 * time_base);
+        next_sample = time.max(next_sample) + SAMPLE_INTERVAL;
+
+        if scaler.is_none() {
+            scaler = Some(
+                scaling::Context::get(
+                    decoded.format(),
+                    decoded.width(),
+                    decoded.height(),
+                    avformat::Pixel::GRAY8,
+                    SAMPLE_WIDTH,
+                    SAMPLE_HEIGHT,
+                    scaling::Flags::AREA,
+                )
+                .map_err(|e| format!("Failed to create scaler: {}", e))?,
+            );
+        }
+
+        let mut scaled = ffmpeg::frame::Video::empty();
+        scaler
+            .as_mut()
+            .unwrap()
+            .run(&decoded, &mut scaled)
+            .map_err(|e| format!("Failed to scale frame: {}", e))?;
+
+        // account for stride > width
+        let luma = scaled
+            .data(0)
+            .chunks(scaled.stride(0))
+            .take(SAMPLE_HEIGHT as usize)
+            .flat_map(|row| &row[..SAMPLE_WIDTH as usize])
+            .copied()
+            .collect::<Vec<_>>();
+
+        if let Some(previous) = &previous {
+            if change_score(previous, &luma) >= threshold {
+                changes.push(time);
+            }
+        }
+        previous = Some(luma);
+
+        on_progress(time);
+    }
+
+    Ok(changes)
+}
+
+/// How different two samples are, from 0 to 1.
+/// Histograms catch changes in overall content, while pixel differences catch content
+/// that moved or changed without changing the mix of brightness.
+fn change_score(a: &[u8], b: &[u8]) -> f32 {
+    let (histogram_a, histogram_b) = (histogram(a), histogram(b));
+    let histogram_delta = histogram_a
+        .iter()
+        .zip(&histogram_b)
+        .map(|(a, b)| (a - b).abs())
+        .sum::<f32>()
+        / 2.0;
+
+    let pixel_delta = a
+        .iter()
+        .zip(b)
+        .map(|(a, b)| a.abs_diff(*b) as f32)
+        .sum::<f32>()
+        / (a.len().max(1) as f32 * 255.0);
+
+    histogram_delta.max(pixel_delta)
+}
+
+fn histogram(luma: &[u8]) -> [f32; HISTOGRAM_BINS] {
+    let mut histogram = [0.0; HISTOGRAM_BINS];
+    for value in luma {
+        histogram[*value as usize * HISTOGRAM_BINS / 256] += 1.0;
+    }
+
+    let total = luma.len().max(1) as f32;
+    histogram.map(|count| count / total)
+}
+
+fn merge_nearby(mut changes: Vec<f64>) -> Vec<f64> {
+    changes.sort_by(|a, b| a.total_cmp(b));
+
+    let mut merged: Vec<f64> = vec![];
+    for time in changes {
+        if merged
+            .last()
+            .is_some_and(|last| time - last < MIN_SCENE_GAP)
+        {
+            continue;
+        }
+        merged.push(time);
+    }
+
+    merged
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn scores_changes() {
+        let dark = vec![10; 64];
+        let light = vec![240; 64];
+
+        assert_eq!(change_score(&dark, &dark), 0.0);
+        assert!(change_score(&dark, &light) > 0.9);
+    }
+
+    #[test]
+    fn merges_nearby_changes() {
+        assert_eq!(
+            merge_nearby(vec![5.0, 1.0, 1.5, 10.0]),
+            vec![1.0, 5.0, 10.0]
+        );
+    }
+}
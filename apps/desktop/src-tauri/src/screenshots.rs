@@ -1,16 +1,31 @@
 use std::fs::File;
 use std::io::BufWriter;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
 
-use cap_project::{RecordingMeta, RecordingMetaInner, VideoMeta, SharingMeta, Platform};
-use clipboard_rs::Clipboard;
+use cap_media::{
+    platform::Bounds,
+    sources::{list_screens, ScreenCaptureTarget},
+};
+use cap_project::{
+    Platform, RecordingMeta, RecordingMetaInner, SharingMeta, StudioRecordingMeta, VideoMeta, XY,
+};
+use clipboard_rs::{
+    common::{RustImage, RustImageData},
+    Clipboard,
+};
+use device_query::{DeviceQuery, DeviceState};
 use png::{ColorType, Encoder};
 use relative_path::RelativePathBuf;
 use scap::{
     capturer::Capturer,
     frame::{Frame, VideoFrame},
 };
+use serde::{Deserialize, Serialize};
+use specta::Type;
 use tauri::{AppHandle, Manager, State};
+use tokio::sync::oneshot;
 
 use crate::{
     AppSounds, AuthStore, CapWindowId, ClipboardContext, MutableState,
@@ -19,65 +34,461 @@ use crate::{
 };
 use tauri_specta::Event;
 
+#[derive(Serialize, Deserialize, Type, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum ScreenshotFormat {
+    #[default]
+    Png,
+    /// `quality` is 1-100
+    Jpeg { quality: u8 },
+    /// Encoded losslessly, as the `image` crate can't write lossy WebP
+    WebP,
+}
+
+impl ScreenshotFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Jpeg { .. } => "jpg",
+            Self::WebP => "webp",
+        }
+    }
+}
+
+const SCREENSHOT_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "webp"];
+
+// Standard PNG text keywords, so other tools can show them too
+const PNG_CREATION_TIME_KEYWORD: &str = "Creation Time";
+const PNG_SOFTWARE_KEYWORD: &str = "Software";
+const PNG_RESOLUTION_KEYWORD: &str = "Display Resolution";
+
+/// Capture details embedded in PNG screenshots as text chunks.
+/// JPEG and WebP screenshots don't carry any.
+#[derive(Serialize, Type, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenshotMetadata {
+    /// RFC 3339 timestamp in the local timezone
+    pub captured_at: Option<String>,
+    /// Size of the captured display or window, before cropping to an area
+    pub display_resolution: Option<XY<u32>>,
+    pub app_version: Option<String>,
+}
+
+impl ScreenshotMetadata {
+    fn text_chunks(&self, app_name: &str) -> Vec<(&'static str, String)> {
+        let mut chunks = vec![];
+        if let Some(captured_at) = &self.captured_at {
+            chunks.push((PNG_CREATION_TIME_KEYWORD, captured_at.clone()));
+        }
+        if let Some(resolution) = &self.display_resolution {
+            chunks.push((
+                PNG_RESOLUTION_KEYWORD,
+                format!("{}x{}", resolution.x, resolution.y),
+            ));
+        }
+        if let Some(version) = &self.app_version {
+            chunks.push((PNG_SOFTWARE_KEYWORD, format!("{app_name} {version}")));
+        }
+        chunks
+    }
+
+    fn from_text_chunks<'a>(chunks: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        let mut metadata = Self::default();
+        for (keyword, text) in chunks {
+            match keyword {
+                PNG_CREATION_TIME_KEYWORD => metadata.captured_at = Some(text.to_string()),
+                PNG_RESOLUTION_KEYWORD => {
+                    metadata.display_resolution = text.split_once('x').and_then(|(x, y)| {
+                        Some(XY::new(x.trim().parse().ok()?, y.trim().parse().ok()?))
+                    });
+                }
+                PNG_SOFTWARE_KEYWORD => {
+                    metadata.app_version = text
+                        .rsplit_once(' ')
+                        .map(|(_, version)| version.to_string());
+                }
+                _ => {}
+            }
+        }
+        metadata
+    }
+}
+
+#[derive(Serialize, Deserialize, Type, Debug, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum ScreenshotTarget {
+    Capture(ScreenCaptureTarget),
+    /// Every connected display, stitched together as they're arranged
+    AllDisplays,
+}
+
+#[derive(Serialize, Deserialize, Type, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum InsetPosition {
+    TopLeft,
+    #[default]
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// A zoomed in copy of part of the screenshot, drawn with a border in one of its corners
+#[derive(Serialize, Deserialize, Type, Debug, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct MagnifyRegion {
+    /// The part to magnify, in pixels of the screenshot
+    pub rect: Bounds,
+    pub factor: f64,
+    pub position: InsetPosition,
+}
+
+const INSET_MARGIN: u32 = 16;
+const INSET_BORDER: u32 = 4;
+// BGRA
+const INSET_BORDER_COLOR: [u8; 4] = [255, 255, 255, 255];
+
+/// Emitted once a second while waiting to take a delayed screenshot,
+/// and with no seconds remaining when the wait is over
+#[derive(Serialize, Type, tauri_specta::Event, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenshotCountdown {
+    seconds_remaining: u32,
+}
+
+/// Cancels the delayed screenshot that's counting down, if there is one
+pub type PendingScreenshotState = Mutex<Option<oneshot::Sender<()>>>;
+
+/// Stops the scrolling screenshot that's being captured, if there is one
+pub type ScrollingScreenshotState = Mutex<Option<oneshot::Sender<()>>>;
+
+/// Emitted whenever a scrolling screenshot grows
+#[derive(Serialize, Type, tauri_specta::Event, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScrollingScreenshotProgress {
+    frames: u32,
+    height: u32,
+}
+
+// Beyond this the image gets too large to open in most viewers
+const MAX_SCROLLING_SCREENSHOT_HEIGHT: u32 = 32_000;
+const SCROLLING_FRAME_INTERVAL: Duration = Duration::from_millis(150);
+
+/// Screenshots `target`, or the whole primary display when it's `None`, as a PNG unless
+/// another `format` is given. Waits `delay_secs` first, if any, so things like menus can be set up.
+/// `magnify` adds a zoomed in inset of part of the screenshot,
+/// and `show_cursor` draws the cursor where it was when the screenshot was taken.
 #[tauri::command]
 #[specta::specta]
-pub async fn take_screenshot(app: AppHandle, _state: MutableState<'_, crate::App>) -> Result<(), String> {
-    let id = uuid::Uuid::new_v4().to_string();
+pub async fn take_screenshot(
+    app: AppHandle,
+    _state: MutableState<'_, crate::App>,
+    target: Option<ScreenshotTarget>,
+    format: Option<ScreenshotFormat>,
+    delay_secs: Option<u32>,
+    magnify: Option<MagnifyRegion>,
+    show_cursor: Option<bool>,
+) -> Result<(), String> {
+    capture_screenshot(
+        app,
+        target,
+        format.unwrap_or_default(),
+        delay_secs.unwrap_or(0),
+        magnify,
+        show_cursor.unwrap_or(false),
+        None,
+    )
+    .await
+}
 
-    let recording_dir = app
-        .path()
-        .app_data_dir()
+/// Screenshot the primary display after `delay_secs`, which can be cut short with
+/// `cancel_delayed_screenshot`. Starting another delayed screenshot cancels this one.
+#[tauri::command]
+#[specta::specta]
+pub async fn take_delayed_screenshot(app: AppHandle, delay_secs: u32) -> Result<(), String> {
+    let (cancel_tx, cancel_rx) = oneshot::channel();
+    app.state::<PendingScreenshotState>()
+        .lock()
         .unwrap()
-        .join("screenshots")
-        .join(format!("{id}.cap"));
+        .replace(cancel_tx);
 
-    std::fs::create_dir_all(&recording_dir).map_err(|e| e.to_string())?;
+    let result = capture_screenshot(
+        app.clone(),
+        None,
+        ScreenshotFormat::default(),
+        delay_secs,
+        None,
+        false,
+        Some(cancel_rx),
+    )
+    .await;
 
-    let (width, height, bgra_data) = {
-        let options = scap::capturer::Options {
-            fps: 1,
-            output_type: scap::frame::FrameType::BGRAFrame,
-            show_highlight: false,
-            ..Default::default()
-        };
+    // Our receiver is gone now, so only clear the pending screenshot if it's still this one
+    let state = app.state::<PendingScreenshotState>();
+    let mut pending = state.lock().unwrap();
+    if pending.as_ref().is_some_and(|tx| tx.is_closed()) {
+        pending.take();
+    }
 
-        if let Some(window) = CapWindowId::Main.get(&app) {
-            let _ = window.hide();
+    result
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_delayed_screenshot(app: AppHandle) {
+    if let Some(cancel_tx) = app.state::<PendingScreenshotState>().lock().unwrap().take() {
+        cancel_tx.send(()).ok();
+    }
+}
+
+/// Capture `target` repeatedly while the user scrolls, stitching the frames into one tall PNG.
+/// Runs until `stop_scrolling_screenshot` is called, then returns the image's path.
+#[tauri::command]
+#[specta::specta]
+pub async fn capture_scrolling_screenshot(
+    app: AppHandle,
+    target: ScreenCaptureTarget,
+) -> Result<PathBuf, String> {
+    let (stop_tx, mut stop_rx) = oneshot::channel();
+    app.state::<ScrollingScreenshotState>()
+        .lock()
+        .unwrap()
+        .replace(stop_tx);
+
+    if let Some(window) = CapWindowId::Main.get(&app) {
+        let _ = window.hide();
+    }
+
+    let stitched = tokio::task::spawn_blocking({
+        let app = app.clone();
+        move || -> Result<Option<ScrollStitcher>, String> {
+            let mut stitcher: Option<ScrollStitcher> = None;
+
+            // An error means either a stop was requested or the command's sender was dropped
+            while stop_rx.try_recv() == Err(oneshot::error::TryRecvError::Empty) {
+                let frame = target
+                    .get_target()
+                    .ok_or_else(|| "Screenshot target not found".to_string())
+                    .and_then(|target| capture_frame(Some(target)))?;
+                let (width, height, data) = crop_to_target(&target, frame)?;
+
+                let grew = match stitcher.as_mut() {
+                    Some(stitcher) => stitcher.push(width, height, &data),
+                    None => {
+                        stitcher = Some(ScrollStitcher::new(width, height, data));
+                        true
+                    }
+                };
+
+                if let Some(stitcher) = stitcher.as_ref().filter(|_| grew) {
+                    ScrollingScreenshotProgress {
+                        frames: stitcher.frames,
+                        height: stitcher.height(),
+                    }
+                    .emit(&app)
+                    .ok();
+
+                    if stitcher.height() >= MAX_SCROLLING_SCREENSHOT_HEIGHT {
+                        break;
+                    }
+                }
+
+                std::thread::sleep(SCROLLING_FRAME_INTERVAL);
+            }
+
+            Ok(stitcher)
+        }
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e));
+
+    if let Some(window) = CapWindowId::Main.get(&app) {
+        let _ = window.show();
+    }
+
+    {
+        let state = app.state::<ScrollingScreenshotState>();
+        let mut pending = state.lock().unwrap();
+        if pending.as_ref().is_some_and(|tx| tx.is_closed()) {
+            pending.take();
         }
+    }
+
+    let Some(stitcher) = stitched?? else {
+        return Err("No frames were captured".to_string());
+    };
 
-        let mut capturer =
-            Capturer::build(options).map_err(|e| format!("Failed to construct error: {e}"))?;
-        capturer.start_capture();
-        let frame = capturer
-            .get_next_frame()
-            .map_err(|e| format!("Failed to get frame: {}", e))?;
-        capturer.stop_capture();
+    let (width, height, data) = stitcher.finish();
+    save_screenshot(
+        &app,
+        width,
+        height,
+        data,
+        ScreenshotFormat::Png,
+        capture_target_meta(&target),
+        None,
+    )
+    .await
+}
 
+#[tauri::command]
+#[specta::specta]
+pub fn stop_scrolling_screenshot(app: AppHandle) {
+    if let Some(stop_tx) = app
+        .state::<ScrollingScreenshotState>()
+        .lock()
+        .unwrap()
+        .take()
+    {
+        stop_tx.send(()).ok();
+    }
+}
+
+/// Wait `delay_secs`, emitting `ScreenshotCountdown` every second and once more when it's done.
+/// Errors if `cancel` fires or its sender is dropped before the delay is over.
+async fn countdown(
+    app: &AppHandle,
+    delay_secs: u32,
+    mut cancel: Option<oneshot::Receiver<()>>,
+) -> Result<(), String> {
+    for seconds_remaining in (1..=delay_secs).rev() {
+        ScreenshotCountdown { seconds_remaining }.emit(app).ok();
+
+        let sleep = tokio::time::sleep(Duration::from_secs(1));
+        match cancel.as_mut() {
+            Some(cancel) => tokio::select! {
+                _ = sleep => {}
+                _ = cancel => {
+                    ScreenshotCountdown {
+                        seconds_remaining: 0,
+                    }
+                    .emit(app)
+                    .ok();
+                    return Err("Screenshot cancelled".to_string());
+                }
+            },
+            None => sleep.await,
+        }
+    }
+
+    // Lets the UI take down its countdown, which isn't shown at all without a delay
+    if delay_secs > 0 {
+        ScreenshotCountdown {
+            seconds_remaining: 0,
+        }
+        .emit(app)
+        .ok();
+    }
+
+    Ok(())
+}
+
+async fn capture_screenshot(
+    app: AppHandle,
+    target: Option<ScreenshotTarget>,
+    format: ScreenshotFormat,
+    delay_secs: u32,
+    magnify: Option<MagnifyRegion>,
+    show_cursor: bool,
+    cancel: Option<oneshot::Receiver<()>>,
+) -> Result<(), String> {
+    if let Some(window) = CapWindowId::Main.get(&app) {
+        let _ = window.hide();
+    }
+
+    if let Err(e) = countdown(&app, delay_secs, cancel).await {
         if let Some(window) = CapWindowId::Main.get(&app) {
             let _ = window.show();
         }
+        return Err(e);
+    }
 
-        match frame {
-            Frame::Video(VideoFrame::BGRA(bgra_frame)) => Ok((
-                bgra_frame.width as u32,
-                bgra_frame.height as u32,
-                bgra_frame.data,
-            )),
-            _ => Err("Unexpected frame type".to_string()),
-        }
-    }?;
+    let frame = match target {
+        Some(ScreenshotTarget::AllDisplays) => capture_all_displays(show_cursor),
+        // Areas capture their whole screen and are cropped once the frame arrives
+        Some(ScreenshotTarget::Capture(target)) => target
+            .get_target()
+            .ok_or_else(|| "Screenshot target not found".to_string())
+            .and_then(|scap_target| capture_frame(Some(scap_target)))
+            .map(|frame| with_cursor(frame, &target, show_cursor)),
+        None => capture_frame(None)
+            .map(|frame| with_cursor(frame, &ScreenCaptureTarget::primary_display(), show_cursor)),
+    };
+
+    if let Some(window) = CapWindowId::Main.get(&app) {
+        let _ = window.show();
+    }
+
+    let frame = frame?;
+    let display_resolution = XY::new(frame.0, frame.1);
+
+    let (width, height, mut bgra_data) = match &target {
+        Some(ScreenshotTarget::Capture(target)) => crop_to_target(target, frame)?,
+        _ => frame,
+    };
+
+    if let Some(magnify) = &magnify {
+        draw_magnified_inset(&mut bgra_data, width, height, magnify)?;
+    }
+
+    let capture_target = match target {
+        Some(ScreenshotTarget::AllDisplays) => cap_project::CaptureTargetMeta::AllDisplays,
+        Some(ScreenshotTarget::Capture(target)) => capture_target_meta(&target),
+        None => capture_target_meta(&ScreenCaptureTarget::primary_display()),
+    };
+
+    save_screenshot(
+        &app,
+        width,
+        height,
+        bgra_data,
+        format,
+        capture_target,
+        Some(display_resolution),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Encode a BGRA frame into a new screenshot project, returning the image's path
+async fn save_screenshot(
+    app: &AppHandle,
+    width: u32,
+    height: u32,
+    bgra_data: Vec<u8>,
+    format: ScreenshotFormat,
+    capture_target: cap_project::CaptureTargetMeta,
+    display_resolution: Option<XY<u32>>,
+) -> Result<PathBuf, String> {
+    let id = uuid::Uuid::new_v4().to_string();
+
+    let recording_dir = app
+        .path()
+        .app_data_dir()
+        .unwrap()
+        .join("screenshots")
+        .join(format!("{id}.cap"));
+
+    std::fs::create_dir_all(&recording_dir).map_err(|e| e.to_string())?;
 
     let now = chrono::Local::now();
     let screenshot_name = format!(
-        "Cap {} at {}.png",
+        "Cap {} at {}.{}",
         now.format("%Y-%m-%d"),
-        now.format("%H.%M.%S")
+        now.format("%H.%M.%S"),
+        format.extension()
     );
     let screenshot_path = recording_dir.join(&screenshot_name);
+    let metadata = ScreenshotMetadata {
+        captured_at: Some(now.to_rfc3339()),
+        display_resolution,
+        app_version: Some(app.package_info().version.to_string()),
+    };
+    let app_name = app.package_info().name.clone();
 
     let app_handle = app.clone();
     let recording_dir = recording_dir.clone();
-    tokio::task::spawn_blocking(move || -> Result<(), String> {
+    tokio::task::spawn_blocking(move || -> Result<PathBuf, String> {
         let mut rgba_data = vec![0; bgra_data.len()];
         for (bgra, rgba) in bgra_data.chunks_exact(4).zip(rgba_data.chunks_exact_mut(4)) {
             rgba[0] = bgra[2];
@@ -86,32 +497,26 @@ pub async fn take_screenshot(app: AppHandle, _state: MutableState<'_, crate::App
             rgba[3] = bgra[3];
         }
 
-        let file = File::create(&screenshot_path).map_err(|e| e.to_string())?;
-        let w = &mut BufWriter::new(file);
-
-        let mut encoder = Encoder::new(w, width, height);
-        encoder.set_color(ColorType::Rgba);
-        encoder.set_compression(png::Compression::Fast);
-        let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
-
-        writer
-            .write_image_data(&rgba_data)
-            .map_err(|e| e.to_string())?;
+        write_image(
+            &screenshot_path,
+            width,
+            height,
+            rgba_data,
+            format,
+            &metadata.text_chunks(&app_name),
+        )?;
 
         AppSounds::Screenshot.play();
 
-        let now = chrono::Local::now();
-        let screenshot_name = format!(
-            "Cap {} at {}.png",
-            now.format("%Y-%m-%d"),
-            now.format("%H.%M.%S")
-        );
-
         use cap_project::*;
         RecordingMeta {
             platform: Some(Platform::default()),
             project_path: recording_dir.clone(),
             sharing: None,
+            capture_target: Some(capture_target),
+            system_audio_source: None,
+            pinned: false,
+            optimization: None,
             pretty_name: screenshot_name,
             inner: RecordingMetaInner::Studio(cap_project::StudioRecordingMeta::SingleSegment {
                 segment: cap_project::SingleSegment {
@@ -122,6 +527,7 @@ pub async fn take_screenshot(app: AppHandle, _state: MutableState<'_, crate::App
                         .unwrap(),
                         fps: 0,
                         start_time: None,
+                        frame_timestamps: None,
                     },
                     camera: None,
                     audio: None,
@@ -133,17 +539,527 @@ pub async fn take_screenshot(app: AppHandle, _state: MutableState<'_, crate::App
         .unwrap();
 
         NewScreenshotAdded {
-            path: screenshot_path,
+            path: screenshot_path.clone(),
         }.emit(&app_handle).ok();
 
-        Ok(())
+        Ok(screenshot_path)
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))??;
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// `text_chunks` are keyword and text pairs only written to PNGs
+fn write_image(
+    path: &Path,
+    width: u32,
+    height: u32,
+    rgba_data: Vec<u8>,
+    format: ScreenshotFormat,
+    text_chunks: &[(&str, String)],
+) -> Result<(), String> {
+    let file = File::create(path).map_err(|e| e.to_string())?;
+    let w = &mut BufWriter::new(file);
+
+    match format {
+        ScreenshotFormat::Png => {
+            let mut encoder = Encoder::new(w, width, height);
+            encoder.set_color(ColorType::Rgba);
+            encoder.set_compression(png::Compression::Fast);
+            for (keyword, text) in text_chunks {
+                encoder
+                    .add_text_chunk(keyword.to_string(), text.clone())
+                    .map_err(|e| e.to_string())?;
+            }
+            let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+
+            writer
+                .write_image_data(&rgba_data)
+                .map_err(|e| e.to_string())?;
+        }
+        ScreenshotFormat::Jpeg { quality } => {
+            let image = image::RgbaImage::from_raw(width, height, rgba_data)
+                .ok_or("Failed to create image from frame data")?;
+
+            // JPEG has no alpha channel
+            image::codecs::jpeg::JpegEncoder::new_with_quality(w, quality.clamp(1, 100))
+                .encode_image(&image::DynamicImage::ImageRgba8(image).to_rgb8())
+                .map_err(|e| e.to_string())?;
+        }
+        ScreenshotFormat::WebP => {
+            image::codecs::webp::WebPEncoder::new_lossless(w)
+                .encode(&rgba_data, width, height, image::ExtendedColorType::Rgba8)
+                .map_err(|e| e.to_string())?;
+        }
+    }
 
     Ok(())
 }
 
+/// Point a screenshot's metadata at `image_path`, which may be in a different project.
+/// Any shared link is dropped, as it points at the image as it was before.
+pub(crate) fn set_screenshot_image(meta: &mut RecordingMeta, image_path: &Path) {
+    let file_name = image_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    if let Some(project_path) = image_path.parent() {
+        meta.project_path = project_path.to_path_buf();
+    }
+    meta.pretty_name = file_name.clone();
+    meta.sharing = None;
+    if let RecordingMetaInner::Studio(StudioRecordingMeta::SingleSegment { segment }) =
+        &mut meta.inner
+    {
+        segment.display.path = RelativePathBuf::from(file_name);
+    }
+}
+
+/// Grab a single BGRA frame of `target`, or of the primary display when it's `None`
+fn capture_frame(target: Option<scap::Target>) -> Result<(u32, u32, Vec<u8>), String> {
+    let options = scap::capturer::Options {
+        fps: 1,
+        output_type: scap::frame::FrameType::BGRAFrame,
+        show_highlight: false,
+        target,
+        ..Default::default()
+    };
+
+    let mut capturer =
+        Capturer::build(options).map_err(|e| format!("Failed to construct error: {e}"))?;
+    capturer.start_capture();
+    let frame = capturer
+        .get_next_frame()
+        .map_err(|e| format!("Failed to get frame: {}", e))?;
+    capturer.stop_capture();
+
+    match frame {
+        Frame::Video(VideoFrame::BGRA(bgra_frame)) => Ok((
+            bgra_frame.width as u32,
+            bgra_frame.height as u32,
+            bgra_frame.data,
+        )),
+        _ => Err("Unexpected frame type".to_string()),
+    }
+}
+
+/// Draw the cursor onto a frame of `target` if `show_cursor` is set.
+/// Must be called before cropping areas, as their frames cover the whole screen.
+fn with_cursor(
+    (width, height, mut data): (u32, u32, Vec<u8>),
+    target: &ScreenCaptureTarget,
+    show_cursor: bool,
+) -> (u32, u32, Vec<u8>) {
+    let bounds = match target {
+        _ if !show_cursor => None,
+        ScreenCaptureTarget::Screen { id } => Some(cap_media::platform::monitor_bounds(*id)),
+        ScreenCaptureTarget::Area { screen, .. } => {
+            Some(cap_media::platform::monitor_bounds(*screen))
+        }
+        ScreenCaptureTarget::Window { id } => cap_media::platform::get_on_screen_windows()
+            .into_iter()
+            .find(|window| window.window_id == *id)
+            .map(|window| window.bounds),
+    };
+
+    if let Some(bounds) = bounds {
+        draw_cursor(&mut data, width, height, bounds);
+    }
+
+    (width, height, data)
+}
+
+/// Draw the current cursor onto a BGRA frame showing the part of the desktop at `bounds`,
+/// which are in the same units as `monitor_bounds`. Nothing is drawn if it's elsewhere.
+fn draw_cursor(data: &mut [u8], width: u32, height: u32, bounds: Bounds) {
+    let Some(cursor) = cap_recording::cursor::get_cursor_image_data() else {
+        return;
+    };
+    let Ok(image) = image::load_from_memory(&cursor.image) else {
+        return;
+    };
+    let (x, y) = DeviceState::new().get_mouse().coords;
+
+    composite_cursor(
+        data,
+        width,
+        height,
+        bounds,
+        XY::new(x as f64, y as f64),
+        &image.into_rgba8(),
+        cursor.hotspot,
+    );
+}
+
+/// Blend `cursor` onto the frame with its hotspot at `position`.
+/// The frame's size over `bounds` gives the display's scale factor, which the cursor
+/// image is scaled by as it's in the same units as the bounds.
+fn composite_cursor(
+    data: &mut [u8],
+    width: u32,
+    height: u32,
+    bounds: Bounds,
+    position: XY<f64>,
+    cursor: &image::RgbaImage,
+    hotspot: XY<f64>,
+) {
+    if bounds.width <= 0.0 {
+        return;
+    }
+
+    let scale = width as f64 / bounds.width;
+    let cursor_width = (cursor.width() as f64 * scale).round() as u32;
+    let cursor_height = (cursor.height() as f64 * scale).round() as u32;
+    let cursor = image::imageops::resize(
+        cursor,
+        cursor_width,
+        cursor_height,
+        image::imageops::FilterType::Triangle,
+    );
+
+    let left = ((position.x - bounds.x) * scale - hotspot.x * cursor_width as f64).round() as i64;
+    let top = ((position.y - bounds.y) * scale - hotspot.y * cursor_height as f64).round() as i64;
+
+    for (x, y, pixel) in cursor.enumerate_pixels() {
+        let (frame_x, frame_y) = (left + x as i64, top + y as i64);
+        if frame_x < 0 || frame_y < 0 || frame_x >= width as i64 || frame_y >= height as i64 {
+            continue;
+        }
+
+        let i = (frame_y as usize * width as usize + frame_x as usize) * 4;
+        let [r, g, b, a] = pixel.0;
+        let alpha = a as f32 / 255.0;
+        for (channel, value) in [b, g, r].into_iter().enumerate() {
+            data[i + channel] =
+                (value as f32 * alpha + data[i + channel] as f32 * (1.0 - alpha)).round() as u8;
+        }
+    }
+}
+
+struct DisplayFrame {
+    /// Where the display sits in the desktop, in the same units as its `monitor_bounds`
+    bounds: Bounds,
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+fn capture_all_displays(show_cursor: bool) -> Result<(u32, u32, Vec<u8>), String> {
+    let frames = list_screens()
+        .into_iter()
+        .map(|(screen, target)| {
+            let bounds = cap_media::platform::monitor_bounds(screen.id);
+            let (width, height, mut data) = capture_frame(Some(target))?;
+            if show_cursor {
+                draw_cursor(&mut data, width, height, bounds);
+            }
+
+            Ok(DisplayFrame {
+                bounds,
+                width,
+                height,
+                data,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    stitch_displays(&frames)
+}
+
+/// Composite display frames into one BGRA image covering the bounding box of all their bounds.
+/// Displays left of or above the primary have negative positions, so everything is translated
+/// to start at zero. Positions are scaled to pixels using the densest display.
+fn stitch_displays(frames: &[DisplayFrame]) -> Result<(u32, u32, Vec<u8>), String> {
+    if frames.is_empty() {
+        return Err("No displays available for screenshot".to_string());
+    }
+
+    let min_x = frames.iter().map(|f| f.bounds.x).fold(f64::MAX, f64::min);
+    let min_y = frames.iter().map(|f| f.bounds.y).fold(f64::MAX, f64::min);
+    let max_x = frames
+        .iter()
+        .map(|f| f.bounds.x + f.bounds.width)
+        .fold(f64::MIN, f64::max);
+    let max_y = frames
+        .iter()
+        .map(|f| f.bounds.y + f.bounds.height)
+        .fold(f64::MIN, f64::max);
+
+    let scale = frames
+        .iter()
+        .filter(|f| f.bounds.width > 0.0)
+        .map(|f| f.width as f64 / f.bounds.width)
+        .fold(1.0, f64::max);
+
+    let width = ((max_x - min_x) * scale).round() as u32;
+    let height = ((max_y - min_y) * scale).round() as u32;
+    let stride = width as usize * 4;
+    let mut data = vec![0; stride * height as usize];
+
+    for frame in frames {
+        let left = ((frame.bounds.x - min_x) * scale).round() as u32;
+        let top = ((frame.bounds.y - min_y) * scale).round() as u32;
+        let columns = frame.width.min(width.saturating_sub(left)) as usize;
+        let rows = frame.height.min(height.saturating_sub(top));
+
+        for y in 0..rows {
+            let src = y as usize * frame.width as usize * 4;
+            let dst = (top + y) as usize * stride + left as usize * 4;
+            data[dst..dst + columns * 4].copy_from_slice(&frame.data[src..src + columns * 4]);
+        }
+    }
+
+    Ok((width, height, data))
+}
+
+/// Crop a tightly packed BGRA frame to `bounds`, which are scaled to frame pixels
+/// by `scale` and clamped to the frame
+fn crop_bgra(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    bounds: Bounds,
+    scale: f64,
+) -> Result<(u32, u32, Vec<u8>), String> {
+    let to_pixels = |value: f64, max: u32| ((value * scale).round().max(0.0) as u32).min(max);
+
+    let left = to_pixels(bounds.x, width);
+    let top = to_pixels(bounds.y, height);
+    let right = to_pixels(bounds.x + bounds.width, width);
+    let bottom = to_pixels(bounds.y + bounds.height, height);
+
+    if right <= left || bottom <= top {
+        return Err("Screenshot area is outside the screen".to_string());
+    }
+
+    let stride = width as usize * 4;
+    let row_len = (right - left) as usize * 4;
+    let mut cropped = Vec::with_capacity(row_len * (bottom - top) as usize);
+
+    for y in top..bottom {
+        let start = y as usize * stride + left as usize * 4;
+        cropped.extend_from_slice(&data[start..start + row_len]);
+    }
+
+    Ok((right - left, bottom - top, cropped))
+}
+
+/// Scale up `region.rect` and draw it over a corner of the tightly packed BGRA frame.
+/// The zoom is reduced if the inset wouldn't otherwise fit.
+fn draw_magnified_inset(
+    data: &mut [u8],
+    width: u32,
+    height: u32,
+    region: &MagnifyRegion,
+) -> Result<(), String> {
+    let (crop_width, crop_height, crop) = crop_bgra(data, width, height, region.rect, 1.0)?;
+
+    let padding = 2 * (INSET_MARGIN + INSET_BORDER);
+    let factor = region
+        .factor
+        .max(1.0)
+        .min(width.saturating_sub(padding) as f64 / crop_width as f64)
+        .min(height.saturating_sub(padding) as f64 / crop_height as f64);
+    let inset_width = (crop_width as f64 * factor).round() as u32;
+    let inset_height = (crop_height as f64 * factor).round() as u32;
+
+    if inset_width == 0 || inset_height == 0 {
+        return Err("Screenshot is too small for a magnified inset".to_string());
+    }
+
+    let crop = image::RgbaImage::from_raw(crop_width, crop_height, crop)
+        .ok_or("Failed to create image from frame data")?;
+    // Resizing treats every channel the same, so BGRA can be resized as is.
+    // Nearest neighbour keeps magnified UI crisp.
+    let inset = image::imageops::resize(
+        &crop,
+        inset_width,
+        inset_height,
+        image::imageops::FilterType::Nearest,
+    );
+
+    let outer_width = inset_width + 2 * INSET_BORDER;
+    let outer_height = inset_height + 2 * INSET_BORDER;
+    let left = match region.position {
+        InsetPosition::TopLeft | InsetPosition::BottomLeft => INSET_MARGIN,
+        InsetPosition::TopRight | InsetPosition::BottomRight => width - INSET_MARGIN - outer_width,
+    };
+    let top = match region.position {
+        InsetPosition::TopLeft | InsetPosition::TopRight => INSET_MARGIN,
+        InsetPosition::BottomLeft | InsetPosition::BottomRight => {
+            height - INSET_MARGIN - outer_height
+        }
+    };
+
+    for y in 0..outer_height {
+        for x in 0..outer_width {
+            let dst = ((top + y) as usize * width as usize + (left + x) as usize) * 4;
+            let inside = (INSET_BORDER..INSET_BORDER + inset_width).contains(&x)
+                && (INSET_BORDER..INSET_BORDER + inset_height).contains(&y);
+
+            let pixel = if inside {
+                inset.get_pixel(x - INSET_BORDER, y - INSET_BORDER).0
+            } else {
+                INSET_BORDER_COLOR
+            };
+            data[dst..dst + 4].copy_from_slice(&pixel);
+        }
+    }
+
+    Ok(())
+}
+
+/// Areas are captured as their whole screen, so crop frames of them down to the area
+fn crop_to_target(
+    target: &ScreenCaptureTarget,
+    (width, height, data): (u32, u32, Vec<u8>),
+) -> Result<(u32, u32, Vec<u8>), String> {
+    match target {
+        ScreenCaptureTarget::Area { screen, bounds } => {
+            let screen_bounds = cap_media::platform::monitor_bounds(*screen);
+            let scale = if screen_bounds.width > 0.0 {
+                width as f64 / screen_bounds.width
+            } else {
+                1.0
+            };
+
+            crop_bgra(&data, width, height, *bounds, scale)
+        }
+        _ => Ok((width, height, data)),
+    }
+}
+
+/// Builds a tall image from frames of a scrolling view. The rows that stay put between
+/// frames, like fixed headers and footers, are only included once.
+struct ScrollStitcher {
+    width: u32,
+    frame_height: u32,
+    /// Stitched BGRA rows, always ending with the whole of the latest frame
+    data: Vec<u8>,
+    previous: Vec<u8>,
+    frames: u32,
+}
+
+impl ScrollStitcher {
+    fn new(width: u32, height: u32, frame: Vec<u8>) -> Self {
+        Self {
+            width,
+            frame_height: height,
+            data: frame.clone(),
+            previous: frame,
+            frames: 1,
+        }
+    }
+
+    fn height(&self) -> u32 {
+        (self.data.len() / (self.width as usize * 4)) as u32
+    }
+
+    /// Add the rows `frame` scrolled into view. Returns false if it didn't scroll or its
+    /// overlap with the previous frame couldn't be found, in which case it's skipped.
+    fn push(&mut self, width: u32, height: u32, frame: &[u8]) -> bool {
+        if width != self.width || height != self.frame_height {
+            return false;
+        }
+
+        let row_len = width as usize * 4;
+        let previous_rows = hash_rows(&self.previous, row_len);
+        let rows = hash_rows(frame, row_len);
+        let height = rows.len();
+
+        let header = previous_rows
+            .iter()
+            .zip(&rows)
+            .take_while(|(a, b)| a == b)
+            .count();
+        if header == height {
+            return false;
+        }
+
+        let footer = previous_rows
+            .iter()
+            .rev()
+            .zip(rows.iter().rev())
+            .take(height - header - 1)
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        let scrolling = header..height - footer;
+        let Some(offset) =
+            find_scroll_offset(&previous_rows[scrolling.clone()], &rows[scrolling.clone()])
+                .filter(|offset| *offset > 0)
+        else {
+            return false;
+        };
+
+        // Move the footer below the newly revealed rows
+        self.data.truncate(self.data.len() - footer * row_len);
+        self.data
+            .extend_from_slice(&frame[(scrolling.end - offset) * row_len..]);
+        self.previous = frame.to_vec();
+        self.frames += 1;
+
+        true
+    }
+
+    fn finish(self) -> (u32, u32, Vec<u8>) {
+        let height = self.height();
+        (self.width, height, self.data)
+    }
+}
+
+fn hash_rows(data: &[u8], row_len: usize) -> Vec<u64> {
+    use std::hash::{Hash, Hasher};
+
+    data.chunks_exact(row_len)
+        .map(|row| {
+            let mut hasher = std::hash::DefaultHasher::new();
+            row.hash(&mut hasher);
+            hasher.finish()
+        })
+        .collect()
+}
+
+/// How many rows `current` has scrolled past `previous`, found by the offset where the most
+/// rows line up. Needs at least a quarter of the rows to overlap, and 90% of those to match
+/// so things like blinking cursors don't throw it off.
+fn find_scroll_offset(previous: &[u64], current: &[u64]) -> Option<usize> {
+    let len = previous.len();
+    let min_overlap = (len / 4).max(1);
+
+    (0..=len - min_overlap)
+        .map(|offset| {
+            let overlap = len - offset;
+            let matching = previous[offset..]
+                .iter()
+                .zip(current)
+                .filter(|(a, b)| a == b)
+                .count();
+            (offset, matching as f64 / overlap as f64)
+        })
+        .filter(|(_, score)| *score >= 0.9)
+        // `max_by` keeps the last of equal scores, so search from the largest offset down
+        .rev()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(offset, _)| offset)
+}
+
+fn capture_target_meta(target: &ScreenCaptureTarget) -> cap_project::CaptureTargetMeta {
+    use cap_project::CaptureTargetMeta;
+
+    match target {
+        ScreenCaptureTarget::Screen { id } => CaptureTargetMeta::Screen { id: *id },
+        ScreenCaptureTarget::Window { id } => CaptureTargetMeta::Window { id: *id },
+        ScreenCaptureTarget::Area { screen, bounds } => CaptureTargetMeta::Area {
+            screen: *screen,
+            x: bounds.x,
+            y: bounds.y,
+            width: bounds.width,
+            height: bounds.height,
+        },
+    }
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn copy_screenshot_to_clipboard(
@@ -151,9 +1067,31 @@ pub async fn copy_screenshot_to_clipboard(
     path: String,
 ) -> Result<(), String> {
     println!("Copying screenshot to clipboard: {:?}", path);
-    
-    // Use set_files since clipboard_rs doesn't have set_image
-    let _ = clipboard.write().await.set_files(vec![path]);
+
+    let image = tokio::task::spawn_blocking({
+        let path = path.clone();
+        move || image::open(path)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?;
+
+    // Copy the bitmap so it pastes into apps that don't accept files,
+    // falling back to the file where image data can't be copied
+    let clipboard = clipboard.write().await;
+    let copied = match image {
+        Ok(image) => clipboard
+            .set_image(RustImageData::from_dynamic_image(image))
+            .map_err(|e| e.to_string()),
+        Err(e) => Err(e.to_string()),
+    };
+
+    if let Err(e) = copied {
+        println!("Copying screenshot as a file instead: {}", e);
+        clipboard
+            .set_files(vec![path])
+            .map_err(|e| format!("Failed to copy screenshot: {}", e))?;
+    }
+
     Ok(())
 }
 
@@ -206,6 +1144,164 @@ pub async fn upload_screenshot(
     Ok(UploadResult::Success(share_link))
 }
 
+#[derive(Serialize, Type, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenshotConversion {
+    pub source: PathBuf,
+    /// The converted image, if conversion succeeded
+    pub path: Option<PathBuf>,
+    pub error: Option<String>,
+}
+
+/// Re-encode each screenshot in `paths` as `format`. With `replace` set the converted image
+/// takes the original's place, otherwise it's added alongside it as a new screenshot.
+/// Every screenshot is attempted, with failures reported per file.
+#[tauri::command]
+#[specta::specta]
+pub async fn convert_screenshots(
+    app: AppHandle,
+    paths: Vec<PathBuf>,
+    format: ScreenshotFormat,
+    replace: bool,
+) -> Vec<ScreenshotConversion> {
+    let mut conversions = Vec::with_capacity(paths.len());
+
+    for source in paths {
+        let result = convert_screenshot(&app, &source, format, replace).await;
+        if let Err(e) = &result {
+            println!("Failed to convert screenshot {}: {}", source.display(), e);
+        }
+
+        let (path, error) = match result {
+            Ok(path) => (Some(path), None),
+            Err(e) => (None, Some(e)),
+        };
+        conversions.push(ScreenshotConversion {
+            source,
+            path,
+            error,
+        });
+    }
+
+    conversions
+}
+
+async fn convert_screenshot(
+    app: &AppHandle,
+    source: &Path,
+    format: ScreenshotFormat,
+    replace: bool,
+) -> Result<PathBuf, String> {
+    let Some(screenshot_dir) = source.parent() else {
+        return Err("Invalid screenshot path".to_string());
+    };
+    let mut meta = RecordingMeta::load_for_project(screenshot_dir)
+        .map_err(|e| format!("Failed to load screenshot metadata: {e}"))?;
+
+    let output = if replace {
+        source.with_extension(format.extension())
+    } else {
+        let stem = source
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let output_dir = screenshots_path(app).join(format!("{}.cap", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&output_dir).map_err(|e| e.to_string())?;
+        output_dir.join(format!("{stem}.{}", format.extension()))
+    };
+
+    tokio::task::spawn_blocking({
+        let source = source.to_path_buf();
+        let output = output.clone();
+        move || -> Result<(), String> {
+            // Read before the source might be overwritten
+            let text_chunks = read_png_text_chunks(&source).unwrap_or_default();
+            let image = image::open(&source)
+                .map_err(|e| format!("Failed to open screenshot: {e}"))?
+                .into_rgba8();
+            let (width, height) = image.dimensions();
+
+            write_image(
+                &output,
+                width,
+                height,
+                image.into_raw(),
+                format,
+                &text_chunks
+                    .iter()
+                    .map(|(keyword, text)| (keyword.as_str(), text.clone()))
+                    .collect::<Vec<_>>(),
+            )
+        }
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??;
+
+    if output != source {
+        if replace {
+            std::fs::remove_file(source).map_err(|e| e.to_string())?;
+        }
+
+        set_screenshot_image(&mut meta, &output);
+        meta.save_for_project()
+            .map_err(|e| format!("Failed to save screenshot metadata: {e:?}"))?;
+    }
+
+    if !replace {
+        NewScreenshotAdded {
+            path: output.clone(),
+        }
+        .emit(app)
+        .ok();
+    }
+
+    Ok(output)
+}
+
+/// Read the capture details embedded in a screenshot. Fields that weren't recorded,
+/// or every field for non-PNG screenshots, are `None`.
+#[tauri::command]
+#[specta::specta]
+pub async fn read_screenshot_metadata(path: PathBuf) -> Result<ScreenshotMetadata, String> {
+    if !path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("png"))
+    {
+        return Ok(ScreenshotMetadata::default());
+    }
+
+    let chunks = tokio::task::spawn_blocking(move || read_png_text_chunks(&path))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))??;
+
+    Ok(ScreenshotMetadata::from_text_chunks(
+        chunks
+            .iter()
+            .map(|(keyword, text)| (keyword.as_str(), text.as_str())),
+    ))
+}
+
+fn read_png_text_chunks(path: &Path) -> Result<Vec<(String, String)>, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open screenshot: {e}"))?;
+    let reader = png::Decoder::new(std::io::BufReader::new(file))
+        .read_info()
+        .map_err(|e| format!("Failed to read screenshot: {e}"))?;
+    let info = reader.info();
+
+    let mut chunks = info
+        .uncompressed_latin1_text
+        .iter()
+        .map(|chunk| (chunk.keyword.clone(), chunk.text.clone()))
+        .collect::<Vec<_>>();
+    for chunk in &info.utf8_text {
+        if let Ok(text) = chunk.get_text() {
+            chunks.push((chunk.keyword.clone(), text));
+        }
+    }
+
+    Ok(chunks)
+}
+
 pub fn screenshots_path(app: &AppHandle) -> PathBuf {
     let path = app.path().app_data_dir().unwrap().join("screenshots");
     std::fs::create_dir_all(&path).unwrap_or_default();
@@ -234,7 +1330,12 @@ pub fn list_screenshots(app: AppHandle) -> Result<Vec<(PathBuf, RecordingMeta)>,
                 let png_path = std::fs::read_dir(&path)
                     .ok()?
                     .filter_map(|e| e.ok())
-                    .find(|e| e.path().extension().and_then(|s| s.to_str()) == Some("png"))
+                    .find(|e| {
+                        e.path()
+                            .extension()
+                            .and_then(|s| s.to_str())
+                            .is_some_and(|ext| SCREENSHOT_EXTENSIONS.contains(&ext))
+                    })
                     .map(|e| e.path())?;
 
                 Some((png_path, meta))
@@ -363,4 +1464,158 @@ pub async fn create_screenshot_from_video(
     .map_err(|e| format!("Task join error: {}", e))?;
 
     result
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn stitches_displays_left_of_primary() {
+        let display = |x: f64, value: u8| DisplayFrame {
+            bounds: bounds(x, 0.0, 2.0, 1.0),
+            width: 2,
+            height: 1,
+            data: [value, 0, 0, 255].repeat(2),
+        };
+
+        // Primary at the origin with a second display to its left
+        let (width, height, data) = stitch_displays(&[display(0.0, 1), display(-2.0, 2)]).unwrap();
+
+        assert_eq!((width, height), (4, 1));
+        assert_eq!(
+            data.chunks_exact(4)
+                .map(|pixel| pixel[0])
+                .collect::<Vec<_>>(),
+            vec![2, 2, 1, 1]
+        );
+
+        assert!(stitch_displays(&[]).is_err());
+    }
+
+    fn bounds(x: f64, y: f64, width: f64, height: f64) -> Bounds {
+        Bounds {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn crops_and_clamps_to_frame() {
+        // 4x2 frame where each pixel's first byte is its index
+        let data = (0..8u8).flat_map(|i| [i, 0, 0, 255]).collect::<Vec<_>>();
+
+        let (width, height, cropped) =
+            crop_bgra(&data, 4, 2, bounds(1.0, 1.0, 2.0, 1.0), 1.0).unwrap();
+        assert_eq!((width, height), (2, 1));
+        assert_eq!(cropped, vec![5, 0, 0, 255, 6, 0, 0, 255]);
+
+        let (width, height, _) =
+            crop_bgra(&data, 4, 2, bounds(1.0, -3.0, 10.0, 10.0), 2.0).unwrap();
+        assert_eq!((width, height), (2, 2));
+
+        assert!(crop_bgra(&data, 4, 2, bounds(5.0, 0.0, 2.0, 2.0), 1.0).is_err());
+        assert!(crop_bgra(&data, 4, 2, bounds(1.0, 1.0, 0.0, 1.0), 1.0).is_err());
+    }
+
+    #[test]
+    fn draws_magnified_inset_in_corner() {
+        // 64x64 black frame with a single marked pixel at (1, 1)
+        let mut data = [0, 0, 0, 255].repeat(64 * 64);
+        data[(64 + 1) * 4] = 10;
+
+        let region = MagnifyRegion {
+            rect: bounds(0.0, 0.0, 2.0, 2.0),
+            factor: 4.0,
+            position: InsetPosition::TopRight,
+        };
+        draw_magnified_inset(&mut data, 64, 64, &region).unwrap();
+
+        let pixel = |x: usize, y: usize| data[(y * 64 + x) * 4];
+        // The 8x8 inset and its border sit 16px in from the top right corner
+        assert_eq!(pixel(32, 16), 255);
+        assert_eq!(pixel(37, 21), 0);
+        assert_eq!(pixel(41, 25), 10);
+        assert_eq!(pixel(31, 16), 0);
+    }
+
+    #[test]
+    fn round_trips_metadata_through_text_chunks() {
+        let metadata = ScreenshotMetadata {
+            captured_at: Some("2024-05-01T09:30:00+02:00".to_string()),
+            display_resolution: Some(XY::new(3024, 1964)),
+            app_version: Some("0.3.50".to_string()),
+        };
+
+        let chunks = metadata.text_chunks("Cap");
+        assert!(chunks.contains(&("Software", "Cap 0.3.50".to_string())));
+
+        let read = ScreenshotMetadata::from_text_chunks(
+            chunks
+                .iter()
+                .map(|(keyword, text)| (*keyword, text.as_str())),
+        );
+        assert_eq!(read, metadata);
+    }
+
+    #[test]
+    fn composites_cursor_at_scaled_position() {
+        // A 2x display showing 10x10 points of the desktop, starting at (100, 100)
+        let mut data = [0, 0, 0, 255].repeat(20 * 20);
+        let cursor = image::RgbaImage::from_pixel(2, 2, image::Rgba([255, 0, 0, 255]));
+
+        composite_cursor(
+            &mut data,
+            20,
+            20,
+            bounds(100.0, 100.0, 10.0, 10.0),
+            XY::new(105.0, 102.0),
+            &cursor,
+            XY::new(0.5, 0.5),
+        );
+
+        // The cursor doubles to 4x4 and is centered on its hotspot at (10, 4)
+        let pixel = |x: usize, y: usize| &data[(y * 20 + x) * 4..(y * 20 + x) * 4 + 4];
+        assert_eq!(pixel(8, 2), &[0, 0, 255, 255]);
+        assert_eq!(pixel(11, 5), &[0, 0, 255, 255]);
+        assert_eq!(pixel(7, 2), &[0, 0, 0, 255]);
+        assert_eq!(pixel(12, 5), &[0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn stitches_scrolled_frames_under_fixed_header() {
+        const HEADER: u8 = 255;
+        const FOOTER: u8 = 254;
+
+        // One pixel wide viewport of a page whose row `i` has the value `i`,
+        // showing seven rows at a time between a two row header and a footer
+        let frame = |scroll: u8| {
+            [HEADER, HEADER]
+                .into_iter()
+                .chain(scroll..scroll + 7)
+                .chain([FOOTER])
+                .flat_map(|value| [value, 0, 0, 255])
+                .collect::<Vec<_>>()
+        };
+
+        let mut stitcher = ScrollStitcher::new(1, 10, frame(0));
+        assert!(!stitcher.push(1, 10, &frame(0)));
+        assert!(stitcher.push(1, 10, &frame(3)));
+        assert!(stitcher.push(1, 10, &frame(5)));
+
+        let (width, height, data) = stitcher.finish();
+        assert_eq!((width, height), (1, 15));
+        assert_eq!(
+            data.chunks_exact(4)
+                .map(|pixel| pixel[0])
+                .collect::<Vec<_>>(),
+            [HEADER, HEADER]
+                .into_iter()
+                .chain(0..12)
+                .chain([FOOTER])
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,246 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use specta::Type;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+
+use crate::{
+    auth::AuthStore,
+    general_settings::GeneralSettingsStore,
+    hotkeys::{self, HotkeysStore},
+    presets::PresetsStore,
+    screenshots::screenshots_path,
+    App, ArcLock,
+};
+
+const SNAPSHOT_VERSION: u32 = 1;
+
+// Identify this install or its license, so they stay on the machine they belong to
+const MACHINE_SPECIFIC_SETTINGS: &[&str] = &["instanceId", "commercialLicense"];
+
+/// A portable copy of the user's settings. Auth is never included.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SettingsSnapshot {
+    version: u32,
+    #[serde(default)]
+    general_settings: Option<Value>,
+    #[serde(default)]
+    hotkeys: Option<Value>,
+    #[serde(default)]
+    presets: Option<Value>,
+}
+
+fn strip_machine_specific(general_settings: &mut Value) {
+    if let Value::Object(settings) = general_settings {
+        for field in MACHINE_SPECIFIC_SETTINGS {
+            settings.remove(*field);
+        }
+    }
+}
+
+/// Write general settings, hotkeys and presets to a JSON file that can be imported on another machine
+#[tauri::command]
+#[specta::specta]
+pub fn export_settings(app: AppHandle, output: PathBuf) -> Result<(), String> {
+    let Ok(store) = app.store("store") else {
+        return Err("Store not found".to_string());
+    };
+
+    let mut general_settings = store.get("general_settings");
+    if let Some(general_settings) = general_settings.as_mut() {
+        strip_machine_specific(general_settings);
+    }
+
+    let snapshot = SettingsSnapshot {
+        version: SNAPSHOT_VERSION,
+        general_settings,
+        hotkeys: store.get("hotkeys"),
+        presets: store.get("presets"),
+    };
+
+    let json = serde_json::to_string_pretty(&snapshot)
+        .map_err(|e| format!("Failed to serialize settings: {e}"))?;
+
+    std::fs::write(&output, json).map_err(|e| format!("Failed to write settings file: {e}"))
+}
+
+/// Apply settings from a file written by `export_settings`.
+/// Every section is validated first, so an invalid file leaves the current settings untouched.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_settings(app: AppHandle, input: PathBuf) -> Result<(), String> {
+    let json = std::fs::read_to_string(&input)
+        .map_err(|e| format!("Failed to read settings file: {e}"))?;
+    let snapshot: SettingsSnapshot =
+        serde_json::from_str(&json).map_err(|e| format!("Invalid settings file: {e}"))?;
+
+    if snapshot.version > SNAPSHOT_VERSION {
+        return Err(format!(
+            "Settings file version {} is newer than this version of Cap supports",
+            snapshot.version
+        ));
+    }
+
+    let general_settings = match snapshot.general_settings {
+        Some(value) => {
+            let mut settings: GeneralSettingsStore = serde_json::from_value(value)
+                .map_err(|e| format!("Invalid general settings: {e}"))?;
+
+            let current = GeneralSettingsStore::get(&app)?.unwrap_or_default();
+            settings.instance_id = current.instance_id;
+            settings.commercial_license = current.commercial_license;
+
+            Some(settings)
+        }
+        None => None,
+    };
+
+    let hotkeys = snapshot
+        .hotkeys
+        .map(serde_json::from_value::<HotkeysStore>)
+        .transpose()
+        .map_err(|e| format!("Invalid hotkeys: {e}"))?;
+
+    let presets = snapshot
+        .presets
+        .map(serde_json::from_value::<PresetsStore>)
+        .transpose()
+        .map_err(|e| format!("Invalid presets: {e}"))?;
+
+    let Ok(store) = app.store("store") else {
+        return Err("Store not found".to_string());
+    };
+
+    if let Some(settings) = &general_settings {
+        store.set("general_settings", json!(settings));
+    }
+    if let Some(hotkeys) = &hotkeys {
+        store.set("hotkeys", json!(hotkeys));
+    }
+    if let Some(presets) = &presets {
+        store.set("presets", json!(presets));
+    }
+    store.save().map_err(|e| e.to_string())?;
+
+    if let Some(hotkeys) = hotkeys {
+        hotkeys::replace_all(&app, hotkeys);
+    }
+
+    if let Some(settings) = general_settings {
+        app.state::<ArcLock<App>>().write().await.server_url = settings.server_url;
+    }
+
+    Ok(())
+}
+
+#[derive(Deserialize, Type, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ResetScope {
+    General,
+    Hotkeys,
+    Presets,
+    All,
+}
+
+/// Restore settings to their defaults. Recordings, screenshots and the signed in account are
+/// only removed when resetting `All` with `clear_user_data` set.
+#[tauri::command]
+#[specta::specta]
+pub async fn reset_settings(
+    app: AppHandle,
+    scope: ResetScope,
+    clear_user_data: bool,
+) -> Result<(), String> {
+    if clear_user_data && scope != ResetScope::All {
+        return Err("User data can only be cleared when resetting everything".to_string());
+    }
+
+    if clear_user_data {
+        let state = app.state::<ArcLock<App>>();
+        if state.read().await.current_recording.is_some() {
+            return Err("Can't clear recordings while recording".to_string());
+        }
+    }
+
+    let reset_general = matches!(scope, ResetScope::General | ResetScope::All);
+    let reset_hotkeys = matches!(scope, ResetScope::Hotkeys | ResetScope::All);
+    let reset_presets = matches!(scope, ResetScope::Presets | ResetScope::All);
+
+    let Ok(store) = app.store("store") else {
+        return Err("Store not found".to_string());
+    };
+
+    // The current settings may be what's broken, so fall back to defaults if they can't be read
+    let current = GeneralSettingsStore::get(&app)
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+    let mut general_settings = GeneralSettingsStore::default();
+    general_settings.instance_id = current.instance_id;
+    general_settings.has_completed_startup = true;
+    if !clear_user_data {
+        general_settings.commercial_license = current.commercial_license;
+    }
+    let server_url = general_settings.server_url.clone();
+
+    if reset_general {
+        store.set("general_settings", json!(general_settings));
+    }
+    if reset_hotkeys {
+        store.set("hotkeys", json!(HotkeysStore::default()));
+    }
+    if reset_presets {
+        store.set("presets", json!(PresetsStore::default()));
+    }
+    store.save().map_err(|e| e.to_string())?;
+
+    if reset_hotkeys {
+        hotkeys::replace_all(&app, HotkeysStore::default());
+    }
+
+    if reset_general {
+        app.state::<ArcLock<App>>().write().await.server_url = server_url;
+
+        for window in app.webview_windows().values() {
+            window.set_theme(None).ok();
+        }
+    }
+
+    if clear_user_data {
+        // Nothing recorded or captured yet means there's nothing to delete
+        for dir in [crate::recordings_path(&app), screenshots_path(&app)] {
+            match std::fs::remove_dir_all(&dir) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                    return Err(format!("Failed to delete {}: {e}", dir.display()));
+                }
+                _ => {}
+            }
+        }
+
+        // Signed out last, so a failure above leaves the user signed in to retry
+        AuthStore::set(&app, None)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn strips_machine_specific_settings() {
+        let mut settings = json!({
+            "instanceId": "0b6a3f1e-0000-0000-0000-000000000000",
+            "commercialLicense": { "licenseKey": "secret" },
+            "hapticsEnabled": false,
+        });
+
+        strip_machine_specific(&mut settings);
+
+        assert_eq!(settings, json!({ "hapticsEnabled": false }));
+    }
+}
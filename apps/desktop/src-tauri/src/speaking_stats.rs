@@ -0,0 +1,165 @@
+use std::path::PathBuf;
+
+use cap_audio::AudioData;
+use cap_project::{RecordingMeta, RecordingMetaInner, StudioRecordingMeta};
+use serde::Serialize;
+use specta::Type;
+use tauri::AppHandle;
+
+use crate::{audio, captions};
+
+// `audio::get_waveform` averages the level over chunks of this many seconds
+const WAVEFORM_CHUNK_SECS: f64 = 0.1;
+// Quieter than this is treated as not speaking. Room noise on most mics sits well below it.
+const SILENCE_THRESHOLD_DBFS: f32 = -40.0;
+// Shorter gaps are the pauses between words and sentences, which count towards talking
+const MIN_SILENCE_SECS: f64 = 0.5;
+
+#[derive(Serialize, Type, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SpeakingStats {
+    /// Words over the time the captions are shown. `None` without captions, as are the other caption stats.
+    pub words_per_minute: Option<f64>,
+    pub total_words: Option<u32>,
+    /// Seconds spent talking, from the microphone's level
+    pub talk_time: f64,
+    /// Seconds of silence long enough to be more than a pause between words
+    pub silence_time: f64,
+    pub filler_count: Option<u32>,
+}
+
+/// Pace and filler usage over the whole recording, ignoring any edits.
+/// Talk and silence times come from the audio, so recordings without captions still get those.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_speaking_stats(
+    app: AppHandle,
+    project_path: PathBuf,
+) -> Result<SpeakingStats, String> {
+    let meta = RecordingMeta::load_for_project(&project_path).map_err(|e| e.to_string())?;
+    let audio_paths = voice_audio_paths(&meta);
+    if audio_paths.is_empty() {
+        return Err("This recording has no audio".to_string());
+    }
+
+    let video_id = project_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| "Invalid project path".to_string())?;
+    let captions_path = captions::app_captions_dir(&app, &video_id)?.join("captions.json");
+
+    tokio::task::spawn_blocking(move || {
+        let mut waveform = vec![];
+        for path in &audio_paths {
+            let audio = AudioData::from_file(path)
+                .map_err(|e| format!("Failed to read audio {}: {e}", path.display()))?;
+            waveform.extend(audio::get_waveform(&audio));
+        }
+        let (talk_time, silence_time) = talk_and_silence(&waveform);
+
+        let captions = std::fs::read_to_string(&captions_path)
+            .ok()
+            .and_then(|json| captions::parse_captions_json(&json).ok())
+            .filter(|captions| !captions.segments.is_empty());
+
+        let mut stats = SpeakingStats {
+            words_per_minute: None,
+            total_words: None,
+            talk_time,
+            silence_time,
+            filler_count: None,
+        };
+
+        if let Some(captions) = captions {
+            let total_words = captions
+                .segments
+                .iter()
+                .map(|segment| captions::spoken_words(&segment.text).len())
+                .sum::<usize>();
+            let caption_secs = captions
+                .segments
+                .iter()
+                .map(|segment| f64::from(segment.end - segment.start).max(0.0))
+                .sum::<f64>();
+            let fillers = captions::filler_phrases(vec![], captions::DEFAULT_FILLER_WORDS);
+
+            stats.total_words = Some(total_words as u32);
+            stats.words_per_minute =
+                (caption_secs > 0.0).then(|| total_words as f64 / (caption_secs / 60.0));
+            stats.filler_count =
+                Some(captions::find_filler_words(&captions.segments, &fillers).len() as u32);
+        }
+
+        Ok(stats)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// The audio with the presenter's voice in it, in recording order. The microphone is
+/// preferred, falling back to system audio for segments recorded without one.
+fn voice_audio_paths(meta: &RecordingMeta) -> Vec<PathBuf> {
+    match &meta.inner {
+        RecordingMetaInner::Instant(_) => vec![meta.project_path.join("content/output.mp4")],
+        RecordingMetaInner::Studio(StudioRecordingMeta::SingleSegment { segment }) => segment
+            .audio
+            .iter()
+            .map(|audio| meta.path(&audio.path))
+            .collect(),
+        RecordingMetaInner::Studio(StudioRecordingMeta::MultipleSegments { inner }) => inner
+            .segments
+            .iter()
+            .filter_map(|segment| segment.mic.as_ref().or(segment.system_audio.as_ref()))
+            .map(|audio| meta.path(&audio.path))
+            .collect(),
+    }
+}
+
+/// Seconds of talking and of silence in a waveform from `audio::get_waveform`
+fn talk_and_silence(waveform: &[f32]) -> (f64, f64) {
+    let min_silent_chunks = (MIN_SILENCE_SECS / WAVEFORM_CHUNK_SECS).round() as usize;
+
+    let mut silent_chunks = 0;
+    let mut run = 0;
+    for level in waveform {
+        if *level < SILENCE_THRESHOLD_DBFS {
+            run += 1;
+            continue;
+        }
+
+        if run >= min_silent_chunks {
+            silent_chunks += run;
+        }
+        run = 0;
+    }
+    if run >= min_silent_chunks {
+        silent_chunks += run;
+    }
+
+    let talk_chunks = waveform.len() - silent_chunks;
+    (
+        talk_chunks as f64 * WAVEFORM_CHUNK_SECS,
+        silent_chunks as f64 * WAVEFORM_CHUNK_SECS,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn short_pauses_count_as_talking() {
+        let (loud, quiet) = (-20.0, -60.0);
+        let mut waveform = vec![loud; 10];
+        // A 0.3s pause between words
+        waveform.extend([quiet; 3]);
+        waveform.extend([loud; 10]);
+        // A 1s silence
+        waveform.extend([quiet; 10]);
+
+        let (talk_time, silence_time) = talk_and_silence(&waveform);
+
+        assert!((talk_time - 2.3).abs() < 1e-9);
+        assert!((silence_time - 1.0).abs() < 1e-9);
+    }
+}
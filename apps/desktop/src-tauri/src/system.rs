@@ -3,7 +3,10 @@ use std::fs::File;
 use std::io::BufReader;
 use std::path::PathBuf;
 
-use cap_project::{RecordingMeta, RecordingMetaInner, StudioRecordingMeta};
+use cap_project::{
+    AudioMeta, FrameTimestamps, MultipleSegment, RecordingMeta, RecordingMetaInner,
+    StudioRecordingMeta, VideoMeta,
+};
 use mp4::Mp4Reader;
 use serde::Serialize;
 use specta::Type;
@@ -96,27 +99,58 @@ pub async fn get_video_metadata(path: PathBuf) -> Result<VideoRecordingMetadata,
         Ok(current_duration)
     }
 
-    let display_paths = match &recording_meta.inner {
-        RecordingMetaInner::Instant(_) => {
-            vec![path.join("content/output.mp4")]
+    // Prefer real frame timestamps, since the container duration drifts when frames were dropped
+    fn get_duration_for_video(recording_meta: &RecordingMeta, video: &VideoMeta) -> Result<f64, String> {
+        let timestamps_duration = video
+            .frame_timestamps
+            .as_ref()
+            .and_then(|path| FrameTimestamps::load_from_file(&recording_meta.path(path)).ok())
+            .and_then(|timestamps| timestamps.duration(video.fps));
+
+        match timestamps_duration {
+            Some(duration) => Ok(duration),
+            None => get_duration_for_path(recording_meta.path(&video.path)),
+        }
+    }
+
+    fn get_duration_for_audio(
+        recording_meta: &RecordingMeta,
+        audio: &AudioMeta,
+    ) -> Result<f64, String> {
+        let input = ffmpeg::format::input(&recording_meta.path(&audio.path))
+            .map_err(|e| format!("Failed to open audio file: {}", e))?;
+
+        Ok(input.duration().max(0) as f64 / ffmpeg::ffi::AV_TIME_BASE as f64)
+    }
+
+    // Audio-only recordings have no display video, so their length comes from the audio
+    fn get_duration_for_segment(
+        recording_meta: &RecordingMeta,
+        segment: &MultipleSegment,
+    ) -> Result<f64, String> {
+        let audio = segment.mic.as_ref().or(segment.system_audio.as_ref());
+
+        match (&segment.display, audio) {
+            (Some(display), _) => get_duration_for_video(recording_meta, display),
+            (None, Some(audio)) => get_duration_for_audio(recording_meta, audio),
+            (None, None) => Ok(0.0),
         }
+    }
+
+    let duration = match &recording_meta.inner {
+        RecordingMetaInner::Instant(_) => get_duration_for_path(path.join("content/output.mp4"))?,
         RecordingMetaInner::Studio(meta) => match meta {
             StudioRecordingMeta::SingleSegment { segment } => {
-                vec![recording_meta.path(&segment.display.path)]
+                get_duration_for_video(&recording_meta, &segment.display)?
             }
             StudioRecordingMeta::MultipleSegments { inner, .. } => inner
                 .segments
                 .iter()
-                .map(|s| recording_meta.path(&s.display.path))
-                .collect(),
+                .map(|s| get_duration_for_segment(&recording_meta, s))
+                .sum::<Result<_, _>>()?,
         },
     };
 
-    let duration = display_paths
-        .into_iter()
-        .map(get_duration_for_path)
-        .sum::<Result<_, _>>()?;
-
     let (width, height) = (1920, 1080);
     let fps = 30;
 
@@ -131,7 +165,14 @@ pub async fn get_video_metadata(path: PathBuf) -> Result<VideoRecordingMetadata,
     };
 
     let fps_factor = (fps as f64) / 30.0;
-    let video_bitrate = base_bitrate * fps_factor;
+    let audio_only = recording_meta
+        .studio_meta()
+        .is_some_and(|meta| meta.is_audio_only());
+    let video_bitrate = if audio_only {
+        0.0
+    } else {
+        base_bitrate * fps_factor
+    };
     let audio_bitrate = 192_000.0;
     let total_bitrate = video_bitrate + audio_bitrate;
     let estimated_size_mb = (total_bitrate * duration) / (8.0 * 1024.0 * 1024.0);
@@ -165,12 +206,18 @@ pub fn close_recordings_overlay_window(app: AppHandle) {
 #[specta::specta]
 pub fn focus_captures_panel(app: AppHandle) {
     #[cfg(target_os = "macos")]
-    {
+    if crate::permissions::has_accessibility_permission() {
         use tauri_nspanel::ManagerExt;
         if let Ok(panel) = app.get_webview_panel(&CapWindowId::RecordingsOverlay.label()) {
             panel.make_key_window();
+            return;
         }
     }
+
+    // Without accessibility, fall back to Tauri's own window APIs
+    if let Some(window) = CapWindowId::RecordingsOverlay.get(&app) {
+        crate::windows::raise_window(&window);
+    }
 }
 
 #[tauri::command]
@@ -1,7 +1,11 @@
 #![allow(unused_mut)]
 #![allow(unused_imports)]
 
-use crate::{fake_window, general_settings::AppTheme, permissions, App, ArcLock};
+use crate::{
+    fake_window,
+    general_settings::{AppTheme, GeneralSettingsStore},
+    permissions, App, ArcLock, MutableState,
+};
 use cap_flags::FLAGS;
 use cap_media::{platform::logical_monitor_bounds, sources::CaptureScreen};
 use futures::pin_mut;
@@ -11,7 +15,10 @@ use std::{
     ops::Deref,
     path::PathBuf,
     str::FromStr,
-    sync::{atomic::AtomicU32, Arc, Mutex},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    },
 };
 use tauri::{
     AppHandle, LogicalPosition, Manager, Monitor, PhysicalPosition, PhysicalSize, WebviewUrl,
@@ -20,6 +27,9 @@ use tauri::{
 use tokio::sync::RwLock;
 use tracing::debug;
 
+/// How long the recording indicator has to stay put before its position is saved
+const INDICATOR_POSITION_SAVE_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
+
 #[cfg(target_os = "macos")]
 const DEFAULT_TRAFFIC_LIGHTS_INSET: LogicalPosition<f64> = LogicalPosition::new(12.0, 12.0);
 
@@ -440,11 +450,19 @@ impl ShowCapWindow {
 
                 window
             }
-            Self::InProgressRecording {
-                position: _position,
-            } => {
-                let width = 244.0;
-                let height = 40.0;
+            Self::InProgressRecording { position } => {
+                let indicator = GeneralSettingsStore::get(app)
+                    .ok()
+                    .flatten()
+                    .map(|s| s.recording_indicator)
+                    .unwrap_or_default();
+                let (width, height) = indicator.size.window_size();
+                let (x, y) = indicator.position.or(*position).unwrap_or_else(|| {
+                    (
+                        ((monitor.size().width as f64) / monitor.scale_factor() - width) / 2.0,
+                        (monitor.size().height as f64) / monitor.scale_factor() - height - 120.0,
+                    )
+                });
 
                 let window = self
                     .window_builder(app, "/in-progress-recording")
@@ -457,13 +475,43 @@ impl ShowCapWindow {
                     .visible_on_all_workspaces(true)
                     .content_protected(true)
                     .inner_size(width, height)
-                    .position(
-                        ((monitor.size().width as f64) / monitor.scale_factor() - width) / 2.0,
-                        (monitor.size().height as f64) / monitor.scale_factor() - height - 120.0,
-                    )
+                    .position(x, y)
                     .skip_taskbar(true)
                     .build()?;
 
+                // Remember where the user drags the indicator to. Moves arrive continuously
+                // while dragging, so the position is only saved once it stops changing.
+                let moves = Arc::new(AtomicU32::new(0));
+                window.on_window_event({
+                    let app = app.clone();
+                    let window = window.clone();
+                    move |event| {
+                        let tauri::WindowEvent::Moved(position) = event else {
+                            return;
+                        };
+                        let Ok(scale_factor) = window.scale_factor() else {
+                            return;
+                        };
+
+                        let position = position.to_logical::<f64>(scale_factor);
+                        let move_id = moves.fetch_add(1, Ordering::Relaxed) + 1;
+                        let moves = moves.clone();
+                        let app = app.clone();
+
+                        tauri::async_runtime::spawn(async move {
+                            tokio::time::sleep(INDICATOR_POSITION_SAVE_DELAY).await;
+                            if moves.load(Ordering::Relaxed) != move_id {
+                                return;
+                            }
+
+                            GeneralSettingsStore::update(&app, |s| {
+                                s.recording_indicator.position = Some((position.x, position.y));
+                            })
+                            .ok();
+                        });
+                    }
+                });
+
                 #[cfg(target_os = "macos")]
                 {
                     crate::platform::set_window_level(window.as_ref().window(), 1000);
@@ -626,6 +674,14 @@ fn add_traffic_lights(window: &WebviewWindow<Wry>, controls_inset: Option<Logica
         .ok();
 }
 
+/// Bring one of Cap's windows to the front using only Tauri's window APIs,
+/// which work without accessibility permission
+pub fn raise_window(window: &WebviewWindow) {
+    window.unminimize().ok();
+    window.show().ok();
+    window.set_focus().ok();
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn set_theme(window: tauri::Window, theme: AppTheme) {
@@ -734,3 +790,22 @@ impl EditorWindowIds {
         app.state::<EditorWindowIds>().deref().clone()
     }
 }
+
+/// Show the recording indicator without recording so its appearance and position can be adjusted
+#[tauri::command]
+#[specta::specta]
+pub async fn preview_recording_indicator(
+    app: AppHandle,
+    state: MutableState<'_, App>,
+) -> Result<(), String> {
+    if state.read().await.current_recording.is_some() {
+        return Err("Cannot preview the indicator while recording".to_string());
+    }
+
+    ShowCapWindow::InProgressRecording { position: None }
+        .show(&app)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
@@ -205,6 +205,7 @@ function Page() {
           capture_target: options.target(),
           mode: rawOptions.mode,
           capture_system_audio: rawOptions.captureSystemAudio,
+          push_to_talk: rawOptions.pushToTalk,
         });
       } else await commands.stopRecording();
     },
@@ -449,6 +450,7 @@ function Page() {
         onChange={(v) => setMicInput.mutate(v)}
       />
       <SystemAudio />
+      <PushToTalk />
       <div class="flex items-center space-x-1 w-full">
         {/* AUTH BYPASS: Removed auth check for instant mode - always show record button */}
         <Button
@@ -927,6 +929,32 @@ function SystemAudio() {
   );
 }
 
+function PushToTalk() {
+  const { rawOptions, setOptions } = useRecordingOptions();
+  const currentRecording = createCurrentRecordingQuery();
+
+  return (
+    <button
+      onClick={() => {
+        if (!rawOptions) return;
+        setOptions({ pushToTalk: !rawOptions.pushToTalk });
+      }}
+      disabled={!!currentRecording.data}
+      class="relative flex flex-row items-center h-[2rem] px-[0.375rem] gap-[0.375rem] border rounded-lg border-gray-3 w-full disabled:text-gray-11 transition-colors KSelect overflow-hidden z-10"
+    >
+      <div class="size-[1.25rem] flex items-center justify-center">
+        <IconCapMicrophone class="text-gray-11 size-[1.25rem]" />
+      </div>
+      <span class="flex-1 text-left truncate">
+        {rawOptions.pushToTalk ? "Push to Talk" : "Always Record Mic"}
+      </span>
+      <InfoPill variant={rawOptions.pushToTalk ? "blue" : "red"}>
+        {rawOptions.pushToTalk ? "On" : "Off"}
+      </InfoPill>
+    </button>
+  );
+}
+
 function TargetSelect<T extends { id: number; name: string }>(props: {
   options: Array<T>;
   onChange: (value: T) => void;
@@ -16,6 +16,8 @@ import {
   type GeneralSettingsStore,
   type MainWindowRecordingStartBehaviour,
   type PostStudioRecordingBehaviour,
+  type RecordingIndicatorSettings,
+  type RecordingIndicatorSize,
 } from "~/utils/tauri";
 // import { themeStore } from "~/store/theme";
 import { CheckMenuItem, Menu } from "@tauri-apps/api/menu";
@@ -121,6 +123,16 @@ function Inner(props: { initialStore: GeneralSettingsStore | null }) {
     generalSettingsStore.set({ [key]: value });
   };
 
+  const updateIndicator = async (
+    changes: Partial<RecordingIndicatorSettings>
+  ) => {
+    // Read fresh, as the indicator's position is saved whenever it's dragged
+    const current = (await generalSettingsStore.get())?.recordingIndicator;
+    const indicator = { ...current, ...changes };
+    setSettings("recordingIndicator", indicator);
+    await commands.setRecordingIndicatorSettings(indicator);
+  };
+
   const ostype: OsType = type();
 
   return (
@@ -299,6 +311,57 @@ function Inner(props: { initialStore: GeneralSettingsStore | null }) {
               <IconCapChevronDown class="size-4" />
             </button>
           </Setting>
+          <Setting
+            label="Recording indicator"
+            description="The size and color of the controls shown while recording. Drag the indicator to move it."
+          >
+            <div class="flex flex-row gap-2 items-center">
+              <input
+                type="color"
+                class="w-8 h-7 rounded-md border border-gray-300 bg-transparent"
+                value={settings.recordingIndicator?.color ?? "#E5484D"}
+                onChange={(e) =>
+                  updateIndicator({ color: e.currentTarget.value })
+                }
+              />
+              <button
+                class="flex flex-row gap-1 items-center px-2 py-1 rounded-md border border-gray-300"
+                onClick={async () => {
+                  const item = (text: string, value: RecordingIndicatorSize) =>
+                    CheckMenuItem.new({
+                      text,
+                      checked:
+                        (settings.recordingIndicator?.size ?? "medium") ===
+                        value,
+                      action: () => updateIndicator({ size: value }),
+                    });
+                  const menu = await Menu.new({
+                    items: await Promise.all([
+                      item("Small", "small"),
+                      item("Medium", "medium"),
+                      item("Large", "large"),
+                    ]),
+                  });
+                  menu.popup();
+                }}
+              >
+                {{ small: "Small", medium: "Medium", large: "Large" }[
+                  settings.recordingIndicator?.size ?? "medium"
+                ]}
+                <IconCapChevronDown class="size-4" />
+              </button>
+            </div>
+          </Setting>
+          <ToggleSetting
+            label="Show elapsed time while recording"
+            value={settings.recordingIndicator?.showElapsedTime ?? true}
+            onChange={(value) => updateIndicator({ showElapsedTime: value })}
+          />
+          <ToggleSetting
+            label="Show stop button while recording"
+            value={settings.recordingIndicator?.showStopButton ?? true}
+            onChange={(value) => updateIndicator({ showStopButton: value })}
+          />
           <ServerURLSetting
             value={settings.serverUrl ?? "https://cap.so"}
             onChange={async (v) => {
@@ -20,7 +20,11 @@ import {
 import { createStore } from "solid-js/store";
 
 import { createCameraMutation } from "~/utils/queries";
-import { createImageDataWS, createLazySignal } from "~/utils/socket";
+import {
+  createImageDataWS,
+  createLazySignal,
+  type FramePreference,
+} from "~/utils/socket";
 import {
   RecordingOptionsProvider,
   useRecordingOptions,
@@ -38,6 +42,12 @@ namespace CameraWindow {
 
 const BAR_HEIGHT = 56;
 
+// The preview window is at most a few hundred pixels wide, so full size RGBA is wasted
+const PREVIEW_FRAME_PREFERENCE: FramePreference = {
+  format: { type: "jpeg", quality: 85 },
+  maxWidth: 800,
+};
+
 const { cameraWsPort } = (window as any).__CAP__;
 
 export default function () {
@@ -93,7 +103,8 @@ function Page() {
 
   const [ws, isConnected] = createImageDataWS(
     `ws://localhost:${cameraWsPort}`,
-    imageDataHandler
+    imageDataHandler,
+    PREVIEW_FRAME_PREFERENCE
   );
 
   const reconnectInterval = setInterval(() => {
@@ -103,7 +114,8 @@ function Page() {
 
       const newWs = createImageDataWS(
         `ws://localhost:${cameraWsPort}`,
-        imageDataHandler
+        imageDataHandler,
+        PREVIEW_FRAME_PREFERENCE
       );
       Object.assign(ws, newWs[0]);
     }
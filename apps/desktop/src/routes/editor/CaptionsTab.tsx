@@ -1,7 +1,5 @@
 import { batch, createEffect, createSignal, onMount, Show } from "solid-js";
 import { debounce } from "@solid-primitives/scheduled";
-import { appLocalDataDir, join } from "@tauri-apps/api/path";
-import { exists } from "@tauri-apps/plugin-fs";
 import toast from "solid-toast";
 import { Button } from "@cap/ui-solid";
 import { Select as KSelect } from "@kobalte/core/select";
@@ -11,7 +9,7 @@ import { MenuItem, MenuItemList, PopperContent } from "./ui";
 import { topLeftAnimateClasses } from "./ui";
 import { TextInput } from "./TextInput";
 // import { Toggle } from "~/components/Toggle"; // No longer needed - using custom CaptionToggle
-import type { CaptionSettings, CaptionSegment } from "~/utils/tauri";
+import type { CaptionSettings, CaptionSegment, WhisperModel } from "~/utils/tauri";
 import { Field, Slider, Subfield, Input, IconCapMessageBubble, IconCapChevronDown } from "./ui";
 import { useEditorContext, FPS, OUTPUT_SIZE } from "./context";
 import { commands, events } from "~/utils/tauri";
@@ -19,7 +17,7 @@ import { captionsStore } from "~/store/captions";
 
 // Model information
 interface ModelOption {
-  name: string;
+  name: WhisperModel;
   label: string;
 }
 
@@ -35,10 +33,14 @@ interface FontOption {
 
 const MODEL_OPTIONS: ModelOption[] = [
   { name: "tiny", label: "Tiny (75MB) - Fastest, less accurate" },
+  { name: "tinyEn", label: "Tiny English (75MB) - English only" },
   { name: "base", label: "Base (142MB) - Fast, decent accuracy" },
+  { name: "baseEn", label: "Base English (142MB) - English only" },
   { name: "small", label: "Small (466MB) - Balanced speed/accuracy" },
+  { name: "smallEn", label: "Small English (466MB) - English only" },
   { name: "medium", label: "Medium (1.5GB) - Slower, more accurate" },
-  { name: "large-v3", label: "Large (3GB) - Slowest, most accurate" },
+  { name: "mediumEn", label: "Medium English (1.5GB) - English only" },
+  { name: "largeV3", label: "Large (3GB) - Slowest, most accurate" },
 ];
 
 const LANGUAGE_OPTIONS: LanguageOption[] = [
@@ -58,8 +60,7 @@ const LANGUAGE_OPTIONS: LanguageOption[] = [
   { code: "zh", label: "Chinese" },
 ];
 
-const DEFAULT_MODEL = "tiny";
-const MODEL_FOLDER = "transcription_models";
+const DEFAULT_MODEL: WhisperModel = "tiny";
 
 // Custom flat button component since we can't import it
 function FlatButton(props: {
@@ -256,20 +257,18 @@ export function CaptionsTab() {
 
 
   // Add model selection state
-  const [selectedModel, setSelectedModel] = createSignal(DEFAULT_MODEL);
+  const [selectedModel, setSelectedModel] = createSignal<WhisperModel>(DEFAULT_MODEL);
   const [selectedLanguage, setSelectedLanguage] = createSignal("auto");
-  const [downloadedModels, setDownloadedModels] = createSignal<string[]>([]);
+  const [downloadedModels, setDownloadedModels] = createSignal<WhisperModel[]>([]);
 
   // States for captions
   const [modelExists, setModelExists] = createSignal(false);
   const [isDownloading, setIsDownloading] = createSignal(false);
   const [downloadProgress, setDownloadProgress] = createSignal(0);
-  const [downloadingModel, setDownloadingModel] = createSignal<string | null>(
-    null
-  );
+  const [downloadingModel, setDownloadingModel] =
+    createSignal<WhisperModel | null>(null);
   const [isGenerating, setIsGenerating] = createSignal(false);
   const [hasAudio, setHasAudio] = createSignal(false);
-  const [modelPath, setModelPath] = createSignal("");
   const [currentCaption, setCurrentCaption] = createSignal<string | null>(null);
 
   // Ensure captions object is initialized in project config and sync with captionsStore
@@ -305,15 +304,6 @@ export function CaptionsTab() {
   // Check downloaded models on mount
   onMount(async () => {
     try {
-      // Check for downloaded models
-      const appDataDirPath = await appLocalDataDir();
-      const modelsPath = await join(appDataDirPath, MODEL_FOLDER);
-
-      // Create models directory if it doesn't exist
-      if (!(await exists(modelsPath))) {
-        await commands.createDir(modelsPath, true);
-      }
-
       // Check which models are already downloaded
       const models = await Promise.all(
         MODEL_OPTIONS.map(async (model) => {
@@ -415,12 +405,8 @@ export function CaptionsTab() {
     }
   });
 
-  const checkModelExists = async (modelName: string) => {
-    const appDataDirPath = await appLocalDataDir();
-    const modelsPath = await join(appDataDirPath, MODEL_FOLDER);
-    const modelPath = await join(modelsPath, `${modelName}.bin`);
-    setModelPath(modelPath);
-    return await commands.checkModelExists(modelPath);
+  const checkModelExists = async (model: WhisperModel) => {
+    return await commands.checkModelExists(model);
   };
 
   const downloadModel = async () => {
@@ -430,24 +416,13 @@ export function CaptionsTab() {
       setDownloadProgress(0);
       setDownloadingModel(modelToDownload);
 
-      // Create the directory if it doesn't exist
-      const appDataDirPath = await appLocalDataDir();
-      const modelsPath = await join(appDataDirPath, MODEL_FOLDER);
-      const modelPath = await join(modelsPath, `${modelToDownload}.bin`);
-
-      try {
-        await commands.createDir(modelsPath, true);
-      } catch (err) {
-        console.error("Error creating directory:", err);
-      }
-
       // Set up progress listener
       const unlisten = await events.downloadProgress.listen((event) => {
         setDownloadProgress(event.payload.progress);
       });
 
-      // Download the model
-      await commands.downloadWhisperModel(modelToDownload, modelPath);
+      // Download the model, which is stored wherever the backend keeps models of that size
+      await commands.downloadWhisperModel(modelToDownload);
 
       // Clean up listener
       unlisten();
@@ -475,18 +450,14 @@ export function CaptionsTab() {
 
     try {
       const videoPath = editorInstance.path;
+      // No language hint lets the backend detect it
       const lang = selectedLanguage();
-      const currentModelPath = await join(
-        await appLocalDataDir(),
-        MODEL_FOLDER,
-        `${selectedModel()}.bin`
-      );
 
-      // Verify file existence before proceeding
       const result = await commands.transcribeAudio(
         videoPath,
-        currentModelPath,
-        lang
+        selectedModel(),
+        lang === "auto" ? null : lang,
+        false
       );
 
       if (result && result.segments.length > 0) {
@@ -599,12 +570,12 @@ export function CaptionsTab() {
                 <div class="space-y-4">
                   <div class="space-y-2">
                     <label class="text-xs text-gray-500">Current Model</label>
-                    <KSelect<string>
+                    <KSelect<WhisperModel>
                       options={MODEL_OPTIONS.filter((m) =>
                         downloadedModels().includes(m.name)
                       ).map((m) => m.name)}
                       value={selectedModel()}
-                      onChange={(value: string | null) => {
+                      onChange={(value: WhisperModel | null) => {
                         if (value) {
                           batch(() => {
                             setSelectedModel(value);
@@ -628,7 +599,7 @@ export function CaptionsTab() {
                       )}
                     >
                       <KSelect.Trigger class="flex flex-row items-center h-9 px-3 gap-2 border rounded-lg border-gray-200 w-full text-gray-700 text-sm focus:border-blue-500 focus:ring-1 focus:ring-blue-500 transition-colors">
-                        <KSelect.Value<string> class="flex-1 text-left truncate">
+                        <KSelect.Value<WhisperModel> class="flex-1 text-left truncate">
                           {(state) => {
                             const model = MODEL_OPTIONS.find(
                               (m) => m.name === state.selectedOption()
@@ -660,10 +631,10 @@ export function CaptionsTab() {
                     <label class="text-xs text-gray-500">
                       Download New Model
                     </label>
-                    <KSelect<string>
+                    <KSelect<WhisperModel>
                       options={MODEL_OPTIONS.map((m) => m.name)}
                       value={selectedModel()}
-                      onChange={(value: string | null) => {
+                      onChange={(value: WhisperModel | null) => {
                         if (value) setSelectedModel(value);
                       }}
                       disabled={isDownloading()}
@@ -686,7 +657,7 @@ export function CaptionsTab() {
                       )}
                     >
                       <KSelect.Trigger class="flex flex-row items-center h-9 px-3 gap-2 border rounded-lg border-gray-200 w-full text-gray-700 text-sm focus:border-blue-500 focus:ring-1 focus:ring-blue-500 transition-colors">
-                        <KSelect.Value<string> class="flex-1 text-left truncate">
+                        <KSelect.Value<WhisperModel> class="flex-1 text-left truncate">
                           {(state) => {
                             const model = MODEL_OPTIONS.find(
                               (m) => m.name === state.selectedOption()
@@ -20,7 +20,8 @@ import { createStore } from "solid-js/store";
 import Cropper, { cropToFloor } from "~/components/Cropper";
 import { Toggle } from "~/components/Toggle";
 import Tooltip from "~/components/Tooltip";
-import { events, type Crop } from "~/utils/tauri";
+import { captionsStore } from "~/store/captions";
+import { commands, events, type Crop } from "~/utils/tauri";
 import { ConfigSidebar } from "./ConfigSidebar";
 import {
   EditorContextProvider,
@@ -73,7 +74,8 @@ export function Editor() {
 }
 
 function Inner() {
-  const { project, editorState, setEditorState } = useEditorContext();
+  const { project, setProject, editorInstance, editorState, setEditorState } =
+    useEditorContext();
 
   onMount(() =>
     events.editorStateChanged.listen((e) => {
@@ -82,6 +84,25 @@ function Inner() {
     })
   );
 
+  // Imported subtitles replace the captions on disk, so pick them up if they're for this recording
+  onMount(() =>
+    events.captionsImported.listen(async (e) => {
+      const pathParts = editorInstance.path.split("/");
+      const videoId = pathParts[pathParts.length - 1].replace(".cap", "");
+      if (e.payload.videoId !== videoId) return;
+
+      const captions = await commands.loadCaptions(videoId);
+      if (!captions?.settings) return;
+
+      setProject("captions", {
+        segments: captions.segments,
+        settings: captions.settings,
+      });
+      captionsStore.updateSegments(captions.segments);
+      captionsStore.updateSettings(captions.settings);
+    })
+  );
+
   const renderFrame = throttle((time: number) => {
     if (!editorState.playing) {
       events.renderFrameEvent.emit({
@@ -150,14 +150,14 @@ export function ExportDialog() {
       {
         format: settings.format as ExportFormat,
         fps: settings.fps,
-        burn_captions: burnCaptions,
         resolution_base: {
           x: settings.resolution.width,
           y: settings.resolution.height,
         },
         compression: settings.compression,
       },
-      onProgress
+      onProgress,
+      burnCaptions
     );
   };
 
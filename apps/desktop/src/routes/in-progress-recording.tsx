@@ -4,9 +4,15 @@ import { getCurrentWindow } from "@tauri-apps/api/window";
 import * as dialog from "@tauri-apps/plugin-dialog";
 import { type as ostype } from "@tauri-apps/plugin-os";
 import { cx } from "cva";
-import { createEffect, createSignal, type ComponentProps } from "solid-js";
+import {
+  createEffect,
+  createSignal,
+  Show,
+  type ComponentProps,
+} from "solid-js";
 import { createStore, produce } from "solid-js/store";
 
+import { generalSettingsStore } from "~/store";
 import {
   createCurrentRecordingQuery,
   createOptionsQuery,
@@ -21,6 +27,8 @@ export default function () {
   const [state, setState] = createSignal<State>("recording");
   const currentRecording = createCurrentRecordingQuery();
   const { rawOptions } = createOptionsQuery();
+  const generalSettings = generalSettingsStore.createQuery();
+  const indicator = () => generalSettings.data?.recordingIndicator;
 
   const audioLevel = createAudioInputLevel();
 
@@ -118,17 +126,38 @@ export default function () {
   return (
     <div class="flex flex-row items-stretch w-full h-full bg-gray-1 animate-in fade-in">
       <div class="flex flex-row justify-between p-[0.25rem] flex-1">
-        <button
-          disabled={stopRecording.isPending}
-          class="py-[0.25rem] px-[0.5rem] text-red-300 gap-[0.25rem] flex flex-row items-center rounded-lg transition-opacity disabled:opacity-60"
-          type="button"
-          onClick={() => stopRecording.mutate()}
+        <Show
+          when={indicator()?.showStopButton ?? true}
+          fallback={
+            <div
+              class="py-[0.25rem] px-[0.5rem] text-red-300 gap-[0.5rem] flex flex-row items-center"
+              style={{ color: indicator()?.color }}
+              data-tauri-drag-region
+            >
+              <div class="rounded-full size-2.5 bg-current" />
+              <Show when={indicator()?.showElapsedTime ?? true}>
+                <span class="font-[500] text-[0.875rem] tabular-nums">
+                  {formatTime(adjustedTime() / 1000)}
+                </span>
+              </Show>
+            </div>
+          }
         >
-          <IconCapStopCircle />
-          <span class="font-[500] text-[0.875rem] tabular-nums">
-            {formatTime(adjustedTime() / 1000)}
-          </span>
-        </button>
+          <button
+            disabled={stopRecording.isPending}
+            class="py-[0.25rem] px-[0.5rem] text-red-300 gap-[0.25rem] flex flex-row items-center rounded-lg transition-opacity disabled:opacity-60"
+            style={{ color: indicator()?.color }}
+            type="button"
+            onClick={() => stopRecording.mutate()}
+          >
+            <IconCapStopCircle />
+            <Show when={indicator()?.showElapsedTime ?? true}>
+              <span class="font-[500] text-[0.875rem] tabular-nums">
+                {formatTime(adjustedTime() / 1000)}
+              </span>
+            </Show>
+          </button>
+        </Show>
 
         <div class="flex gap-1 items-center">
           <div class="flex relative justify-center items-center w-8 h-8">
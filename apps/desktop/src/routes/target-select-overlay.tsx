@@ -763,6 +763,7 @@ function RecordingControls(props: {
               capture_target: props.target,
               mode: rawOptions.mode,
               capture_system_audio: rawOptions.captureSystemAudio,
+              push_to_talk: rawOptions.pushToTalk,
             });
           }}
         >
@@ -1,13 +1,26 @@
 import { Channel } from "@tauri-apps/api/core";
 import { commands, ExportSettings, FramesRendered } from "./tauri";
 
+// Captions follow the project's own settings unless `burnCaptions` is given
 export async function exportVideo(
   projectPath: string,
   settings: ExportSettings,
-  onProgress: (progress: FramesRendered) => void
+  onProgress: (progress: FramesRendered) => void,
+  burnCaptions: boolean | null = null
 ) {
   const progress = new Channel<FramesRendered>((e) => {
     onProgress(e);
   });
-  return await commands.exportVideo(projectPath, progress, settings);
+  const result = await commands.exportVideo(
+    projectPath,
+    progress,
+    settings,
+    null,
+    null,
+    burnCaptions,
+    null,
+    null,
+    null
+  );
+  return result.path;
 }
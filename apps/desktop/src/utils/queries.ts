@@ -87,6 +87,7 @@ export function createOptionsQuery() {
       cameraLabel: string | null;
       mode: RecordingMode;
       captureSystemAudio?: boolean;
+      pushToTalk?: boolean;
       targetMode?: "display" | "window" | "area" | null;
     }>({
       captureTarget: { variant: "screen", id: 0 },
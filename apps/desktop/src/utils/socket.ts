@@ -1,9 +1,19 @@
 import { createWS } from "@solid-primitives/websocket";
 import { createResource, createSignal } from "solid-js";
 
+/** Asks the server to encode or downscale frames, eg. to save bandwidth on high-res cameras */
+export type FramePreference = {
+  format?:
+    | { type: "rgba" }
+    | { type: "jpeg"; quality: number }
+    | { type: "webP" };
+  maxWidth?: number;
+};
+
 export function createImageDataWS(
   url: string,
-  onmessage: (data: { width: number; data: ImageData }) => void
+  onmessage: (data: { width: number; data: ImageData }) => void,
+  preference?: FramePreference
 ): [Omit<WebSocket, "onmessage">, () => boolean] {
   const [isConnected, setIsConnected] = createSignal(false);
   const ws = createWS(url);
@@ -11,6 +21,7 @@ export function createImageDataWS(
   ws.addEventListener("open", () => {
     console.log("WebSocket connected");
     setIsConnected(true);
+    if (preference) ws.send(JSON.stringify(preference));
   });
 
   ws.addEventListener("close", () => {
@@ -49,6 +60,24 @@ export function createImageDataWS(
         (strideArr[3] << 24)) /
       4;
 
+    // Encoded frames have no stride and are decoded by the browser
+    if (stride === 0) {
+      const blob = new Blob([clamped.slice(0, clamped.length - 12)]);
+      createImageBitmap(blob).then((bitmap) => {
+        const canvas = new OffscreenCanvas(bitmap.width, bitmap.height);
+        const ctx = canvas.getContext("2d");
+        if (!ctx) return;
+        ctx.drawImage(bitmap, 0, 0);
+        bitmap.close();
+
+        onmessage({
+          width: canvas.width,
+          data: ctx.getImageData(0, 0, canvas.width, canvas.height),
+        });
+      });
+      return;
+    }
+
     const imageData = new ImageData(
       clamped.slice(0, clamped.length - 12),
       stride,
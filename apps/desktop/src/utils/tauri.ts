@@ -38,8 +38,14 @@ async listCaptureWindows() : Promise<CaptureWindow[]> {
 async listCaptureScreens() : Promise<CaptureScreen[]> {
     return await TAURI_INVOKE("list_capture_screens");
 },
-async takeScreenshot() : Promise<null> {
-    return await TAURI_INVOKE("take_screenshot");
+/**
+ * Screenshots `target`, or the whole primary display when it's `None`, as a PNG unless
+ * another `format` is given. Waits `delay_secs` first, if any, so things like menus can be set up.
+ * `magnify` adds a zoomed in inset of part of the screenshot,
+ * and `show_cursor` draws the cursor where it was when the screenshot was taken.
+ */
+async takeScreenshot(target: ScreenshotTarget | null, format: ScreenshotFormat | null, delaySecs: number | null, magnify: MagnifyRegion | null, showCursor: boolean | null) : Promise<null> {
+    return await TAURI_INVOKE("take_screenshot", { target, format, delaySecs, magnify, showCursor });
 },
 async listAudioDevices() : Promise<string[]> {
     return await TAURI_INVOKE("list_audio_devices");
@@ -59,8 +65,11 @@ async focusCapturesPanel() : Promise<void> {
 async getCurrentRecording() : Promise<JsonValue<CurrentRecording | null>> {
     return await TAURI_INVOKE("get_current_recording");
 },
-async exportVideo(projectPath: string, progress: TAURI_CHANNEL<FramesRendered>, settings: ExportSettings) : Promise<string> {
-    return await TAURI_INVOKE("export_video", { projectPath, progress, settings });
+/**
+ * `start` and `end` limit the export to that part of the timeline, in seconds
+ */
+async exportVideo(projectPath: string, progress: TAURI_CHANNEL<FramesRendered>, settings: ExportSettings, exportMetadata: ExportMetadata | null, lut: string | null, burnCaptions: boolean | null, start: number | null, end: number | null, aspect: ExportAspect | null) : Promise<ExportResult> {
+    return await TAURI_INVOKE("export_video", { projectPath, progress, settings, exportMetadata, lut, burnCaptions, start, end, aspect });
 },
 async getExportEstimates(path: string, resolution: XY<number>, fps: number) : Promise<ExportEstimates> {
     return await TAURI_INVOKE("get_export_estimates", { path, resolution, fps });
@@ -201,10 +210,12 @@ async saveModelFile(path: string, data: number[]) : Promise<null> {
     return await TAURI_INVOKE("save_model_file", { path, data });
 },
 /**
- * Function to transcribe audio from a video file using Whisper
+ * Function to transcribe audio from a video file using Whisper.
+ * `language` is an ISO 639-1 hint, and the language is detected from the audio when it's `None`.
+ * With `translate` the captions are English, and `language` still records the source language.
  */
-async transcribeAudio(videoPath: string, modelPath: string, language: string) : Promise<CaptionData> {
-    return await TAURI_INVOKE("transcribe_audio", { videoPath, modelPath, language });
+async transcribeAudio(videoPath: string, model: WhisperModel, language: string | null, translate: boolean) : Promise<CaptionData> {
+    return await TAURI_INVOKE("transcribe_audio", { videoPath, model, language, translate });
 },
 /**
  * Function to save caption data to a file
@@ -221,20 +232,20 @@ async loadCaptions(videoId: string) : Promise<CaptionData | null> {
 /**
  * Helper function to download a Whisper model from Hugging Face Hub
  */
-async downloadWhisperModel(modelName: string, outputPath: string) : Promise<null> {
-    return await TAURI_INVOKE("download_whisper_model", { modelName, outputPath });
+async downloadWhisperModel(model: WhisperModel) : Promise<null> {
+    return await TAURI_INVOKE("download_whisper_model", { model });
 },
 /**
- * Function to check if a model file exists
+ * Function to check if a model file exists and is complete
  */
-async checkModelExists(modelPath: string) : Promise<boolean> {
-    return await TAURI_INVOKE("check_model_exists", { modelPath });
+async checkModelExists(model: WhisperModel) : Promise<boolean> {
+    return await TAURI_INVOKE("check_model_exists", { model });
 },
 /**
  * Function to delete a downloaded model
  */
-async deleteWhisperModel(modelPath: string) : Promise<null> {
-    return await TAURI_INVOKE("delete_whisper_model", { modelPath });
+async deleteWhisperModel(model: WhisperModel) : Promise<null> {
+    return await TAURI_INVOKE("delete_whisper_model", { model });
 },
 /**
  * Export captions to an SRT file
@@ -247,6 +258,18 @@ async setInstantSavePath(path: string | null) : Promise<null> {
 },
 async getInstantSavePath() : Promise<string | null> {
     return await TAURI_INVOKE("get_instant_save_path");
+},
+/**
+ * Save the recording indicator's appearance and apply it to the indicator window if it's open
+ */
+async setRecordingIndicatorSettings(settings: RecordingIndicatorSettings) : Promise<null> {
+    return await TAURI_INVOKE("set_recording_indicator_settings", { settings });
+},
+/**
+ * Show the recording indicator without recording so its appearance and position can be adjusted
+ */
+async previewRecordingIndicator() : Promise<null> {
+    return await TAURI_INVOKE("preview_recording_indicator");
 }
 }
 
@@ -256,6 +279,7 @@ async getInstantSavePath() : Promise<string | null> {
 export const events = __makeEvents__<{
 audioInputLevelChange: AudioInputLevelChange,
 authenticationInvalid: AuthenticationInvalid,
+captionsImported: CaptionsImported,
 currentRecordingChanged: CurrentRecordingChanged,
 downloadProgress: DownloadProgress,
 editorStateChanged: EditorStateChanged,
@@ -269,10 +293,12 @@ renderFrameEvent: RenderFrameEvent,
 requestNewScreenshot: RequestNewScreenshot,
 requestOpenSettings: RequestOpenSettings,
 requestStartRecording: RequestStartRecording,
+screenshotCountdown: ScreenshotCountdown,
 uploadProgress: UploadProgress
 }>({
 audioInputLevelChange: "audio-input-level-change",
 authenticationInvalid: "authentication-invalid",
+captionsImported: "captions-imported",
 currentRecordingChanged: "current-recording-changed",
 downloadProgress: "download-progress",
 editorStateChanged: "editor-state-changed",
@@ -286,6 +312,7 @@ renderFrameEvent: "render-frame-event",
 requestNewScreenshot: "request-new-screenshot",
 requestOpenSettings: "request-open-settings",
 requestStartRecording: "request-start-recording",
+screenshotCountdown: "screenshot-countdown",
 uploadProgress: "upload-progress"
 })
 
@@ -317,9 +344,23 @@ export type CameraShape = "square" | "source"
 export type CameraXPosition = "left" | "center" | "right"
 export type CameraYPosition = "top" | "bottom"
 export type CaptionData = { segments: CaptionSegment[]; settings: CaptionSettings | null }
-export type CaptionSegment = { id: string; start: number; end: number; text: string }
+export type CaptionSegment = { id: string; start: number; end: number; text: string; 
+/**
+ * Style for this segment only, merged on top of the global settings
+ */
+styleOverride?: CaptionStyleOverride | null }
 export type CaptionSettings = { enabled: boolean; font: string; size: number; color: string; backgroundColor: string; backgroundOpacity: number; position: string; bold: boolean; italic: boolean; outline: boolean; outlineColor: string; exportWithSubtitles: boolean }
+/**
+ * Caption style for a single segment. Fields that are set replace the global setting,
+ * including when they're set back to the default. `enabled`, `export_with_subtitles`
+ * and `language` are project-wide, so they can't be overridden.
+ */
+export type CaptionStyleOverride = { font?: string | null; size?: number | null; color?: string | null; backgroundColor?: string | null; backgroundOpacity?: number | null; position?: string | null; positionOffset?: number | null; bold?: boolean | null; italic?: boolean | null; outline?: boolean | null; outlineColor?: string | null; outlineWidth?: number | null; highlightColor?: string | null; maxLines?: number | null; truncateOverflow?: boolean | null; avoidCamera?: boolean | null; fadeDuration?: number | null }
 export type CaptionsData = { segments: CaptionSegment[]; settings: CaptionSettings }
+/**
+ * Emitted when subtitles are imported for a recording so an open editor can reload them
+ */
+export type CaptionsImported = { videoId: string }
 export type CaptureScreen = { id: number; name: string; refresh_rate: number }
 export type CaptureWindow = { id: number; owner_name: string; name: string; bounds: Bounds; refresh_rate: number }
 export type CommercialLicense = { licenseKey: string; expiryDate: number | null; refresh: number; activatedOn: number }
@@ -334,8 +375,35 @@ export type CursorType = "pointer" | "circle"
 export type Cursors = { [key in string]: string } | { [key in string]: CursorMeta }
 export type DownloadProgress = { progress: number; message: string }
 export type EditorStateChanged = { playhead_position: number }
+/**
+ * Frames an export to a different aspect ratio than the project's, such as 9:16 for
+ * social media, with the project's background filling the extra space
+ */
+export type ExportAspect = { aspectRatio: AspectRatio; 
+/**
+ * Where the content sits horizontally when there's space beside it,
+ * from -1 for the left edge to 1 for the right. Centered by default.
+ */
+offsetX?: number }
 export type ExportCompression = "Minimal" | "Social" | "Web" | "Potato"
 export type ExportEstimates = { duration_seconds: number; estimated_time_seconds: number; estimated_size_mb: number }
+/**
+ * Descriptive fields written into the exported file's container metadata
+ */
+export type ExportMetadata = { 
+/**
+ * Defaults to the recording's name
+ */
+title?: string | null; author?: string | null; comment?: string | null; 
+/**
+ * Written as-is, ISO 8601 (eg. `2025-01-31`) is the most widely understood
+ */
+date?: string | null }
+export type ExportResult = { path: string; 
+/**
+ * The encoder MP4s were encoded with, after falling back from unavailable hardware encoders
+ */
+encoder: VideoEncoder | null }
 export type ExportSettings = ({ format: "Mp4" } & Mp4ExportSettings) | ({ format: "Gif" } & GifExportSettings)
 export type Flags = { captions: boolean }
 export type FramesRendered = { renderedCount: number; totalFrames: number; type: "FramesRendered" }
@@ -343,16 +411,29 @@ export type GeneralSettingsStore = { instanceId?: string; uploadIndividualFiles?
 /**
  * @deprecated
  */
-openEditorAfterRecording?: boolean; instantModeSavePath?: string | null }
+openEditorAfterRecording?: boolean; instantModeSavePath?: string | null; recordingIndicator?: RecordingIndicatorSettings; 
+/**
+ * Seconds to count down before recordings start, used when a recording doesn't set its own
+ */
+recordingCountdown?: number | null }
 export type GifExportSettings = { fps: number; resolution_base: XY<number> }
 export type HapticPattern = "Alignment" | "LevelChange" | "Generic"
 export type HapticPerformanceTime = "Default" | "Now" | "DrawCompleted"
 export type Hotkey = { code: string; meta: boolean; ctrl: boolean; alt: boolean; shift: boolean }
-export type HotkeyAction = "startRecording" | "stopRecording" | "restartRecording"
+export type HotkeyAction = "startRecording" | "stopRecording" | "restartRecording" | "pushToTalk"
 export type HotkeysConfiguration = { show: boolean }
 export type HotkeysStore = { hotkeys: { [key in HotkeyAction]: Hotkey } }
+export type InsetPosition = "topLeft" | "topRight" | "bottomLeft" | "bottomRight"
 export type InstantRecordingMeta = { fps: number; sample_rate: number | null }
 export type JsonValue<T> = [T]
+/**
+ * A zoomed in copy of part of the screenshot, drawn with a border in one of its corners
+ */
+export type MagnifyRegion = { 
+/**
+ * The part to magnify, in pixels of the screenshot
+ */
+rect: Bounds; factor: number; position: InsetPosition }
 export type MainWindowRecordingStartBehaviour = "close" | "minimise"
 export type Mp4ExportSettings = { fps: number; resolution_base: XY<number>; compression: ExportCompression }
 export type MultipleSegment = { display: VideoMeta; camera?: VideoMeta | null; mic?: AudioMeta | null; system_audio?: AudioMeta | null; cursor?: string | null }
@@ -363,6 +444,18 @@ export type NewStudioRecordingAdded = { path: string }
 export type OSPermission = "screenRecording" | "camera" | "microphone" | "accessibility"
 export type OSPermissionStatus = "notNeeded" | "empty" | "granted" | "denied"
 export type OSPermissionsCheck = { screenRecording: OSPermissionStatus; microphone: OSPermissionStatus; camera: OSPermissionStatus; accessibility: OSPermissionStatus }
+/**
+ * Videos replaced when the recording was optimized, with the originals kept in `backup`
+ */
+export type OptimizationMeta = { backup: string; files: string[]; 
+/**
+ * Size of the recording's videos in MB before optimizing
+ */
+sizeBefore: number; 
+/**
+ * Size of the recording's videos in MB after optimizing
+ */
+sizeAfter: number }
 export type Plan = { upgraded: boolean; manual: boolean; last_checked: number }
 export type Platform = "MacOS" | "Windows"
 export type PostStudioRecordingBehaviour = "openEditor" | "showOverlay"
@@ -370,8 +463,26 @@ export type Preset = { name: string; config: ProjectConfiguration }
 export type PresetsStore = { presets: Preset[]; default: number | null }
 export type ProjectConfiguration = { aspectRatio: AspectRatio | null; background: BackgroundConfiguration; camera: Camera; audio: AudioConfiguration; cursor: CursorConfiguration; hotkeys: HotkeysConfiguration; timeline?: TimelineConfiguration | null; captions?: CaptionsData | null }
 export type ProjectRecordingsMeta = { segments: SegmentRecordings[] }
-export type RecordingMeta = (StudioRecordingMeta | InstantRecordingMeta) & { platform: Platform | null; pretty_name: string; sharing?: SharingMeta | null }
-export type RecordingMetaWithType = ((StudioRecordingMeta | InstantRecordingMeta) & { platform: Platform | null; pretty_name: string; sharing?: SharingMeta | null }) & { type: RecordingType }
+export type RecordingIndicatorSettings = { 
+/**
+ * Hex color of the recording dot, eg. `#E5484D`
+ */
+color?: string; size?: RecordingIndicatorSize; showElapsedTime?: boolean; showStopButton?: boolean; 
+/**
+ * Logical position of the window, bottom center of the primary monitor when unset
+ */
+position?: [number, number] | null }
+export type RecordingIndicatorSize = "small" | "medium" | "large"
+export type RecordingMeta = (StudioRecordingMeta | InstantRecordingMeta) & { platform: Platform | null; pretty_name: string; sharing?: SharingMeta | null; 
+/**
+ * The last optimization, until its backup is restored
+ */
+optimization?: OptimizationMeta | null }
+export type RecordingMetaWithType = ((StudioRecordingMeta | InstantRecordingMeta) & { platform: Platform | null; pretty_name: string; sharing?: SharingMeta | null; 
+/**
+ * The last optimization, until its backup is restored
+ */
+optimization?: OptimizationMeta | null }) & { type: RecordingType }
 export type RecordingMode = "studio" | "instant"
 export type RecordingOptionsChanged = null
 export type RecordingStarted = null
@@ -385,13 +496,36 @@ export type S3UploadMeta = { id: string }
 export type SceneMode = "default" | "cameraOnly" | "hideCamera"
 export type SceneSegment = { start: number; end: number; mode?: SceneMode | null }
 export type ScreenCaptureTarget = { variant: "window"; id: number } | { variant: "screen"; id: number } | { variant: "area"; screen: number; bounds: Bounds }
+/**
+ * Emitted once a second while waiting to take a delayed screenshot,
+ * and with no seconds remaining when the wait is over
+ */
+export type ScreenshotCountdown = { secondsRemaining: number }
+export type ScreenshotFormat = { type: "png" } | 
+/**
+ * `quality` is 1-100
+ */
+{ type: "jpeg"; quality: number } | 
+/**
+ * Encoded losslessly, as the `image` crate can't write lossy WebP
+ */
+{ type: "webP" }
+export type ScreenshotTarget = { capture: ScreenCaptureTarget } | 
+/**
+ * Every connected display, stitched together as they're arranged
+ */
+"allDisplays"
 export type SegmentRecordings = { display: Video; camera: Video | null; mic: Audio | null; system_audio: Audio | null }
 export type SerializedEditorInstance = { framesSocketUrl: string; recordingDuration: number; savedProjectConfig: ProjectConfiguration; recordings: ProjectRecordingsMeta; path: string }
 export type ShadowConfiguration = { size: number; opacity: number; blur: number }
 export type SharingMeta = { id: string; link: string }
 export type ShowCapWindow = "Setup" | "Main" | { Settings: { page: string | null } } | { Editor: { project_path: string } } | "RecordingsOverlay" | { WindowCaptureOccluder: { screen_id: number } } | { CaptureArea: { screen_id: number } } | "Camera" | { InProgressRecording: { position: [number, number] | null } } | "Upgrade" | "ModeSelect"
 export type SingleSegment = { display: VideoMeta; camera?: VideoMeta | null; audio?: AudioMeta | null; cursor?: string | null }
-export type StartRecordingInputs = { capture_target: ScreenCaptureTarget; capture_system_audio?: boolean; mode: RecordingMode }
+export type StartRecordingInputs = { capture_target: ScreenCaptureTarget; capture_system_audio?: boolean; mode: RecordingMode; 
+/**
+ * Only records the mic while the push-to-talk hotkey is held, with silence in between
+ */
+push_to_talk?: boolean }
 export type StereoMode = "stereo" | "monoL" | "monoR"
 export type StudioRecordingMeta = { segment: SingleSegment } | { inner: MultipleSegments }
 export type TimelineConfiguration = { segments: TimelineSegment[]; zoomSegments: ZoomSegment[]; sceneSegments?: SceneSegment[] | null }
@@ -400,6 +534,10 @@ export type UploadMode = { Initial: { pre_created_video: VideoUploadInfo | null
 export type UploadProgress = { progress: number }
 export type UploadResult = { Success: string } | "NotAuthenticated" | "PlanCheckFailed" | "UpgradeRequired"
 export type Video = { duration: number; width: number; height: number; fps: number; start_time: number }
+/**
+ * The H.264 encoders that can be picked between, by the ffmpeg codec backing them
+ */
+export type VideoEncoder = "X264" | "VideoToolbox" | "Nvenc" | "QuickSync"
 export type VideoMeta = { path: string; fps?: number; 
 /**
  * unix time of the first frame
@@ -407,6 +545,10 @@ export type VideoMeta = { path: string; fps?: number;
 start_time?: number | null }
 export type VideoRecordingMetadata = { duration: number; size: number }
 export type VideoUploadInfo = { id: string; link: string; config: S3UploadMeta }
+/**
+ * Whisper model sizes that can be downloaded, trading transcription speed for accuracy
+ */
+export type WhisperModel = "tiny" | "tinyEn" | "base" | "baseEn" | "small" | "smallEn" | "medium" | "mediumEn" | "largeV3"
 export type XY<T> = { x: T; y: T }
 export type ZoomMode = "auto" | { manual: { x: number; y: number } }
 export type ZoomSegment = { start: number; end: number; amount: number; mode: ZoomMode }
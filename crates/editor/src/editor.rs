@@ -112,6 +112,7 @@ impl Renderer {
                                 width: output_size.0,
                                 height: output_size.1,
                                 stride: frame.padded_bytes_per_row,
+                                timings: None,
                             })
                             .ok();
                         finished.send(()).ok();
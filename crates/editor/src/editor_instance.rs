@@ -62,12 +62,12 @@ impl EditorInstance {
 
         let (frame_tx, frame_rx) = flume::bounded(4);
 
-        let (ws_port, ws_shutdown) = create_frame_ws(frame_rx).await;
+        let (ws_port, ws_shutdown) = create_frame_ws(frame_rx, None).await;
 
         let render_constants = Arc::new(
             RenderVideoConstants::new(&recordings.segments, &recording_meta, meta)
                 .await
-                .unwrap(),
+                .map_err(|e| format!("Failed to initialize renderer: {e}"))?,
         );
 
         let renderer = Arc::new(editor::Renderer::spawn(
@@ -323,6 +323,7 @@ pub async fn create_segments(
                 SegmentVideoPaths {
                     display: recording_meta.path(&s.display.path),
                     camera: s.camera.as_ref().map(|c| recording_meta.path(&c.path)),
+                    additional_cameras: vec![],
                 },
                 0,
             )
@@ -366,8 +367,13 @@ pub async fn create_segments(
                     &recording_meta,
                     meta,
                     SegmentVideoPaths {
-                        display: recording_meta.path(&s.display.path),
+                        display: recording_meta.path(&s.display()?.path),
                         camera: s.camera.as_ref().map(|c| recording_meta.path(&c.path)),
+                        additional_cameras: s
+                            .additional_cameras
+                            .iter()
+                            .map(|c| c.as_ref().map(|c| recording_meta.path(&c.path)))
+                            .collect(),
                     },
                     i,
                 )
@@ -1,6 +1,9 @@
 use std::{fmt::Display, path::PathBuf};
 
-use cap_export::{gif::GifExportSettings, mp4::Mp4ExportSettings, ExporterBase, ExporterBuilder};
+use cap_export::{
+    filters::TimestampFilter, gif::GifExportSettings, mp4::Mp4ExportSettings, ExporterBase,
+    ExporterBuilder,
+};
 use clap::{Parser, ValueEnum};
 
 #[derive(Parser, Debug)]
@@ -9,6 +12,9 @@ struct Cli {
     #[arg(value_enum)]
     format: Option<ExportFormat>,
     settings: Option<String>,
+    /// Burn the time into the corner of each frame
+    #[arg(long)]
+    timestamp: bool,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
@@ -32,7 +38,11 @@ async fn main() {
 
     let path: PathBuf = std::env::args().collect::<Vec<_>>().swap_remove(1).into();
 
-    let base = ExporterBase::builder(path).build().await.unwrap();
+    let mut builder = ExporterBase::builder(path);
+    if cli.timestamp {
+        builder = builder.with_filter(TimestampFilter::default());
+    }
+    let base = builder.build().await.unwrap();
 
     let format = cli.format.unwrap_or_else(|| {
         inquire::Select::new(
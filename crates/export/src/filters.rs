@@ -0,0 +1,130 @@
+use cap_rendering::RenderedFrame;
+
+/// A compositing pass run on every frame once it's rendered, before it's encoded.
+/// Register filters with `ExporterBuilder::with_filter` to change exports without
+/// touching the render pipeline.
+pub trait ExportFilter: Send + Sync {
+    /// Draw onto `frame`, which is RGBA with rows `padded_bytes_per_row` apart.
+    /// `time` is how far into the export the frame is, in seconds.
+    fn apply(&self, frame: &mut RenderedFrame, time: f64);
+}
+
+// Digits are drawn from a tiny bitmap font, so no font files are needed
+const GLYPH_WIDTH: u32 = 3;
+const GLYPH_HEIGHT: u32 = 5;
+
+/// Burns the time into the bottom left corner of each frame, for reviewing exports
+/// against notes or logs
+#[derive(Default, Clone, Copy, Debug)]
+pub struct TimestampFilter {
+    /// Added to each frame's time, eg. to show recording times when exporting part of a recording
+    pub offset: f64,
+}
+
+impl ExportFilter for TimestampFilter {
+    fn apply(&self, frame: &mut RenderedFrame, time: f64) {
+        let text = format_timestamp(time + self.offset);
+
+        // Font pixels are scaled so the text is about a fortieth of the frame's height
+        let scale = (frame.height / (GLYPH_HEIGHT * 40)).max(1);
+        let advance = (GLYPH_WIDTH + 1) * scale;
+        let padding = 2 * scale;
+        let margin = 4 * scale;
+
+        let width = text.len() as u32 * advance - scale + padding * 2;
+        let height = GLYPH_HEIGHT * scale + padding * 2;
+        let left = margin;
+        let top = frame.height.saturating_sub(margin + height);
+
+        fill_rect(frame, left, top, width, height, [0, 0, 0, 160]);
+
+        for (i, c) in text.chars().enumerate() {
+            let glyph_left = left + padding + i as u32 * advance;
+            for (row, bits) in glyph(c).iter().enumerate() {
+                for col in 0..GLYPH_WIDTH {
+                    if bits & (0b100 >> col) != 0 {
+                        fill_rect(
+                            frame,
+                            glyph_left + col * scale,
+                            top + padding + row as u32 * scale,
+                            scale,
+                            scale,
+                            [255, 255, 255, 255],
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// `M:SS`, or `H:MM:SS` from an hour in
+fn format_timestamp(time: f64) -> String {
+    let secs = time.max(0.0) as u64;
+    let (hours, minutes, secs) = (secs / 3600, (secs / 60) % 60, secs % 60);
+
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{secs:02}")
+    } else {
+        format!("{minutes}:{secs:02}")
+    }
+}
+
+/// Rows of a character, top first, with the leftmost pixel in the highest of three bits
+fn glyph(c: char) -> [u8; GLYPH_HEIGHT as usize] {
+    match c {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b001, 0b001],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        ':' => [0b000, 0b010, 0b000, 0b010, 0b000],
+        _ => [0; GLYPH_HEIGHT as usize],
+    }
+}
+
+/// Alpha blend `color` over a rectangle of the frame, clipped to its edges
+fn fill_rect(frame: &mut RenderedFrame, x: u32, y: u32, width: u32, height: u32, color: [u8; 4]) {
+    let alpha = u32::from(color[3]);
+
+    for py in y..(y + height).min(frame.height) {
+        for px in x..(x + width).min(frame.width) {
+            let i = py as usize * frame.padded_bytes_per_row as usize + px as usize * 4;
+            for (channel, value) in frame.data[i..i + 3].iter_mut().zip(color) {
+                *channel =
+                    ((u32::from(value) * alpha + u32::from(*channel) * (255 - alpha)) / 255) as u8;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn burns_timestamp_into_corner() {
+        assert_eq!(format_timestamp(65.9), "1:05");
+        assert_eq!(format_timestamp(3725.0), "1:02:05");
+
+        let mut frame = RenderedFrame {
+            data: [128, 128, 128, 255].repeat(200 * 100),
+            width: 200,
+            height: 100,
+            padded_bytes_per_row: 200 * 4,
+        };
+        let pixel = |frame: &RenderedFrame, x: usize, y: usize| frame.data[(y * 200 + x) * 4];
+
+        TimestampFilter::default().apply(&mut frame, 0.0);
+
+        // The top left of the first "0", inside the darkened box
+        assert_eq!(pixel(&frame, 6, 89), 255);
+        assert!(pixel(&frame, 5, 88) < 128);
+        assert_eq!(pixel(&frame, 100, 10), 128);
+    }
+}
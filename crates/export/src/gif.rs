@@ -4,16 +4,19 @@ use cap_media::encoders::GifEncoderWrapper;
 use cap_project::XY;
 use cap_rendering::{ProjectUniforms, RenderSegment, RenderedFrame};
 use futures::FutureExt;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use specta::Type;
 use tracing::trace;
 
-use crate::{ExportError, ExporterBase};
+use crate::{apply_filters, resolution::OutputResolution, ExportError, ExporterBase};
 
-#[derive(Deserialize, Clone, Copy, Debug, Type)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Type)]
 pub struct GifExportSettings {
     pub fps: u32,
     pub resolution_base: XY<u32>,
+    /// Exact output size, scaling the composited frame to it. Rendered at `resolution_base` when unset.
+    #[serde(default)]
+    pub output_resolution: Option<OutputResolution>,
 }
 
 impl GifExportSettings {
@@ -28,11 +31,21 @@ impl GifExportSettings {
 
         let fps = self.fps;
 
-        let output_size = ProjectUniforms::get_output_size(
-            &base.render_constants.options,
-            &base.project_config,
-            self.resolution_base,
-        );
+        let resolution_base = match &self.output_resolution {
+            Some(resolution) => {
+                resolution.resolution_base(&base.render_constants.options, &base.project_config)
+            }
+            None => self.resolution_base,
+        };
+
+        let output_size = match &self.output_resolution {
+            Some(resolution) => resolution.size(),
+            None => ProjectUniforms::get_output_size(
+                &base.render_constants.options,
+                &base.project_config,
+                resolution_base,
+            ),
+        };
 
         // Ensure the output path has .gif extension
         let mut gif_output_path = base.output_path.clone();
@@ -50,12 +63,33 @@ impl GifExportSettings {
             GifEncoderWrapper::new(&gif_output_path, output_size.0, output_size.1, fps)
                 .map_err(|e| format!("Failed to create GIF encoder: {}", e))?;
 
+        let control = base.control.clone();
+        let output_resolution = self.output_resolution;
+        let filters = base.filters.clone();
         let encoder_thread = tokio::task::spawn_blocking(move || {
             let mut frame_count = 0;
 
-            while let Some((frame, _frame_number)) = video_rx.blocking_recv() {
+            loop {
+                // Rendering stalls on the full channel while paused, so nothing is lost
+                control.blocking_wait_while_paused();
+
+                if control.is_cancelled() {
+                    drop(gif_encoder);
+                    return Err(ExportError::Cancelled);
+                }
+
+                let Some((frame, frame_number)) = video_rx.blocking_recv() else {
+                    break;
+                };
+
                 (on_progress)(frame_count);
 
+                let mut frame = match &output_resolution {
+                    Some(resolution) => resolution.place_frame(frame),
+                    None => frame,
+                };
+                apply_filters(&filters, &mut frame, frame_number as f64 / fps as f64);
+
                 if let Err(e) =
                     gif_encoder.add_frame(&frame.data, frame.padded_bytes_per_row as usize)
                 {
@@ -93,8 +127,9 @@ impl GifExportSettings {
                 })
                 .collect(),
             fps,
-            self.resolution_base,
+            resolution_base,
             &base.recordings,
+            base.layers,
         )
         .then(|f| async { f.map_err(|v| v.to_string()) });
 
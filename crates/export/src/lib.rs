@@ -1,10 +1,23 @@
+pub mod filters;
 pub mod gif;
+pub mod metadata;
 pub mod mp4;
+pub mod resolution;
 
 use cap_editor::Segment;
-use cap_project::{ProjectConfiguration, RecordingMeta, StudioRecordingMeta, XY};
-use cap_rendering::{ProjectRecordingsMeta, RenderVideoConstants};
-use std::{path::PathBuf, sync::Arc};
+use cap_project::{
+    AspectRatio, ProjectConfiguration, RecordingMeta, StudioRecordingMeta, TimelineConfiguration,
+    TimelineSegment, XY,
+};
+use cap_rendering::{
+    LayerPosition, ProjectRecordingsMeta, RenderLayer, RenderVideoConstants, RenderedFrame,
+};
+use filters::ExportFilter;
+use metadata::ExportMetadata;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use tracing::error;
 
 #[derive(thiserror::Error, Debug)]
@@ -29,6 +42,9 @@ pub enum ExportError {
 
     #[error("Exporting timed out")]
     Timeout(#[from] tokio::time::error::Elapsed),
+
+    #[error("Export cancelled")]
+    Cancelled,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -47,12 +63,118 @@ pub enum ExporterBuildError {
     MediaLoad(String),
     #[error("IO error at path '{0}': {1}")]
     IO(PathBuf, std::io::Error),
+    #[error("{0}")]
+    InvalidTimeRange(String),
+}
+
+/// Fill in a missing `start` or `end` from a timeline `duration` seconds long,
+/// checking the range is in order and within the timeline
+pub fn resolve_time_range(
+    start: Option<f64>,
+    end: Option<f64>,
+    duration: f64,
+) -> Result<(f64, f64), String> {
+    // Leeway for ranges picked from a rounded duration
+    const TOLERANCE: f64 = 0.001;
+
+    let start = start.unwrap_or(0.0);
+    let end = end.unwrap_or(duration);
+
+    if start >= end {
+        return Err(format!(
+            "Export start ({start:.2}s) must be before its end ({end:.2}s)"
+        ));
+    }
+    if start < 0.0 || end > duration + TOLERANCE {
+        return Err(format!(
+            "Export range {start:.2}s to {end:.2}s is outside the {duration:.2}s recording"
+        ));
+    }
+
+    Ok((start, end.min(duration)))
+}
+
+/// Pauses and resumes exports at frame boundaries. Clones share the same state.
+#[derive(Clone)]
+pub struct ExportControl {
+    paused: Arc<tokio::sync::watch::Sender<bool>>,
+    cancelled: Arc<tokio::sync::watch::Sender<bool>>,
+}
+
+impl Default for ExportControl {
+    fn default() -> Self {
+        Self {
+            paused: Arc::new(tokio::sync::watch::Sender::new(false)),
+            cancelled: Arc::new(tokio::sync::watch::Sender::new(false)),
+        }
+    }
+}
+
+impl PartialEq for ExportControl {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.paused, &other.paused) && Arc::ptr_eq(&self.cancelled, &other.cancelled)
+    }
+}
+
+impl ExportControl {
+    /// A control for a single export, which pauses along with this one
+    /// but can be cancelled on its own
+    pub fn for_export(&self) -> Self {
+        Self {
+            paused: self.paused.clone(),
+            cancelled: Arc::new(tokio::sync::watch::Sender::new(false)),
+        }
+    }
+
+    pub fn pause(&self) {
+        self.paused.send_replace(true);
+    }
+
+    pub fn resume(&self) {
+        self.paused.send_replace(false);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        *self.paused.borrow()
+    }
+
+    /// Stops the export at the next frame, even if it's paused
+    pub fn cancel(&self) {
+        self.cancelled.send_replace(true);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        *self.cancelled.borrow()
+    }
+
+    /// Waits until the export isn't paused, holding the render state in the meantime
+    pub async fn wait_while_paused(&self) {
+        let mut paused = self.paused.subscribe();
+        let mut cancelled = self.cancelled.subscribe();
+        tokio::select! {
+            _ = paused.wait_for(|paused| !*paused) => {}
+            _ = cancelled.wait_for(|cancelled| *cancelled) => {}
+        }
+    }
+
+    pub fn blocking_wait_while_paused(&self) {
+        futures::executor::block_on(self.wait_while_paused());
+    }
 }
 
 pub struct ExporterBuilder {
     project_path: PathBuf,
     config: Option<ProjectConfiguration>,
     output_path: Option<PathBuf>,
+    control: ExportControl,
+    metadata: ExportMetadata,
+    lut: Option<PathBuf>,
+    burn_captions: Option<bool>,
+    start: Option<f64>,
+    end: Option<f64>,
+    aspect_ratio: Option<(AspectRatio, f64)>,
+    filters: Vec<Arc<dyn ExportFilter>>,
+    layers: Vec<(LayerPosition, Box<dyn RenderLayer>)>,
 }
 
 impl ExporterBuilder {
@@ -61,15 +183,92 @@ impl ExporterBuilder {
         self
     }
 
+    pub fn with_output_path(mut self, output_path: PathBuf) -> Self {
+        self.output_path = Some(output_path);
+        self
+    }
+
+    pub fn with_control(mut self, control: ExportControl) -> Self {
+        self.control = control;
+        self
+    }
+
+    pub fn with_metadata(mut self, metadata: ExportMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Applies this LUT instead of the project's own
+    pub fn with_lut(mut self, lut: PathBuf) -> Self {
+        self.lut = Some(lut);
+        self
+    }
+
+    /// Whether to render the project's captions into the exported frames,
+    /// regardless of whether they're enabled in the editor
+    pub fn with_burned_captions(mut self, burn_captions: bool) -> Self {
+        self.burn_captions = Some(burn_captions);
+        self
+    }
+
+    /// Only export the part of the timeline between `start` and `end`, in seconds.
+    /// Either can be left out to keep the timeline's own start or end.
+    pub fn with_time_range(mut self, start: Option<f64>, end: Option<f64>) -> Self {
+        self.start = start;
+        self.end = end;
+        self
+    }
+
+    /// Frame the output to `aspect_ratio` instead of the project's own, filling the extra space
+    /// with the project's background. `offset_x` is where the display sits horizontally if
+    /// there's space beside it, from -1 to 1.
+    pub fn with_aspect_ratio(mut self, aspect_ratio: AspectRatio, offset_x: f64) -> Self {
+        self.aspect_ratio = Some((aspect_ratio, offset_x));
+        self
+    }
+
+    /// Run `filter` over every exported frame, after any filters added before it
+    pub fn with_filter(mut self, filter: impl ExportFilter + 'static) -> Self {
+        self.filters.push(Arc::new(filter));
+        self
+    }
+
+    /// Draw `layer` into every frame at `position` as it's rendered. Unlike filters,
+    /// layers are drawn on the GPU and can sit beneath the built-in layers.
+    pub fn with_layer(
+        mut self,
+        position: LayerPosition,
+        layer: impl RenderLayer + 'static,
+    ) -> Self {
+        self.layers.push((position, Box::new(layer)));
+        self
+    }
+
     pub async fn build(self) -> Result<ExporterBase, ExporterBuildError> {
         type Error = ExporterBuildError;
 
-        let project_config = serde_json::from_reader(
+        let mut project_config: ProjectConfiguration = serde_json::from_reader(
             std::fs::File::open(self.project_path.join("project-config.json"))
                 .map_err(|v| Error::ConfigLoad(v.into()))?,
         )
         .map_err(|v| Error::ConfigLoad(v.into()))?;
 
+        if let Some(lut) = self.lut {
+            project_config.lut = Some(lut);
+        }
+
+        if let Some((aspect_ratio, offset_x)) = self.aspect_ratio {
+            project_config.aspect_ratio = Some(aspect_ratio);
+            project_config.display_offset_x = offset_x;
+        }
+
+        // The caption layer only draws enabled captions
+        if let (Some(burn_captions), Some(captions)) =
+            (self.burn_captions, project_config.captions.as_mut())
+        {
+            captions.settings.enabled = burn_captions;
+        }
+
         let recording_meta = RecordingMeta::load_for_project(&self.project_path)
             .map_err(|v| Error::MetaLoad(v.into()))?;
         let studio_meta = recording_meta
@@ -81,6 +280,44 @@ impl ExporterBuilder {
                 .map_err(Error::RecordingsMeta)?,
         );
 
+        if self.start.is_some() || self.end.is_some() {
+            // Projects that were never edited play every segment in full
+            let timeline =
+                project_config
+                    .timeline
+                    .take()
+                    .unwrap_or_else(|| TimelineConfiguration {
+                        segments: recordings
+                            .segments
+                            .iter()
+                            .enumerate()
+                            .map(|(i, segment)| TimelineSegment {
+                                recording_segment: i as u32,
+                                timescale: 1.0,
+                                start: 0.0,
+                                end: segment.duration(),
+                                interpolate: false,
+                            })
+                            .collect(),
+                        zoom_segments: vec![],
+                        scene_segments: None,
+                    });
+
+            let (start, end) = resolve_time_range(self.start, self.end, timeline.duration())
+                .map_err(Error::InvalidTimeRange)?;
+            project_config.timeline = Some(timeline.trimmed(start, end));
+
+            // Captions and lower thirds are timed on the timeline too, so they move with it
+            if let Some(captions) = project_config.captions.as_mut() {
+                captions.trim(start as f32, end as f32);
+            }
+            project_config.lower_thirds = project_config
+                .lower_thirds
+                .iter()
+                .filter_map(|lower_third| lower_third.trimmed(start as f32, end as f32))
+                .collect();
+        }
+
         let render_constants = Arc::new(
             RenderVideoConstants::new(&recordings.segments, &recording_meta, studio_meta)
                 .await
@@ -100,6 +337,14 @@ impl ExporterBuilder {
                 .map_err(|e| ExporterBuildError::IO(parent.to_path_buf(), e))?;
         }
 
+        let metadata = ExportMetadata {
+            title: self
+                .metadata
+                .title
+                .or_else(|| Some(recording_meta.pretty_name.clone())),
+            ..self.metadata
+        };
+
         Ok(ExporterBase {
             output_path,
             studio_meta: studio_meta.clone(),
@@ -109,6 +354,10 @@ impl ExporterBuilder {
             recording_meta,
             project_config,
             project_path: self.project_path,
+            control: self.control,
+            metadata,
+            filters: self.filters,
+            layers: self.layers,
         })
     }
 }
@@ -122,6 +371,10 @@ pub struct ExporterBase {
     render_constants: Arc<RenderVideoConstants>,
     segments: Vec<Segment>,
     output_path: PathBuf,
+    control: ExportControl,
+    metadata: ExportMetadata,
+    filters: Vec<Arc<dyn ExportFilter>>,
+    layers: Vec<(LayerPosition, Box<dyn RenderLayer>)>,
 }
 
 impl ExporterBase {
@@ -136,11 +389,51 @@ impl ExporterBase {
         (fps as f64 * duration).ceil() as u32
     }
 
+    /// Where the export is written. GIFs have their extension replaced with `.gif`.
+    pub fn output_path(&self) -> &Path {
+        &self.output_path
+    }
+
     pub fn builder(project_path: PathBuf) -> ExporterBuilder {
         ExporterBuilder {
             project_path,
             config: None,
             output_path: None,
+            control: ExportControl::default(),
+            metadata: ExportMetadata::default(),
+            lut: None,
+            burn_captions: None,
+            start: None,
+            end: None,
+            aspect_ratio: None,
+            filters: vec![],
+            layers: vec![],
         }
     }
 }
+
+/// Run each filter over the frame, in the order they were added
+fn apply_filters(filters: &[Arc<dyn ExportFilter>], frame: &mut RenderedFrame, time: f64) {
+    for filter in filters {
+        filter.apply(frame, time);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn resolves_time_range() {
+        assert_eq!(resolve_time_range(None, None, 10.0), Ok((0.0, 10.0)));
+        assert_eq!(resolve_time_range(Some(2.0), None, 10.0), Ok((2.0, 10.0)));
+        assert_eq!(
+            resolve_time_range(None, Some(10.0005), 10.0),
+            Ok((0.0, 10.0))
+        );
+
+        assert!(resolve_time_range(Some(5.0), Some(5.0), 10.0).is_err());
+        assert!(resolve_time_range(Some(-1.0), Some(5.0), 10.0).is_err());
+        assert!(resolve_time_range(None, Some(12.0), 10.0).is_err());
+    }
+}
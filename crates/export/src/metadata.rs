@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// Descriptive fields written into the exported file's container metadata
+#[derive(Serialize, Deserialize, Type, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportMetadata {
+    /// Defaults to the recording's name
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub comment: Option<String>,
+    /// Written as-is, ISO 8601 (eg. `2025-01-31`) is the most widely understood
+    #[serde(default)]
+    pub date: Option<String>,
+}
+
+impl ExportMetadata {
+    /// FFmpeg metadata keys and their values, skipping empty fields
+    pub fn entries(&self) -> Vec<(&'static str, String)> {
+        let fields = [
+            ("title", &self.title),
+            // The MP4 muxer writes `artist` to iTunes-style tags and `author` to QuickTime ones
+            ("artist", &self.author),
+            ("author", &self.author),
+            ("comment", &self.comment),
+            ("date", &self.date),
+        ];
+
+        fields
+            .into_iter()
+            .filter_map(|(key, value)| {
+                let value = value.as_deref()?.trim();
+                (!value.is_empty()).then(|| (key, value.to_string()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn skips_empty_fields() {
+        let metadata = ExportMetadata {
+            title: Some("Demo".to_string()),
+            author: Some("  ".to_string()),
+            comment: None,
+            date: Some("2025-01-31".to_string()),
+        };
+
+        assert_eq!(
+            metadata.entries(),
+            vec![
+                ("title", "Demo".to_string()),
+                ("date", "2025-01-31".to_string())
+            ]
+        );
+    }
+}
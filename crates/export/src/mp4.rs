@@ -1,21 +1,21 @@
 use std::{path::PathBuf, time::Duration};
 
-use crate::ExporterBase;
+use crate::{apply_filters, resolution::OutputResolution, ExportError, ExporterBase};
 use cap_editor::get_audio_segments;
 use cap_media::{
     data::{RawVideoFormat, VideoInfo},
-    encoders::{AACEncoder, AudioEncoder, H264Encoder, MP4Input},
+    encoders::{AACEncoder, AudioEncoder, H264Encoder, MP4Input, VideoEncoder},
     feeds::AudioRenderer,
 };
 use cap_project::XY;
 use cap_rendering::{ProjectUniforms, RenderSegment, RenderedFrame};
 use futures::FutureExt;
 use image::ImageBuffer;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use specta::Type;
 use tracing::{info, trace, warn};
 
-#[derive(Deserialize, Type, Clone, Copy, Debug)]
+#[derive(Serialize, Deserialize, Type, Clone, Copy, Debug)]
 pub enum ExportCompression {
     Minimal,
     Social,
@@ -34,11 +34,63 @@ impl ExportCompression {
     }
 }
 
-#[derive(Deserialize, Type, Clone, Copy, Debug)]
+/// Constant quality encoding, as an alternative to the bitrate `ExportCompression` picks
+#[derive(Serialize, Deserialize, Type, Clone, Copy, Debug, PartialEq)]
+pub enum ExportQuality {
+    Low,
+    Medium,
+    High,
+    /// x264 CRF from 0 (lossless) to 51, where lower is better
+    Custom {
+        crf: u8,
+    },
+}
+
+impl ExportQuality {
+    pub const MAX_CRF: u8 = 51;
+
+    pub fn crf(&self) -> u8 {
+        match self {
+            Self::Low => 28,
+            Self::Medium => 23,
+            Self::High => 18,
+            Self::Custom { crf } => *crf,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.crf() > Self::MAX_CRF {
+            return Err(format!(
+                "CRF must be between 0 and {}, got {}",
+                Self::MAX_CRF,
+                self.crf()
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Roughly how much bigger the output is than at `Medium`.
+    /// Every 6 steps of CRF about halves or doubles the bitrate.
+    pub fn size_factor(&self) -> f64 {
+        2f64.powf((Self::Medium.crf() as f64 - self.crf() as f64) / 6.0)
+    }
+}
+
+#[derive(Serialize, Deserialize, Type, Clone, Copy, Debug)]
 pub struct Mp4ExportSettings {
     pub fps: u32,
     pub resolution_base: XY<u32>,
     pub compression: ExportCompression,
+    /// Exact output size, scaling the composited frame to it. Rendered at `resolution_base` when unset.
+    #[serde(default)]
+    pub output_resolution: Option<OutputResolution>,
+    /// Encodes at this quality instead of `compression`'s bitrate when set
+    #[serde(default)]
+    pub quality: Option<ExportQuality>,
+    /// Falls back to x264 when this encoder isn't available
+    #[serde(default)]
+    pub encoder: VideoEncoder,
 }
 
 impl Mp4ExportSettings {
@@ -47,6 +99,10 @@ impl Mp4ExportSettings {
         base: ExporterBase,
         mut on_progress: impl FnMut(u32) + Send + 'static,
     ) -> Result<PathBuf, String> {
+        if let Some(quality) = &self.quality {
+            quality.validate()?;
+        }
+
         let output_path = base.output_path.clone();
         let meta = &base.studio_meta;
 
@@ -58,11 +114,21 @@ impl Mp4ExportSettings {
 
         let fps = self.fps;
 
-        let output_size = ProjectUniforms::get_output_size(
-            &base.render_constants.options,
-            &base.project_config,
-            self.resolution_base,
-        );
+        let resolution_base = match &self.output_resolution {
+            Some(resolution) => {
+                resolution.resolution_base(&base.render_constants.options, &base.project_config)
+            }
+            None => self.resolution_base,
+        };
+
+        let output_size = match &self.output_resolution {
+            Some(resolution) => resolution.size(),
+            None => ProjectUniforms::get_output_size(
+                &base.render_constants.options,
+                &base.project_config,
+                resolution_base,
+            ),
+        };
 
         let mut video_info =
             VideoInfo::from_raw(RawVideoFormat::Rgba, output_size.0, output_size.1, fps);
@@ -79,13 +145,19 @@ impl Mp4ExportSettings {
         let encoder_thread = tokio::task::spawn_blocking(move || {
             trace!("Creating MP4File encoder");
 
-            let mut encoder = cap_media::encoders::MP4File::init(
+            let mut encoder = cap_media::encoders::MP4File::init_with_metadata(
                 "output",
                 base.output_path.clone(),
+                base.metadata.entries(),
                 |o| {
-                    H264Encoder::builder("output_video", video_info)
+                    let builder = H264Encoder::builder("output_video", video_info)
                         .with_bpp(self.compression.bits_per_pixel())
-                        .build(o)
+                        .with_encoder(self.encoder);
+
+                    match self.quality {
+                        Some(quality) => builder.with_crf(quality.crf()).build(o),
+                        None => builder.build(o),
+                    }
                 },
                 |o| {
                     has_audio.then(|| {
@@ -118,6 +190,9 @@ impl Mp4ExportSettings {
         let render_task = tokio::spawn({
             let project = base.project_config.clone();
             let project_path = base.project_path.clone();
+            let control = base.control.clone();
+            let output_resolution = self.output_resolution;
+            let filters = base.filters.clone();
             async move {
                 let mut frame_count = 0;
                 let mut first_frame = None;
@@ -126,6 +201,14 @@ impl Mp4ExportSettings {
                     (f64::from(AudioRenderer::SAMPLE_RATE) / f64::from(fps)).ceil() as usize;
 
                 loop {
+                    // Rendering stalls on the full channel while paused, so nothing is lost
+                    control.wait_while_paused().await;
+
+                    // Dropping the frame sender lets the encoder finish the file cleanly
+                    if control.is_cancelled() {
+                        return Err(ExportError::Cancelled.to_string());
+                    }
+
                     let (frame, frame_number) =
                         match tokio::time::timeout(Duration::from_secs(6), video_rx.recv()).await {
                             Err(_) => {
@@ -140,6 +223,12 @@ impl Mp4ExportSettings {
 
                     (on_progress)(frame_count);
 
+                    let mut frame = match &output_resolution {
+                        Some(resolution) => resolution.place_frame(frame),
+                        None => frame,
+                    };
+                    apply_filters(&filters, &mut frame, frame_number as f64 / fps as f64);
+
                     if frame_count == 0 {
                         first_frame = Some(frame.clone());
                         if let Some(audio) = &mut audio_renderer {
@@ -223,13 +312,34 @@ impl Mp4ExportSettings {
                 })
                 .collect(),
             fps,
-            self.resolution_base,
+            resolution_base,
             &base.recordings,
+            base.layers,
         )
         .then(|v| async { v.map_err(|e| e.to_string()) });
 
-        tokio::try_join!(encoder_thread, render_video_task, render_task)?;
+        // Waits for every task even if one fails, so the encoder is done with the file on return
+        let (encoded, rendered, render_result) =
+            tokio::join!(encoder_thread, render_video_task, render_task);
+        encoded?;
+        rendered?;
+        render_result?;
 
         Ok(output_path)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn validates_custom_crf() {
+        assert!(ExportQuality::Custom { crf: 0 }.validate().is_ok());
+        assert!(ExportQuality::Custom { crf: 51 }.validate().is_ok());
+        assert!(ExportQuality::Custom { crf: 52 }.validate().is_err());
+
+        assert_eq!(ExportQuality::Medium.size_factor(), 1.0);
+        assert_eq!(ExportQuality::Custom { crf: 17 }.size_factor(), 2.0);
+    }
+}
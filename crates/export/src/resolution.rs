@@ -0,0 +1,147 @@
+use cap_project::{ProjectConfiguration, XY};
+use cap_rendering::{ProjectUniforms, RenderOptions, RenderedFrame};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+#[derive(Serialize, Deserialize, Type, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ResolutionFit {
+    /// Show the whole frame, letterboxing any leftover space
+    #[default]
+    Fit,
+    /// Cover the whole output, cropping whatever overflows
+    Fill,
+}
+
+/// An exact output size, independent of the size of the recording
+#[derive(Serialize, Deserialize, Type, Clone, Copy, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputResolution {
+    pub width: u32,
+    pub height: u32,
+    #[serde(default)]
+    pub fit: ResolutionFit,
+}
+
+impl OutputResolution {
+    /// Output dimensions, rounded up to even numbers as the encoders require
+    pub fn size(&self) -> (u32, u32) {
+        ((self.width.max(2) + 1) & !1, (self.height.max(2) + 1) & !1)
+    }
+
+    /// The resolution base to render at so the composited frame fits within or covers the output
+    pub fn resolution_base(
+        &self,
+        options: &RenderOptions,
+        project: &ProjectConfiguration,
+    ) -> XY<u32> {
+        let (width, height) = self.size();
+
+        match self.fit {
+            ResolutionFit::Fit => XY::new(width, height),
+            ResolutionFit::Fill => {
+                let (fit_width, fit_height) =
+                    ProjectUniforms::get_output_size(options, project, XY::new(width, height));
+                let aspect = fit_width as f64 / fit_height.max(1) as f64;
+
+                if aspect > width as f64 / height as f64 {
+                    XY::new((height as f64 * aspect).ceil() as u32 + 2, height + 2)
+                } else {
+                    XY::new(width + 2, (width as f64 / aspect).ceil() as u32 + 2)
+                }
+            }
+        }
+    }
+
+    /// Centers a rendered frame on the output, cropping overflow and filling gaps with black
+    pub fn place_frame(&self, frame: RenderedFrame) -> RenderedFrame {
+        let (width, height) = self.size();
+
+        if frame.width == width && frame.height == height {
+            return frame;
+        }
+
+        let row_bytes = width as usize * 4;
+        let mut data = [0, 0, 0, 255].repeat(width as usize * height as usize);
+
+        let copy_width = frame.width.min(width);
+        let copy_height = frame.height.min(height);
+        let (src_x, src_y) = (
+            (frame.width - copy_width) / 2,
+            (frame.height - copy_height) / 2,
+        );
+        let (dst_x, dst_y) = ((width - copy_width) / 2, (height - copy_height) / 2);
+        let copy_bytes = copy_width as usize * 4;
+
+        for row in 0..copy_height {
+            let src =
+                (src_y + row) as usize * frame.padded_bytes_per_row as usize + src_x as usize * 4;
+            let dst = (dst_y + row) as usize * row_bytes + dst_x as usize * 4;
+
+            data[dst..dst + copy_bytes].copy_from_slice(&frame.data[src..src + copy_bytes]);
+        }
+
+        RenderedFrame {
+            data,
+            width,
+            height,
+            padded_bytes_per_row: row_bytes as u32,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn frame(width: u32, height: u32, value: u8) -> RenderedFrame {
+        RenderedFrame {
+            data: vec![value; (width * height * 4) as usize],
+            width,
+            height,
+            padded_bytes_per_row: width * 4,
+        }
+    }
+
+    #[test]
+    fn letterboxes_smaller_frames() {
+        let resolution = OutputResolution {
+            width: 4,
+            height: 4,
+            fit: ResolutionFit::Fit,
+        };
+
+        let placed = resolution.place_frame(frame(4, 2, 200));
+
+        assert_eq!(placed.width, 4);
+        assert_eq!(placed.height, 4);
+        // Top row is a black bar, the middle rows hold the frame
+        assert_eq!(&placed.data[0..4], &[0, 0, 0, 255]);
+        assert_eq!(&placed.data[16..20], &[200, 200, 200, 200]);
+    }
+
+    #[test]
+    fn crops_larger_frames() {
+        let resolution = OutputResolution {
+            width: 2,
+            height: 2,
+            fit: ResolutionFit::Fill,
+        };
+
+        let placed = resolution.place_frame(frame(6, 2, 100));
+
+        assert_eq!(placed.data.len(), 2 * 2 * 4);
+        assert!(placed.data.iter().all(|v| *v == 100));
+    }
+
+    #[test]
+    fn rounds_size_to_even() {
+        let resolution = OutputResolution {
+            width: 1919,
+            height: 1080,
+            fit: ResolutionFit::Fit,
+        };
+
+        assert_eq!(resolution.size(), (1920, 1080));
+    }
+}
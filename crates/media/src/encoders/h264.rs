@@ -6,14 +6,92 @@ use ffmpeg::{
     codec::{codec::Codec, context, encoder},
     format::{self},
     threading::Config,
-    Dictionary,
+    Dictionary, Rational,
 };
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// The H.264 encoders that can be picked between, by the ffmpeg codec backing them
+#[derive(Serialize, Deserialize, Type, Clone, Copy, Debug, Default, PartialEq)]
+pub enum VideoEncoder {
+    #[default]
+    X264,
+    VideoToolbox,
+    Nvenc,
+    QuickSync,
+}
+
+impl VideoEncoder {
+    fn codec_name(&self) -> &'static str {
+        match self {
+            Self::X264 => "libx264",
+            Self::VideoToolbox => "h264_videotoolbox",
+            Self::Nvenc => "h264_nvenc",
+            Self::QuickSync => "h264_qsv",
+        }
+    }
+
+    /// The encoder option for constant quality encoding, if it has one on the CRF scale
+    fn quality_option(&self) -> Option<&'static str> {
+        match self {
+            Self::X264 => Some("crf"),
+            Self::Nvenc => Some("cq"),
+            Self::QuickSync => Some("global_quality"),
+            Self::VideoToolbox => None,
+        }
+    }
+
+    /// Whether the encoder can actually be used. ffmpeg builds often include hardware encoders
+    /// for hardware or drivers that aren't present, so this opens a short lived test session.
+    pub fn is_available(&self) -> bool {
+        let Some(codec) = encoder::find_by_name(self.codec_name()) else {
+            return false;
+        };
+        let Ok(mut encoder) = context::Context::new_with_codec(codec).encoder().video() else {
+            return false;
+        };
+
+        // The first listed format can be a hardware surface format that won't open without
+        // a hardware frames context, so prefer the YUV420P frames are converted to for encoding
+        let formats = codec
+            .video()
+            .ok()
+            .and_then(|video| video.formats())
+            .map(|formats| formats.collect::<Vec<_>>())
+            .unwrap_or_default();
+        let format = if formats.is_empty() || formats.contains(&format::Pixel::YUV420P) {
+            format::Pixel::YUV420P
+        } else {
+            formats[0]
+        };
+
+        encoder.set_width(256);
+        encoder.set_height(256);
+        encoder.set_format(format);
+        encoder.set_time_base(Rational::new(1, 30));
+        encoder.set_frame_rate(Some(Rational::new(30, 1)));
+
+        encoder.open().is_ok()
+    }
+
+    /// This encoder if it's available, otherwise x264
+    pub fn resolve(self) -> Self {
+        if self == Self::X264 || self.is_available() {
+            return self;
+        }
+
+        tracing::warn!("{:?} encoder isn't available, falling back to x264", self);
+        Self::X264
+    }
+}
 
 pub struct H264EncoderBuilder {
     name: &'static str,
     bpp: f32,
+    crf: Option<u8>,
     input_config: VideoInfo,
     preset: H264Preset,
+    video_encoder: VideoEncoder,
 }
 
 #[derive(Clone, Copy)]
@@ -31,7 +109,9 @@ impl H264EncoderBuilder {
             name,
             input_config,
             bpp: Self::QUALITY_BPP,
+            crf: None,
             preset: H264Preset::Ultrafast,
+            video_encoder: VideoEncoder::X264,
         }
     }
 
@@ -45,9 +125,24 @@ impl H264EncoderBuilder {
         self
     }
 
+    /// Encode at a constant quality (0-51, lower is better) instead of the bitrate from `bpp`.
+    /// VideoToolbox has no equivalent, so it keeps using the bitrate.
+    pub fn with_crf(mut self, crf: u8) -> Self {
+        self.crf = Some(crf);
+        self
+    }
+
+    /// Encode with `video_encoder` when it's available, falling back to x264
+    pub fn with_encoder(mut self, video_encoder: VideoEncoder) -> Self {
+        self.video_encoder = video_encoder;
+        self
+    }
+
     pub fn build(self, output: &mut format::context::Output) -> Result<H264Encoder, MediaError> {
         let input_config = &self.input_config;
-        let (codec, encoder_options) = get_codec_and_options(&input_config, self.preset)?;
+        let video_encoder = self.video_encoder.resolve();
+        let (codec, mut encoder_options) =
+            get_codec_and_options(&input_config, self.preset, video_encoder)?;
 
         let (format, converter) = if !codec
             .video()
@@ -95,16 +190,20 @@ impl H264EncoderBuilder {
         encoder.set_time_base(input_config.frame_rate.invert());
         encoder.set_frame_rate(Some(input_config.frame_rate));
 
-        // let target_bitrate = compression.bitrate();
-        let bitrate = get_bitrate(
-            input_config.width,
-            input_config.height,
-            input_config.frame_rate.0 as f32 / input_config.frame_rate.1 as f32,
-            self.bpp,
-        );
+        if let Some((option, crf)) = video_encoder.quality_option().zip(self.crf) {
+            encoder_options.set(option, &crf.to_string());
+        } else {
+            // let target_bitrate = compression.bitrate();
+            let bitrate = get_bitrate(
+                input_config.width,
+                input_config.height,
+                input_config.frame_rate.0 as f32 / input_config.frame_rate.1 as f32,
+                self.bpp,
+            );
 
-        encoder.set_bit_rate(bitrate);
-        encoder.set_max_bit_rate(bitrate);
+            encoder.set_bit_rate(bitrate);
+            encoder.set_max_bit_rate(bitrate);
+        }
 
         let video_encoder = encoder.open_with(encoder_options)?;
 
@@ -195,27 +294,16 @@ impl H264Encoder {
 fn get_codec_and_options(
     config: &VideoInfo,
     preset: H264Preset,
+    video_encoder: VideoEncoder,
 ) -> Result<(Codec, Dictionary), MediaError> {
-    let encoder_name = {
-        if cfg!(target_os = "macos") {
-            "libx264"
-            // looks terrible rn :(
-            // "h264_videotoolbox"
-        } else {
-            "libx264"
-        }
-    };
-
-    if let Some(codec) = encoder::find_by_name(encoder_name) {
+    if let Some(codec) = encoder::find_by_name(video_encoder.codec_name()) {
         let mut options = Dictionary::new();
 
-        if encoder_name == "h264_videotoolbox" {
-            options.set("realtime", "true");
-        } else {
-            let keyframe_interval_secs = 2;
-            let keyframe_interval = keyframe_interval_secs * config.frame_rate.numerator();
-            let keyframe_interval_str = keyframe_interval.to_string();
+        let keyframe_interval_secs = 2;
+        let keyframe_interval = keyframe_interval_secs * config.frame_rate.numerator();
+        let keyframe_interval_str = keyframe_interval.to_string();
 
+        if video_encoder == VideoEncoder::X264 {
             options.set(
                 "preset",
                 match preset {
@@ -228,9 +316,9 @@ fn get_codec_and_options(
                 options.set("tune", "zerolatency");
             }
             options.set("vsync", "1");
-            options.set("g", &keyframe_interval_str);
             options.set("keyint_min", &keyframe_interval_str);
         }
+        options.set("g", &keyframe_interval_str);
 
         return Ok((codec, options));
     }
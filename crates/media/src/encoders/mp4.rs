@@ -41,8 +41,21 @@ impl From<InitError> for MediaError {
 
 impl MP4File {
     pub fn init(
+        tag: &'static str,
+        output: PathBuf,
+        video: impl FnOnce(&mut format::context::Output) -> Result<H264Encoder, MediaError>,
+        audio: impl FnOnce(
+            &mut format::context::Output,
+        ) -> Option<Result<Box<dyn AudioEncoder + Send>, MediaError>>,
+    ) -> Result<Self, InitError> {
+        Self::init_with_metadata(tag, output, vec![], video, audio)
+    }
+
+    /// Like `init`, additionally writing `metadata` key/value pairs into the container
+    pub fn init_with_metadata(
         tag: &'static str,
         mut output: PathBuf,
+        metadata: Vec<(&'static str, String)>,
         video: impl FnOnce(&mut format::context::Output) -> Result<H264Encoder, MediaError>,
         audio: impl FnOnce(
             &mut format::context::Output,
@@ -65,6 +78,14 @@ impl MP4File {
 
         info!("Prepared encoders for mp4 file");
 
+        if !metadata.is_empty() {
+            let mut dictionary = ffmpeg::Dictionary::new();
+            for (key, value) in &metadata {
+                dictionary.set(key, value);
+            }
+            output.set_metadata(dictionary);
+        }
+
         // make sure this happens after adding all encoders!
         output.write_header().map_err(InitError::Ffmpeg)?;
 
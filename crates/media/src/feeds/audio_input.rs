@@ -5,6 +5,7 @@ use flume::{Receiver, Sender, TrySendError};
 use indexmap::IndexMap;
 use tracing::{debug, error, info, trace, warn};
 
+use super::NoiseGate;
 use crate::{
     data::{ffmpeg_sample_format_for, AudioInfo},
     MediaError,
@@ -20,6 +21,12 @@ pub struct AudioInputSamples {
 pub enum AudioInputControl {
     Switch(String, Sender<Result<SupportedStreamConfig, MediaError>>),
     AttachSender(AudioInputSamplesSender),
+    /// Linear multiplier applied to samples before they're sent
+    SetGain(f32),
+    /// Threshold in decibels below which samples are silenced, or `None` to pass them through
+    SetNoiseGate(Option<f32>),
+    /// Replace samples with silence while muted, so their timing is kept
+    SetMuted(bool),
     Shutdown,
 }
 
@@ -45,6 +52,9 @@ pub type AudioInputDeviceMap = IndexMap<String, (Device, SupportedStreamConfig)>
 
 pub const MAX_AUDIO_CHANNELS: u16 = 2;
 
+/// Largest boost or cut `set_gain` applies, in decibels
+pub const MAX_GAIN_DB: f32 = 24.0;
+
 #[derive(Clone)]
 pub struct AudioInputFeed {
     pub control_tx: Sender<AudioInputControl>,
@@ -192,6 +202,50 @@ impl AudioInputFeed {
         Ok(())
     }
 
+    /// Boost or cut the input by `gain_db` decibels, clamped to `MAX_GAIN_DB` either way.
+    /// Applies to every sender, so recordings and level meters see the same samples.
+    pub async fn set_gain(&self, gain_db: f32) -> Result<(), MediaError> {
+        let gain = 10f32.powf(gain_db.clamp(-MAX_GAIN_DB, MAX_GAIN_DB) / 20.0);
+
+        self.control_tx
+            .send_async(AudioInputControl::SetGain(gain))
+            .await
+            .map_err(|error| {
+                eprintln!("Error while setting audio input gain: {error}");
+                MediaError::TaskLaunch("Failed to set audio input gain".into())
+            })?;
+
+        Ok(())
+    }
+
+    /// Silence the input while it's below `threshold_db`, or stop gating it with `None`.
+    /// Like gain, this applies to every sender.
+    pub async fn set_noise_gate(&self, threshold_db: Option<f32>) -> Result<(), MediaError> {
+        self.control_tx
+            .send_async(AudioInputControl::SetNoiseGate(threshold_db))
+            .await
+            .map_err(|error| {
+                eprintln!("Error while setting audio input noise gate: {error}");
+                MediaError::TaskLaunch("Failed to set audio input noise gate".into())
+            })?;
+
+        Ok(())
+    }
+
+    /// Send silence instead of the input while `muted`. Samples keep flowing,
+    /// so recordings stay in sync and level meters drop to silence.
+    pub async fn set_muted(&self, muted: bool) -> Result<(), MediaError> {
+        self.control_tx
+            .send_async(AudioInputControl::SetMuted(muted))
+            .await
+            .map_err(|error| {
+                eprintln!("Error while muting audio input: {error}");
+                MediaError::TaskLaunch("Failed to mute audio input".into())
+            })?;
+
+        Ok(())
+    }
+
     pub fn audio_info(&self) -> AudioInfo {
         self.audio_info
     }
@@ -214,12 +268,23 @@ fn start_capturing(
         config
     );
     let mut senders: Vec<AudioInputSamplesSender> = vec![];
+    let mut gain = 1.0;
+    let mut noise_gate_threshold = None;
+    let mut muted = false;
 
     loop {
         let (tx, rx) = flume::bounded(4);
         debug!("Building input stream with config: {:?}", config);
 
         let stream_config: StreamConfig = config.clone().into();
+        let new_noise_gate = |threshold_db| {
+            NoiseGate::new(
+                threshold_db,
+                stream_config.sample_rate.0,
+                stream_config.channels.min(MAX_AUDIO_CHANNELS),
+            )
+        };
+        let mut noise_gate = noise_gate_threshold.map(new_noise_gate);
         let stream = match device.build_input_stream_raw(
             &stream_config,
             config.sample_format(),
@@ -297,6 +362,19 @@ fn start_capturing(
                     senders.push(sender);
                     info!("New audio sender attached");
                 }
+                Ok(AudioInputControl::SetGain(new_gain)) => {
+                    debug!("Setting audio input gain to {}", new_gain);
+                    gain = new_gain;
+                }
+                Ok(AudioInputControl::SetNoiseGate(threshold_db)) => {
+                    debug!("Setting audio input noise gate to {:?}", threshold_db);
+                    noise_gate_threshold = threshold_db;
+                    noise_gate = threshold_db.map(new_noise_gate);
+                }
+                Ok(AudioInputControl::SetMuted(new_muted)) => {
+                    debug!("Setting audio input muted to {}", new_muted);
+                    muted = new_muted;
+                }
                 Err(flume::TryRecvError::Disconnected) => {
                     warn!("Control receiver is unreachable! Shutting down audio capture");
                     return;
@@ -327,6 +405,19 @@ fn start_capturing(
                         data.data = new_data;
                     }
 
+                    if muted {
+                        apply_gain(&mut data.data, data.format, 0.0);
+                    } else {
+                        if gain != 1.0 {
+                            apply_gain(&mut data.data, data.format, gain);
+                        }
+
+                        // Gated after the gain, so the threshold applies to what's recorded
+                        if let Some(noise_gate) = &mut noise_gate {
+                            noise_gate.process(&mut data.data, data.format);
+                        }
+                    }
+
                     for (i, sender) in senders.iter().enumerate() {
                         if let Err(TrySendError::Disconnected(_)) = sender.try_send(data.clone()) {
                             warn!("Audio sender {} disconnected, will be removed", i);
@@ -350,3 +441,70 @@ fn start_capturing(
         }
     }
 }
+
+/// Multiply every sample by `gain`, saturating at the format's limits so loud input
+/// clips cleanly instead of wrapping around
+fn apply_gain(data: &mut [u8], format: SampleFormat, gain: f32) {
+    let gain = gain as f64;
+
+    macro_rules! scale {
+        ($ty:ty, |$sample:ident| $scaled:expr) => {
+            for chunk in data.chunks_exact_mut(std::mem::size_of::<$ty>()) {
+                let $sample = <$ty>::from_ne_bytes(chunk.try_into().unwrap()) as f64;
+                // Float to int casts saturate
+                chunk.copy_from_slice(&(($scaled) as $ty).to_ne_bytes());
+            }
+        };
+    }
+
+    // Unsigned formats are centered on their midpoint
+    match format {
+        SampleFormat::I8 => scale!(i8, |s| s * gain),
+        SampleFormat::I16 => scale!(i16, |s| s * gain),
+        SampleFormat::I32 => scale!(i32, |s| s * gain),
+        SampleFormat::I64 => scale!(i64, |s| s * gain),
+        SampleFormat::U8 => scale!(u8, |s| (s - 128.0) * gain + 128.0),
+        SampleFormat::U16 => scale!(u16, |s| (s - 32768.0) * gain + 32768.0),
+        SampleFormat::U32 => scale!(u32, |s| (s - 2147483648.0) * gain + 2147483648.0),
+        SampleFormat::U64 => {
+            scale!(u64, |s| (s - 9223372036854775808.0) * gain
+                + 9223372036854775808.0)
+        }
+        SampleFormat::F32 => scale!(f32, |s| (s * gain).clamp(-1.0, 1.0)),
+        SampleFormat::F64 => scale!(f64, |s| (s * gain).clamp(-1.0, 1.0)),
+        format => warn!("Can't apply gain to {:?} samples", format),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn gain_saturates_instead_of_wrapping() {
+        let mut data = [1000i16, -1000, 20000, -20000]
+            .iter()
+            .flat_map(|s| s.to_ne_bytes())
+            .collect::<Vec<_>>();
+        apply_gain(&mut data, SampleFormat::I16, 2.0);
+
+        let samples = data
+            .chunks_exact(2)
+            .map(|c| i16::from_ne_bytes([c[0], c[1]]))
+            .collect::<Vec<_>>();
+        assert_eq!(samples, [2000, -2000, i16::MAX, i16::MIN]);
+
+        let mut data = [0.25f32, 0.75]
+            .iter()
+            .flat_map(|s| s.to_ne_bytes())
+            .collect::<Vec<_>>();
+        apply_gain(&mut data, SampleFormat::F32, 2.0);
+        assert_eq!(f32::from_ne_bytes(data[..4].try_into().unwrap()), 0.5);
+        assert_eq!(f32::from_ne_bytes(data[4..].try_into().unwrap()), 1.0);
+
+        // Unsigned silence stays silent
+        let mut data = vec![128u8, 138];
+        apply_gain(&mut data, SampleFormat::U8, 0.5);
+        assert_eq!(data, [128, 133]);
+    }
+}
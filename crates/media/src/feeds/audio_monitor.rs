@@ -0,0 +1,170 @@
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{SampleFormat, SupportedStreamConfig};
+use flume::Receiver;
+use ringbuf::{
+    traits::{Consumer, Producer, Split},
+    HeapRb,
+};
+use tracing::{error, info};
+
+use super::{AudioInputFeed, AudioInputSamples};
+use crate::{
+    data::{AudioInfo, FFAudio, FromSampleBytes},
+    MediaError,
+};
+
+/// Most audio the monitor buffers, in seconds. Anything more is dropped rather than
+/// played late, so the monitor doesn't drift behind the mic.
+const MAX_LATENCY_SECS: f64 = 0.1;
+
+/// Plays a mic feed on the default output device, so you can hear yourself while recording.
+/// It's played from this process, which screen capture leaves out of system audio where
+/// the platform allows it. Dropping the monitor tears the output stream down.
+pub struct AudioMonitor {
+    _stop_tx: flume::Sender<()>,
+}
+
+impl AudioMonitor {
+    pub async fn start(feed: &AudioInputFeed) -> Result<Self, MediaError> {
+        let input_info = feed.audio_info();
+        let (samples_tx, samples_rx) = flume::bounded(8);
+        let (stop_tx, stop_rx) = flume::bounded::<()>(1);
+        let (ready_tx, ready_rx) = flume::bounded(1);
+
+        // cpal streams can't be moved between threads, so the stream lives on this one
+        std::thread::spawn(move || {
+            let host = cpal::default_host();
+            let result = host
+                .default_output_device()
+                .ok_or_else(|| MediaError::Any("No default output device found".into()))
+                .and_then(|device| {
+                    let config = device.default_output_config().map_err(|e| {
+                        MediaError::TaskLaunch(format!("Failed to get output config: {e}"))
+                    })?;
+
+                    match config.sample_format() {
+                        SampleFormat::I16 => run::<i16>(device, config, input_info, samples_rx),
+                        SampleFormat::I32 => run::<i32>(device, config, input_info, samples_rx),
+                        SampleFormat::F32 => run::<f32>(device, config, input_info, samples_rx),
+                        SampleFormat::I64 => run::<i64>(device, config, input_info, samples_rx),
+                        SampleFormat::U8 => run::<u8>(device, config, input_info, samples_rx),
+                        SampleFormat::F64 => run::<f64>(device, config, input_info, samples_rx),
+                        format => Err(MediaError::TaskLaunch(format!(
+                            "Unsupported output sample format {format:?}"
+                        ))),
+                    }
+                });
+
+            match result {
+                Ok((stream, monitor)) => {
+                    ready_tx.send(Ok(())).ok();
+                    monitor(stop_rx);
+                    drop(stream);
+                    info!("Audio monitor stopped");
+                }
+                Err(e) => {
+                    ready_tx.send(Err(e)).ok();
+                }
+            }
+        });
+
+        ready_rx
+            .recv_async()
+            .await
+            .map_err(|_| MediaError::TaskLaunch("Audio monitor thread exited".into()))??;
+
+        feed.add_sender(samples_tx).await?;
+        info!("Audio monitor started");
+
+        Ok(Self { _stop_tx: stop_tx })
+    }
+}
+
+/// Resamples mic samples into the output stream until the monitor or the feed goes away
+type Monitor = Box<dyn FnOnce(Receiver<()>)>;
+
+/// Starts an output stream on `device`, returning it along with what feeds it
+fn run<T: FromSampleBytes>(
+    device: cpal::Device,
+    config: SupportedStreamConfig,
+    input_info: AudioInfo,
+    samples_rx: Receiver<AudioInputSamples>,
+) -> Result<(cpal::Stream, Monitor), MediaError> {
+    let mut output_info = AudioInfo::from_stream_config(&config);
+    output_info.sample_format = output_info.sample_format.packed();
+
+    let mut resampler = ffmpeg::software::resampler(
+        (
+            input_info.sample_format,
+            input_info.channel_layout(),
+            input_info.sample_rate,
+        ),
+        (
+            output_info.sample_format,
+            output_info.channel_layout(),
+            output_info.sample_rate,
+        ),
+    )?;
+
+    let capacity =
+        (output_info.sample_rate as f64 * MAX_LATENCY_SECS) as usize * output_info.channels;
+    let (mut producer, mut consumer) = HeapRb::<T>::new(capacity).split();
+
+    // Output channels past the ones the feed provides are left silent
+    let output_channels = config.channels() as usize;
+
+    let stream = device
+        .build_output_stream(
+            &config.config(),
+            move |buffer: &mut [T], _| {
+                for frame in buffer.chunks_mut(output_channels) {
+                    for (channel, sample) in frame.iter_mut().enumerate() {
+                        *sample = if channel < output_info.channels {
+                            consumer.try_pop().unwrap_or(T::EQUILIBRIUM)
+                        } else {
+                            T::EQUILIBRIUM
+                        };
+                    }
+                }
+            },
+            |e| error!("Error in audio monitor stream: {}", e),
+            None,
+        )
+        .map_err(|e| MediaError::TaskLaunch(format!("Failed to build output stream: {e}")))?;
+
+    stream
+        .play()
+        .map_err(|e| MediaError::TaskLaunch(format!("Failed to play output stream: {e}")))?;
+
+    let monitor = move |stop_rx: Receiver<()>| {
+        let sample_size = output_info.sample_size();
+        let mut resampled = FFAudio::empty();
+
+        loop {
+            // Stopping is signalled by the monitor dropping its sender
+            let samples = flume::Selector::new()
+                .recv(&samples_rx, |samples| samples.ok())
+                .recv(&stop_rx, |_| None)
+                .wait();
+            let Some(samples) = samples else {
+                break;
+            };
+
+            let frame = input_info.wrap_frame(&samples.data, 0);
+            if let Err(e) = resampler.run(&frame, &mut resampled) {
+                error!("Failed to resample monitored audio: {}", e);
+                continue;
+            }
+
+            let len = resampled.samples() * output_info.channels * sample_size;
+            let typed = resampled.data(0)[..len]
+                .chunks(sample_size)
+                .map(T::from_bytes)
+                .collect::<Vec<_>>();
+            // Whatever doesn't fit is dropped, keeping latency bounded
+            producer.push_slice(&typed);
+        }
+    };
+
+    Ok((stream, Box::new(monitor)))
+}
@@ -3,7 +3,10 @@ use ffmpeg::format::Pixel;
 use flume::{Receiver, Sender, TryRecvError, TrySendError};
 use nokhwa::{pixel_format::RgbAFormat, utils::*, Camera};
 use std::{
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     thread::{self},
     time::SystemTime,
 };
@@ -31,9 +34,15 @@ pub struct RawCameraFrame {
 
 pub struct CameraConnection {
     control: Sender<CameraControl>,
+    mirrored: Arc<AtomicBool>,
 }
 
 impl CameraConnection {
+    /// Whether recordings should flip frames horizontally, see `CameraFeed::set_mirrored`
+    pub fn mirrored(&self) -> bool {
+        self.mirrored.load(Ordering::Relaxed)
+    }
+
     pub fn attach(&self) -> Receiver<RawCameraFrame> {
         let (sender, receiver) = flume::bounded(60);
         self.control
@@ -48,6 +57,7 @@ pub struct CameraFeed {
     pub camera_info: CameraInfo,
     video_info: VideoInfo,
     control: Sender<CameraControl>,
+    mirrored: Arc<AtomicBool>,
 }
 
 impl CameraFeed {
@@ -68,6 +78,7 @@ impl CameraFeed {
             camera_info,
             video_info,
             control,
+            mirrored: Arc::new(AtomicBool::new(false)),
         };
 
         Ok(camera_feed)
@@ -99,6 +110,11 @@ impl CameraFeed {
         }
     }
 
+    /// Whether the camera this feed opened is still connected as the same device
+    pub fn is_connected(&self) -> bool {
+        nokhwa::query(ApiBackend::Auto).is_ok_and(|cameras| cameras.contains(&self.camera_info))
+    }
+
     pub fn camera_info(&self) -> CameraInfo {
         self.camera_info.clone()
     }
@@ -137,9 +153,16 @@ impl CameraFeed {
     pub fn create_connection(&self) -> CameraConnection {
         CameraConnection {
             control: self.control.clone(),
+            mirrored: self.mirrored.clone(),
         }
     }
 
+    /// Flip the camera horizontally in recordings, including ones already in progress.
+    /// Attached consumers, like the preview, still get frames as captured.
+    pub fn set_mirrored(&self, mirrored: bool) {
+        self.mirrored.store(mirrored, Ordering::Relaxed);
+    }
+
     pub fn attach(&self, sender: Sender<RawCameraFrame>) {
         self.control
             .send(CameraControl::AttachConsumer(sender))
@@ -381,6 +404,51 @@ fn run_camera_feed(
     info!("Camera feed stopping");
 }
 
+/// Flip `frame` horizontally in place, for any format a camera feed produces
+pub fn mirror_frame(frame: &mut FFVideo) {
+    let (width, height) = (frame.width() as usize, frame.height() as usize);
+
+    // For each plane, the bytes per unit that's moved as a whole, units per row and rows.
+    // 4:2:2 formats pack two pixels into each unit, which share their chroma.
+    let planes = match frame.format() {
+        Pixel::RGBA | Pixel::BGRA | Pixel::ARGB | Pixel::ABGR => vec![(4, width, height)],
+        Pixel::RGB24 | Pixel::BGR24 => vec![(3, width, height)],
+        Pixel::GRAY8 => vec![(1, width, height)],
+        Pixel::NV12 => vec![
+            (1, width, height),
+            (2, width.div_ceil(2), height.div_ceil(2)),
+        ],
+        Pixel::YUYV422 | Pixel::UYVY422 => vec![(4, width / 2, height)],
+        format => {
+            warn!("Can't mirror {:?} frames", format);
+            return;
+        }
+    };
+    // Where the two lumas sit in a 4:2:2 unit, as they swap places too
+    let lumas = match frame.format() {
+        Pixel::YUYV422 => Some((0, 2)),
+        Pixel::UYVY422 => Some((1, 3)),
+        _ => None,
+    };
+
+    for (plane, (unit_size, units, rows)) in planes.into_iter().enumerate() {
+        let stride = frame.stride(plane);
+        let data = frame.data_mut(plane);
+
+        for row in 0..rows {
+            let row = &mut data[row * stride..row * stride + units * unit_size];
+            // Reversing the whole row reverses each unit's bytes too, so they're put back
+            row.reverse();
+            for unit in row.chunks_exact_mut(unit_size) {
+                unit.reverse();
+                if let Some((first, second)) = lumas {
+                    unit.swap(first, second);
+                }
+            }
+        }
+    }
+}
+
 fn buffer_to_ffvideo(buffer: nokhwa::Buffer) -> FFVideo {
     use ffmpeg::format::Pixel;
     let (format, load_data): (Pixel, fn(&mut FFVideo, &nokhwa::Buffer)) = {
@@ -471,3 +539,26 @@ fn buffer_to_ffvideo(buffer: nokhwa::Buffer) -> FFVideo {
 
     frame
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn frame_with(format: Pixel, width: u32, bytes: &[u8]) -> FFVideo {
+        let mut frame = FFVideo::new(format, width, 1);
+        frame.data_mut(0)[..bytes.len()].copy_from_slice(bytes);
+        frame
+    }
+
+    #[test]
+    fn mirrors_packed_and_422_frames() {
+        let mut frame = frame_with(Pixel::RGBA, 2, &[1, 2, 3, 4, 5, 6, 7, 8]);
+        mirror_frame(&mut frame);
+        assert_eq!(&frame.data(0)[..8], &[5, 6, 7, 8, 1, 2, 3, 4]);
+
+        // Y0 U Y1 V for each pair of pixels
+        let mut frame = frame_with(Pixel::YUYV422, 4, &[1, 2, 3, 4, 5, 6, 7, 8]);
+        mirror_frame(&mut frame);
+        assert_eq!(&frame.data(0)[..8], &[7, 6, 5, 8, 3, 2, 1, 4]);
+    }
+}
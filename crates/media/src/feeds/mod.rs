@@ -1,7 +1,11 @@
 mod audio;
 mod audio_input;
+mod audio_monitor;
 mod camera;
+mod noise_gate;
 
 pub use audio::*;
 pub use audio_input::*;
+pub use audio_monitor::*;
 pub use camera::*;
+pub use noise_gate::*;
@@ -0,0 +1,118 @@
+use cpal::SampleFormat;
+use tracing::warn;
+
+/// Seconds the gate takes to fully open once the input crosses the threshold
+const ATTACK_SECS: f64 = 0.005;
+/// Seconds the gate stays open after the input drops below the threshold
+const HOLD_SECS: f64 = 0.05;
+/// Seconds the gate takes to fully close after the hold
+const RELEASE_SECS: f64 = 0.15;
+
+/// Silences input while it stays below a threshold, eg. keyboard noise between sentences.
+/// It opens quickly and closes gradually, so quiet word endings aren't chopped off.
+pub struct NoiseGate {
+    threshold: f64,
+    attack_step: f64,
+    release_step: f64,
+    hold_samples: u32,
+    /// Samples left before the gate starts closing
+    hold_remaining: u32,
+    gain: f64,
+}
+
+impl NoiseGate {
+    pub fn new(threshold_db: f32, sample_rate: u32, channels: u16) -> Self {
+        // Channels are interleaved, so every sample advances the gate
+        let samples_per_sec = sample_rate as f64 * channels.max(1) as f64;
+
+        Self {
+            threshold: 10f64.powf(threshold_db as f64 / 20.0),
+            attack_step: 1.0 / (ATTACK_SECS * samples_per_sec),
+            release_step: 1.0 / (RELEASE_SECS * samples_per_sec),
+            hold_samples: (HOLD_SECS * samples_per_sec) as u32,
+            hold_remaining: 0,
+            gain: 0.0,
+        }
+    }
+
+    /// Gate the interleaved samples in `data` in place
+    pub fn process(&mut self, data: &mut [u8], format: SampleFormat) {
+        macro_rules! gate {
+            ($ty:ty, $scale:expr, $center:expr) => {
+                for chunk in data.chunks_exact_mut(std::mem::size_of::<$ty>()) {
+                    let sample = <$ty>::from_ne_bytes(chunk.try_into().unwrap()) as f64;
+                    let gain = self.next_gain((sample - $center) / $scale);
+                    chunk.copy_from_slice(
+                        &(((sample - $center) * gain + $center) as $ty).to_ne_bytes(),
+                    );
+                }
+            };
+        }
+
+        // Unsigned formats are centered on their midpoint
+        match format {
+            SampleFormat::I8 => gate!(i8, i8::MAX as f64, 0.0),
+            SampleFormat::I16 => gate!(i16, i16::MAX as f64, 0.0),
+            SampleFormat::I32 => gate!(i32, i32::MAX as f64, 0.0),
+            SampleFormat::I64 => gate!(i64, i64::MAX as f64, 0.0),
+            SampleFormat::U8 => gate!(u8, 128.0, 128.0),
+            SampleFormat::U16 => gate!(u16, 32768.0, 32768.0),
+            SampleFormat::U32 => gate!(u32, 2147483648.0, 2147483648.0),
+            SampleFormat::U64 => gate!(u64, 9223372036854775808.0, 9223372036854775808.0),
+            SampleFormat::F32 => gate!(f32, 1.0, 0.0),
+            SampleFormat::F64 => gate!(f64, 1.0, 0.0),
+            format => warn!("Can't gate {:?} samples", format),
+        }
+    }
+
+    /// Advance the gate by one sample of `level`, from -1 to 1, returning the gain to apply
+    fn next_gain(&mut self, level: f64) -> f64 {
+        if level.abs() >= self.threshold {
+            self.hold_remaining = self.hold_samples;
+            self.gain = (self.gain + self.attack_step).min(1.0);
+        } else if self.hold_remaining > 0 {
+            self.hold_remaining -= 1;
+            self.gain = (self.gain + self.attack_step).min(1.0);
+        } else {
+            self.gain = (self.gain - self.release_step).max(0.0);
+        }
+
+        self.gain
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn gate_f32(gate: &mut NoiseGate, samples: &[f32]) -> Vec<f32> {
+        let mut data = samples
+            .iter()
+            .flat_map(|s| s.to_ne_bytes())
+            .collect::<Vec<_>>();
+        gate.process(&mut data, SampleFormat::F32);
+
+        data.chunks_exact(4)
+            .map(|c| f32::from_ne_bytes([c[0], c[1], c[2], c[3]]))
+            .collect()
+    }
+
+    #[test]
+    fn silences_quiet_input_and_opens_for_loud_input() {
+        // 1kHz mono, so attack is 5 samples, hold 50 and release 150
+        let mut gate = NoiseGate::new(-20.0, 1000, 1);
+
+        let quiet = gate_f32(&mut gate, &[0.05; 10]);
+        assert!(quiet.iter().all(|s| *s == 0.0));
+
+        let loud = gate_f32(&mut gate, &[0.5; 10]);
+        assert!(loud[0] > 0.0 && loud[0] < 0.5);
+        assert_eq!(loud[9], 0.5);
+
+        // Held open, then fading out rather than cutting off
+        let tail = gate_f32(&mut gate, &[0.05; 300]);
+        assert_eq!(tail[49], 0.05);
+        assert!(tail[100] > 0.0 && tail[100] < 0.05);
+        assert_eq!(tail[299], 0.0);
+    }
+}
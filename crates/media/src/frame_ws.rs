@@ -1,6 +1,8 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::SystemTime};
 
-use flume::Receiver;
+use flume::{Receiver, Sender};
+use image::{imageops::FilterType, ImageError, RgbaImage};
+use serde::Deserialize;
 use tokio::sync::mpsc;
 
 use crate::feeds::RawCameraFrame;
@@ -10,9 +12,118 @@ pub struct WSFrame {
     pub width: u32,
     pub height: u32,
     pub stride: u32,
+    /// Set for camera frames so the preview's latency can be measured
+    pub timings: Option<FrameTimings>,
 }
 
-pub async fn create_frame_ws(frame_rx: Receiver<WSFrame>) -> (u16, mpsc::Sender<()>) {
+/// When a frame passed through each stage on its way to the websocket
+#[derive(Debug, Clone, Copy)]
+pub struct FrameTimings {
+    pub captured_at: SystemTime,
+    /// When the frame was picked up for conversion to RGBA
+    pub received_at: SystemTime,
+    pub converted_at: SystemTime,
+}
+
+/// How a client wants frames encoded
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum WSFrameFormat {
+    #[default]
+    Rgba,
+    /// `quality` is 1-100
+    Jpeg { quality: u8 },
+    /// Lossless, so smaller than RGBA without losing detail but slower to encode
+    WebP,
+}
+
+/// Sent by a client as a JSON text message, usually right after connecting, eg.
+/// `{"format":{"type":"jpeg","quality":80},"maxWidth":640}`.
+/// Clients that don't send one get full size RGBA frames.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WSFramePreference {
+    #[serde(default)]
+    pub format: WSFrameFormat,
+    /// Wider frames are downscaled to this width before they're sent
+    pub max_width: Option<u32>,
+}
+
+/// Encode `frame` as `preference` asks, followed by its stride, height and width as
+/// little endian u32s. The stride is 0 for JPEG and WebP frames.
+fn encode_frame(frame: WSFrame, preference: WSFramePreference) -> Result<Vec<u8>, ImageError> {
+    let WSFrame {
+        mut data,
+        width,
+        height,
+        stride,
+        ..
+    } = frame;
+    let target_width = preference
+        .max_width
+        .map_or(width, |max| width.min(max.max(1)));
+
+    if preference.format == WSFrameFormat::Rgba && target_width == width {
+        data.extend_from_slice(&stride.to_le_bytes());
+        data.extend_from_slice(&height.to_le_bytes());
+        data.extend_from_slice(&width.to_le_bytes());
+        return Ok(data);
+    }
+
+    // Rows may be padded, which the image crate doesn't handle
+    let row_len = width as usize * 4;
+    let rows = data
+        .chunks(stride as usize)
+        .take(height as usize)
+        .flat_map(|row| &row[..row_len])
+        .copied()
+        .collect();
+    let mut image = RgbaImage::from_raw(width, height, rows).ok_or_else(|| {
+        ImageError::Parameter(image::error::ParameterError::from_kind(
+            image::error::ParameterErrorKind::DimensionMismatch,
+        ))
+    })?;
+
+    if target_width != width {
+        let target_height = ((height as u64 * target_width as u64) / width as u64).max(1) as u32;
+        image = image::imageops::resize(&image, target_width, target_height, FilterType::Triangle);
+    }
+
+    let (width, height) = image.dimensions();
+    let (mut data, stride) = match preference.format {
+        WSFrameFormat::Rgba => (image.into_raw(), width * 4),
+        WSFrameFormat::Jpeg { quality } => {
+            let mut data = vec![];
+            // JPEG has no alpha channel
+            image::codecs::jpeg::JpegEncoder::new_with_quality(&mut data, quality.clamp(1, 100))
+                .encode_image(&image::DynamicImage::ImageRgba8(image).to_rgb8())?;
+            (data, 0)
+        }
+        WSFrameFormat::WebP => {
+            let mut data = vec![];
+            image::codecs::webp::WebPEncoder::new_lossless(&mut data).encode(
+                &image,
+                width,
+                height,
+                image::ExtendedColorType::Rgba8,
+            )?;
+            (data, 0)
+        }
+    };
+
+    data.extend_from_slice(&stride.to_le_bytes());
+    data.extend_from_slice(&height.to_le_bytes());
+    data.extend_from_slice(&width.to_le_bytes());
+
+    Ok(data)
+}
+
+/// Frames sent over the websocket are reported to `sent_tx`, along with when sending finished,
+/// if they have `timings`
+pub async fn create_frame_ws(
+    frame_rx: Receiver<WSFrame>,
+    sent_tx: Option<Sender<(FrameTimings, SystemTime)>>,
+) -> (u16, mpsc::Sender<()>) {
     use axum::{
         extract::{
             ws::{Message, WebSocket, WebSocketUpgrade},
@@ -23,7 +134,10 @@ pub async fn create_frame_ws(frame_rx: Receiver<WSFrame>) -> (u16, mpsc::Sender<
     };
     use tokio::sync::Mutex;
 
-    type RouterState = Arc<Mutex<Receiver<WSFrame>>>;
+    type RouterState = Arc<(
+        Mutex<Receiver<WSFrame>>,
+        Option<Sender<(FrameTimings, SystemTime)>>,
+    )>;
 
     #[axum::debug_handler]
     async fn ws_handler(
@@ -34,28 +148,61 @@ pub async fn create_frame_ws(frame_rx: Receiver<WSFrame>) -> (u16, mpsc::Sender<
     }
 
     async fn handle_socket(mut socket: WebSocket, state: RouterState) {
-        let camera_rx = state.lock().await;
+        let (camera_rx, sent_tx) = &*state;
+        let camera_rx = camera_rx.lock().await;
         println!("socket connection established");
         tracing::info!("Socket connection established");
         let now = std::time::Instant::now();
+        let mut preference = WSFramePreference::default();
 
         loop {
             tokio::select! {
-                _ = socket.recv() => {
-                    tracing::info!("Received message from socket");
-                    break;
+                message = socket.recv() => {
+                    match message {
+                        Some(Ok(Message::Text(text))) => {
+                            match serde_json::from_str::<WSFramePreference>(&text) {
+                                Ok(new_preference) => {
+                                    tracing::info!("Sending frames as {:?}", new_preference);
+                                    preference = new_preference;
+                                }
+                                Err(e) => tracing::warn!("Invalid frame preference: {}", e),
+                            }
+                        }
+                        Some(Ok(Message::Ping(_) | Message::Pong(_))) => {}
+                        _ => {
+                            tracing::info!("Received message from socket");
+                            break;
+                        }
+                    }
                 },
                 incoming_frame = camera_rx.recv_async() => {
                     match incoming_frame {
-                        Ok(mut frame) => {
-                            frame.data.extend_from_slice(&frame.stride.to_le_bytes());
-                            frame.data.extend_from_slice(&frame.height.to_le_bytes());
-                            frame.data.extend_from_slice(&frame.width.to_le_bytes());
+                        Ok(frame) => {
+                            let timings = frame.timings;
+                            let data = if preference == WSFramePreference::default() {
+                                encode_frame(frame, preference)
+                            } else {
+                                // Scaling and encoding are too slow to run on the async runtime
+                                tokio::task::spawn_blocking(move || encode_frame(frame, preference))
+                                    .await
+                                    .expect("Frame encoding panicked")
+                            };
+                            let data = match data {
+                                Ok(data) => data,
+                                Err(e) => {
+                                    tracing::error!("Failed to encode frame: {}", e);
+                                    continue;
+                                }
+                            };
 
-                            if let Err(e) = socket.send(Message::Binary(frame.data)).await {
+                            if let Err(e) = socket.send(Message::Binary(data)).await {
                                 tracing::error!("Failed to send frame to socket: {:?}", e);
                                 break;
                             }
+
+                            if let (Some(sent_tx), Some(timings)) = (sent_tx, timings) {
+                                sent_tx.try_send((timings, SystemTime::now())).ok();
+                            }
                         }
                         Err(e) => {
                             tracing::error!(
@@ -76,7 +223,7 @@ pub async fn create_frame_ws(frame_rx: Receiver<WSFrame>) -> (u16, mpsc::Sender<
 
     let router = axum::Router::new()
         .route("/", get(ws_handler))
-        .with_state(Arc::new(Mutex::new(frame_rx)));
+        .with_state(Arc::new((Mutex::new(frame_rx), sent_tx)));
 
     let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
     let port = listener.local_addr().unwrap().port();
@@ -95,3 +242,66 @@ pub async fn create_frame_ws(frame_rx: Receiver<WSFrame>) -> (u16, mpsc::Sender<
 
     (port, shutdown_tx)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn frame(width: u32, height: u32, stride: u32) -> WSFrame {
+        WSFrame {
+            data: vec![255; (stride * height) as usize],
+            width,
+            height,
+            stride,
+            timings: None,
+        }
+    }
+
+    fn trailer(data: &[u8]) -> [u32; 3] {
+        let end = data.len();
+        [end - 12, end - 8, end - 4].map(|i| u32::from_le_bytes(data[i..i + 4].try_into().unwrap()))
+    }
+
+    #[test]
+    fn parses_preference() {
+        let preference: WSFramePreference =
+            serde_json::from_str(r#"{"format":{"type":"jpeg","quality":80},"maxWidth":640}"#)
+                .unwrap();
+
+        assert_eq!(
+            preference,
+            WSFramePreference {
+                format: WSFrameFormat::Jpeg { quality: 80 },
+                max_width: Some(640),
+            }
+        );
+        assert_eq!(
+            serde_json::from_str::<WSFramePreference>("{}").unwrap(),
+            WSFramePreference::default()
+        );
+    }
+
+    #[test]
+    fn downscales_padded_frames() {
+        let preference = WSFramePreference {
+            format: WSFrameFormat::Rgba,
+            max_width: Some(4),
+        };
+        let data = encode_frame(frame(8, 4, 64), preference).unwrap();
+
+        assert_eq!(trailer(&data), [16, 2, 4]);
+        assert_eq!(data.len(), 16 * 2 + 12);
+    }
+
+    #[test]
+    fn encodes_jpeg() {
+        let preference = WSFramePreference {
+            format: WSFrameFormat::Jpeg { quality: 80 },
+            max_width: None,
+        };
+        let data = encode_frame(frame(8, 4, 32), preference).unwrap();
+
+        assert_eq!(&data[..2], &[0xFF, 0xD8]);
+        assert_eq!(trailer(&data), [0, 4, 8]);
+    }
+}
@@ -0,0 +1,324 @@
+use std::time::SystemTime;
+
+use flume::Sender;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tracing::info;
+
+use crate::pipeline::{
+    clock::RealTimeClock,
+    control::{Control, PipelineControlSignal},
+    task::{PipelineReadySignal, PipelineSourceTask},
+};
+
+use super::supports_app_audio_capture;
+
+/// A running application whose audio output can be captured on its own
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioApplication {
+    pub pid: i32,
+    pub bundle_id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+#[serde(rename_all = "camelCase", tag = "variant")]
+pub enum AppAudioTarget {
+    Pid { pid: i32 },
+    BundleId { bundle_id: String },
+}
+
+impl AppAudioTarget {
+    /// Treats numeric values as a process id and anything else as a bundle identifier
+    pub fn parse(pid_or_bundle: &str) -> Self {
+        match pid_or_bundle.trim().parse() {
+            Ok(pid) => Self::Pid { pid },
+            Err(_) => Self::BundleId {
+                bundle_id: pid_or_bundle.trim().to_string(),
+            },
+        }
+    }
+
+    pub fn matches(&self, app: &AudioApplication) -> bool {
+        match self {
+            Self::Pid { pid } => app.pid == *pid,
+            Self::BundleId { bundle_id } => app.bundle_id == *bundle_id,
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+pub async fn list_audio_applications() -> Vec<AudioApplication> {
+    use cidre::sc;
+
+    let Ok(content) = sc::ShareableContent::current().await else {
+        return vec![];
+    };
+
+    let mut apps = content
+        .applications()
+        .iter()
+        .map(|app| AudioApplication {
+            pid: app.process_id(),
+            bundle_id: app.bundle_id().to_string(),
+            name: app.app_name().to_string(),
+        })
+        // Background helpers have no name and can't be told apart by the user
+        .filter(|app| !app.name.is_empty())
+        .collect::<Vec<_>>();
+
+    apps.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    apps
+}
+
+#[cfg(not(target_os = "macos"))]
+pub async fn list_audio_applications() -> Vec<AudioApplication> {
+    vec![]
+}
+
+/// Captures the audio output of a single application, in the same format as system audio capture
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub struct AppAudioSource {
+    target: AppAudioTarget,
+    tx: Sender<(ffmpeg::frame::Audio, f64)>,
+    start_time: SystemTime,
+}
+
+impl AppAudioSource {
+    /// Fails if the platform doesn't support per-app capture or the application isn't running,
+    /// so the caller can fall back to capturing all system audio
+    pub async fn init(
+        target: &AppAudioTarget,
+        tx: Sender<(ffmpeg::frame::Audio, f64)>,
+        start_time: SystemTime,
+    ) -> Result<Self, String> {
+        if !supports_app_audio_capture() {
+            return Err(
+                "Capturing a single application's audio isn't supported on this platform"
+                    .to_string(),
+            );
+        }
+
+        if !list_audio_applications()
+            .await
+            .iter()
+            .any(|app| target.matches(app))
+        {
+            return Err(format!("Application {target:?} is not running"));
+        }
+
+        Ok(Self {
+            target: target.clone(),
+            tx,
+            start_time,
+        })
+    }
+}
+
+impl PipelineSourceTask for AppAudioSource {
+    type Clock = RealTimeClock<()>;
+
+    fn run(
+        &mut self,
+        _: Self::Clock,
+        ready_signal: PipelineReadySignal,
+        mut control_signal: PipelineControlSignal,
+    ) {
+        info!("Preparing app audio source thread for {:?}", self.target);
+
+        #[cfg(target_os = "macos")]
+        let stream = match macos::create_stream(&self.target, self.tx.clone(), self.start_time) {
+            Ok(stream) => stream,
+            Err(e) => {
+                tracing::error!("Failed to create app audio stream: {e}");
+                let _ = ready_signal.send(Err(crate::MediaError::TaskLaunch(e)));
+                return;
+            }
+        };
+
+        let _ = ready_signal.send(Ok(()));
+
+        let mut capturing = false;
+
+        loop {
+            match control_signal.blocking_last() {
+                Some(Control::Play) => {
+                    if !capturing {
+                        #[cfg(target_os = "macos")]
+                        if let Err(e) = futures::executor::block_on(stream.start()) {
+                            tracing::error!("Failed to start app audio capture: {e:?}");
+                            break;
+                        }
+
+                        capturing = true;
+                        info!("App audio capture started");
+                    }
+                }
+                Some(Control::Shutdown) | None => {
+                    #[cfg(target_os = "macos")]
+                    if capturing {
+                        let _ = futures::executor::block_on(stream.stop());
+                    }
+
+                    break;
+                }
+            }
+        }
+
+        info!("Shut down app audio source thread.");
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use std::time::SystemTime;
+
+    use cidre::{
+        arc, cm, define_obj_type, ns, objc,
+        sc::{
+            self,
+            stream::{Output, OutputImpl},
+        },
+    };
+    use ffmpeg::ChannelLayout;
+    use ffmpeg_sys_next::AV_TIME_BASE_Q;
+    use flume::Sender;
+
+    use super::{AppAudioTarget, AudioApplication};
+
+    #[repr(C)]
+    pub struct AudioOutputInner {
+        tx: Sender<(ffmpeg::frame::Audio, f64)>,
+        // Offset from a sample buffer's host time to seconds since the recording started
+        time_offset: f64,
+    }
+
+    define_obj_type!(AudioOutput + OutputImpl, AudioOutputInner, APP_AUDIO_OUTPUT);
+
+    impl Output for AudioOutput {}
+
+    #[objc::add_methods]
+    impl OutputImpl for AudioOutput {
+        extern "C" fn impl_stream_did_output_sample_buf(
+            &mut self,
+            _cmd: Option<&objc::Sel>,
+            _stream: &sc::Stream,
+            sample_buf: &mut cm::SampleBuf,
+            kind: sc::OutputType,
+        ) {
+            if !matches!(kind, sc::OutputType::Audio) {
+                return;
+            }
+
+            let Ok(buf_list) = sample_buf.audio_buf_list::<2>() else {
+                return;
+            };
+            let Ok(slice) = buf_list.block().as_slice() else {
+                return;
+            };
+
+            let relative_time = sample_buf.pts().value as f64 / sample_buf.pts().scale as f64
+                + self.inner().time_offset;
+            // Capture may start before the recording does so it has time to warm up
+            if relative_time < 0.0 {
+                return;
+            }
+
+            let mut frame = ffmpeg::frame::Audio::new(
+                ffmpeg::format::Sample::F32(ffmpeg::format::sample::Type::Planar),
+                sample_buf.num_samples() as usize,
+                ChannelLayout::STEREO,
+            );
+            frame.set_rate(48_000);
+            let data_bytes_size = buf_list.list().buffers[0].data_bytes_size as usize;
+            for i in 0..frame.planes() {
+                frame
+                    .plane_data_mut(i)
+                    .copy_from_slice(&slice[i * data_bytes_size..(i + 1) * data_bytes_size]);
+            }
+            frame.set_pts(Some((relative_time * AV_TIME_BASE_Q.den as f64) as i64));
+
+            let _ = self.inner().tx.send((frame, relative_time));
+        }
+    }
+
+    pub struct AppAudioStream {
+        stream: arc::R<sc::Stream>,
+        _output: arc::R<AudioOutput>,
+    }
+
+    impl AppAudioStream {
+        pub async fn start(&self) -> Result<(), arc::R<ns::Error>> {
+            self.stream.start().await
+        }
+
+        pub async fn stop(&self) -> Result<(), arc::R<ns::Error>> {
+            self.stream.stop().await
+        }
+    }
+
+    pub fn create_stream(
+        target: &AppAudioTarget,
+        tx: Sender<(ffmpeg::frame::Audio, f64)>,
+        start_time: SystemTime,
+    ) -> Result<AppAudioStream, String> {
+        let content = futures::executor::block_on(sc::ShareableContent::current())
+            .map_err(|e| format!("Failed to get shareable content: {e:?}"))?;
+
+        let app = content
+            .applications()
+            .iter()
+            .find(|app| {
+                target.matches(&AudioApplication {
+                    pid: app.process_id(),
+                    bundle_id: app.bundle_id().to_string(),
+                    name: app.app_name().to_string(),
+                })
+            })
+            .map(|app| app.retained())
+            .ok_or_else(|| format!("Application {target:?} is not running"))?;
+        let display = content
+            .displays()
+            .get(0)
+            .map_err(|_| "No display to attach the audio stream to".to_string())?;
+
+        let apps = ns::Array::from_slice(&[app.as_ref()]);
+        let filter = sc::ContentFilter::with_display_including_apps_excepting_windows(
+            &display,
+            &apps,
+            &ns::Array::new(),
+        );
+
+        let mut cfg = sc::StreamCfg::new();
+        cfg.set_captures_audio(true);
+        cfg.set_sample_rate(48_000);
+        cfg.set_channel_count(2);
+        // Only the audio is wanted, so keep the unavoidable video output as cheap as possible
+        cfg.set_width(2);
+        cfg.set_height(2);
+        cfg.set_minimum_frame_interval(cm::Time::new(1, 1));
+
+        let host_time = cm::Clock::host_time_clock().time();
+        let host_time = host_time.value as f64 / host_time.scale as f64;
+        let unix_secs = |time: SystemTime| {
+            time.duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_secs_f64()
+        };
+        // The start can be in the future if capture is warming up, so keep the offset signed
+        let time_offset = unix_secs(SystemTime::now()) - unix_secs(start_time) - host_time;
+
+        let output = AudioOutput::with(AudioOutputInner { tx, time_offset });
+
+        let stream = sc::Stream::new(&filter, &cfg);
+        stream
+            .add_stream_output(output.as_ref(), sc::OutputType::Audio, None)
+            .map_err(|e| format!("Failed to add audio output: {e:?}"))?;
+
+        Ok(AppAudioStream {
+            stream,
+            _output: output,
+        })
+    }
+}
@@ -76,6 +76,11 @@ impl AudioInputSource {
             .as_secs_f64()
             - self.start_time;
 
+        // Capture may start before the recording does so it has time to warm up
+        if timestamp < 0.0 {
+            return Ok(());
+        }
+
         let frame = self.audio_info.wrap_frame(
             &samples.data,
             (elapsed.as_secs_f64() * AV_TIME_BASE_Q.den as f64) as i64,
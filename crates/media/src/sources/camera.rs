@@ -1,10 +1,10 @@
 use flume::{Receiver, Sender};
 use std::time::{Instant, SystemTime};
-use tracing::{error, info, warn};
+use tracing::{error, info, trace};
 
 use crate::{
     data::{FFVideo, VideoInfo},
-    feeds::{CameraConnection, CameraFeed, RawCameraFrame},
+    feeds::{mirror_frame, CameraConnection, CameraFeed, RawCameraFrame},
     pipeline::{clock::RealTimeClock, control::Control, task::PipelineSourceTask},
     MediaError,
 };
@@ -31,7 +31,16 @@ impl CameraSource {
     }
 
     fn process_frame(&self, camera_frame: RawCameraFrame) -> Result<(), MediaError> {
-        let RawCameraFrame { frame, captured_at } = camera_frame;
+        let RawCameraFrame {
+            mut frame,
+            captured_at,
+        } = camera_frame;
+
+        // Flipped before encoding, so exports match
+        if self.feed_connection.mirrored() {
+            mirror_frame(&mut frame);
+        }
+
         match captured_at.duration_since(self.start_time) {
             Ok(time) => {
                 if let Err(_) = self.output.send((frame, time.as_secs_f64())) {
@@ -40,8 +49,9 @@ impl CameraSource {
                     ));
                 }
             }
+            // Frames from before the start are expected while capture warms up
             Err(error) => {
-                warn!(
+                trace!(
                     "Camera frame captured {} millis before start time",
                     error.duration().as_millis()
                 );
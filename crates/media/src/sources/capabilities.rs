@@ -0,0 +1,61 @@
+use ffmpeg::format::Pixel;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use super::screen_capture::CAPTURE_PIXEL_FORMAT;
+use crate::feeds::CameraFeed;
+
+/// What the current platform can capture, so unsupported options can be hidden
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureCapabilities {
+    pub window_capture: bool,
+    pub area_capture: bool,
+    pub system_audio: bool,
+    /// Recording a single application's audio instead of all system audio
+    pub per_app_audio: bool,
+    /// Recording more than one camera at a time
+    pub multiple_cameras: bool,
+    pub hdr: bool,
+}
+
+/// Probes the platform, including the connected cameras, so it's best kept off the main thread
+pub fn capture_capabilities() -> CaptureCapabilities {
+    let screen_capture = scap::is_supported();
+
+    CaptureCapabilities {
+        window_capture: screen_capture && supports_window_capture(),
+        // Areas are cropped from a display capture
+        area_capture: screen_capture,
+        system_audio: screen_capture,
+        per_app_audio: screen_capture && supports_app_audio_capture(),
+        // Each camera gets its own feed, so this only needs a second one connected
+        multiple_cameras: CameraFeed::list_cameras().len() > 1,
+        hdr: screen_capture && bits_per_channel(CAPTURE_PIXEL_FORMAT) > 8,
+    }
+}
+
+/// Whether a single window can be captured, which needs the display it's on to be looked up
+pub fn supports_window_capture() -> bool {
+    cfg!(any(target_os = "macos", windows))
+}
+
+/// Whether this platform can capture a single application's audio rather than all system audio
+pub fn supports_app_audio_capture() -> bool {
+    cfg!(target_os = "macos")
+}
+
+/// Whether system audio capture leaves out audio played by this app, eg. mic monitoring
+pub fn excludes_own_audio() -> bool {
+    cfg!(target_os = "macos")
+}
+
+fn bits_per_channel(format: Pixel) -> i32 {
+    // Descriptors are static, and null for formats ffmpeg doesn't know
+    let descriptor = unsafe { ffmpeg::sys::av_pix_fmt_desc_get(format.into()) };
+    if descriptor.is_null() {
+        return 8;
+    }
+
+    unsafe { (*descriptor).comp[0].depth }
+}
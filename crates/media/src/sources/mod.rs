@@ -1,10 +1,14 @@
+mod app_audio;
 mod audio_input;
 mod audio_mixer;
 mod camera;
+mod capabilities;
 mod screen_capture;
 // pub mod system_audio;
 
+pub use app_audio::*;
 pub use audio_input::*;
 pub use audio_mixer::*;
 pub use camera::*;
+pub use capabilities::*;
 pub use screen_capture::*;
@@ -1,5 +1,8 @@
 use cpal::traits::{DeviceTrait, HostTrait};
-use ffmpeg::{format::Sample, ChannelLayout};
+use ffmpeg::{
+    format::{Pixel, Sample},
+    ChannelLayout,
+};
 use ffmpeg_sys_next::AV_TIME_BASE_Q;
 use flume::Sender;
 use scap::{
@@ -14,12 +17,15 @@ use std::{collections::HashMap, ops::ControlFlow, sync::Arc, time::SystemTime};
 use tracing::{debug, error, info, trace, warn};
 
 use crate::{
-    data::{AudioInfo, FFVideo, PlanarData, RawVideoFormat, VideoInfo},
+    data::{AudioInfo, FFVideo, PlanarData, VideoInfo},
     pipeline::{clock::*, control::Control, task::PipelineSourceTask},
     platform::{self, logical_monitor_bounds, Bounds, Window},
     MediaError,
 };
 
+/// Pixel format screen frames are captured in, before encoders convert them
+pub(crate) const CAPTURE_PIXEL_FORMAT: Pixel = Pixel::BGRA;
+
 static EXCLUDED_WINDOWS: &[&str] = &[
     "Cap Camera",
     "Cap Recordings Overlay",
@@ -230,7 +236,7 @@ impl<TCaptureFormat: ScreenCaptureFormat> ScreenCaptureSource<TCaptureFormat> {
             output_resolution: None,
             output_type,
             fps,
-            video_info: VideoInfo::from_raw(RawVideoFormat::Bgra, 0, 0, 0),
+            video_info: VideoInfo::from_raw_ffmpeg(CAPTURE_PIXEL_FORMAT, 0, 0, 0),
             options: Arc::new(Default::default()),
             bounds,
             display_size,
@@ -261,7 +267,7 @@ impl<TCaptureFormat: ScreenCaptureFormat> ScreenCaptureSource<TCaptureFormat> {
         };
 
         this.video_info =
-            VideoInfo::from_raw(RawVideoFormat::Bgra, video_size.0, video_size.1, fps);
+            VideoInfo::from_raw_ffmpeg(CAPTURE_PIXEL_FORMAT, video_size.0, video_size.1, fps);
 
         Ok(this)
     }
@@ -482,8 +488,9 @@ impl PipelineSourceTask for ScreenCaptureSource<AVFrameCapture> {
                         return ControlFlow::Continue(());
                     }
 
+                    // Frames from before the start are expected while capture warms up
                     let Ok(elapsed) = frame.display_time.duration_since(start_time) else {
-                        warn!("Skipping video frame {video_i} as elapsed time is invalid");
+                        trace!("Skipping video frame {video_i} captured before start time");
                         return ControlFlow::Continue(());
                     };
 
@@ -540,7 +547,7 @@ impl PipelineSourceTask for ScreenCaptureSource<AVFrameCapture> {
                 Ok(Frame::Audio(frame)) => {
                     if let Some(audio_tx) = &audio_tx {
                         let Ok(elapsed) = frame.time().duration_since(start_time) else {
-                            warn!("Skipping audio frame {audio_i} as elapsed time is invalid");
+                            trace!("Skipping audio frame {audio_i} captured before start time");
                             return ControlFlow::Continue(());
                         };
                         let mut frame = scap_audio_to_ffmpeg(frame);
@@ -689,6 +696,11 @@ impl PipelineSourceTask for ScreenCaptureSource<CMSampleBufferCapture> {
                     let unix_timestamp = start_time_unix + frame_time - start_cmtime;
                     let relative_time = unix_timestamp - start_time_f64;
 
+                    // Capture may start before the recording does so it has time to warm up
+                    if relative_time < 0.0 {
+                        return ControlFlow::Continue(());
+                    }
+
                     match typ {
                         sc::stream::OutputType::Screen => {
                             let Some(pixel_buffer) = sample_buffer.image_buf() else {
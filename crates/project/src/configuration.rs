@@ -1,6 +1,6 @@
 use std::{
     ops::{Add, Div, Mul, Sub},
-    path::Path,
+    path::{Path, PathBuf},
 };
 
 use serde::{Deserialize, Serialize};
@@ -17,6 +17,19 @@ pub enum AspectRatio {
     Tall,
 }
 
+impl AspectRatio {
+    /// Width over height
+    pub fn ratio(&self) -> f64 {
+        match self {
+            Self::Wide => 16.0 / 9.0,
+            Self::Vertical => 9.0 / 16.0,
+            Self::Square => 1.0,
+            Self::Classic => 4.0 / 3.0,
+            Self::Tall => 3.0 / 4.0,
+        }
+    }
+}
+
 pub type Color = [u16; 3];
 
 #[derive(Type, Serialize, Deserialize, Clone, Debug)]
@@ -395,6 +408,10 @@ pub struct TimelineSegment {
     pub timescale: f64,
     pub start: f64,
     pub end: f64,
+    /// Synthesize in-between frames when slowed below 1x instead of repeating frames.
+    /// Only applied when exporting, as it's expensive.
+    #[serde(default)]
+    pub interpolate: bool,
 }
 
 impl TimelineSegment {
@@ -406,7 +423,7 @@ impl TimelineSegment {
         }
     }
 
-    fn duration(&self) -> f64 {
+    pub fn duration(&self) -> f64 {
         (self.end - self.start) / self.timescale
     }
 }
@@ -474,6 +491,149 @@ impl TimelineConfiguration {
         self.segments.iter().map(|s| s.duration()).sum()
     }
 
+    /// Where `time` in a recording segment first plays on the timeline, or `None` if it's been cut
+    pub fn recording_time_to_timeline(&self, recording_segment: u32, time: f64) -> Option<f64> {
+        let mut accum_duration = 0.0;
+
+        for segment in &self.segments {
+            if segment.recording_segment == recording_segment
+                && time >= segment.start
+                && time < segment.end
+            {
+                return Some(accum_duration + (time - segment.start) / segment.timescale);
+            }
+
+            accum_duration += segment.duration();
+        }
+
+        None
+    }
+
+    /// The timeline segment playing at `frame_time`, and its index
+    pub fn get_segment_at_time(&self, frame_time: f64) -> Option<(usize, &TimelineSegment)> {
+        let mut accum_duration = 0.0;
+
+        for (i, segment) in self.segments.iter().enumerate() {
+            if frame_time < accum_duration + segment.duration() {
+                return Some((i, segment));
+            }
+
+            accum_duration += segment.duration();
+        }
+
+        None
+    }
+
+    /// The part of the timeline between `start` and `end`, with zoom and scene segments moved along
+    pub fn trimmed(&self, start: f64, end: f64) -> Self {
+        let mut segments = vec![];
+        let mut accum_duration = 0.0;
+
+        for segment in &self.segments {
+            let segment_start = accum_duration;
+            accum_duration += segment.duration();
+
+            let from = start.max(segment_start);
+            let to = end.min(accum_duration);
+            if to <= from {
+                continue;
+            }
+
+            segments.push(TimelineSegment {
+                start: segment.start + (from - segment_start) * segment.timescale,
+                end: segment.start + (to - segment_start) * segment.timescale,
+                ..segment.clone()
+            });
+        }
+
+        let trim = |s: f64, e: f64| {
+            (e > start && s < end).then(|| (s.max(start) - start, e.min(end) - start))
+        };
+
+        Self {
+            segments,
+            zoom_segments: self
+                .zoom_segments
+                .iter()
+                .filter_map(|segment| {
+                    let (start, end) = trim(segment.start, segment.end)?;
+                    Some(ZoomSegment {
+                        start,
+                        end,
+                        ..segment.clone()
+                    })
+                })
+                .collect(),
+            scene_segments: self.scene_segments.as_ref().map(|scene_segments| {
+                scene_segments
+                    .iter()
+                    .filter_map(|segment| {
+                        let (start, end) = trim(segment.start, segment.end)?;
+                        Some(SceneSegment {
+                            start,
+                            end,
+                            ..segment.clone()
+                        })
+                    })
+                    .collect()
+            }),
+        }
+    }
+
+    /// Remove `start` to `end` of the timeline, splitting segments that only partly overlap it
+    /// and pulling zoom and scene segments in to close the gap.
+    /// Returns how many seconds the timeline got shorter by.
+    pub fn cut_timeline_span(&mut self, start: f64, end: f64) -> f64 {
+        let mut segments = vec![];
+        let mut removed = 0.0;
+        let mut accum_duration = 0.0;
+
+        for segment in &self.segments {
+            let segment_start = accum_duration;
+            accum_duration += segment.duration();
+
+            let cut_start = start.max(segment_start);
+            let cut_end = end.min(accum_duration);
+            if cut_start >= cut_end {
+                segments.push(segment.clone());
+                continue;
+            }
+
+            removed += cut_end - cut_start;
+            if cut_start > segment_start {
+                segments.push(TimelineSegment {
+                    end: segment.start + (cut_start - segment_start) * segment.timescale,
+                    ..segment.clone()
+                });
+            }
+            if cut_end < accum_duration {
+                segments.push(TimelineSegment {
+                    start: segment.start + (cut_end - segment_start) * segment.timescale,
+                    ..segment.clone()
+                });
+            }
+        }
+
+        self.segments = segments;
+
+        let start = start.max(0.0);
+        let shift = |time: f64| time_after_cut(time, start, start + removed);
+        self.zoom_segments.retain_mut(|segment| {
+            segment.start = shift(segment.start);
+            segment.end = shift(segment.end);
+            segment.end > segment.start
+        });
+        if let Some(scene_segments) = &mut self.scene_segments {
+            scene_segments.retain_mut(|segment| {
+                segment.start = shift(segment.start);
+                segment.end = shift(segment.end);
+                segment.end > segment.start
+            });
+        }
+
+        removed
+    }
+
     pub fn get_scene_mode_at_time(&self, time: f64) -> Option<SceneMode> {
         if let Some(ref scene_segments) = self.scene_segments {
             for segment in scene_segments {
@@ -486,6 +646,17 @@ impl TimelineConfiguration {
     }
 }
 
+/// Where `time` on the timeline ends up once `start` to `end` has been cut out of it
+pub fn time_after_cut(time: f64, start: f64, end: f64) -> f64 {
+    if time <= start {
+        time
+    } else if time < end {
+        start
+    } else {
+        time - (end - start)
+    }
+}
+
 pub const WALLPAPERS_PATH: &str = "assets/backgrounds/macOS";
 
 #[derive(Type, Serialize, Deserialize, Clone, Debug, Default)]
@@ -495,10 +666,55 @@ pub struct CaptionSegment {
     pub start: f32,
     pub end: f32,
     pub text: String,
+    /// Style for this segment only, merged on top of the global settings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style_override: Option<CaptionStyleOverride>,
+    /// Per-word timings, when the transcription provided them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub words: Option<Vec<CaptionWord>>,
 }
 
-#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+#[derive(Type, Serialize, Deserialize, Clone, Debug, Default)]
 #[serde(rename_all = "camelCase")]
+pub struct CaptionWord {
+    pub start: f32,
+    pub end: f32,
+    pub text: String,
+}
+
+impl CaptionsData {
+    /// Keep only captions shown between `start` and `end`, moved so `start` becomes zero
+    pub fn trim(&mut self, start: f32, end: f32) {
+        self.segments
+            .retain(|segment| segment.end > start && segment.start < end);
+
+        for segment in &mut self.segments {
+            segment.start = segment.start.max(start) - start;
+            segment.end = segment.end.min(end) - start;
+
+            if let Some(words) = &mut segment.words {
+                words.retain(|word| word.end > start && word.start < end);
+                for word in words {
+                    word.start = word.start.max(start) - start;
+                    word.end = word.end.min(end) - start;
+                }
+            }
+        }
+    }
+}
+
+impl CaptionSegment {
+    /// Index of the word being spoken at `time`, if word timings are available
+    pub fn active_word(&self, time: f32) -> Option<usize> {
+        self.words
+            .as_ref()?
+            .iter()
+            .position(|word| time >= word.start && time < word.end)
+    }
+}
+
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase", default)]
 pub struct CaptionSettings {
     pub enabled: bool,
     pub font: String,
@@ -509,13 +725,39 @@ pub struct CaptionSettings {
     #[serde(alias = "backgroundOpacity")]
     pub background_opacity: u32,
     pub position: String,
+    /// Vertical nudge applied on top of `position`, as a fraction of the frame height
+    #[serde(default, alias = "positionOffset")]
+    pub position_offset: f32,
     pub bold: bool,
     pub italic: bool,
     pub outline: bool,
     #[serde(alias = "outlineColor")]
     pub outline_color: String,
+    /// Outline thickness in pixels at 1080p, 0 hides the outline
+    #[serde(alias = "outlineWidth")]
+    pub outline_width: f32,
+    /// Color of the word being spoken, when the segment has word timings
+    #[serde(alias = "highlightColor")]
+    pub highlight_color: String,
     #[serde(alias = "exportWithSubtitles")]
     pub export_with_subtitles: bool,
+    /// Maximum number of wrapped lines to display, 0 for no limit
+    #[serde(default, alias = "maxLines")]
+    pub max_lines: u32,
+    /// Truncate with an ellipsis instead of shrinking the font when over `max_lines`
+    #[serde(default, alias = "truncateOverflow")]
+    pub truncate_overflow: bool,
+    /// Move captions out of the way of the camera overlay instead of using the fixed anchor
+    #[serde(default, alias = "avoidCamera")]
+    pub avoid_camera: bool,
+    /// Seconds captions take to fade in and out, 0 to switch instantly
+    #[serde(alias = "fadeDuration")]
+    pub fade_duration: f32,
+    /// ISO 639-1 code of the captions' spoken language, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Whether the text was translated to English from `language` rather than transcribed
+    pub translated: bool,
 }
 
 impl Default for CaptionSettings {
@@ -528,15 +770,105 @@ impl Default for CaptionSettings {
             background_color: "#000000".to_string(),
             background_opacity: 80,
             position: "bottom".to_string(),
+            position_offset: 0.0,
             bold: false,
             italic: false,
             outline: false,
             outline_color: "#000000".to_string(),
+            outline_width: 1.0,
+            highlight_color: "#FFFFFF".to_string(),
             export_with_subtitles: false,
+            max_lines: 0,
+            truncate_overflow: false,
+            avoid_camera: false,
+            fade_duration: 0.0,
+            language: None,
+            translated: false,
         }
     }
 }
 
+/// Caption style for a single segment. Fields that are set replace the global setting,
+/// including when they're set back to the default. `enabled`, `export_with_subtitles`
+/// and `language` are project-wide, so they can't be overridden.
+#[derive(Type, Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CaptionStyleOverride {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub font: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background_opacity: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position_offset: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bold: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub italic: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outline: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outline_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outline_width: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlight_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_lines: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncate_overflow: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avoid_camera: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fade_duration: Option<f32>,
+}
+
+impl CaptionSettings {
+    /// Applies every field that's set in `overrides` on top of `self`
+    pub fn merged_with(&self, overrides: &CaptionStyleOverride) -> CaptionSettings {
+        let mut merged = self.clone();
+
+        macro_rules! merge {
+            ($($field:ident),* $(,)?) => {
+                $(
+                    if let Some(value) = &overrides.$field {
+                        merged.$field = value.clone();
+                    }
+                )*
+            };
+        }
+
+        merge!(
+            font,
+            size,
+            color,
+            background_color,
+            background_opacity,
+            position,
+            position_offset,
+            bold,
+            italic,
+            outline,
+            outline_color,
+            outline_width,
+            highlight_color,
+            max_lines,
+            truncate_overflow,
+            avoid_camera,
+            fade_duration,
+        );
+
+        merged
+    }
+}
+
 #[derive(Type, Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct CaptionsData {
@@ -553,12 +885,146 @@ impl Default for CaptionsData {
     }
 }
 
+/// A name card shown near the bottom left of the frame, eg. to introduce a speaker
+#[derive(Type, Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LowerThird {
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subtitle: Option<String>,
+    /// Seconds into the edited timeline, like caption segments
+    pub start: f32,
+    pub end: f32,
+    #[serde(default)]
+    pub style: LowerThirdStyle,
+}
+
+impl LowerThird {
+    /// The part shown between `start` and `end`, moved so `start` becomes zero
+    pub fn trimmed(&self, start: f32, end: f32) -> Option<Self> {
+        (self.end > start && self.start < end).then(|| Self {
+            start: self.start.max(start) - start,
+            end: self.end.min(end) - start,
+            ..self.clone()
+        })
+    }
+}
+
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LowerThirdStyle {
+    pub font: String,
+    /// Size of `text` at 1080p. The subtitle is drawn at 70% of it.
+    pub size: u32,
+    pub color: String,
+    pub subtitle_color: String,
+    pub background_color: String,
+    pub background_opacity: u32,
+    pub animation: LowerThirdAnimation,
+    /// Seconds the entrance and exit take, 0 to switch instantly
+    pub animation_duration: f32,
+}
+
+impl Default for LowerThirdStyle {
+    fn default() -> Self {
+        Self {
+            font: "System Sans-Serif".to_string(),
+            size: 36,
+            color: "#FFFFFF".to_string(),
+            subtitle_color: "#D0D0D0".to_string(),
+            background_color: "#000000".to_string(),
+            background_opacity: 70,
+            animation: LowerThirdAnimation::default(),
+            animation_duration: 0.4,
+        }
+    }
+}
+
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum LowerThirdAnimation {
+    /// Slides in from the left edge and back out again
+    #[default]
+    Slide,
+    Fade,
+}
+
+/// Color correction applied to the composited frame. Every value ranges from -1 to 1,
+/// with 0 leaving the frame unchanged.
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ColorAdjust {
+    #[serde(default)]
+    pub brightness: f32,
+    #[serde(default)]
+    pub contrast: f32,
+    /// -1 is greyscale
+    #[serde(default)]
+    pub saturation: f32,
+    /// Negative values are cooler (bluer), positive values warmer (more orange)
+    #[serde(default)]
+    pub temperature: f32,
+}
+
+impl ColorAdjust {
+    /// Values limited to -1 to 1, with anything non-finite treated as neutral
+    pub fn clamped(&self) -> Self {
+        let clamp = |v: f32| {
+            if v.is_finite() {
+                v.clamp(-1.0, 1.0)
+            } else {
+                0.0
+            }
+        };
+
+        Self {
+            brightness: clamp(self.brightness),
+            contrast: clamp(self.contrast),
+            saturation: clamp(self.saturation),
+            temperature: clamp(self.temperature),
+        }
+    }
+
+    pub fn is_neutral(&self) -> bool {
+        self.clamped() == Self::default()
+    }
+}
+
+/// Clockwise rotation of the recorded display
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum Rotation {
+    #[default]
+    None,
+    Cw90,
+    Cw180,
+    Cw270,
+}
+
+impl Rotation {
+    pub fn swaps_axes(&self) -> bool {
+        matches!(self, Self::Cw90 | Self::Cw270)
+    }
+
+    pub fn quarter_turns(&self) -> u32 {
+        match self {
+            Self::None => 0,
+            Self::Cw90 => 1,
+            Self::Cw180 => 2,
+            Self::Cw270 => 3,
+        }
+    }
+}
+
 #[derive(Type, Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ProjectConfiguration {
     pub aspect_ratio: Option<AspectRatio>,
     pub background: BackgroundConfiguration,
     pub camera: Camera,
+    /// Settings for cameras beyond the primary one, in slot order starting at slot 1
+    #[serde(default)]
+    pub additional_cameras: Vec<Camera>,
     pub audio: AudioConfiguration,
     pub cursor: CursorConfiguration,
     pub hotkeys: HotkeysConfiguration,
@@ -566,6 +1032,25 @@ pub struct ProjectConfiguration {
     pub timeline: Option<TimelineConfiguration>,
     #[serde(default)]
     pub captions: Option<CaptionsData>,
+    /// Drawn above bottom captions. The first listed wins where they overlap in time.
+    #[serde(default)]
+    pub lower_thirds: Vec<LowerThird>,
+    /// `.cube` color lookup table applied to the screen and cameras
+    #[serde(default)]
+    pub lut: Option<PathBuf>,
+    #[serde(default)]
+    pub color_adjust: Option<ColorAdjust>,
+    /// Applied to the display before cropping, so the crop is in rotated coordinates
+    #[serde(default)]
+    pub rotation: Rotation,
+    #[serde(default)]
+    pub flip_horizontal: bool,
+    #[serde(default)]
+    pub flip_vertical: bool,
+    /// Where the display sits horizontally when it's narrower than the output,
+    /// from -1 for the left edge to 1 for the right, with 0 centering it
+    #[serde(default)]
+    pub display_offset_x: f64,
 }
 
 impl ProjectConfiguration {
@@ -584,6 +1069,57 @@ impl ProjectConfiguration {
         )
     }
 
+    /// Settings for the camera in `slot`, where slot 0 is the primary camera.
+    /// Additional cameras without settings default to the bottom left so they don't cover the primary.
+    pub fn camera_for_slot(&self, slot: usize) -> Camera {
+        match slot {
+            0 => self.camera.clone(),
+            slot => self
+                .additional_cameras
+                .get(slot - 1)
+                .cloned()
+                .unwrap_or_else(|| Camera {
+                    position: CameraPosition {
+                        x: CameraXPosition::Left,
+                        y: CameraYPosition::Bottom,
+                    },
+                    ..Default::default()
+                }),
+        }
+    }
+
+    /// Size of the display once rotated
+    pub fn oriented_size(&self, size: XY<u32>) -> XY<u32> {
+        if self.rotation.swaps_axes() {
+            XY::new(size.y, size.x)
+        } else {
+            size
+        }
+    }
+
+    /// Maps a 0-1 position on the recorded display to where it ends up after rotating and flipping
+    pub fn orient_uv(&self, uv: XY<f64>) -> XY<f64> {
+        let rotated = match self.rotation {
+            Rotation::None => uv,
+            Rotation::Cw90 => XY::new(1.0 - uv.y, uv.x),
+            Rotation::Cw180 => XY::new(1.0 - uv.x, 1.0 - uv.y),
+            Rotation::Cw270 => XY::new(uv.y, 1.0 - uv.x),
+        };
+
+        XY::new(
+            if self.flip_horizontal {
+                1.0 - rotated.x
+            } else {
+                rotated.x
+            },
+            if self.flip_vertical {
+                1.0 - rotated.y
+            } else {
+                rotated.y
+            },
+        )
+    }
+
     pub fn get_segment_time(&self, frame_time: f64) -> Option<(f64, u32)> {
         self.timeline
             .as_ref()
@@ -598,11 +1134,19 @@ impl Default for ProjectConfiguration {
             aspect_ratio: None,
             background: BackgroundConfiguration::default(),
             camera: Camera::default(),
+            additional_cameras: vec![],
             audio: AudioConfiguration::default(),
             cursor: CursorConfiguration::default(),
             hotkeys: HotkeysConfiguration::default(),
             timeline: None,
             captions: None,
+            lower_thirds: vec![],
+            lut: None,
+            color_adjust: None,
+            rotation: Rotation::default(),
+            flip_horizontal: false,
+            flip_vertical: false,
+            display_offset_x: 0.0,
         }
     }
 }
@@ -614,3 +1158,171 @@ pub const FAST_SMOOTHING_SAMPLES: usize = 10;
 pub const SLOW_VELOCITY_THRESHOLD: f64 = 0.003;
 pub const REGULAR_VELOCITY_THRESHOLD: f64 = 0.008;
 pub const FAST_VELOCITY_THRESHOLD: f64 = 0.015;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn orients_display_positions() {
+        let mut config = ProjectConfiguration {
+            rotation: Rotation::Cw90,
+            ..Default::default()
+        };
+
+        // The top left corner ends up top right after a clockwise turn
+        assert_eq!(config.orient_uv(XY::new(0.0, 0.0)), XY::new(1.0, 0.0));
+        assert_eq!(
+            config.oriented_size(XY::new(1920, 1080)),
+            XY::new(1080, 1920)
+        );
+
+        config.flip_horizontal = true;
+        assert_eq!(config.orient_uv(XY::new(0.0, 0.0)), XY::new(0.0, 0.0));
+
+        config.rotation = Rotation::Cw270;
+        config.flip_horizontal = false;
+        assert_eq!(config.orient_uv(XY::new(0.0, 0.0)), XY::new(0.0, 1.0));
+    }
+
+    #[test]
+    fn trims_timeline_across_segments() {
+        let timeline = TimelineConfiguration {
+            segments: vec![
+                TimelineSegment {
+                    recording_segment: 0,
+                    timescale: 1.0,
+                    start: 10.0,
+                    end: 20.0,
+                    interpolate: false,
+                },
+                TimelineSegment {
+                    recording_segment: 1,
+                    timescale: 2.0,
+                    start: 0.0,
+                    end: 20.0,
+                    interpolate: false,
+                },
+            ],
+            zoom_segments: vec![ZoomSegment {
+                start: 4.0,
+                end: 8.0,
+                amount: 2.0,
+                mode: ZoomMode::Auto,
+            }],
+            scene_segments: None,
+        };
+
+        let trimmed = timeline.trimmed(5.0, 15.0);
+
+        assert_eq!(trimmed.segments.len(), 2);
+        assert_eq!(
+            (trimmed.segments[0].start, trimmed.segments[0].end),
+            (15.0, 20.0)
+        );
+        // Five seconds of the 2x segment covers ten seconds of its recording
+        assert_eq!(
+            (trimmed.segments[1].start, trimmed.segments[1].end),
+            (0.0, 10.0)
+        );
+        assert_eq!(trimmed.duration(), 10.0);
+        assert_eq!(
+            (trimmed.zoom_segments[0].start, trimmed.zoom_segments[0].end),
+            (0.0, 3.0)
+        );
+    }
+
+    #[test]
+    fn cuts_timeline_span_across_segments() {
+        // Already trimmed to the last ten seconds of the first recording segment
+        let mut timeline = TimelineConfiguration {
+            segments: vec![
+                TimelineSegment {
+                    recording_segment: 0,
+                    timescale: 1.0,
+                    start: 10.0,
+                    end: 20.0,
+                    interpolate: false,
+                },
+                TimelineSegment {
+                    recording_segment: 1,
+                    timescale: 2.0,
+                    start: 0.0,
+                    end: 20.0,
+                    interpolate: false,
+                },
+            ],
+            zoom_segments: vec![
+                ZoomSegment {
+                    start: 1.0,
+                    end: 2.5,
+                    amount: 2.0,
+                    mode: ZoomMode::Auto,
+                },
+                ZoomSegment {
+                    start: 12.0,
+                    end: 16.0,
+                    amount: 2.0,
+                    mode: ZoomMode::Auto,
+                },
+            ],
+            scene_segments: None,
+        };
+
+        // Two seconds from the end of each segment, which at 2x is four seconds of the second
+        assert_eq!(timeline.cut_timeline_span(8.0, 12.0), 4.0);
+        assert_eq!(
+            timeline
+                .segments
+                .iter()
+                .map(|s| (s.recording_segment, s.start, s.end))
+                .collect::<Vec<_>>(),
+            [(0, 10.0, 18.0), (1, 4.0, 20.0)]
+        );
+        assert_eq!(timeline.duration(), 16.0);
+        assert_eq!(
+            (
+                timeline.zoom_segments[1].start,
+                timeline.zoom_segments[1].end
+            ),
+            (8.0, 12.0)
+        );
+
+        assert_eq!(timeline.cut_timeline_span(2.0, 3.0), 1.0);
+        assert_eq!(
+            timeline
+                .segments
+                .iter()
+                .map(|s| (s.recording_segment, s.start, s.end))
+                .collect::<Vec<_>>(),
+            [(0, 10.0, 12.0), (0, 13.0, 18.0), (1, 4.0, 20.0)]
+        );
+        assert_eq!(
+            (
+                timeline.zoom_segments[0].start,
+                timeline.zoom_segments[0].end
+            ),
+            (1.0, 2.0)
+        );
+
+        assert_eq!(timeline.cut_timeline_span(30.0, 40.0), 0.0);
+        assert_eq!(timeline.segments.len(), 3);
+    }
+
+    #[test]
+    fn caption_overrides_can_restore_defaults() {
+        let settings = CaptionSettings {
+            bold: true,
+            color: "#FF0000".to_string(),
+            ..Default::default()
+        };
+
+        let merged = settings.merged_with(&CaptionStyleOverride {
+            bold: Some(false),
+            ..Default::default()
+        });
+
+        assert!(!merged.bold);
+        assert_eq!(merged.color, "#FF0000");
+    }
+}
@@ -0,0 +1,98 @@
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Presentation timestamps of every frame written to a video, in seconds since its first frame.
+/// Captures drop frames under load, so these are the source of truth for timing rather than frame count / fps.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FrameTimestamps {
+    pub timestamps: Vec<f64>,
+}
+
+impl FrameTimestamps {
+    /// Builds timestamps from capture times, which may be in any clock as long as they're in seconds
+    pub fn from_capture_times(times: &[f64]) -> Self {
+        let Some(first) = times.first().copied() else {
+            return Self::default();
+        };
+
+        Self {
+            timestamps: times.iter().map(|t| t - first).collect(),
+        }
+    }
+
+    /// Where the timestamps for the video at `video_path` are stored, eg. `display.timestamps.json`
+    pub fn sidecar_path(video_path: &Path) -> PathBuf {
+        video_path.with_extension("timestamps.json")
+    }
+
+    pub fn load_from_file(path: &Path) -> Result<Self, String> {
+        let file =
+            File::open(path).map_err(|e| format!("Failed to open frame timestamps: {}", e))?;
+        serde_json::from_reader(file).map_err(|e| format!("Failed to parse frame timestamps: {}", e))
+    }
+
+    pub fn save_to_file(&self, path: &Path) -> Result<(), String> {
+        let file =
+            File::create(path).map_err(|e| format!("Failed to create frame timestamps: {}", e))?;
+        serde_json::to_writer(file, self)
+            .map_err(|e| format!("Failed to write frame timestamps: {}", e))
+    }
+
+    /// Duration from the first frame to the end of the last one, where the last frame is shown for one nominal frame interval
+    pub fn duration(&self, fps: u32) -> Option<f64> {
+        let last = *self.timestamps.last()?;
+        Some(last + 1.0 / fps.max(1) as f64)
+    }
+
+    /// Number of frames missing between captured frames, judging gaps against the nominal frame interval
+    pub fn dropped_frames(&self, fps: u32) -> u32 {
+        let interval = 1.0 / fps.max(1) as f64;
+
+        self.timestamps
+            .windows(2)
+            .map(|pair| ((pair[1] - pair[0]) / interval).round() as i64 - 1)
+            .filter(|missing| *missing > 0)
+            .sum::<i64>() as u32
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn duration_accounts_for_dropped_frames() {
+        let fps = 30;
+
+        // One second of capture with frames 10 through 19 dropped
+        let times = (0..30)
+            .filter(|i| !(10..20).contains(i))
+            .map(|i| 1_700_000_000.0 + i as f64 / fps as f64)
+            .collect::<Vec<_>>();
+
+        let timestamps = FrameTimestamps::from_capture_times(&times);
+
+        assert_eq!(timestamps.timestamps.len(), 20);
+        assert!((timestamps.duration(fps).unwrap() - 1.0).abs() < 1e-9);
+        assert_eq!(timestamps.dropped_frames(fps), 10);
+    }
+
+    #[test]
+    fn duration_of_uniform_sequence() {
+        let times = (0..60).map(|i| i as f64 / 60.0).collect::<Vec<_>>();
+
+        let timestamps = FrameTimestamps::from_capture_times(&times);
+
+        assert!((timestamps.duration(60).unwrap() - 1.0).abs() < 1e-9);
+        assert_eq!(timestamps.dropped_frames(60), 0);
+    }
+
+    #[test]
+    fn empty_sequence_has_no_duration() {
+        assert_eq!(FrameTimestamps::default().duration(30), None);
+    }
+}
@@ -1,10 +1,14 @@
 mod configuration;
 pub mod cursor;
+mod frame_timestamps;
 mod meta;
+mod timeline_export;
 
 pub use configuration::*;
 pub use cursor::*;
+pub use frame_timestamps::*;
 pub use meta::*;
+pub use timeline_export::*;
 
 use serde::{Deserialize, Serialize};
 use specta::Type;
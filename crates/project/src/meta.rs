@@ -21,6 +21,10 @@ pub struct VideoMeta {
     /// unix time of the first frame
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub start_time: Option<f64>,
+    /// sidecar with the presentation timestamp of each captured frame
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[specta(type = Option<String>)]
+    pub frame_timestamps: Option<RelativePathBuf>,
 }
 
 fn legacy_static_video_fps() -> u32 {
@@ -67,10 +71,64 @@ pub struct RecordingMeta {
     pub pretty_name: String,
     #[serde(default)]
     pub sharing: Option<SharingMeta>,
+    /// What was captured, if recorded at capture time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capture_target: Option<CaptureTargetMeta>,
+    /// The only application whose audio was recorded as system audio, if one was picked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_audio_source: Option<SystemAudioSourceMeta>,
+    /// Pinned recordings are listed before the rest
+    #[serde(default)]
+    pub pinned: bool,
+    /// The last optimization, until its backup is restored
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub optimization: Option<OptimizationMeta>,
     #[serde(flatten)]
     pub inner: RecordingMetaInner,
 }
 
+/// Videos replaced when the recording was optimized, with the originals kept in `backup`
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct OptimizationMeta {
+    #[specta(type = String)]
+    pub backup: RelativePathBuf,
+    #[specta(type = Vec<String>)]
+    pub files: Vec<RelativePathBuf>,
+    /// Size of the recording's videos in MB before optimizing
+    pub size_before: f64,
+    /// Size of the recording's videos in MB after optimizing
+    pub size_after: f64,
+}
+
+/// Mirrors the capture target used by the recorder, with an area's bounds in logical pixels
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase", tag = "variant")]
+pub enum CaptureTargetMeta {
+    Screen {
+        id: u32,
+    },
+    Window {
+        id: u32,
+    },
+    Area {
+        screen: u32,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    },
+    AllDisplays,
+}
+
+/// Mirrors the recorder's per-application audio target
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+#[serde(rename_all = "camelCase", tag = "variant")]
+pub enum SystemAudioSourceMeta {
+    Pid { pid: i32 },
+    BundleId { bundle_id: String },
+}
+
 impl specta::Flatten for RecordingMetaInner {}
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -160,6 +218,9 @@ pub enum StudioRecordingMeta {
     },
 }
 
+// Frame rate reported for audio-only recordings, which have no video to take one from
+const AUDIO_ONLY_FPS: u32 = 30;
+
 impl StudioRecordingMeta {
     pub fn camera_path(&self) -> Option<RelativePathBuf> {
         match self {
@@ -176,17 +237,36 @@ impl StudioRecordingMeta {
     pub fn min_fps(&self) -> u32 {
         match self {
             StudioRecordingMeta::SingleSegment { segment } => segment.display.fps,
-            StudioRecordingMeta::MultipleSegments { inner, .. } => {
-                inner.segments.iter().map(|s| s.display.fps).min().unwrap()
-            }
+            StudioRecordingMeta::MultipleSegments { inner, .. } => inner
+                .segments
+                .iter()
+                .filter_map(|s| s.display.as_ref())
+                .map(|display| display.fps)
+                .min()
+                .unwrap_or(AUDIO_ONLY_FPS),
         }
     }
 
     pub fn max_fps(&self) -> u32 {
         match self {
             StudioRecordingMeta::SingleSegment { segment } => segment.display.fps,
-            StudioRecordingMeta::MultipleSegments { inner, .. } => {
-                inner.segments.iter().map(|s| s.display.fps).max().unwrap()
+            StudioRecordingMeta::MultipleSegments { inner, .. } => inner
+                .segments
+                .iter()
+                .filter_map(|s| s.display.as_ref())
+                .map(|display| display.fps)
+                .max()
+                .unwrap_or(AUDIO_ONLY_FPS),
+        }
+    }
+
+    /// Whether the recording was made without capturing the screen.
+    /// These only have audio tracks, so they can't be opened in the editor or rendered.
+    pub fn is_audio_only(&self) -> bool {
+        match self {
+            StudioRecordingMeta::SingleSegment { .. } => false,
+            StudioRecordingMeta::MultipleSegments { inner } => {
+                inner.segments.iter().any(|s| s.display.is_none())
             }
         }
     }
@@ -270,9 +350,15 @@ impl MultipleSegments {
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct MultipleSegment {
-    pub display: VideoMeta,
+    /// `None` for audio-only recordings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display: Option<VideoMeta>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub camera: Option<VideoMeta>,
+    /// Cameras recorded alongside the primary one, indexed by slot starting at slot 1.
+    /// Slots that weren't in use are `None`, so later slots keep their numbers.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub additional_cameras: Vec<Option<VideoMeta>>,
     #[serde(default, skip_serializing_if = "Option::is_none", alias = "audio")]
     pub mic: Option<AudioMeta>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -283,6 +369,13 @@ pub struct MultipleSegment {
 }
 
 impl MultipleSegment {
+    /// The screen recording, or an error for audio-only recordings
+    pub fn display(&self) -> Result<&VideoMeta, String> {
+        self.display
+            .as_ref()
+            .ok_or_else(|| "Audio-only recordings have no video".to_string())
+    }
+
     pub fn path(&self, meta: &RecordingMeta, path: impl AsRef<Path>) -> PathBuf {
         meta.project_path.join(path)
     }
@@ -305,21 +398,23 @@ impl MultipleSegment {
     }
 
     pub fn latest_start_time(&self) -> Option<f64> {
-        let mut value = self.display.start_time?;
-
-        if let Some(camera) = &self.camera {
-            value = value.max(camera.start_time?);
-        }
-
-        if let Some(mic) = &self.mic {
-            value = value.max(mic.start_time?);
-        }
-
-        if let Some(system_audio) = &self.system_audio {
-            value = value.max(system_audio.start_time?);
-        }
-
-        Some(value)
+        let videos = self
+            .display
+            .iter()
+            .chain(&self.camera)
+            .chain(self.additional_cameras.iter().flatten())
+            .map(|video| video.start_time);
+        let audio = self
+            .mic
+            .iter()
+            .chain(&self.system_audio)
+            .map(|audio| audio.start_time);
+
+        videos
+            .chain(audio)
+            .collect::<Option<Vec<_>>>()?
+            .into_iter()
+            .reduce(f64::max)
     }
 }
 
@@ -432,4 +527,76 @@ mod test {
 		        }"#,
         );
     }
+
+    #[test]
+    fn additional_cameras() {
+        let meta: RecordingMeta = serde_json::from_str(
+            r#"{
+              "pretty_name": "Cap 2025-03-02 at 10.12.45",
+              "sharing": null,
+              "segments": [
+                {
+                  "display": {
+                    "path": "content/segments/segment-0/display.mp4"
+                  },
+                  "camera": {
+                    "path": "content/segments/segment-0/camera.mp4"
+                  },
+                  "additional_cameras": [
+                    null,
+                    {
+                      "path": "content/segments/segment-0/camera-2.mp4"
+                    }
+                  ]
+                }
+              ]
+            }"#,
+        )
+        .unwrap();
+
+        let super::RecordingMetaInner::Studio(super::StudioRecordingMeta::MultipleSegments {
+            inner,
+        }) = meta.inner
+        else {
+            panic!("expected a multiple segment recording");
+        };
+
+        let additional_cameras = &inner.segments[0].additional_cameras;
+        assert_eq!(additional_cameras.len(), 2);
+        assert!(additional_cameras[0].is_none());
+        assert!(additional_cameras[1].is_some());
+    }
+
+    #[test]
+    fn audio_only() {
+        let meta: RecordingMeta = serde_json::from_str(
+            r#"{
+              "pretty_name": "Audio 2025-03-02 at 10.12.45",
+              "sharing": null,
+              "segments": [
+                {
+                  "mic": {
+                    "path": "content/segments/segment-0/audio-input.ogg",
+                    "start_time": 0.2
+                  },
+                  "system_audio": {
+                    "path": "content/segments/segment-0/system_audio.ogg",
+                    "start_time": 0.1
+                  }
+                }
+              ],
+              "cursors": {}
+            }"#,
+        )
+        .unwrap();
+
+        let studio_meta = meta.studio_meta().unwrap();
+        assert!(studio_meta.is_audio_only());
+
+        let super::StudioRecordingMeta::MultipleSegments { inner } = studio_meta else {
+            panic!("expected a multiple segment recording");
+        };
+        assert!(inner.segments[0].display().is_err());
+        assert_eq!(inner.segments[0].latest_start_time(), Some(0.2));
+    }
 }
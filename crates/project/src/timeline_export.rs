@@ -0,0 +1,371 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::{SceneMode, TimelineConfiguration};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum TimelineFormat {
+    /// CMX3600 edit decision list
+    Edl,
+    /// Final Cut Pro XML, also read by DaVinci Resolve and Premiere
+    Fcpxml,
+}
+
+impl TimelineFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Edl => "edl",
+            Self::Fcpxml => "fcpxml",
+        }
+    }
+}
+
+/// Source media for one recording segment
+#[derive(Debug, Clone)]
+pub struct TimelineSource {
+    pub name: String,
+    pub path: PathBuf,
+    pub duration: f64,
+}
+
+/// A cut from a source, with times in frames at the project fps
+struct EditEvent<'a> {
+    source: &'a TimelineSource,
+    source_in: u64,
+    source_out: u64,
+    record_in: u64,
+    record_out: u64,
+    speed: f64,
+}
+
+/// A note at a point on the output timeline, in frames
+struct Marker {
+    frame: u64,
+    name: String,
+}
+
+fn to_frames(seconds: f64, fps: u32) -> u64 {
+    (seconds.max(0.0) * fps as f64).round() as u64
+}
+
+fn edit_events<'a>(
+    timeline: &TimelineConfiguration,
+    sources: &'a [TimelineSource],
+    fps: u32,
+) -> Result<Vec<EditEvent<'a>>, String> {
+    let mut record_time = 0.0;
+    let mut events = vec![];
+
+    for segment in &timeline.segments {
+        let source = sources
+            .get(segment.recording_segment as usize)
+            .ok_or_else(|| {
+                format!(
+                    "Timeline references missing recording segment {}",
+                    segment.recording_segment
+                )
+            })?;
+
+        let duration = segment.duration();
+        let record_in = to_frames(record_time, fps);
+        record_time += duration;
+
+        events.push(EditEvent {
+            source,
+            source_in: to_frames(segment.start, fps),
+            source_out: to_frames(segment.end, fps),
+            record_in,
+            record_out: to_frames(record_time, fps),
+            speed: segment.timescale,
+        });
+    }
+
+    Ok(events)
+}
+
+fn markers(timeline: &TimelineConfiguration, fps: u32) -> Vec<Marker> {
+    let zooms = timeline.zoom_segments.iter().map(|zoom| Marker {
+        frame: to_frames(zoom.start, fps),
+        name: format!("Zoom {:.1}x", zoom.amount),
+    });
+
+    let scenes = timeline
+        .scene_segments
+        .iter()
+        .flatten()
+        .map(|scene| Marker {
+            frame: to_frames(scene.start, fps),
+            name: match scene.mode.as_ref().unwrap_or(&SceneMode::Default) {
+                SceneMode::Default => "Scene: Default",
+                SceneMode::CameraOnly => "Scene: Camera Only",
+                SceneMode::HideCamera => "Scene: Hide Camera",
+            }
+            .to_string(),
+        });
+
+    let mut markers = zooms.chain(scenes).collect::<Vec<_>>();
+    markers.sort_by_key(|marker| marker.frame);
+    markers
+}
+
+/// Non-drop-frame SMPTE timecode, eg. `00:01:02:15`
+fn timecode(frames: u64, fps: u32) -> String {
+    let fps = fps.max(1) as u64;
+    let total_seconds = frames / fps;
+
+    format!(
+        "{:02}:{:02}:{:02}:{:02}",
+        total_seconds / 3600,
+        (total_seconds / 60) % 60,
+        total_seconds % 60,
+        frames % fps
+    )
+}
+
+/// Describes the timeline's cuts as a CMX3600 EDL. Zoom and scene changes become `LOC` markers.
+pub fn timeline_to_edl(
+    title: &str,
+    timeline: &TimelineConfiguration,
+    sources: &[TimelineSource],
+    fps: u32,
+) -> Result<String, String> {
+    let events = edit_events(timeline, sources, fps)?;
+    let markers = markers(timeline, fps);
+
+    let mut edl = format!("TITLE: {title}\nFCM: NON-DROP FRAME\n\n");
+
+    for (i, event) in events.iter().enumerate() {
+        let number = i + 1;
+
+        edl.push_str(&format!(
+            "{number:03}  AX       V     C        {} {} {} {}\n",
+            timecode(event.source_in, fps),
+            timecode(event.source_out, fps),
+            timecode(event.record_in, fps),
+            timecode(event.record_out, fps),
+        ));
+
+        if event.speed != 1.0 {
+            edl.push_str(&format!(
+                "M2   AX       {:05.1}    {}\n",
+                fps as f64 * event.speed,
+                timecode(event.source_in, fps)
+            ));
+        }
+
+        edl.push_str(&format!("* FROM CLIP NAME: {}\n", event.source.name));
+        edl.push_str(&format!("* SOURCE FILE: {}\n", event.source.path.display()));
+
+        for marker in markers
+            .iter()
+            .filter(|m| m.frame >= event.record_in && m.frame < event.record_out)
+        {
+            edl.push_str(&format!(
+                "* LOC: {} BLUE    {}\n",
+                timecode(marker.frame, fps),
+                marker.name
+            ));
+        }
+
+        edl.push('\n');
+    }
+
+    Ok(edl)
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn file_url(path: &std::path::Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut url = String::from("file://");
+    if !path.starts_with('/') {
+        url.push('/');
+    }
+
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' | b':' => {
+                url.push(byte as char)
+            }
+            _ => url.push_str(&format!("%{byte:02X}")),
+        }
+    }
+
+    url
+}
+
+/// Rational time in seconds at the project fps, eg. `90/30s`
+fn rational(frames: u64, fps: u32) -> String {
+    format!("{frames}/{}s", fps.max(1))
+}
+
+/// Describes the timeline's cuts as FCPXML. Zoom and scene changes become markers on the clips they fall in.
+pub fn timeline_to_fcpxml(
+    title: &str,
+    timeline: &TimelineConfiguration,
+    sources: &[TimelineSource],
+    fps: u32,
+) -> Result<String, String> {
+    let events = edit_events(timeline, sources, fps)?;
+    let markers = markers(timeline, fps);
+    let title = xml_escape(title);
+    let total_frames = events.last().map(|e| e.record_out).unwrap_or_default();
+
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!DOCTYPE fcpxml>\n<fcpxml version=\"1.9\">\n  <resources>\n",
+    );
+
+    xml.push_str(&format!(
+        "    <format id=\"r0\" frameDuration=\"{}\"/>\n",
+        rational(1, fps)
+    ));
+
+    for (i, source) in sources.iter().enumerate() {
+        xml.push_str(&format!(
+            "    <asset id=\"r{}\" name=\"{}\" start=\"0s\" duration=\"{}\" hasVideo=\"1\" format=\"r0\">\n      <media-rep kind=\"original-media\" src=\"{}\"/>\n    </asset>\n",
+            i + 1,
+            xml_escape(&source.name),
+            rational(to_frames(source.duration, fps), fps),
+            xml_escape(&file_url(&source.path)),
+        ));
+    }
+
+    xml.push_str(&format!(
+        "  </resources>\n  <library>\n    <event name=\"{title}\">\n      <project name=\"{title}\">\n        <sequence format=\"r0\" duration=\"{}\" tcStart=\"0s\" tcFormat=\"NDF\">\n          <spine>\n",
+        rational(total_frames, fps)
+    ));
+
+    for event in &events {
+        let asset_index = sources
+            .iter()
+            .position(|source| std::ptr::eq(source, event.source))
+            .unwrap_or_default();
+        let duration = event.record_out - event.record_in;
+
+        xml.push_str(&format!(
+            "            <asset-clip ref=\"r{}\" name=\"{}\" offset=\"{}\" start=\"{}\" duration=\"{}\" format=\"r0\">\n",
+            asset_index + 1,
+            xml_escape(&event.source.name),
+            rational(event.record_in, fps),
+            rational(event.source_in, fps),
+            rational(duration, fps),
+        ));
+
+        if event.speed != 1.0 {
+            xml.push_str(&format!(
+                "              <timeMap>\n                <timept time=\"{}\" value=\"{}\" interp=\"linear\"/>\n                <timept time=\"{}\" value=\"{}\" interp=\"linear\"/>\n              </timeMap>\n",
+                rational(event.source_in, fps),
+                rational(event.source_in, fps),
+                rational(event.source_in + duration, fps),
+                rational(event.source_out, fps),
+            ));
+        }
+
+        // Clip children are positioned in the clip's own timeline, which begins at its start
+        for marker in markers
+            .iter()
+            .filter(|m| m.frame >= event.record_in && m.frame < event.record_out)
+        {
+            xml.push_str(&format!(
+                "              <marker start=\"{}\" duration=\"{}\" value=\"{}\"/>\n",
+                rational(event.source_in + (marker.frame - event.record_in), fps),
+                rational(1, fps),
+                xml_escape(&marker.name),
+            ));
+        }
+
+        xml.push_str("            </asset-clip>\n");
+    }
+
+    xml.push_str(
+        "          </spine>\n        </sequence>\n      </project>\n    </event>\n  </library>\n</fcpxml>\n",
+    );
+
+    Ok(xml)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{TimelineSegment, ZoomMode, ZoomSegment};
+
+    fn timeline() -> TimelineConfiguration {
+        TimelineConfiguration {
+            segments: vec![
+                TimelineSegment {
+                    recording_segment: 0,
+                    timescale: 1.0,
+                    start: 1.0,
+                    end: 3.0,
+                    interpolate: false,
+                },
+                TimelineSegment {
+                    recording_segment: 1,
+                    timescale: 1.0,
+                    start: 0.5,
+                    end: 2.0,
+                    interpolate: false,
+                },
+            ],
+            zoom_segments: vec![ZoomSegment {
+                start: 2.5,
+                end: 3.0,
+                amount: 1.5,
+                mode: ZoomMode::Auto,
+            }],
+            scene_segments: None,
+        }
+    }
+
+    fn sources() -> Vec<TimelineSource> {
+        (0..2)
+            .map(|i| TimelineSource {
+                name: format!("segment-{i}"),
+                path: PathBuf::from(format!("/tmp/My Recording.cap/segment-{i}/display.mp4")),
+                duration: 10.0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn timecodes() {
+        assert_eq!(timecode(0, 30), "00:00:00:00");
+        assert_eq!(timecode(30 * 3661 + 15, 30), "01:01:01:15");
+    }
+
+    #[test]
+    fn edl_events_follow_timeline() {
+        let edl = timeline_to_edl("Test", &timeline(), &sources(), 30).unwrap();
+
+        assert!(edl.contains(
+            "001  AX       V     C        00:00:01:00 00:00:03:00 00:00:00:00 00:00:02:00"
+        ));
+        assert!(edl.contains(
+            "002  AX       V     C        00:00:00:15 00:00:02:00 00:00:02:00 00:00:03:15"
+        ));
+        assert!(edl.contains("* LOC: 00:00:02:15 BLUE    Zoom 1.5x"));
+    }
+
+    #[test]
+    fn fcpxml_places_clips_and_markers() {
+        let xml = timeline_to_fcpxml("Test", &timeline(), &sources(), 30).unwrap();
+
+        assert!(xml.contains("offset=\"60/30s\" start=\"15/30s\" duration=\"45/30s\""));
+        assert!(xml.contains("<marker start=\"30/30s\" duration=\"1/30s\" value=\"Zoom 1.5x\"/>"));
+        assert!(xml.contains("file:///tmp/My%20Recording.cap/segment-1/display.mp4"));
+    }
+
+    #[test]
+    fn missing_source_is_an_error() {
+        assert!(timeline_to_edl("Test", &timeline(), &sources()[..1], 30).is_err());
+    }
+}
@@ -20,9 +20,12 @@ pub async fn main() {
         RecordingBaseInputs {
             capture_target: ScreenCaptureTarget::primary_display(),
             capture_system_audio: false,
+            app_audio: None,
             mic_feed: &None,
+            start_time: None,
         },
         None,
+        vec![],
         false,
     )
     .await
@@ -11,15 +11,16 @@ use cap_media::{
     feeds::AudioInputFeed,
     pipeline::{builder::PipelineBuilder, task::PipelineSinkTask, RealTimeClock},
     sources::{
-        AVFrameCapture, AudioInputSource, AudioMixer, CMSampleBufferCapture, ScreenCaptureFormat,
-        ScreenCaptureSource, ScreenCaptureTarget,
+        AVFrameCapture, AppAudioSource, AppAudioTarget, AudioInputSource, AudioMixer,
+        CMSampleBufferCapture, ScreenCaptureFormat, ScreenCaptureSource, ScreenCaptureTarget,
     },
     MediaError,
 };
+use cap_project::FrameTimestamps;
 use ffmpeg::ffi::AV_TIME_BASE_Q;
 use flume::{Receiver, Sender};
 use tokio::sync::oneshot;
-use tracing::error;
+use tracing::{error, warn};
 
 use crate::RecordingError;
 
@@ -63,6 +64,7 @@ impl MakeCapturePipeline for cap_media::sources::CMSampleBufferCapture {
         output_path: PathBuf,
     ) -> Result<(CapturePipelineBuilder, flume::Receiver<f64>), MediaError> {
         let screen_config = source.0.info();
+        let timestamps_path = FrameTimestamps::sidecar_path(&output_path);
         let mut screen_encoder = cap_media::encoders::MP4AVAssetWriterEncoder::init(
             "screen",
             screen_config,
@@ -77,6 +79,7 @@ impl MakeCapturePipeline for cap_media::sources::CMSampleBufferCapture {
             use std::time::Duration;
 
             let mut timestamp_tx = Some(timestamp_tx);
+            let mut frame_times = Vec::new();
             let _ = ready.send(Ok(()));
 
             let Ok(frame) = source.1.recv() else {
@@ -92,7 +95,9 @@ impl MakeCapturePipeline for cap_media::sources::CMSampleBufferCapture {
 
                 match source.1.recv() {
                     Ok(frame) => {
-                        let _ = screen_encoder.queue_video_frame(frame.0.as_ref());
+                        if screen_encoder.queue_video_frame(frame.0.as_ref()).is_ok() {
+                            frame_times.push(frame.1);
+                        }
                     }
                     // Err(RecvTimeoutError::Timeout) => {
                     //     break Err("Frame receive timeout".to_string());
@@ -104,6 +109,7 @@ impl MakeCapturePipeline for cap_media::sources::CMSampleBufferCapture {
             };
 
             screen_encoder.finish();
+            save_frame_timestamps(&timestamps_path, &frame_times);
 
             result
         });
@@ -238,6 +244,7 @@ impl MakeCapturePipeline for AVFrameCapture {
         Self: Sized,
     {
         let screen_config = source.0.info();
+        let timestamps_path = FrameTimestamps::sidecar_path(&output_path);
         let mut screen_encoder = MP4File::init(
             "screen",
             output_path.into(),
@@ -251,15 +258,18 @@ impl MakeCapturePipeline for AVFrameCapture {
 
         builder.spawn_task("screen_capture_encoder", move |ready| {
             let mut timestamp_tx = Some(timestamp_tx);
+            let mut frame_times = Vec::new();
             let _ = ready.send(Ok(()));
 
             while let Ok(frame) = source.1.recv() {
                 if let Some(timestamp_tx) = timestamp_tx.take() {
                     timestamp_tx.send(frame.1).unwrap();
                 }
+                frame_times.push(frame.1);
                 screen_encoder.queue_video_frame(frame.0);
             }
             screen_encoder.finish();
+            save_frame_timestamps(&timestamps_path, &frame_times);
             Ok(())
         });
 
@@ -359,6 +369,12 @@ pub type ScreenCaptureMethod = CMSampleBufferCapture;
 #[cfg(not(target_os = "macos"))]
 pub type ScreenCaptureMethod = AVFrameCapture;
 
+fn save_frame_timestamps(path: &std::path::Path, frame_times: &[f64]) {
+    if let Err(e) = FrameTimestamps::from_capture_times(frame_times).save_to_file(path) {
+        error!("{e}");
+    }
+}
+
 pub async fn create_screen_capture(
     capture_target: &ScreenCaptureTarget,
     show_camera: bool,
@@ -383,3 +399,22 @@ pub async fn create_screen_capture(
     .map(|v| (v, video_rx))
     .map_err(|e| RecordingError::Media(MediaError::TaskLaunch(e)))
 }
+
+/// Sets up capture of a single application's audio into `audio_tx` if one was requested.
+/// Returns `None` when that isn't possible, in which case the screen capture should provide
+/// full system audio instead.
+pub async fn create_app_audio_capture(
+    app_audio: Option<&AppAudioTarget>,
+    audio_tx: Option<&Sender<(ffmpeg::frame::Audio, f64)>>,
+    start_time: SystemTime,
+) -> Option<AppAudioSource> {
+    let (target, audio_tx) = app_audio.zip(audio_tx)?;
+
+    match AppAudioSource::init(target, audio_tx.clone(), start_time).await {
+        Ok(source) => Some(source),
+        Err(e) => {
+            warn!("{e}, falling back to capturing all system audio");
+            None
+        }
+    }
+}
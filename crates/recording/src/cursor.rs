@@ -231,13 +231,15 @@ pub fn spawn_cursor_recorder(
 }
 
 #[derive(Debug)]
-struct CursorData {
-    image: Vec<u8>,
-    hotspot: XY<f64>,
+pub struct CursorData {
+    /// Encoded image, readable with `image::load_from_memory`
+    pub image: Vec<u8>,
+    /// Relative to the image's size, from 0 to 1
+    pub hotspot: XY<f64>,
 }
 
 #[cfg(target_os = "macos")]
-fn get_cursor_image_data() -> Option<CursorData> {
+pub fn get_cursor_image_data() -> Option<CursorData> {
     use cocoa::base::{id, nil};
     use cocoa::foundation::{NSPoint, NSSize, NSUInteger};
     use objc::rc::autoreleasepool;
@@ -294,7 +296,7 @@ fn get_cursor_image_data() -> Option<CursorData> {
 }
 
 #[cfg(windows)]
-fn get_cursor_image_data() -> Option<CursorData> {
+pub fn get_cursor_image_data() -> Option<CursorData> {
     use windows::Win32::Foundation::{HWND, POINT};
     use windows::Win32::Graphics::Gdi::{
         CreateCompatibleDC, CreateDIBSection, DeleteDC, DeleteObject, GetDC, GetObjectA, ReleaseDC,
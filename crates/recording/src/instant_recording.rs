@@ -9,7 +9,7 @@ use cap_media::{
     feeds::AudioInputFeed,
     pipeline::{Pipeline, RealTimeClock},
     platform::Bounds,
-    sources::{ScreenCaptureSource, ScreenCaptureTarget},
+    sources::{AppAudioSource, ScreenCaptureSource, ScreenCaptureTarget},
     MediaError,
 };
 use cap_project::InstantRecordingMeta;
@@ -19,7 +19,7 @@ use tokio::sync::oneshot;
 use tracing::{debug, error, info, trace, Instrument};
 
 use crate::{
-    capture_pipeline::{create_screen_capture, MakeCapturePipeline},
+    capture_pipeline::{create_app_audio_capture, create_screen_capture, MakeCapturePipeline},
     ActorError, RecordingBaseInputs, RecordingError,
 };
 
@@ -119,6 +119,7 @@ async fn create_pipeline<TCaptureFormat: MakeCapturePipeline>(
     ),
     audio_input_feed: Option<&AudioInputFeed>,
     system_audio: Option<Receiver<(ffmpeg::frame::Audio, f64)>>,
+    app_audio: Option<AppAudioSource>,
 ) -> Result<
     (
         InstantRecordingPipeline,
@@ -127,7 +128,11 @@ async fn create_pipeline<TCaptureFormat: MakeCapturePipeline>(
     MediaError,
 > {
     let clock = RealTimeClock::<()>::new();
-    let pipeline_builder = Pipeline::builder(clock);
+    let mut pipeline_builder = Pipeline::builder(clock);
+
+    if let Some(app_audio) = app_audio {
+        pipeline_builder.spawn_source("app_audio_capture", app_audio);
+    }
 
     let pause_flag = Arc::new(AtomicBool::new(false));
     let system_audio = system_audio.map(|v| (v, screen_source.0.audio_info()));
@@ -168,7 +173,7 @@ pub async fn spawn_instant_recording_actor<'a>(
 > {
     ensure_dir(&recording_dir)?;
 
-    let start_time = SystemTime::now();
+    let start_time = inputs.start_time.unwrap_or_else(SystemTime::now);
 
     let (done_tx, done_rx) = oneshot::channel();
 
@@ -176,19 +181,26 @@ pub async fn spawn_instant_recording_actor<'a>(
 
     let content_dir = ensure_dir(&recording_dir.join("content"))?;
 
-    let system_audio = if inputs.capture_system_audio {
+    let system_audio = if inputs.capture_system_audio || inputs.app_audio.is_some() {
         let (tx, rx) = flume::bounded(64);
         (Some(tx), Some(rx))
     } else {
         (None, None)
     };
 
+    let app_audio = create_app_audio_capture(
+        inputs.app_audio.as_ref(),
+        system_audio.0.as_ref(),
+        start_time,
+    )
+    .await;
+
     let (screen_source, screen_rx) = create_screen_capture(
         &inputs.capture_target,
         true,
         true,
         30,
-        system_audio.0,
+        system_audio.0.filter(|_| app_audio.is_none()),
         start_time,
     )
     .await?;
@@ -204,10 +216,11 @@ pub async fn spawn_instant_recording_actor<'a>(
         (screen_source.clone(), screen_rx.clone()),
         inputs.mic_feed.as_ref(),
         system_audio.1,
+        app_audio,
     )
     .await?;
 
-    let segment_start_time = current_time_f64();
+    let segment_start_time = start_time.duration_since(UNIX_EPOCH).unwrap().as_secs_f64();
 
     let (ctrl_tx, ctrl_rx) = flume::bounded(1);
 
@@ -442,10 +455,3 @@ async fn stop_recording(
         display_source: actor.capture_target,
     })
 }
-
-fn current_time_f64() -> f64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs_f64()
-}
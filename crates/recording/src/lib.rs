@@ -1,12 +1,14 @@
 mod capture_pipeline;
 pub mod cursor;
 pub mod instant_recording;
+pub mod replay_buffer;
 pub mod studio_recording;
 
-use std::sync::Arc;
+use std::{sync::Arc, time::SystemTime};
 
 pub use studio_recording::{
-    spawn_studio_recording_actor, CompletedStudioRecording, StudioRecordingHandle,
+    spawn_audio_recording_actor, spawn_studio_recording_actor, CompletedStudioRecording,
+    StudioRecordingHandle,
 };
 
 use cap_media::{
@@ -42,7 +44,13 @@ pub struct RecordingOptions {
 pub struct RecordingBaseInputs<'a> {
     pub capture_target: ScreenCaptureTarget,
     pub capture_system_audio: bool,
+    /// Restricts system audio to a single application's output where the platform supports it
+    pub app_audio: Option<AppAudioTarget>,
     pub mic_feed: &'a Option<AudioInputFeed>,
+    /// Where the recording's output begins, defaulting to when the recording is spawned.
+    /// Capture starts immediately either way, so a start in the future lets it warm up first.
+    /// Anything captured before the start is dropped.
+    pub start_time: Option<SystemTime>,
 }
 
 #[derive(specta::Type, Serialize, Deserialize, Clone, Debug)]
@@ -0,0 +1,368 @@
+use std::{
+    collections::VecDeque,
+    path::PathBuf,
+    time::{Duration, Instant, SystemTime},
+};
+
+use cap_media::{feeds::AudioInputFeed, sources::ScreenCaptureTarget};
+use cap_project::{
+    AudioMeta, Cursors, FrameTimestamps, MultipleSegment, MultipleSegments, StudioRecordingMeta,
+    VideoMeta,
+};
+use cap_utils::{ensure_dir, spawn_actor};
+use relative_path::RelativePathBuf;
+use tokio::sync::oneshot;
+use tracing::{error, info, trace};
+
+use crate::{
+    studio_recording::{create_segment_pipeline, PipelineOutput, StudioRecordingPipeline},
+    ActorError, RecordingBaseInputs, RecordingError,
+};
+
+// Footage is captured in short chunks so the oldest can be discarded as new ones arrive.
+// Saved replays cover the requested duration at this granularity before being trimmed.
+const CHUNK_SECS: u64 = 5;
+
+pub enum ReplayBufferControlMessage {
+    Save(
+        PathBuf,
+        oneshot::Sender<Result<CompletedReplay, RecordingError>>,
+    ),
+    Stop(oneshot::Sender<Result<(), RecordingError>>),
+}
+
+impl std::fmt::Debug for ReplayBufferControlMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Save(path, _) => write!(f, "Save({})", path.display()),
+            Self::Stop(_) => write!(f, "Stop"),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ReplayBufferHandle {
+    ctrl_tx: flume::Sender<ReplayBufferControlMessage>,
+    pub capture_target: ScreenCaptureTarget,
+    pub duration: Duration,
+}
+
+impl ReplayBufferHandle {
+    /// Copies the buffered footage into `project_path` as a studio recording. Buffering continues afterwards.
+    pub async fn save(&self, project_path: PathBuf) -> Result<CompletedReplay, RecordingError> {
+        let (tx, rx) = oneshot::channel();
+        self.ctrl_tx
+            .send(ReplayBufferControlMessage::Save(project_path, tx))
+            .map_err(|_| flume::SendError(()))
+            .map_err(ActorError::from)?;
+        rx.await.map_err(|_| ActorError::ActorStopped)?
+    }
+
+    /// Stops capturing and deletes everything that was buffered
+    pub async fn stop(&self) -> Result<(), RecordingError> {
+        let (tx, rx) = oneshot::channel();
+        self.ctrl_tx
+            .send(ReplayBufferControlMessage::Stop(tx))
+            .map_err(|_| flume::SendError(()))
+            .map_err(ActorError::from)?;
+        rx.await.map_err(|_| ActorError::ActorStopped)?
+    }
+}
+
+pub struct CompletedReplay {
+    pub project_path: PathBuf,
+    pub meta: StudioRecordingMeta,
+}
+
+struct BufferedChunk {
+    dir: PathBuf,
+    duration: f64,
+    // Paths are relative to `dir` and get rebased when the chunk is saved into a project
+    segment: MultipleSegment,
+}
+
+struct ActiveChunk {
+    pipeline: StudioRecordingPipeline,
+    pipeline_done_rx: oneshot::Receiver<Result<(), String>>,
+    dir: PathBuf,
+    started_at: Instant,
+}
+
+struct ReplayBufferActor {
+    buffer_dir: PathBuf,
+    segments_dir: PathBuf,
+    cursors_dir: PathBuf,
+    capture_target: ScreenCaptureTarget,
+    mic_feed: Option<AudioInputFeed>,
+    capture_system_audio: bool,
+    start_time: SystemTime,
+    duration: f64,
+    next_index: u32,
+    chunks: VecDeque<BufferedChunk>,
+}
+
+impl ReplayBufferActor {
+    async fn start_chunk(&mut self) -> Result<ActiveChunk, RecordingError> {
+        let index = self.next_index;
+        self.next_index += 1;
+
+        let (pipeline, pipeline_done_rx) = create_segment_pipeline(
+            &self.segments_dir,
+            &self.cursors_dir,
+            index,
+            self.capture_target,
+            &self.mic_feed,
+            self.capture_system_audio,
+            None,
+            None,
+            &[],
+            Default::default(),
+            0,
+            false,
+            false,
+            self.start_time,
+        )
+        .await?;
+
+        Ok(ActiveChunk {
+            pipeline,
+            pipeline_done_rx,
+            dir: self.segments_dir.join(format!("segment-{index}")),
+            started_at: Instant::now(),
+        })
+    }
+
+    async fn finish_chunk(&mut self, chunk: ActiveChunk) -> Result<(), RecordingError> {
+        let ActiveChunk {
+            mut pipeline,
+            dir,
+            started_at,
+            ..
+        } = chunk;
+
+        pipeline.inner.shutdown().await?;
+
+        let make_relative =
+            |path: &PathBuf| RelativePathBuf::from_path(path.strip_prefix(&dir).unwrap()).unwrap();
+        let recv_timestamp = |output: &PipelineOutput| output.first_timestamp_rx.try_recv().ok();
+
+        let segment = MultipleSegment {
+            display: pipeline.screen.as_ref().map(|screen| VideoMeta {
+                path: make_relative(&screen.inner.path),
+                fps: screen.video_info.fps(),
+                start_time: recv_timestamp(&screen.inner),
+                frame_timestamps: Some(FrameTimestamps::sidecar_path(&screen.inner.path))
+                    .filter(|path| path.exists())
+                    .map(|path| make_relative(&path)),
+            }),
+            camera: None,
+            additional_cameras: vec![],
+            mic: pipeline.microphone.as_ref().map(|mic| AudioMeta {
+                path: make_relative(&mic.path),
+                start_time: recv_timestamp(mic),
+            }),
+            system_audio: pipeline.system_audio.as_ref().map(|audio| AudioMeta {
+                path: make_relative(&audio.path),
+                start_time: recv_timestamp(audio),
+            }),
+            cursor: None,
+        };
+
+        self.chunks.push_back(BufferedChunk {
+            dir,
+            duration: started_at.elapsed().as_secs_f64(),
+            segment,
+        });
+
+        self.prune();
+
+        Ok(())
+    }
+
+    /// Discards the oldest chunks while the remaining ones still cover the buffer duration,
+    /// so disk usage stays bounded no matter how long the buffer runs
+    fn prune(&mut self) {
+        let mut total = self.chunks.iter().map(|c| c.duration).sum::<f64>();
+
+        while let Some(oldest) = self.chunks.front() {
+            if self.chunks.len() <= 1 || total - oldest.duration < self.duration {
+                break;
+            }
+
+            total -= oldest.duration;
+
+            let Some(oldest) = self.chunks.pop_front() else {
+                break;
+            };
+
+            trace!("discarding replay chunk {}", oldest.dir.display());
+
+            if let Err(e) = std::fs::remove_dir_all(&oldest.dir) {
+                error!("Failed to remove replay chunk: {e}");
+            }
+        }
+    }
+
+    fn save(&self, project_path: &PathBuf) -> Result<CompletedReplay, RecordingError> {
+        let segments_dir = ensure_dir(&project_path.join("content").join("segments"))?;
+
+        let mut segments = Vec::with_capacity(self.chunks.len());
+
+        for (i, chunk) in self.chunks.iter().enumerate() {
+            let dir = ensure_dir(&segments_dir.join(format!("segment-{i}")))?;
+
+            // Finished chunks are never written to again, so linking them is instant where
+            // copying would hold up capture, which is paused until the save is done.
+            // Copying is only needed when the project is on another filesystem.
+            for entry in std::fs::read_dir(&chunk.dir)? {
+                let entry = entry?;
+                let target = dir.join(entry.file_name());
+                if std::fs::hard_link(entry.path(), &target).is_err() {
+                    std::fs::copy(entry.path(), &target)?;
+                }
+            }
+
+            let relative_dir = RelativePathBuf::from(format!("content/segments/segment-{i}"));
+
+            let mut segment = chunk.segment.clone();
+            if let Some(display) = &mut segment.display {
+                display.path = relative_dir.join(&display.path);
+                if let Some(frame_timestamps) = &mut display.frame_timestamps {
+                    *frame_timestamps = relative_dir.join(&*frame_timestamps);
+                }
+            }
+            if let Some(mic) = &mut segment.mic {
+                mic.path = relative_dir.join(&mic.path);
+            }
+            if let Some(system_audio) = &mut segment.system_audio {
+                system_audio.path = relative_dir.join(&system_audio.path);
+            }
+
+            segments.push(segment);
+        }
+
+        Ok(CompletedReplay {
+            project_path: project_path.clone(),
+            meta: StudioRecordingMeta::MultipleSegments {
+                inner: MultipleSegments {
+                    segments,
+                    cursors: Cursors::default(),
+                },
+            },
+        })
+    }
+}
+
+/// Continuously captures `inputs.capture_target` into a rolling on-disk buffer holding
+/// at least the last `duration` of footage. Only a few seconds beyond `duration` are ever kept.
+pub async fn spawn_replay_buffer_actor<'a>(
+    buffer_dir: PathBuf,
+    inputs: RecordingBaseInputs<'a>,
+    duration: Duration,
+) -> Result<ReplayBufferHandle, RecordingError> {
+    // Leftovers from a previous session that didn't shut down cleanly
+    if buffer_dir.exists() {
+        std::fs::remove_dir_all(&buffer_dir)?;
+    }
+
+    let segments_dir = ensure_dir(&buffer_dir.join("segments"))?;
+    let cursors_dir = ensure_dir(&buffer_dir.join("cursors"))?;
+
+    let mut actor = ReplayBufferActor {
+        buffer_dir,
+        segments_dir,
+        cursors_dir,
+        capture_target: inputs.capture_target,
+        mic_feed: inputs.mic_feed.clone(),
+        capture_system_audio: inputs.capture_system_audio,
+        start_time: SystemTime::now(),
+        duration: duration.as_secs_f64(),
+        next_index: 0,
+        chunks: VecDeque::new(),
+    };
+
+    let mut active = actor.start_chunk().await?;
+
+    let (ctrl_tx, ctrl_rx) = flume::bounded(1);
+
+    trace!("spawning replay buffer actor");
+
+    spawn_actor(async move {
+        let stop_response = loop {
+            let rotate_at = active.started_at + Duration::from_secs(CHUNK_SECS);
+
+            tokio::select! {
+                _ = tokio::time::sleep_until(rotate_at.into()) => {
+                    if let Err(e) = actor.finish_chunk(active).await {
+                        error!("Failed to finish replay chunk: {e}");
+                        break None;
+                    }
+
+                    match actor.start_chunk().await {
+                        Ok(chunk) => active = chunk,
+                        Err(e) => {
+                            error!("Failed to start replay chunk: {e}");
+                            break None;
+                        }
+                    }
+                },
+                result = &mut active.pipeline_done_rx => {
+                    error!("Replay buffer pipeline stopped unexpectedly: {result:?}");
+                    break None;
+                },
+                msg = ctrl_rx.recv_async() => {
+                    info!("received control message: {msg:?}");
+
+                    match msg {
+                        Ok(ReplayBufferControlMessage::Save(project_path, tx)) => {
+                            // Wait for minimum chunk duration
+                            tokio::time::sleep_until(
+                                (active.started_at + Duration::from_secs(1)).into(),
+                            )
+                            .await;
+
+                            let result = match actor.finish_chunk(active).await {
+                                Ok(()) => actor.save(&project_path),
+                                Err(e) => Err(e),
+                            };
+                            let _ = tx.send(result);
+
+                            match actor.start_chunk().await {
+                                Ok(chunk) => active = chunk,
+                                Err(e) => {
+                                    error!("Failed to start replay chunk: {e}");
+                                    break None;
+                                }
+                            }
+                        }
+                        Ok(ReplayBufferControlMessage::Stop(tx)) => {
+                            let result = active.pipeline.inner.shutdown().await;
+                            break Some((tx, result.map_err(RecordingError::from)));
+                        }
+                        Err(_) => {
+                            let _ = active.pipeline.inner.shutdown().await;
+                            break None;
+                        }
+                    }
+                }
+            }
+        };
+
+        actor.chunks.clear();
+        if let Err(e) = std::fs::remove_dir_all(&actor.buffer_dir) {
+            error!("Failed to remove replay buffer: {e}");
+        }
+
+        info!("replay buffer actor finished");
+
+        if let Some((tx, result)) = stop_response {
+            let _ = tx.send(result);
+        }
+    });
+
+    Ok(ReplayBufferHandle {
+        ctrl_tx,
+        capture_target: inputs.capture_target,
+        duration,
+    })
+}
@@ -9,9 +9,11 @@ use cap_media::{
     data::VideoInfo,
     encoders::{H264Encoder, MP4File, OggFile, OpusEncoder},
     feeds::{AudioInputFeed, CameraFeed},
-    pipeline::{Pipeline, RealTimeClock},
+    pipeline::{builder::PipelineBuilder, Pipeline, RealTimeClock},
     platform::Bounds,
-    sources::{AudioInputSource, CameraSource, ScreenCaptureFormat, ScreenCaptureTarget},
+    sources::{
+        AppAudioTarget, AudioInputSource, CameraSource, ScreenCaptureFormat, ScreenCaptureTarget,
+    },
     MediaError,
 };
 use cap_project::{CursorEvents, StudioRecordingMeta};
@@ -22,7 +24,9 @@ use tokio::sync::{oneshot, Mutex};
 use tracing::{debug, info, trace};
 
 use crate::{
-    capture_pipeline::{create_screen_capture, MakeCapturePipeline, ScreenCaptureMethod},
+    capture_pipeline::{
+        create_app_audio_capture, create_screen_capture, MakeCapturePipeline, ScreenCaptureMethod,
+    },
     cursor::{spawn_cursor_recorder, CursorActor, Cursors},
     ActorError, RecordingBaseInputs, RecordingError,
 };
@@ -74,16 +78,19 @@ pub struct ScreenPipelineOutput {
     pub video_info: VideoInfo,
 }
 
-struct StudioRecordingPipeline {
+pub(crate) struct StudioRecordingPipeline {
     pub inner: Pipeline<RealTimeClock<()>>,
-    pub screen: ScreenPipelineOutput,
+    /// `None` for audio-only recordings
+    pub screen: Option<ScreenPipelineOutput>,
     pub microphone: Option<PipelineOutput>,
     pub camera: Option<CameraPipelineInfo>,
+    /// Indexed by slot starting at slot 1, with `None` for slots that aren't in use
+    pub additional_cameras: Vec<Option<CameraPipelineInfo>>,
     pub cursor: Option<CursorPipeline>,
     pub system_audio: Option<PipelineOutput>,
 }
 
-struct CursorPipeline {
+pub(crate) struct CursorPipeline {
     output_path: PathBuf,
     actor: Option<CursorActor>,
 }
@@ -129,7 +136,40 @@ pub async fn spawn_studio_recording_actor<'a>(
     recording_dir: PathBuf,
     base_inputs: RecordingBaseInputs<'a>,
     camera_feed: Option<Arc<Mutex<CameraFeed>>>,
+    additional_camera_feeds: Vec<Option<Arc<Mutex<CameraFeed>>>>,
+    custom_cursor_capture: bool,
+) -> Result<(StudioRecordingHandle, oneshot::Receiver<Result<(), String>>), RecordingError> {
+    spawn_recording_actor(
+        id,
+        recording_dir,
+        base_inputs,
+        camera_feed,
+        additional_camera_feeds,
+        custom_cursor_capture,
+        false,
+    )
+    .await
+}
+
+/// Records just the microphone and system audio, as a studio recording with no display video.
+/// The capture target is still used for system audio on platforms that capture it alongside
+/// the screen, but no video is written.
+pub async fn spawn_audio_recording_actor<'a>(
+    id: String,
+    recording_dir: PathBuf,
+    base_inputs: RecordingBaseInputs<'a>,
+) -> Result<(StudioRecordingHandle, oneshot::Receiver<Result<(), String>>), RecordingError> {
+    spawn_recording_actor(id, recording_dir, base_inputs, None, vec![], false, true).await
+}
+
+async fn spawn_recording_actor<'a>(
+    id: String,
+    recording_dir: PathBuf,
+    base_inputs: RecordingBaseInputs<'a>,
+    camera_feed: Option<Arc<Mutex<CameraFeed>>>,
+    additional_camera_feeds: Vec<Option<Arc<Mutex<CameraFeed>>>>,
     custom_cursor_capture: bool,
+    audio_only: bool,
 ) -> Result<(StudioRecordingHandle, oneshot::Receiver<Result<(), String>>), RecordingError> {
     ensure_dir(&recording_dir)?;
 
@@ -142,7 +182,7 @@ pub async fn spawn_studio_recording_actor<'a>(
     let segments_dir = ensure_dir(&content_dir.join("segments"))?;
     let cursors_dir = ensure_dir(&content_dir.join("cursors"))?;
 
-    let start_time = SystemTime::now();
+    let start_time = base_inputs.start_time.unwrap_or_else(SystemTime::now);
 
     // let bounds = screen_source.get_bounds().clone();
 
@@ -154,6 +194,14 @@ pub async fn spawn_studio_recording_actor<'a>(
         debug!("camera video info: {:#?}", camera_feed.video_info());
     }
 
+    for camera_feed in additional_camera_feeds.iter().flatten() {
+        let camera_feed = camera_feed.lock().await;
+        debug!(
+            "additional camera device info: {:#?}",
+            camera_feed.camera_info()
+        );
+    }
+
     if let Some(audio_feed) = base_inputs.mic_feed {
         debug!("mic audio info: {:#?}", audio_feed.audio_info())
     }
@@ -165,8 +213,11 @@ pub async fn spawn_studio_recording_actor<'a>(
         base_inputs.capture_target.clone(),
         audio_input_feed,
         base_inputs.capture_system_audio,
+        base_inputs.app_audio.clone(),
         camera_feed,
+        additional_camera_feeds,
         custom_cursor_capture,
+        audio_only,
         start_time,
     );
 
@@ -175,18 +226,26 @@ pub async fn spawn_studio_recording_actor<'a>(
         .create_next(Default::default(), 0)
         .await?;
 
-    let segment_start_time = current_time_f64();
+    let segment_start_time = start_time.duration_since(UNIX_EPOCH).unwrap().as_secs_f64();
 
     let (ctrl_tx, ctrl_rx) = flume::bounded(1);
 
     trace!("spawning recording actor");
 
-    let bounds = pipeline.screen.bounds;
+    let bounds = pipeline
+        .screen
+        .as_ref()
+        .map(|screen| screen.bounds)
+        .unwrap_or_default();
 
     debug!("screen bounds: {bounds:?}");
 
     let base_inputs = base_inputs.clone();
-    let fps = pipeline.screen.video_info.fps();
+    let fps = pipeline
+        .screen
+        .as_ref()
+        .map(|screen| screen.video_info.fps())
+        .unwrap_or_default();
 
     spawn_actor(async move {
         let mut actor = StudioRecordingActor {
@@ -488,16 +547,35 @@ async fn stop_recording(
                     .segments
                     .iter()
                     .map(|s| MultipleSegment {
-                        display: VideoMeta {
-                            path: make_relative(&s.pipeline.screen.inner.path),
+                        display: s.pipeline.screen.as_ref().map(|screen| VideoMeta {
+                            path: make_relative(&screen.inner.path),
                             fps: actor.fps,
-                            start_time: recv_timestamp(&s.pipeline.screen.inner),
-                        },
+                            start_time: recv_timestamp(&screen.inner),
+                            frame_timestamps: Some(FrameTimestamps::sidecar_path(
+                                &screen.inner.path,
+                            ))
+                            .filter(|path| path.exists())
+                            .map(|path| make_relative(&path)),
+                        }),
                         camera: s.pipeline.camera.as_ref().map(|camera| VideoMeta {
                             path: make_relative(&camera.inner.path),
                             fps: camera.fps,
                             start_time: recv_timestamp(&camera.inner),
+                            frame_timestamps: None,
                         }),
+                        additional_cameras: s
+                            .pipeline
+                            .additional_cameras
+                            .iter()
+                            .map(|camera| {
+                                camera.as_ref().map(|camera| VideoMeta {
+                                    path: make_relative(&camera.inner.path),
+                                    fps: camera.fps,
+                                    start_time: recv_timestamp(&camera.inner),
+                                    frame_timestamps: None,
+                                })
+                            })
+                            .collect(),
                         mic: s.pipeline.microphone.as_ref().map(|mic| AudioMeta {
                             path: make_relative(&mic.path),
                             start_time: recv_timestamp(&mic),
@@ -553,8 +631,11 @@ struct SegmentPipelineFactory {
     capture_target: ScreenCaptureTarget,
     audio_input_feed: Option<AudioInputFeed>,
     capture_system_audio: bool,
+    app_audio: Option<AppAudioTarget>,
     camera_feed: Option<Arc<Mutex<CameraFeed>>>,
+    additional_camera_feeds: Vec<Option<Arc<Mutex<CameraFeed>>>>,
     custom_cursor_capture: bool,
+    audio_only: bool,
     start_time: SystemTime,
     index: u32,
 }
@@ -566,8 +647,11 @@ impl SegmentPipelineFactory {
         capture_target: ScreenCaptureTarget,
         audio_input_feed: Option<AudioInputFeed>,
         capture_system_audio: bool,
+        app_audio: Option<AppAudioTarget>,
         camera_feed: Option<Arc<Mutex<CameraFeed>>>,
+        additional_camera_feeds: Vec<Option<Arc<Mutex<CameraFeed>>>>,
         custom_cursor_capture: bool,
+        audio_only: bool,
         start_time: SystemTime,
     ) -> Self {
         Self {
@@ -576,8 +660,11 @@ impl SegmentPipelineFactory {
             capture_target,
             audio_input_feed,
             capture_system_audio,
+            app_audio,
             camera_feed,
+            additional_camera_feeds,
             custom_cursor_capture,
+            audio_only,
             start_time,
             index: 0,
         }
@@ -601,10 +688,13 @@ impl SegmentPipelineFactory {
             self.capture_target,
             &self.audio_input_feed,
             self.capture_system_audio,
+            self.app_audio.as_ref(),
             self.camera_feed.as_deref(),
+            &self.additional_camera_feeds,
             cursors,
             next_cursors_id,
             self.custom_cursor_capture,
+            self.audio_only,
             self.start_time.clone(),
         )
         .await?;
@@ -616,17 +706,20 @@ impl SegmentPipelineFactory {
 }
 
 #[tracing::instrument(skip_all, name = "segment", fields(index = index))]
-async fn create_segment_pipeline(
+pub(crate) async fn create_segment_pipeline(
     segments_dir: &PathBuf,
     cursors_dir: &PathBuf,
     index: u32,
     capture_target: ScreenCaptureTarget,
     mic_feed: &Option<AudioInputFeed>,
     capture_system_audio: bool,
+    app_audio: Option<&AppAudioTarget>,
     camera_feed: Option<&Mutex<CameraFeed>>,
+    additional_camera_feeds: &[Option<Arc<Mutex<CameraFeed>>>],
     prev_cursors: Cursors,
     next_cursors_id: u32,
     custom_cursor_capture: bool,
+    audio_only: bool,
     start_time: SystemTime,
 ) -> Result<
     (
@@ -635,23 +728,36 @@ async fn create_segment_pipeline(
     ),
     RecordingError,
 > {
-    let system_audio = if capture_system_audio {
+    let system_audio = if capture_system_audio || app_audio.is_some() {
         let (tx, rx) = flume::bounded(64);
         (Some(tx), Some(rx))
     } else {
         (None, None)
     };
 
-    let (screen_source, screen_rx) = create_screen_capture(
-        &capture_target,
-        false,
-        !custom_cursor_capture,
-        120,
-        system_audio.0,
-        start_time,
-    )
-    .await?;
-    let screen_crop_ratio = screen_source.crop_ratio();
+    let app_audio = create_app_audio_capture(app_audio, system_audio.0.as_ref(), start_time).await;
+    // A single app's audio replaces the screen capture's system audio
+    let screen_audio_tx = system_audio.0.filter(|_| app_audio.is_none());
+
+    // Audio-only recordings skip the screen capture unless it's needed for system audio
+    let screen_capture = if !audio_only || screen_audio_tx.is_some() {
+        Some(
+            create_screen_capture(
+                &capture_target,
+                false,
+                !custom_cursor_capture,
+                if audio_only { 1 } else { 120 },
+                screen_audio_tx,
+                start_time,
+            )
+            .await?,
+        )
+    } else {
+        None
+    };
+    let screen_crop_ratio = screen_capture
+        .as_ref()
+        .map(|(screen_source, _)| screen_source.crop_ratio());
 
     let camera_feed = match camera_feed.as_ref() {
         Some(camera_feed) => Some(camera_feed.lock().await),
@@ -668,34 +774,48 @@ async fn create_segment_pipeline(
 
     trace!("preparing segment pipeline {index}");
 
-    let screen = {
-        let bounds = screen_source.get_bounds().clone();
-        let video_info = screen_source.info();
-
-        let (pipeline_builder_, screen_timestamp_rx) =
-            ScreenCaptureMethod::make_studio_mode_pipeline(
-                pipeline_builder,
-                (screen_source, screen_rx),
-                screen_output_path.clone(),
-            )?;
-        pipeline_builder = pipeline_builder_;
-
-        info!(
-            r#"screen pipeline prepared, will output to "{}""#,
-            screen_output_path
-                .strip_prefix(&segments_dir)
-                .unwrap()
-                .display()
-        );
+    let screen = match screen_capture {
+        Some((screen_source, screen_rx)) if audio_only => {
+            pipeline_builder.spawn_source("screen_capture", screen_source);
+            // Only the capture's system audio is kept
+            pipeline_builder.spawn_task("screen_discard", move |ready| {
+                let _ = ready.send(Ok(()));
+                while screen_rx.recv().is_ok() {}
+                Ok(())
+            });
 
-        ScreenPipelineOutput {
-            inner: PipelineOutput {
-                path: screen_output_path,
-                first_timestamp_rx: screen_timestamp_rx,
-            },
-            bounds,
-            video_info,
+            None
         }
+        Some((screen_source, screen_rx)) => {
+            let bounds = screen_source.get_bounds().clone();
+            let video_info = screen_source.info();
+
+            let (pipeline_builder_, screen_timestamp_rx) =
+                ScreenCaptureMethod::make_studio_mode_pipeline(
+                    pipeline_builder,
+                    (screen_source, screen_rx),
+                    screen_output_path.clone(),
+                )?;
+            pipeline_builder = pipeline_builder_;
+
+            info!(
+                r#"screen pipeline prepared, will output to "{}""#,
+                screen_output_path
+                    .strip_prefix(&segments_dir)
+                    .unwrap()
+                    .display()
+            );
+
+            Some(ScreenPipelineOutput {
+                inner: PipelineOutput {
+                    path: screen_output_path,
+                    first_timestamp_rx: screen_timestamp_rx,
+                },
+                bounds,
+                video_info,
+            })
+        }
+        None => None,
     };
 
     let microphone = if let Some(mic_source) = mic_feed {
@@ -743,6 +863,10 @@ async fn create_segment_pipeline(
         None
     };
 
+    if let Some(app_audio) = app_audio {
+        pipeline_builder.spawn_source("app_audio_capture", app_audio);
+    }
+
     let system_audio = if let Some((config, channel)) =
         Some(ScreenCaptureMethod::audio_info()).zip(system_audio.1.clone())
     {
@@ -778,98 +902,73 @@ async fn create_segment_pipeline(
         None
     };
 
-    let camera = if let Some(camera_feed) = camera_feed {
-        let (tx, rx) = flume::bounded(8);
-
-        let camera_source = CameraSource::init(camera_feed, tx, start_time);
-        let camera_config = camera_source.info();
-        let output_path = dir.join("camera.mp4");
-
-        let mut camera_encoder = MP4File::init(
-            "camera",
-            output_path.clone(),
-            |o| H264Encoder::builder("camera", camera_config).build(o),
-            |_| None,
-        )
-        .map_err(|e| RecordingError::Media(e.into()))?;
-
-        pipeline_builder.spawn_source("camera_capture", camera_source);
-
-        let (timestamp_tx, timestamp_rx) = flume::bounded(1);
-
-        pipeline_builder.spawn_task("camera_encoder", move |ready| {
-            let mut timestamp_tx = Some(timestamp_tx);
-            let _ = ready.send(Ok(()));
-
-            let mut start = None;
-            while let Ok(mut frame) = rx.recv() {
-                if let Some(timestamp_tx) = timestamp_tx.take() {
-                    timestamp_tx.send(frame.1).unwrap();
-                }
-
-                if let Some(start) = start {
-                    frame.0.set_pts(Some(
-                        ((camera_config.time_base.denominator() as f64
-                            / camera_config.time_base.numerator() as f64)
-                            * (frame.1 - start)) as i64,
-                    ));
-                } else {
-                    start = Some(frame.1);
-                    frame.0.set_pts(Some(0));
-                }
-
-                camera_encoder.queue_video_frame(frame.0);
-            }
-            camera_encoder.finish();
-            Ok(())
-        });
-
-        info!(
-            "camera pipeline prepared, will output to {}",
-            output_path.strip_prefix(&segments_dir).unwrap().display()
-        );
-
-        Some(CameraPipelineInfo {
-            inner: PipelineOutput {
-                path: output_path,
-                first_timestamp_rx: timestamp_rx,
-            },
-            fps: (camera_config.frame_rate.0 / camera_config.frame_rate.1) as u32,
+    let camera = camera_feed
+        .map(|camera_feed| {
+            create_camera_pipeline(
+                &mut pipeline_builder,
+                camera_feed,
+                "camera",
+                dir.join("camera.mp4"),
+                segments_dir,
+                start_time,
+            )
         })
-    } else {
-        None
-    };
+        .transpose()?;
 
-    let cursor = custom_cursor_capture.then(move || {
-        let cursor = spawn_cursor_recorder(
-            screen.bounds.clone(),
-            #[cfg(target_os = "macos")]
-            cap_displays::Display::list()
-                .into_iter()
-                .find(|m| match &capture_target {
-                    ScreenCaptureTarget::Screen { id }
-                    | ScreenCaptureTarget::Area { screen: id, .. } => {
-                        m.raw_handle().inner().id == *id
-                    }
-                    ScreenCaptureTarget::Window { id } => {
-                        m.raw_handle().inner().id
-                            == cap_media::platform::display_for_window(*id).unwrap().id
-                    }
-                })
-                .unwrap(),
-            #[cfg(target_os = "macos")]
-            screen_crop_ratio,
-            cursors_dir.clone(),
-            prev_cursors,
-            next_cursors_id,
+    let mut additional_cameras = vec![];
+    for (i, camera_feed) in additional_camera_feeds.iter().enumerate() {
+        let Some(camera_feed) = camera_feed else {
+            additional_cameras.push(None);
+            continue;
+        };
+        let camera_feed = camera_feed.lock().await;
+        // Slot 0 is the primary camera, so additional cameras are numbered from 1
+        let name = format!("camera-{}", i + 1);
+
+        additional_cameras.push(Some(create_camera_pipeline(
+            &mut pipeline_builder,
+            &camera_feed,
+            &name,
+            dir.join(format!("{name}.mp4")),
+            segments_dir,
             start_time,
-        );
+        )?));
+    }
 
-        CursorPipeline {
-            output_path: dir.join("cursor.json"),
-            actor: Some(cursor),
-        }
-    });
+    let cursor = screen
+        .as_ref()
+        .filter(|_| custom_cursor_capture)
+        .map(move |screen| {
+            let cursor = spawn_cursor_recorder(
+                screen.bounds.clone(),
+                #[cfg(target_os = "macos")]
+                cap_displays::Display::list()
+                    .into_iter()
+                    .find(|m| match &capture_target {
+                        ScreenCaptureTarget::Screen { id }
+                        | ScreenCaptureTarget::Area { screen: id, .. } => {
+                            m.raw_handle().inner().id == *id
+                        }
+                        ScreenCaptureTarget::Window { id } => {
+                            m.raw_handle().inner().id
+                                == cap_media::platform::display_for_window(*id).unwrap().id
+                        }
+                    })
+                    .unwrap(),
+                // There's always a crop ratio when there's a screen pipeline
+                #[cfg(target_os = "macos")]
+                screen_crop_ratio.unwrap(),
+                cursors_dir.clone(),
+                prev_cursors,
+                next_cursors_id,
+                start_time,
+            );
+
+            CursorPipeline {
+                output_path: dir.join("cursor.json"),
+                actor: Some(cursor),
+            }
+        });
 
     let (mut pipeline, pipeline_done_rx) = pipeline_builder.build().await?;
 
@@ -883,6 +982,7 @@ async fn create_segment_pipeline(
             screen,
             microphone,
             camera,
+            additional_cameras,
             cursor,
             system_audio,
         },
@@ -890,7 +990,73 @@ async fn create_segment_pipeline(
     ))
 }
 
-struct CameraPipelineInfo {
+fn create_camera_pipeline(
+    pipeline_builder: &mut PipelineBuilder<RealTimeClock<()>>,
+    camera_feed: &CameraFeed,
+    name: &str,
+    output_path: PathBuf,
+    segments_dir: &PathBuf,
+    start_time: SystemTime,
+) -> Result<CameraPipelineInfo, RecordingError> {
+    let (tx, rx) = flume::bounded(8);
+
+    let camera_source = CameraSource::init(camera_feed, tx, start_time);
+    let camera_config = camera_source.info();
+
+    let mut camera_encoder = MP4File::init(
+        "camera",
+        output_path.clone(),
+        |o| H264Encoder::builder("camera", camera_config).build(o),
+        |_| None,
+    )
+    .map_err(|e| RecordingError::Media(e.into()))?;
+
+    pipeline_builder.spawn_source(format!("{name}_capture"), camera_source);
+
+    let (timestamp_tx, timestamp_rx) = flume::bounded(1);
+
+    pipeline_builder.spawn_task(format!("{name}_encoder"), move |ready| {
+        let mut timestamp_tx = Some(timestamp_tx);
+        let _ = ready.send(Ok(()));
+
+        let mut start = None;
+        while let Ok(mut frame) = rx.recv() {
+            if let Some(timestamp_tx) = timestamp_tx.take() {
+                timestamp_tx.send(frame.1).unwrap();
+            }
+
+            if let Some(start) = start {
+                frame.0.set_pts(Some(
+                    ((camera_config.time_base.denominator() as f64
+                        / camera_config.time_base.numerator() as f64)
+                        * (frame.1 - start)) as i64,
+                ));
+            } else {
+                start = Some(frame.1);
+                frame.0.set_pts(Some(0));
+            }
+
+            camera_encoder.queue_video_frame(frame.0);
+        }
+        camera_encoder.finish();
+        Ok(())
+    });
+
+    info!(
+        "{name} pipeline prepared, will output to {}",
+        output_path.strip_prefix(&segments_dir).unwrap().display()
+    );
+
+    Ok(CameraPipelineInfo {
+        inner: PipelineOutput {
+            path: output_path,
+            first_timestamp_rx: timestamp_rx,
+        },
+        fps: (camera_config.frame_rate.0 / camera_config.frame_rate.1) as u32,
+    })
+}
+
+pub(crate) struct CameraPipelineInfo {
     inner: PipelineOutput,
     fps: u32,
 }
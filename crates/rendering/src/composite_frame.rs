@@ -1,7 +1,7 @@
 use bytemuck::{Pod, Zeroable};
 use wgpu::{include_wgsl, util::DeviceExt};
 
-use crate::create_shader_render_pipeline;
+use crate::{create_shader_render_pipeline_with_layouts, lut::ColorLut};
 
 pub struct CompositeVideoFramePipeline {
     pub bind_group_layout: wgpu::BindGroupLayout,
@@ -25,7 +25,11 @@ pub struct CompositeVideoFrameUniforms {
     pub shadow_size: f32,
     pub shadow_opacity: f32,
     pub shadow_blur: f32,
-    pub _padding: [f32; 3],
+    /// Clockwise quarter turns applied to the frame before cropping
+    pub rotation: f32,
+    pub flip_x: f32,
+    pub flip_y: f32,
+    pub _padding: [f32; 1],
 }
 
 impl CompositeVideoFrameUniforms {
@@ -50,9 +54,10 @@ pub struct CompositeFrameResources {
 impl CompositeVideoFramePipeline {
     pub fn new(device: &wgpu::Device) -> Self {
         let bind_group_layout = Self::bind_group_layout(device);
-        let render_pipeline = create_shader_render_pipeline(
+        // The color LUT is bound separately as it's shared between the screen and camera layers
+        let render_pipeline = create_shader_render_pipeline_with_layouts(
             device,
-            &bind_group_layout,
+            &[&bind_group_layout, &ColorLut::bind_group_layout(device)],
             include_wgsl!("shaders/composite-video-frame.wgsl"),
         );
 
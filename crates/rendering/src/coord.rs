@@ -14,6 +14,10 @@ pub struct RawDisplayUVSpace;
 #[derive(Default, Clone, Copy, Debug)]
 pub struct CroppedDisplaySpace;
 
+// 0-1 across the cropped region of the display
+#[derive(Default, Clone, Copy, Debug)]
+pub struct CroppedDisplayUVSpace;
+
 #[derive(Default, Clone, Copy, Debug)]
 pub struct FrameSpace;
 
@@ -57,8 +61,14 @@ impl<T> Deref for Coord<T> {
 }
 
 impl Coord<RawDisplayUVSpace> {
-    pub fn to_raw_display_space(&self, options: &RenderOptions) -> Coord<RawDisplaySpace> {
-        Coord::new(self.coord * options.screen_size.map(|v| v as f64))
+    /// Raw display space is rotated and flipped along with the frame, so crops line up with it
+    pub fn to_raw_display_space(
+        &self,
+        options: &RenderOptions,
+        project: &ProjectConfiguration,
+    ) -> Coord<RawDisplaySpace> {
+        let screen_size = ProjectUniforms::get_screen_size(options, project);
+        Coord::new(project.orient_uv(self.coord) * screen_size.map(|v| v as f64))
     }
 
     pub fn to_frame_space(
@@ -67,10 +77,24 @@ impl Coord<RawDisplayUVSpace> {
         project: &ProjectConfiguration,
         resolution_base: XY<u32>,
     ) -> Coord<FrameSpace> {
-        self.to_raw_display_space(options)
+        self.to_raw_display_space(options, project)
             .to_cropped_display_space(options, project)
             .to_frame_space(options, project, resolution_base)
     }
+
+    pub fn to_cropped_display_uv_space(
+        &self,
+        options: &RenderOptions,
+        project: &ProjectConfiguration,
+    ) -> Coord<CroppedDisplayUVSpace> {
+        let crop = ProjectUniforms::get_crop(options, project);
+        let cropped = self
+            .to_raw_display_space(options, project)
+            .to_cropped_display_space(options, project);
+
+        Coord::new(cropped.coord / crop.size.map(|v| v.max(1) as f64))
+            .clamp(XY::new(0.0, 0.0), XY::new(1.0, 1.0))
+    }
 }
 
 impl Coord<RawDisplaySpace> {
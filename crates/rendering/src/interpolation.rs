@@ -0,0 +1,179 @@
+use std::sync::Arc;
+
+use ffmpeg::{filter, format, frame};
+use ffmpeg_sys_next::EAGAIN;
+use tracing::warn;
+
+use crate::decoder::{AsyncVideoDecoderHandle, DecodedFrame};
+
+// Source frames fed without getting an output before interpolation is considered stuck
+const MAX_PENDING_FRAMES: u32 = 8;
+
+/// Synthesizes in-between screen frames for a slowed down timeline segment using ffmpeg's
+/// motion compensated `minterpolate` filter.
+/// Frames are requested in order, one per output frame of the segment.
+pub struct FrameInterpolator {
+    graph: filter::Graph,
+    width: u32,
+    height: u32,
+    source_fps: u32,
+    /// Segment time of the first source frame fed to the filter
+    start: f64,
+    next_source_frame: u32,
+    last_output: Option<(i64, DecodedFrame)>,
+    failed: bool,
+}
+
+impl FrameInterpolator {
+    /// `fps` is the export frame rate, and `timescale` the segment's speed
+    pub fn new(
+        width: u32,
+        height: u32,
+        source_fps: u32,
+        fps: u32,
+        timescale: f64,
+        start: f64,
+    ) -> Result<Self, ffmpeg::Error> {
+        let mut graph = filter::Graph::new();
+
+        graph.add(
+            &filter::find("buffer").ok_or(ffmpeg::Error::FilterNotFound)?,
+            "in",
+            &format!(
+                "video_size={width}x{height}:pix_fmt=rgba:time_base=1/{source_fps}:pixel_aspect=1/1"
+            ),
+        )?;
+        graph.add(
+            &filter::find("buffersink").ok_or(ffmpeg::Error::FilterNotFound)?,
+            "out",
+            "",
+        )?;
+
+        // Output frames are spaced so that, once slowed down, there's one per exported frame.
+        // minterpolate only works on YUV so convert around it.
+        let timescale_millis = (timescale * 1000.0).round().max(1.0) as u32;
+        let spec = format!(
+            "format=yuv444p,minterpolate=fps={}/{timescale_millis}:mi_mode=mci:mc_mode=aobmc:vsbmc=1,format=rgba",
+            fps * 1000
+        );
+
+        graph.output("in", 0)?.input("out", 0)?.parse(&spec)?;
+        graph.validate()?;
+
+        Ok(Self {
+            graph,
+            width,
+            height,
+            source_fps: source_fps.max(1),
+            start,
+            next_source_frame: 0,
+            last_output: None,
+            failed: false,
+        })
+    }
+
+    /// The interpolated frame for `output_frame`, counted from the start of the segment.
+    /// Returns `None` if interpolation has failed, in which case the decoded frame should be used.
+    pub async fn get_frame(
+        &mut self,
+        decoder: &AsyncVideoDecoderHandle,
+        output_frame: u32,
+    ) -> Option<DecodedFrame> {
+        if self.failed {
+            return None;
+        }
+
+        match self.interpolate(decoder, output_frame as i64).await {
+            Ok(frame) => Some(frame),
+            Err(e) => {
+                warn!("Frame interpolation failed, duplicating frames instead: {e}");
+                self.failed = true;
+                None
+            }
+        }
+    }
+
+    async fn interpolate(
+        &mut self,
+        decoder: &AsyncVideoDecoderHandle,
+        output_frame: i64,
+    ) -> Result<DecodedFrame, String> {
+        let mut pending = 0;
+
+        loop {
+            if let Some((pts, frame)) = &self.last_output {
+                if *pts >= output_frame {
+                    return Ok(frame.clone());
+                }
+            }
+
+            let mut output = frame::Video::empty();
+            match self.graph.get("out").unwrap().sink().frame(&mut output) {
+                Ok(()) => {
+                    let pts = output.pts().unwrap_or_default();
+                    self.last_output = Some((pts, self.to_decoded(&output)?));
+                    continue;
+                }
+                Err(ffmpeg::Error::Other { errno }) if errno == EAGAIN => {}
+                Err(e) => return Err(e.to_string()),
+            }
+
+            if pending >= MAX_PENDING_FRAMES {
+                return Err("No output after feeding source frames".to_string());
+            }
+
+            let time = self.start + self.next_source_frame as f64 / self.source_fps as f64;
+            let source = decoder
+                .get_frame(time as f32)
+                .await
+                .ok_or_else(|| "Failed to decode source frame".to_string())?;
+
+            let input = self.to_video(&source, self.next_source_frame as i64)?;
+            self.graph
+                .get("in")
+                .unwrap()
+                .source()
+                .add(&input)
+                .map_err(|e| e.to_string())?;
+
+            self.next_source_frame += 1;
+            pending += 1;
+        }
+    }
+
+    fn to_video(&self, data: &[u8], pts: i64) -> Result<frame::Video, String> {
+        let row_bytes = self.width as usize * 4;
+        if data.len() != row_bytes * self.height as usize {
+            return Err("Source frame doesn't match the recording's size".to_string());
+        }
+
+        let mut video = frame::Video::new(format::Pixel::RGBA, self.width, self.height);
+        let stride = video.stride(0);
+        for (row, line) in data.chunks_exact(row_bytes).enumerate() {
+            video.data_mut(0)[row * stride..row * stride + row_bytes].copy_from_slice(line);
+        }
+        video.set_pts(Some(pts));
+
+        Ok(video)
+    }
+
+    fn to_decoded(&self, video: &frame::Video) -> Result<DecodedFrame, String> {
+        if video.width() != self.width || video.height() != self.height {
+            return Err("Interpolated frame changed size".to_string());
+        }
+
+        let row_bytes = self.width as usize * 4;
+        let mut data = Vec::with_capacity(row_bytes * self.height as usize);
+
+        // account for stride > width
+        for line in video
+            .data(0)
+            .chunks(video.stride(0))
+            .take(self.height as usize)
+        {
+            data.extend_from_slice(&line[..row_bytes]);
+        }
+
+        Ok(Arc::new(data))
+    }
+}
@@ -2,7 +2,8 @@ use cap_project::XY;
 use wgpu::util::DeviceExt;
 
 use crate::{
-    composite_frame::CompositeVideoFramePipeline, CompositeVideoFrameUniforms, DecodedFrame,
+    composite_frame::CompositeVideoFramePipeline, lut::ColorLut, CompositeVideoFrameUniforms,
+    DecodedFrame,
 };
 
 pub struct CameraLayer {
@@ -11,6 +12,8 @@ pub struct CameraLayer {
     uniforms_buffer: wgpu::Buffer,
     bind_group: Option<wgpu::BindGroup>,
     pipeline: CompositeVideoFramePipeline,
+    /// Whether this layer was prepared with a frame for the current render
+    pub enabled: bool,
 }
 
 impl CameraLayer {
@@ -36,6 +39,7 @@ impl CameraLayer {
             uniforms_buffer,
             bind_group,
             pipeline,
+            enabled: false,
         }
     }
 
@@ -86,10 +90,11 @@ impl CameraLayer {
         queue.write_buffer(&self.uniforms_buffer, 0, bytemuck::cast_slice(&[uniforms]));
     }
 
-    pub fn render(&self, pass: &mut wgpu::RenderPass<'_>) {
+    pub fn render(&self, pass: &mut wgpu::RenderPass<'_>, lut: &ColorLut) {
         if let Some(bind_group) = &self.bind_group {
             pass.set_pipeline(&self.pipeline.render_pipeline);
             pass.set_bind_group(0, bind_group, &[]);
+            pass.set_bind_group(1, lut.bind_group(), &[]);
             pass.draw(0..4, 0..1);
         }
     }
@@ -1,12 +1,14 @@
 use bytemuck::{Pod, Zeroable};
 use cap_project::XY;
 use glyphon::{
-    Attrs, Buffer, Cache, Color, Family, FontSystem, Metrics, Resolution, Shaping, Style, SwashCache,
-    TextArea, TextAtlas, TextBounds, TextRenderer, Viewport, Weight,
+    cosmic_text::Align, Attrs, Buffer, Cache, Color, Family, FontSystem, Metrics, Resolution,
+    Shaping, Style, SwashCache, TextArea, TextAtlas, TextBounds, TextRenderer, Viewport, Weight,
 };
 use log::{debug, info, warn};
+use std::path::PathBuf;
 use wgpu::{util::DeviceExt, Device, Queue};
 
+use super::RenderLayer;
 use crate::{parse_color_component, DecodedSegmentFrames, ProjectUniforms, RenderVideoConstants};
 
 /// Represents a caption segment with timing and text
@@ -16,6 +18,10 @@ pub struct CaptionSegment {
     pub start: f32,
     pub end: f32,
     pub text: String,
+    pub style_override: Option<cap_project::CaptionStyleOverride>,
+    pub words: Option<Vec<cap_project::CaptionWord>>,
+    /// Index into `words` of the word being spoken at the lookup time
+    pub active_word: Option<usize>,
 }
 
 /// Settings for caption rendering
@@ -27,12 +33,18 @@ pub struct CaptionSettings {
     pub color: [f32; 4],
     pub background_color: [f32; 4],
     pub position: u32, // 0 = top, 1 = middle, 2 = bottom
+    pub position_offset: f32, // fraction of output height added to the anchor
     pub outline: u32,  // 0 = disabled, 1 = enabled
     pub outline_color: [f32; 4],
+    pub outline_width: f32, // pixels at 1080p, 0 = no outline
+    pub highlight_color: [f32; 4],
     pub font: u32,     // 0 = SansSerif, 1 = Serif, 2 = Monospace
     pub bold: u32,     // 0 = disabled, 1 = enabled
     pub italic: u32,   // 0 = disabled, 1 = enabled
-    pub _padding: [f32; 2], // for alignment (increased for new fields)
+    pub max_lines: u32, // 0 = unlimited
+    pub truncate_overflow: u32, // 0 = shrink font, 1 = truncate with ellipsis
+    pub avoid_camera: u32, // 0 = fixed anchor, 1 = move clear of the camera overlay
+    pub _padding: [f32; 3], // for alignment (increased for new fields)
 }
 
 impl Default for CaptionSettings {
@@ -43,12 +55,18 @@ impl Default for CaptionSettings {
             color: [1.0, 1.0, 1.0, 1.0],            // white
             background_color: [0.0, 0.0, 0.0, 0.8], // 80% black
             position: 2,                            // bottom
+            position_offset: 0.0,                   // no nudge
             outline: 0,                             // disabled
             outline_color: [0.0, 0.0, 0.0, 1.0],    // black
+            outline_width: 1.0,                     // 1px at 1080p
+            highlight_color: [1.0, 1.0, 1.0, 1.0],  // white
             font: 0,                                // SansSerif
             bold: 0,                                // disabled
             italic: 0,                              // disabled
-            _padding: [0.0, 0.0],
+            max_lines: 0,                           // unlimited
+            truncate_overflow: 0,                   // shrink font
+            avoid_camera: 0,                        // fixed anchor
+            _padding: [0.0; 3],
         }
     }
 }
@@ -56,9 +74,9 @@ impl Default for CaptionSettings {
 /// Vertex data for background quad
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Pod, Zeroable)]
-struct QuadVertex {
-    position: [f32; 2],
-    color: [f32; 4],
+pub(crate) struct QuadVertex {
+    pub position: [f32; 2],
+    pub color: [f32; 4],
 }
 
 impl QuadVertex {
@@ -76,6 +94,96 @@ impl QuadVertex {
     }
 }
 
+/// Pipeline for the solid quads drawn behind text, whose vertices are already in NDC
+pub(crate) fn background_quad_pipeline(device: &Device) -> wgpu::RenderPipeline {
+    let shader_source = r#"
+        struct VertexInput {
+            @location(0) position: vec2<f32>,
+            @location(1) color: vec4<f32>,
+        };
+
+        struct VertexOutput {
+            @builtin(position) position: vec4<f32>,
+            @location(0) color: vec4<f32>,
+        };
+
+        @vertex
+        fn vs_main(input: VertexInput) -> VertexOutput {
+            var output: VertexOutput;
+            // Convert from pixel coordinates to NDC
+            // Assuming viewport of 1920x1080 (will be adjusted in prepare)
+            output.position = vec4<f32>(
+                input.position.x,
+                input.position.y,
+                0.0,
+                1.0
+            );
+            output.color = input.color;
+            return output;
+        }
+
+        @fragment
+        fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+            return input.color;
+        }
+    "#;
+
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("Text Background Shader"),
+        source: wgpu::ShaderSource::Wgsl(shader_source.into()),
+    });
+
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("Text Background Pipeline Layout"),
+        bind_group_layouts: &[], // No bind groups needed - color comes from vertex data
+        push_constant_ranges: &[],
+    });
+
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("Text Background Pipeline"),
+        layout: Some(&pipeline_layout),
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: Some("vs_main"),
+            buffers: &[QuadVertex::desc()],
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: &shader,
+            entry_point: Some("fs_main"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format: wgpu::TextureFormat::Rgba8UnormSrgb,
+                blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            ..Default::default()
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        multiview: None,
+        cache: None,
+    })
+}
+
+/// Most captions drawn at once, which is two while one hands over to the next
+const MAX_VISIBLE_CAPTIONS: usize = 2;
+
+/// A caption laid out and styled for the current frame
+struct CaptionLayout {
+    buffer: Buffer,
+    left: f32,
+    top: f32,
+    bounds: TextBounds,
+    color: Color,
+    /// Color and width in pixels of the outline, if it has one
+    outline: Option<(Color, f32)>,
+    background: Option<[QuadVertex; 4]>,
+}
+
 /// Caption layer that renders text using GPU
 pub struct CaptionsLayer {
     settings_buffer: wgpu::Buffer,
@@ -83,19 +191,20 @@ pub struct CaptionsLayer {
     swash_cache: SwashCache,
     text_atlas: TextAtlas,
     text_renderer: TextRenderer,
-    text_buffer: Buffer,
     current_text: Option<String>,
     current_segment_time: f32,
     current_bold: u32,
     current_italic: u32,
     current_font: u32,
+    /// Style override of the segment last drawn, if it had one
+    current_override: Option<cap_project::CaptionStyleOverride>,
     viewport: Viewport,
     // Background rendering resources
     background_pipeline: wgpu::RenderPipeline,
     background_vertex_buffer: wgpu::Buffer,
     background_index_buffer: wgpu::Buffer,
-    current_background_bounds: Option<TextBounds>,
-    current_background_color: [f32; 4],
+    /// Backgrounds written to the vertex buffer for this frame
+    background_quads: u32,
 }
 
 impl CaptionsLayer {
@@ -109,7 +218,8 @@ impl CaptionsLayer {
         });
 
         // Initialize glyphon text rendering components
-        let font_system = FontSystem::new();
+        let mut font_system = FontSystem::new();
+        load_bundled_fonts(&mut font_system);
         let swash_cache = SwashCache::new();
         let cache = Cache::new(device);
         let viewport = Viewport::new(device, &cache);
@@ -122,98 +232,24 @@ impl CaptionsLayer {
             None,
         );
 
-        // Create an empty buffer with default metrics
-        let metrics = Metrics::new(24.0, 24.0 * 1.2); // Default font size and line height
-        let text_buffer = Buffer::new_empty(metrics);
-
         // Create background rendering resources
-        let shader_source = r#"
-            struct VertexInput {
-                @location(0) position: vec2<f32>,
-                @location(1) color: vec4<f32>,
-            };
-
-            struct VertexOutput {
-                @builtin(position) position: vec4<f32>,
-                @location(0) color: vec4<f32>,
-            };
-
-            @vertex
-            fn vs_main(input: VertexInput) -> VertexOutput {
-                var output: VertexOutput;
-                // Convert from pixel coordinates to NDC
-                // Assuming viewport of 1920x1080 (will be adjusted in prepare)
-                output.position = vec4<f32>(
-                    input.position.x,
-                    input.position.y,
-                    0.0,
-                    1.0
-                );
-                output.color = input.color;
-                return output;
-            }
-
-            @fragment
-            fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
-                return input.color;
-            }
-        "#;
+        let background_pipeline = background_quad_pipeline(device);
 
-        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("Caption Background Shader"),
-            source: wgpu::ShaderSource::Wgsl(shader_source.into()),
-        });
-
-        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-            label: Some("Caption Background Pipeline Layout"),
-            bind_group_layouts: &[],  // No bind groups needed - color comes from vertex data
-            push_constant_ranges: &[],
-        });
+        // One quad per visible caption (vertices are updated in prepare)
+        let vertices = [QuadVertex {
+            position: [0.0, 0.0],
+            color: [0.0, 0.0, 0.0, 0.8],
+        }; MAX_VISIBLE_CAPTIONS * 4];
 
-        let background_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Caption Background Pipeline"),
-            layout: Some(&pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: Some("vs_main"),
-                buffers: &[QuadVertex::desc()],
-                compilation_options: Default::default(),
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: Some("fs_main"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: wgpu::TextureFormat::Rgba8UnormSrgb,
-                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: Default::default(),
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                ..Default::default()
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
-            multiview: None,
-            cache: None,
-        });
-
-        // Create vertex buffer for a quad (will update vertices in prepare)
-        let vertices = [
-            QuadVertex { position: [0.0, 0.0], color: [0.0, 0.0, 0.0, 0.8] },
-            QuadVertex { position: [1.0, 0.0], color: [0.0, 0.0, 0.0, 0.8] },
-            QuadVertex { position: [1.0, 1.0], color: [0.0, 0.0, 0.0, 0.8] },
-            QuadVertex { position: [0.0, 1.0], color: [0.0, 0.0, 0.0, 0.8] },
-        ];
-        
         let background_vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Caption Background Vertex Buffer"),
             contents: bytemuck::cast_slice(&vertices),
             usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
         });
 
-        let indices: [u16; 6] = [0, 1, 2, 2, 3, 0];
+        let indices = (0..MAX_VISIBLE_CAPTIONS as u16)
+            .flat_map(|quad| [0, 1, 2, 2, 3, 0].map(|index| quad * 4 + index))
+            .collect::<Vec<u16>>();
         let background_index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Caption Background Index Buffer"),
             contents: bytemuck::cast_slice(&indices),
@@ -226,18 +262,17 @@ impl CaptionsLayer {
             swash_cache,
             text_atlas,
             text_renderer,
-            text_buffer,
             current_text: None,
             current_segment_time: 0.0,
             current_bold: 0, // default from CaptionSettings::default()
             current_italic: 0,
             current_font: 0,
+            current_override: None,
             viewport,
             background_pipeline,
             background_vertex_buffer,
             background_index_buffer,
-            current_background_bounds: None,
-            current_background_color: [0.0, 0.0, 0.0, 0.0],
+            background_quads: 0,
         }
     }
 
@@ -255,299 +290,480 @@ impl CaptionsLayer {
         self.current_segment_time = time;
     }
 
-    pub fn prepare(
+    /// Lay out `caption` with its resolved `settings`, whose colors already include its fade
+    fn layout_caption(
+        &mut self,
+        caption: &CaptionSegment,
+        settings: &CaptionSettings,
+        uniforms: &ProjectUniforms,
+        output_size: XY<u32>,
+    ) -> CaptionLayout {
+        let text = &caption.text;
+        let (width, height) = (output_size.x, output_size.y);
+
+        // Calculate text bounds
+        let mut font_size = settings.font_size * (height as f32 / 1080.0); // Scale font size based on resolution
+
+        // Set up caption appearance
+        let color = Color::rgba(
+            (settings.color[0] * 255.0) as u8,
+            (settings.color[1] * 255.0) as u8,
+            (settings.color[2] * 255.0) as u8,
+            (settings.color[3] * 255.0) as u8,
+        );
+
+        // Get outline color if needed
+        let outline_color = Color::rgba(
+            (settings.outline_color[0] * 255.0) as u8,
+            (settings.outline_color[1] * 255.0) as u8,
+            (settings.outline_color[2] * 255.0) as u8,
+            (settings.outline_color[3] * 255.0) as u8,
+        );
+
+        let metrics = Metrics::new(font_size, font_size * 1.2); // 1.2 line height
+
+        // Check if styles have changed, including moving between segments
+        // with different overrides
+        let styles_changed = self.current_bold != settings.bold
+            || self.current_italic != settings.italic
+            || self.current_font != settings.font
+            || self.current_override != caption.style_override;
+        if styles_changed {
+            debug!(
+                "Caption style changed - override: {:?}",
+                caption.style_override
+            );
+        }
+
+        // Set width for text wrapping
+        let mut text_width = width as f32 * 0.9;
+        let mut text_left = (width as f32 - text_width) / 2.0;
+
+        // Always create a fresh buffer to ensure clean state
+        // This prevents any corruption from style changes
+        info!(
+            "Creating fresh text buffer - font_size: {}, width: {}",
+            font_size, text_width
+        );
+        let mut buffer = Buffer::new(&mut self.font_system, metrics);
+        buffer.set_size(&mut self.font_system, Some(text_width), None);
+        buffer.set_wrap(&mut self.font_system, glyphon::Wrap::Word);
+
+        // Apply text styling directly when setting the text
+        // Create text attributes with or without outline
+        let font_family = match settings.font {
+            0 => Family::SansSerif,
+            1 => Family::Serif,
+            2 => Family::Monospace,
+            _ => Family::SansSerif, // Default to SansSerif for any other value
+        };
+
+        // Build text attributes with style settings
+        let mut attrs = Attrs::new().family(font_family).color(color);
+
+        // Apply bold style if enabled
+        if settings.bold == 1 {
+            attrs = attrs.weight(Weight::BOLD);
+        }
+
+        // Apply italic style if enabled
+        if settings.italic == 1 {
+            attrs = attrs.style(Style::Italic);
+        }
+
+        // Apply text to buffer with the styled attributes
+        info!(
+            "Setting text with attributes - bold: {}, italic: {}, font: {}",
+            settings.bold, settings.italic, settings.font
+        );
+        // Karaoke style: the spoken word is highlighted and the rest are dimmed.
+        // The timed words only supply timing, so edits to the text still show,
+        // but once an edit changes the word count they no longer line up and
+        // the caption is drawn plainly instead
+        let text_words = text.split_whitespace().collect::<Vec<_>>();
+        let karaoke = caption
+            .words
+            .as_ref()
+            .is_some_and(|words| !words.is_empty() && words.len() == text_words.len());
+        let highlight = Color::rgba(
+            (settings.highlight_color[0] * 255.0) as u8,
+            (settings.highlight_color[1] * 255.0) as u8,
+            (settings.highlight_color[2] * 255.0) as u8,
+            (settings.highlight_color[3] * 255.0) as u8,
+        );
+        let dimmed = Color::rgba(
+            color.r(),
+            color.g(),
+            color.b(),
+            (DIMMED_WORD_ALPHA as f32 * settings.color[3]) as u8,
+        );
+        let word_spans = text_words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| {
+                let word_attrs = match (karaoke, caption.active_word == Some(i)) {
+                    (false, _) => attrs.clone(),
+                    (true, true) => attrs.clone().color(highlight),
+                    (true, false) => attrs.clone().color(dimmed),
+                };
+                (*word, word_attrs)
+            })
+            .collect::<Vec<_>>();
+
+        if karaoke {
+            set_word_spans(
+                &mut buffer,
+                &mut self.font_system,
+                &word_spans,
+                &attrs,
+                false,
+            );
+        } else {
+            buffer.set_text(&mut self.font_system, text, &attrs, Shaping::Advanced);
+        }
+
+        // Keep long captions within max_lines by shrinking or truncating
+        if settings.max_lines > 0 {
+            font_size = fit_to_lines(
+                &mut buffer,
+                &mut self.font_system,
+                &word_spans,
+                &attrs,
+                font_size,
+                settings.max_lines as usize,
+                settings.truncate_overflow == 1,
+            );
+        }
+
+        // Lines are centred, except that RTL lines are laid out from the script's base
+        // direction so they start on the right
+        let rtl = is_rtl(text);
+        let align = if rtl { Align::Right } else { Align::Center };
+        for line in buffer.lines.iter_mut() {
+            line.set_align(Some(align));
+        }
+
+        let mut line_count = wrapped_line_count(&mut buffer, &mut self.font_system).max(1) as f32;
+        let mut block_height = font_size * 1.2 * line_count;
+
+        // Find caption position based on settings
+        let mut y_position = caption_y_position(
+            settings.position,
+            settings.position_offset,
+            height as f32,
+            block_height.max(font_size * 1.5),
+        );
+
+        // Move the caption clear of the camera overlay if requested
+        if settings.avoid_camera == 1 {
+            let camera_bounds = camera_bounds_in_output(uniforms, output_size);
+            let caption_rect = [
+                text_left,
+                y_position,
+                text_left + text_width,
+                y_position + block_height,
+            ];
+
+            match camera_bounds.and_then(|camera| {
+                avoid_obstacle(
+                    caption_rect,
+                    camera,
+                    width as f32,
+                    height as f32,
+                    font_size * 0.5,
+                )
+            }) {
+                Some(CaptionPlacement::Vertical(top)) => {
+                    y_position = top;
+                }
+                Some(CaptionPlacement::Horizontal { left, right }) => {
+                    // Narrowing the caption re-wraps it, so re-measure
+                    text_left = left;
+                    text_width = right - left;
+                    buffer.set_size(&mut self.font_system, Some(text_width), None);
+                    line_count =
+                        wrapped_line_count(&mut buffer, &mut self.font_system).max(1) as f32;
+                    block_height = font_size * 1.2 * line_count;
+                    y_position = y_position.min((height as f32 - block_height).max(0.0));
+                }
+                None => {}
+            }
+        }
+
+        // Center the widest RTL line in the caption box. They're laid out from
+        // the box's right edge, so they shift left by half the space that's left
+        let text_x = if rtl {
+            let block_width = buffer
+                .layout_runs()
+                .map(|run| run.line_w)
+                .fold(0.0, f32::max)
+                .min(text_width);
+            text_left - (text_width - block_width) / 2.0
+        } else {
+            text_left
+        };
+
+        // Position text in the center horizontally
+        // The bounds dictate the rendering area
+        let bounds = TextBounds {
+            left: text_left as i32, // Center the text horizontally
+            top: y_position as i32,
+            right: (text_left + text_width) as i32, // Center + width
+            bottom: (y_position + block_height.max(font_size * 4.0)) as i32, // Increased height for better visibility
+        };
+
+        // Update current style state
+        self.current_bold = settings.bold;
+        self.current_italic = settings.italic;
+        self.current_font = settings.font;
+        self.current_override = caption.style_override.clone();
+
+        let background = (settings.background_color[3] > 0.01).then(|| {
+            // Calculate actual text bounds for background
+            // We need to measure the actual text to get proper background size
+            let text_height = font_size * line_count * 1.5; // Add some padding
+
+            // Add padding around text
+            let padding = font_size * 0.5;
+            let bg_left = bounds.left as f32 - padding;
+            let bg_right = bounds.right as f32 + padding;
+            let bg_top = y_position - padding * 0.5;
+            let bg_bottom = y_position + text_height + padding * 0.5;
+
+            // Convert to NDC coordinates
+            let ndc_left = (bg_left / width as f32) * 2.0 - 1.0;
+            let ndc_right = (bg_right / width as f32) * 2.0 - 1.0;
+            let ndc_top = 1.0 - (bg_top / height as f32) * 2.0;
+            let ndc_bottom = 1.0 - (bg_bottom / height as f32) * 2.0;
+
+            [
+                [ndc_left, ndc_top],
+                [ndc_right, ndc_top],
+                [ndc_right, ndc_bottom],
+                [ndc_left, ndc_bottom],
+            ]
+            .map(|position| QuadVertex {
+                position,
+                color: settings.background_color,
+            })
+        });
+
+        // Outline is created by drawing the text multiple times with offsets around a ring
+        let outline_width = settings.outline_width * (height as f32 / 1080.0);
+        let outline = (settings.outline == 1 && outline_width > 0.0)
+            .then_some((outline_color, outline_width));
+
+        CaptionLayout {
+            buffer,
+            left: text_x,
+            top: y_position,
+            bounds,
+            color,
+            outline,
+            background,
+        }
+    }
+}
+
+impl RenderLayer for CaptionsLayer {
+    fn prepare(
         &mut self,
         uniforms: &ProjectUniforms,
-        segment_frames: &DecodedSegmentFrames,
+        _segment_frames: &DecodedSegmentFrames,
         output_size: XY<u32>,
         constants: &RenderVideoConstants,
     ) {
+        let mut layouts = Vec::new();
+
         // Render captions if there are any caption segments to display
         if let Some(caption_data) = &uniforms.project.captions {
             if caption_data.settings.enabled {
-                // Find the current caption for this time
-                let current_time = segment_frames.segment_time;
+                // Captions are timed against the edited timeline, which segment
+                // and recording times don't account for once it's been cut
+                let current_time = uniforms.frame_time;
+
+                // Usually just the current caption, but while one caption hands over to
+                // the next both are drawn, one fading out as the other fades in
+                let segments = &caption_data.segments;
+                for (i, segment) in segments.iter().enumerate() {
+                    if layouts.len() == MAX_VISIBLE_CAPTIONS {
+                        break;
+                    }
+
+                    let fade_duration = segment
+                        .style_override
+                        .as_ref()
+                        .and_then(|style_override| style_override.fade_duration)
+                        .unwrap_or(caption_data.settings.fade_duration);
 
-                if let Some(current_caption) =
-                    find_caption_at_time_project(current_time, &caption_data.segments)
-                {
-                    // Get caption text and time for use in rendering
-                    let caption_text = current_caption.text.clone();
+                    // Skip anything that can't be on screen before doing any real work
+                    if current_time < segment.start - fade_duration / 2.0
+                        || current_time >= segment.end + fade_duration / 2.0
+                    {
+                        continue;
+                    }
+
+                    // Text and background alpha ramp up and down at the caption's ends
+                    let fade = caption_fade(
+                        current_time,
+                        segment,
+                        i.checked_sub(1).map(|previous| &segments[previous]),
+                        segments.get(i + 1),
+                        fade_duration,
+                    );
+                    if fade <= 0.0 {
+                        continue;
+                    }
+
+                    let caption = CaptionSegment {
+                        active_word: segment.active_word(current_time),
+                        ..convert_project_caption(segment)
+                    };
+
+                    // Segment overrides take precedence over the global settings
+                    let caption_settings = match &caption.style_override {
+                        Some(style_override) => caption_data.settings.merged_with(style_override),
+                        None => caption_data.settings.clone(),
+                    };
 
                     // Create settings for the caption
                     let settings = CaptionSettings {
                         enabled: 1,
-                        font_size: caption_data.settings.size as f32,
+                        font_size: caption_settings.size as f32,
                         color: [
-                            parse_color_component(&caption_data.settings.color, 0),
-                            parse_color_component(&caption_data.settings.color, 1),
-                            parse_color_component(&caption_data.settings.color, 2),
-                            1.0,
+                            parse_color_component(&caption_settings.color, 0),
+                            parse_color_component(&caption_settings.color, 1),
+                            parse_color_component(&caption_settings.color, 2),
+                            fade,
                         ],
                         background_color: [
-                            parse_color_component(&caption_data.settings.background_color, 0),
-                            parse_color_component(&caption_data.settings.background_color, 1),
-                            parse_color_component(&caption_data.settings.background_color, 2),
-                            caption_data.settings.background_opacity as f32 / 100.0,
+                            parse_color_component(&caption_settings.background_color, 0),
+                            parse_color_component(&caption_settings.background_color, 1),
+                            parse_color_component(&caption_settings.background_color, 2),
+                            caption_settings.background_opacity as f32 / 100.0 * fade,
                         ],
-                        position: match caption_data.settings.position.as_str() {
+                        position: match caption_settings.position.as_str() {
                             "top" => 0,
                             "middle" => 1,
                             _ => 2, // default to bottom
                         },
-                        outline: if caption_data.settings.outline { 1 } else { 0 },
+                        position_offset: caption_settings.position_offset,
+                        outline: if caption_settings.outline { 1 } else { 0 },
                         outline_color: [
-                            parse_color_component(&caption_data.settings.outline_color, 0),
-                            parse_color_component(&caption_data.settings.outline_color, 1),
-                            parse_color_component(&caption_data.settings.outline_color, 2),
-                            1.0,
+                            parse_color_component(&caption_settings.outline_color, 0),
+                            parse_color_component(&caption_settings.outline_color, 1),
+                            parse_color_component(&caption_settings.outline_color, 2),
+                            fade,
+                        ],
+                        outline_width: caption_settings.outline_width.max(0.0),
+                        highlight_color: [
+                            parse_color_component(&caption_settings.highlight_color, 0),
+                            parse_color_component(&caption_settings.highlight_color, 1),
+                            parse_color_component(&caption_settings.highlight_color, 2),
+                            fade,
                         ],
-                        font: match caption_data.settings.font.as_str() {
+                        font: match caption_settings.font.as_str() {
                             "System Serif" => 1,
                             "System Monospace" => 2,
                             _ => 0, // Default to SansSerif for "System Sans-Serif" and any other value
                         },
-                        bold: if caption_data.settings.bold { 1 } else { 0 },
-                        italic: if caption_data.settings.italic { 1 } else { 0 },
-                        _padding: [0.0, 0.0],
+                        bold: if caption_settings.bold { 1 } else { 0 },
+                        italic: if caption_settings.italic { 1 } else { 0 },
+                        max_lines: caption_settings.max_lines,
+                        truncate_overflow: if caption_settings.truncate_overflow { 1 } else { 0 },
+                        avoid_camera: if caption_settings.avoid_camera { 1 } else { 0 },
+                        _padding: [0.0; 3],
                     };
 
-                    // Update the current caption text
-                    let text_changed = self.current_text.as_ref() != Some(&caption_text);
-                    self.update_caption(Some(caption_text.clone()), current_time);
+                    self.update_caption(Some(caption.text.clone()), current_time);
+                    layouts.push(self.layout_caption(&caption, &settings, uniforms, output_size));
+                }
+            }
+        }
+
+        if layouts.is_empty() {
+            self.current_text = None;
+        }
 
-                    if settings.enabled == 0 {
-                        return;
-                    }
+        // Access device and queue from the pipeline's constants
+        let device = &constants.device;
+        let queue = &constants.queue;
 
-                    if self.current_text.is_none() {
-                        return;
-                    }
+        // Update the viewport with explicit resolution
+        self.viewport.update(
+            queue,
+            Resolution {
+                width: output_size.x,
+                height: output_size.y,
+            },
+        );
 
-                    // Only recreate buffer if text changed or styles changed
-                    if let Some(text) = &self.current_text {
-                        let (width, height) = (output_size.x, output_size.y);
-
-                        // Access device and queue from the pipeline's constants
-                        let device = &constants.device;
-                        let queue = &constants.queue;
-
-                        // Find caption position based on settings
-                        let y_position = match settings.position {
-                            0 => height as f32 * 0.1,  // top
-                            1 => height as f32 * 0.5,  // middle
-                            _ => height as f32 * 0.85, // bottom (default)
-                        };
-
-                        // Set up caption appearance
-                        let color = Color::rgb(
-                            (settings.color[0] * 255.0) as u8,
-                            (settings.color[1] * 255.0) as u8,
-                            (settings.color[2] * 255.0) as u8,
-                        );
-
-                        // Get outline color if needed
-                        let outline_color = Color::rgb(
-                            (settings.outline_color[0] * 255.0) as u8,
-                            (settings.outline_color[1] * 255.0) as u8,
-                            (settings.outline_color[2] * 255.0) as u8,
-                        );
-
-                        // Calculate text bounds
-                        let font_size = settings.font_size * (height as f32 / 1080.0); // Scale font size based on resolution
-                        let metrics = Metrics::new(font_size, font_size * 1.2); // 1.2 line height
-
-                        // Check if styles have changed
-                        let styles_changed = self.current_bold != settings.bold ||
-                                           self.current_italic != settings.italic ||
-                                           self.current_font != settings.font;
-
-                        // Set width for text wrapping
-                        let text_width = width as f32 * 0.9;
-
-                        // Always recreate buffer to ensure clean state
-                        // This prevents any corruption from style changes
-                        info!("Creating fresh text buffer - font_size: {}, width: {}", font_size, text_width);
-                        self.text_buffer = Buffer::new(&mut self.font_system, metrics);
-                        self.text_buffer.set_size(&mut self.font_system, Some(text_width), None);
-                        self.text_buffer.set_wrap(&mut self.font_system, glyphon::Wrap::Word);
-
-                        // Position text in the center horizontally
-                        // The bounds dictate the rendering area
-                        let bounds = TextBounds {
-                            left: ((width as f32 - text_width) / 2.0) as i32, // Center the text horizontally
-                            top: y_position as i32,
-                            right: ((width as f32 + text_width) / 2.0) as i32, // Center + width
-                            bottom: (y_position + font_size * 4.0) as i32, // Increased height for better visibility
-                        };
-
-                        // Apply text styling directly when setting the text
-                        // Create text attributes with or without outline
-                        let font_family = match settings.font {
-                            0 => Family::SansSerif,
-                            1 => Family::Serif,
-                            2 => Family::Monospace,
-                            _ => Family::SansSerif, // Default to SansSerif for any other value
-                        };
-                        
-                        // Build text attributes with style settings
-                        let mut attrs = Attrs::new().family(font_family).color(color);
-                        
-                        // Apply bold style if enabled
-                        if settings.bold == 1 {
-                            attrs = attrs.weight(Weight::BOLD);
-                        }
-                        
-                        // Apply italic style if enabled
-                        if settings.italic == 1 {
-                            attrs = attrs.style(Style::Italic);
-                        }
-
-                        // Apply text to buffer with the styled attributes
-                        // Always set text since we're recreating the buffer
-                        info!("Setting text with attributes - bold: {}, italic: {}, font: {}", settings.bold, settings.italic, settings.font);
-                        self.text_buffer.set_text(
-                            &mut self.font_system,
-                            text,
-                            &attrs,
-                            Shaping::Advanced,
-                        );
-                        // Update current style state
-                        self.current_bold = settings.bold;
-                        self.current_italic = settings.italic;
-                        self.current_font = settings.font;
-
-                        // Update the viewport with explicit resolution
-                        self.viewport.update(queue, Resolution { width, height });
-
-                        // Store background info for rendering
-                        if settings.background_color[3] > 0.01 {
-                            self.current_background_bounds = Some(bounds);
-                            self.current_background_color = settings.background_color;
-
-                            // Calculate actual text bounds for background
-                            // We need to measure the actual text to get proper background size
-                            let line_count = text.lines().count() as f32;
-                            let text_height = font_size * line_count * 1.5; // Add some padding
-                            
-                            // Add padding around text
-                            let padding = font_size * 0.5;
-                            let bg_left = bounds.left as f32 - padding;
-                            let bg_right = bounds.right as f32 + padding;
-                            let bg_top = y_position - padding * 0.5;
-                            let bg_bottom = y_position + text_height + padding * 0.5;
-
-                            // Update vertex buffer with proper NDC coordinates
-                            let ndc_left = (bg_left / width as f32) * 2.0 - 1.0;
-                            let ndc_right = (bg_right / width as f32) * 2.0 - 1.0;
-                            let ndc_top = 1.0 - (bg_top / height as f32) * 2.0;
-                            let ndc_bottom = 1.0 - (bg_bottom / height as f32) * 2.0;
-
-                            let vertices = [
-                                QuadVertex { 
-                                    position: [ndc_left, ndc_top], 
-                                    color: settings.background_color 
-                                },
-                                QuadVertex { 
-                                    position: [ndc_right, ndc_top], 
-                                    color: settings.background_color 
-                                },
-                                QuadVertex { 
-                                    position: [ndc_right, ndc_bottom], 
-                                    color: settings.background_color 
-                                },
-                                QuadVertex { 
-                                    position: [ndc_left, ndc_bottom], 
-                                    color: settings.background_color 
-                                },
-                            ];
-                            
-                            queue.write_buffer(&self.background_vertex_buffer, 0, bytemuck::cast_slice(&vertices));
-                        } else {
-                            self.current_background_bounds = None;
-                        }
-
-                        // Prepare text areas for rendering
-                        let mut text_areas = Vec::new();
-
-                        // Add outline if enabled (by rendering the text multiple times with slight offsets in different positions)
-                        if settings.outline == 1 {
-                            info!("Rendering with outline");
-                            // Outline is created by drawing the text multiple times with small offsets in different directions
-                            let outline_offsets = [
-                                (-1.0, -1.0),
-                                (0.0, -1.0),
-                                (1.0, -1.0),
-                                (-1.0, 0.0),
-                                (1.0, 0.0),
-                                (-1.0, 1.0),
-                                (0.0, 1.0),
-                                (1.0, 1.0),
-                            ];
-
-                            for (offset_x, offset_y) in outline_offsets.iter() {
-                                text_areas.push(TextArea {
-                                    buffer: &self.text_buffer,
-                                    left: bounds.left as f32 + offset_x, // Match bounds with small offset for outline
-                                    top: y_position + offset_y,
-                                    scale: 1.0,
-                                    bounds,
-                                    default_color: outline_color,
-                                    custom_glyphs: &[],
-                                });
-                            }
-                        }
-
-                        // Add main text (rendered last, on top of everything)
-                        text_areas.push(TextArea {
-                            buffer: &self.text_buffer,
-                            left: bounds.left as f32, // Match the bounds left for positioning
-                            top: y_position,
-                            scale: 1.0,
-                            bounds,
-                            default_color: color,
-                            custom_glyphs: &[],
-                        });
-
-                        // Prepare text rendering
-                        let text_areas_count = text_areas.len();
-                        info!("Preparing text renderer with {} text areas", text_areas_count);
-                        match self.text_renderer.prepare(
-                            device,
-                            queue,
-                            &mut self.font_system,
-                            &mut self.text_atlas,
-                            &self.viewport,
-                            text_areas,
-                            &mut self.swash_cache,
-                        ) {
-                            Ok(_) => {
-                                info!("Text renderer prepared successfully");
-                            }
-                            Err(e) => {
-                                warn!("Error preparing text: {:?}", e);
-                                // Log more details about the error
-                                warn!("Text areas count: {}", text_areas_count);
-                                warn!("Buffer metrics: font_size={}", font_size);
-                            }
-                        }
-                    }
-                } else {
+        // Store background info for rendering
+        let backgrounds = layouts
+            .iter()
+            .filter_map(|layout| layout.background)
+            .collect::<Vec<_>>();
+        if !backgrounds.is_empty() {
+            queue.write_buffer(
+                &self.background_vertex_buffer,
+                0,
+                bytemuck::cast_slice(&backgrounds),
+            );
+        }
+        self.background_quads = backgrounds.len() as u32;
+
+        // Prepare text areas for rendering, each caption's outline beneath its text
+        let mut text_areas = Vec::new();
+        for layout in &layouts {
+            if let Some((outline_color, outline_width)) = layout.outline {
+                for (offset_x, offset_y) in outline_offsets(outline_width) {
+                    text_areas.push(TextArea {
+                        buffer: &layout.buffer,
+                        left: layout.left + offset_x,
+                        top: layout.top + offset_y,
+                        scale: 1.0,
+                        bounds: layout.bounds,
+                        default_color: outline_color,
+                        custom_glyphs: &[],
+                    });
                 }
-            } else {
             }
-        } else {
+
+            text_areas.push(TextArea {
+                buffer: &layout.buffer,
+                left: layout.left,
+                top: layout.top,
+                scale: 1.0,
+                bounds: layout.bounds,
+                default_color: layout.color,
+                custom_glyphs: &[],
+            });
+        }
+
+        // Prepared even when empty, so a caption doesn't linger once it's over
+        let text_areas_count = text_areas.len();
+        if let Err(e) = self.text_renderer.prepare(
+            device,
+            queue,
+            &mut self.font_system,
+            &mut self.text_atlas,
+            &self.viewport,
+            text_areas,
+            &mut self.swash_cache,
+        ) {
+            warn!("Error preparing text: {:?}", e);
+            warn!("Text areas count: {}", text_areas_count);
         }
     }
 
-    /// Render the current caption to the frame
-    pub fn render<'a>(&'a self, pass: &mut wgpu::RenderPass<'a>) {
-        // First render the background if present
-        if self.current_background_bounds.is_some() && self.current_background_color[3] > 0.01 {
+    /// Render the visible captions to the frame
+    fn render<'a>(&'a self, pass: &mut wgpu::RenderPass<'a>) {
+        // First render the backgrounds if present
+        if self.background_quads > 0 {
             pass.set_pipeline(&self.background_pipeline);
             pass.set_vertex_buffer(0, self.background_vertex_buffer.slice(..));
             pass.set_index_buffer(self.background_index_buffer.slice(..), wgpu::IndexFormat::Uint16);
-            pass.draw_indexed(0..6, 0, 0..1);
+            pass.draw_indexed(0..self.background_quads * 6, 0, 0..1);
         }
 
         // Then render the text on top
@@ -561,6 +777,291 @@ impl CaptionsLayer {
     }
 }
 
+/// Where a caption block should move to stay clear of an obstacle
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CaptionPlacement {
+    /// Keep the horizontal span and move the block's top to this y
+    Vertical(f32),
+    /// Keep the vertical position and narrow the block to this span
+    Horizontal { left: f32, right: f32 },
+}
+
+/// Compute a placement for `caption` (left, top, right, bottom) that doesn't overlap
+/// `obstacle`, preferring a vertical nudge and falling back to the wider free side.
+/// Returns `None` when the caption already doesn't overlap.
+pub fn avoid_obstacle(
+    caption: [f32; 4],
+    obstacle: [f32; 4],
+    width: f32,
+    height: f32,
+    margin: f32,
+) -> Option<CaptionPlacement> {
+    let overlaps = caption[0] < obstacle[2]
+        && caption[2] > obstacle[0]
+        && caption[1] < obstacle[3]
+        && caption[3] > obstacle[1];
+
+    if !overlaps {
+        return None;
+    }
+
+    let block_height = caption[3] - caption[1];
+    let above = obstacle[1] - margin - block_height;
+    let below = obstacle[3] + margin;
+
+    // Move towards whichever side of the obstacle the caption is already closer to
+    let caption_center = (caption[1] + caption[3]) / 2.0;
+    let obstacle_center = (obstacle[1] + obstacle[3]) / 2.0;
+    let candidates = if caption_center >= obstacle_center {
+        [below, above]
+    } else {
+        [above, below]
+    };
+
+    if let Some(top) = candidates
+        .into_iter()
+        .find(|top| *top >= 0.0 && top + block_height <= height)
+    {
+        return Some(CaptionPlacement::Vertical(top));
+    }
+
+    let left_space = obstacle[0] - margin;
+    let right_space = width - obstacle[2] - margin;
+
+    Some(if left_space >= right_space {
+        CaptionPlacement::Horizontal {
+            left: margin,
+            right: left_space,
+        }
+    } else {
+        CaptionPlacement::Horizontal {
+            left: obstacle[2] + margin,
+            right: width - margin,
+        }
+    })
+}
+
+/// Camera overlay bounds (left, top, right, bottom) scaled into the caption's output space
+fn camera_bounds_in_output(uniforms: &ProjectUniforms, output_size: XY<u32>) -> Option<[f32; 4]> {
+    let camera_visible = match &uniforms.scene_mode {
+        Some(cap_project::SceneMode::HideCamera) | Some(cap_project::SceneMode::CameraOnly) => {
+            false
+        }
+        _ => !uniforms.project.camera.hide,
+    };
+
+    if !camera_visible {
+        return None;
+    }
+
+    let camera = uniforms.camera.as_ref()?;
+    let scale_x = output_size.x as f32 / camera.output_size[0];
+    let scale_y = output_size.y as f32 / camera.output_size[1];
+    let bounds = camera.target_bounds;
+
+    Some([
+        bounds[0] * scale_x,
+        bounds[1] * scale_y,
+        bounds[2] * scale_x,
+        bounds[3] * scale_y,
+    ])
+}
+
+/// Smallest fraction of the configured font size captions may shrink to
+const MIN_CAPTION_SHRINK: f32 = 0.5;
+
+/// Offsets to draw the outline copies of the text at, evenly spaced around a ring of
+/// radius `width`. Thicker outlines get more samples so the ring stays smooth.
+fn outline_offsets(width: f32) -> Vec<(f32, f32)> {
+    let samples = ((std::f32::consts::TAU * width).ceil() as usize).clamp(8, 32);
+
+    (0..samples)
+        .map(|i| {
+            let angle = std::f32::consts::TAU * i as f32 / samples as f32;
+            (angle.cos() * width, angle.sin() * width)
+        })
+        .collect()
+}
+
+/// Alpha of the words around the one being spoken in karaoke captions
+const DIMMED_WORD_ALPHA: u8 = 140;
+
+/// Number of visual lines the buffer wraps to after shaping
+pub(crate) fn wrapped_line_count(buffer: &mut Buffer, font_system: &mut FontSystem) -> usize {
+    buffer.shape_until_scroll(font_system, false);
+    buffer.layout_runs().count()
+}
+
+/// Lay out `words` separated by spaces, each with its own attributes,
+/// optionally followed by an ellipsis
+fn set_word_spans(
+    buffer: &mut Buffer,
+    font_system: &mut FontSystem,
+    words: &[(&str, Attrs)],
+    attrs: &Attrs,
+    ellipsis: bool,
+) {
+    let spans = words
+        .iter()
+        .enumerate()
+        .flat_map(|(i, (word, word_attrs))| {
+            let separator = if i == 0 { "" } else { " " };
+            [(separator, attrs.clone()), (*word, word_attrs.clone())]
+        })
+        .chain(ellipsis.then(|| ("…", attrs.clone())));
+
+    buffer.set_rich_text(font_system, spans, attrs, Shaping::Advanced, None);
+}
+
+/// Shrink the buffer's font from `font_size` until its text wraps to at most `max_lines`,
+/// then truncate whatever still doesn't fit once the font hits its floor. With `truncate`
+/// it's truncated without shrinking. Returns the font size it ends up with.
+fn fit_to_lines(
+    buffer: &mut Buffer,
+    font_system: &mut FontSystem,
+    words: &[(&str, Attrs)],
+    attrs: &Attrs,
+    mut font_size: f32,
+    max_lines: usize,
+    truncate: bool,
+) -> f32 {
+    let min_font_size = font_size * MIN_CAPTION_SHRINK;
+
+    if !truncate {
+        while wrapped_line_count(buffer, font_system) > max_lines && font_size > min_font_size {
+            font_size = (font_size * 0.9).max(min_font_size);
+            buffer.set_metrics(font_system, Metrics::new(font_size, font_size * 1.2));
+        }
+    }
+
+    if wrapped_line_count(buffer, font_system) > max_lines {
+        truncate_to_lines(buffer, font_system, words, attrs, max_lines);
+    }
+
+    font_size
+}
+
+/// Replace the buffer's text with the longest prefix of `words` that, followed by an
+/// ellipsis, wraps to at most `max_lines`. Each word keeps its attributes, so karaoke
+/// highlighting survives truncation.
+fn truncate_to_lines(
+    buffer: &mut Buffer,
+    font_system: &mut FontSystem,
+    words: &[(&str, Attrs)],
+    attrs: &Attrs,
+    max_lines: usize,
+) {
+    let (mut low, mut high) = (0, words.len());
+    while low < high {
+        let mid = (low + high + 1) / 2;
+        set_word_spans(buffer, font_system, &words[..mid], attrs, true);
+
+        if wrapped_line_count(buffer, font_system) <= max_lines {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+
+    set_word_spans(buffer, font_system, &words[..low], attrs, true);
+}
+
+/// Compute the top of the caption block from its anchor and offset, keeping
+/// at least `block_height` pixels of the caption on-screen
+pub fn caption_y_position(position: u32, offset: f32, height: f32, block_height: f32) -> f32 {
+    let anchor = match position {
+        0 => height * 0.1,  // top
+        1 => height * 0.5,  // middle
+        _ => height * 0.85, // bottom (default)
+    };
+
+    let max_y = (height - block_height).max(0.0);
+    (anchor + offset * height).clamp(0.0, max_y)
+}
+
+/// Loads the Noto Sans CJK and Noto Naskh Arabic fonts bundled in `assets/fonts`, so captions
+/// in those scripts don't render as boxes on systems without them. cosmic-text's script
+/// fallback picks them up when the caption font has no glyphs for a character.
+pub(crate) fn load_bundled_fonts(font_system: &mut FontSystem) {
+    let Some(exe_dir) = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(PathBuf::from))
+    else {
+        return;
+    };
+
+    // Resources live in Contents/Resources on macOS and next to the executable elsewhere
+    for dir in [
+        exe_dir.join("../Resources/assets/fonts"),
+        exe_dir.join("assets/fonts"),
+    ] {
+        if dir.is_dir() {
+            info!("Loading bundled caption fonts from {:?}", dir);
+            font_system.db_mut().load_fonts_dir(dir);
+        }
+    }
+}
+
+/// Whether the first character with a strong direction is from a right-to-left script
+pub fn is_rtl(text: &str) -> bool {
+    text.chars()
+        .find(|c| c.is_alphabetic())
+        .is_some_and(|c| {
+            matches!(
+                c as u32,
+                // Hebrew, Arabic, Syriac, Thaana, NKo, Samaritan, Mandaic and their extensions
+                0x0590..=0x08FF | 0xFB1D..=0xFDFF | 0xFE70..=0xFEFF | 0x10800..=0x10FFF | 0x1E800..=0x1EFFF
+            )
+        })
+}
+
+/// Longest gap between two captions that still counts as back-to-back
+const ADJACENT_CAPTION_GAP: f32 = 0.05;
+
+/// Opacity of `caption` at `time`, ramping linearly over `fade_duration` at each end.
+/// An end shared with the `previous` or `next` caption, in the order they're spoken,
+/// cross-fades instead: the ramp is centred on the boundary, so the outgoing caption is
+/// still fading out while the next fades in and their opacities add up to full.
+/// Zero whenever the caption shouldn't be drawn.
+pub fn caption_fade(
+    time: f32,
+    caption: &cap_project::CaptionSegment,
+    previous: Option<&cap_project::CaptionSegment>,
+    next: Option<&cap_project::CaptionSegment>,
+    fade_duration: f32,
+) -> f32 {
+    // Short captions fade for at most half their length each way
+    let fade_duration = fade_duration.min((caption.end - caption.start) / 2.0);
+    if fade_duration <= 0.0 {
+        return if time >= caption.start && time < caption.end {
+            1.0
+        } else {
+            0.0
+        };
+    }
+
+    let follows_another =
+        previous.is_some_and(|segment| segment.end >= caption.start - ADJACENT_CAPTION_GAP);
+    let followed_by_another =
+        next.is_some_and(|segment| segment.start <= caption.end + ADJACENT_CAPTION_GAP);
+
+    let fade_in_start = if follows_another {
+        caption.start - fade_duration / 2.0
+    } else {
+        caption.start
+    };
+    let fade_out_end = if followed_by_another {
+        caption.end + fade_duration / 2.0
+    } else {
+        caption.end
+    };
+
+    let fade_in = (time - fade_in_start) / fade_duration;
+    let fade_out = (fade_out_end - time) / fade_duration;
+
+    fade_in.min(fade_out).clamp(0.0, 1.0)
+}
+
 /// Function to find the current caption segment based on playback time
 pub fn find_caption_at_time(time: f32, segments: &[CaptionSegment]) -> Option<&CaptionSegment> {
     segments
@@ -578,10 +1079,8 @@ pub fn find_caption_at_time_project(
         .iter()
         .find(|segment| time >= segment.start && time < segment.end)
         .map(|segment| CaptionSegment {
-            id: segment.id.clone(),
-            start: segment.start,
-            end: segment.end,
-            text: segment.text.clone(),
+            active_word: segment.active_word(time),
+            ..convert_project_caption(segment)
         })
 }
 
@@ -592,5 +1091,142 @@ pub fn convert_project_caption(segment: &cap_project::CaptionSegment) -> Caption
         start: segment.start,
         end: segment.end,
         text: segment.text.clone(),
+        style_override: segment.style_override.clone(),
+        words: segment.words.clone(),
+        active_word: None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn y_position_presets_without_offset() {
+        assert_eq!(caption_y_position(0, 0.0, 1000.0, 36.0), 100.0);
+        assert_eq!(caption_y_position(1, 0.0, 1000.0, 36.0), 500.0);
+        assert_eq!(caption_y_position(2, 0.0, 1000.0, 36.0), 850.0);
+    }
+
+    #[test]
+    fn y_position_offset_is_clamped_on_screen() {
+        assert_eq!(caption_y_position(2, -0.1, 1000.0, 36.0), 750.0);
+        assert_eq!(caption_y_position(2, 0.5, 1000.0, 36.0), 964.0);
+        assert_eq!(caption_y_position(0, -0.5, 1000.0, 36.0), 0.0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn fades_at_caption_ends() {
+        let segment = |id: &str, start: f32, end: f32| cap_project::CaptionSegment {
+            id: id.to_string(),
+            start,
+            end,
+            ..Default::default()
+        };
+        let (a, b) = (segment("a", 1.0, 3.0), segment("b", 3.0, 5.0));
+
+        assert_eq!(caption_fade(1.25, &a, None, Some(&b), 0.5), 0.5);
+        assert_eq!(caption_fade(2.0, &a, None, Some(&b), 0.5), 1.0);
+        assert_eq!(caption_fade(4.75, &b, Some(&a), None, 0.5), 0.5);
+        assert_eq!(caption_fade(1.25, &a, None, Some(&b), 0.0), 1.0);
+
+        assert_eq!(caption_fade(0.5, &a, None, Some(&b), 0.5), 0.0);
+        assert_eq!(caption_fade(5.0, &b, Some(&a), None, 0.5), 0.0);
+
+        // The shared boundary at 3s cross-fades over 2.75s to 3.25s
+        assert_eq!(caption_fade(2.75, &a, None, Some(&b), 0.5), 1.0);
+        assert_eq!(caption_fade(2.75, &b, Some(&a), None, 0.5), 0.0);
+        assert_eq!(caption_fade(3.0, &a, None, Some(&b), 0.5), 0.5);
+        assert_eq!(caption_fade(3.0, &b, Some(&a), None, 0.5), 0.5);
+        assert_eq!(caption_fade(3.125, &a, None, Some(&b), 0.5), 0.25);
+        assert_eq!(caption_fade(3.125, &b, Some(&a), None, 0.5), 0.75);
+        assert_eq!(caption_fade(3.25, &a, None, Some(&b), 0.5), 0.0);
+    }
+
+    #[test]
+    fn long_captions_fit_max_lines() {
+        let mut font_system = FontSystem::new();
+        let text = "a very long caption ".repeat(50);
+        let words = text
+            .split_whitespace()
+            .map(|word| (word, Attrs::new()))
+            .collect::<Vec<_>>();
+
+        for truncate in [false, true] {
+            let mut buffer = Buffer::new(&mut font_system, Metrics::new(48.0, 48.0 * 1.2));
+            buffer.set_size(&mut font_system, Some(640.0), None);
+            buffer.set_wrap(&mut font_system, glyphon::Wrap::Word);
+            buffer.set_text(&mut font_system, &text, &Attrs::new(), Shaping::Advanced);
+
+            let font_size = fit_to_lines(
+                &mut buffer,
+                &mut font_system,
+                &words,
+                &Attrs::new(),
+                48.0,
+                2,
+                truncate,
+            );
+
+            assert!(wrapped_line_count(&mut buffer, &mut font_system) <= 2);
+            assert!(font_size >= 48.0 * MIN_CAPTION_SHRINK);
+            if truncate {
+                assert_eq!(font_size, 48.0);
+            }
+        }
+    }
+
+    #[test]
+    fn detects_rtl_scripts() {
+        assert!(is_rtl("مرحبا بالعالم"));
+        assert!(is_rtl("1. שלום"));
+        assert!(!is_rtl("Hello مرحبا"));
+        assert!(!is_rtl("こんにちは"));
+        assert!(!is_rtl("123"));
+    }
+
+    #[test]
+    fn outline_offsets_scale_with_width() {
+        let thin = outline_offsets(1.0);
+        assert_eq!(thin.len(), 8);
+        assert!(thin
+            .iter()
+            .all(|(x, y)| ((x * x + y * y).sqrt() - 1.0).abs() < 1e-4));
+
+        let thick = outline_offsets(4.0);
+        assert_eq!(thick.len(), 26);
+        assert!(thick
+            .iter()
+            .all(|(x, y)| ((x * x + y * y).sqrt() - 4.0).abs() < 1e-4));
+    }
+
+    #[test]
+    fn avoid_obstacle_ignores_non_overlapping() {
+        let caption = [50.0, 850.0, 950.0, 900.0];
+        let camera = [800.0, 100.0, 950.0, 250.0];
+        assert_eq!(avoid_obstacle(caption, camera, 1000.0, 1000.0, 10.0), None);
+    }
+
+    #[test]
+    fn avoid_obstacle_moves_above_bottom_camera() {
+        let caption = [50.0, 850.0, 950.0, 900.0];
+        let camera = [800.0, 800.0, 950.0, 950.0];
+        assert_eq!(
+            avoid_obstacle(caption, camera, 1000.0, 1000.0, 10.0),
+            Some(CaptionPlacement::Vertical(740.0))
+        );
+    }
+
+    #[test]
+    fn avoid_obstacle_narrows_when_no_vertical_room() {
+        let caption = [50.0, 100.0, 950.0, 900.0];
+        let camera = [800.0, 50.0, 950.0, 950.0];
+        assert_eq!(
+            avoid_obstacle(caption, camera, 1000.0, 1000.0, 10.0),
+            Some(CaptionPlacement::Horizontal {
+                left: 10.0,
+                right: 790.0
+            })
+        );
+    }
+}
@@ -0,0 +1,213 @@
+use bytemuck::{Pod, Zeroable};
+use cap_project::ColorAdjust;
+use wgpu::util::DeviceExt;
+
+use crate::ProjectUniforms;
+
+/// Applies the project's color adjustments to everything composited so far
+pub struct ColorAdjustLayer {
+    pub enabled: bool,
+    sampler: wgpu::Sampler,
+    uniforms_buffer: wgpu::Buffer,
+    pipeline: ColorAdjustPipeline,
+}
+
+impl ColorAdjustLayer {
+    pub fn new(device: &wgpu::Device) -> Self {
+        Self {
+            enabled: false,
+            sampler: device.create_sampler(&wgpu::SamplerDescriptor {
+                address_mode_u: wgpu::AddressMode::ClampToEdge,
+                address_mode_v: wgpu::AddressMode::ClampToEdge,
+                address_mode_w: wgpu::AddressMode::ClampToEdge,
+                mag_filter: wgpu::FilterMode::Nearest,
+                min_filter: wgpu::FilterMode::Nearest,
+                mipmap_filter: wgpu::FilterMode::Nearest,
+                ..Default::default()
+            }),
+            uniforms_buffer: device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("ColorAdjust Uniform Buffer"),
+                contents: bytemuck::cast_slice(&[ColorAdjustUniforms::default()]),
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            }),
+            pipeline: ColorAdjustPipeline::new(device),
+        }
+    }
+
+    pub fn prepare(&mut self, queue: &wgpu::Queue, uniforms: &ProjectUniforms) {
+        let adjust = uniforms
+            .project
+            .color_adjust
+            .map(|adjust| adjust.clamped())
+            .unwrap_or_default();
+
+        // Neutral settings skip the pass entirely so output is untouched
+        self.enabled = !adjust.is_neutral();
+        if !self.enabled {
+            return;
+        }
+
+        queue.write_buffer(
+            &self.uniforms_buffer,
+            0,
+            bytemuck::cast_slice(&[ColorAdjustUniforms::from(adjust)]),
+        );
+    }
+
+    pub fn render(
+        &self,
+        pass: &mut wgpu::RenderPass<'_>,
+        device: &wgpu::Device,
+        source_texture: &wgpu::TextureView,
+    ) {
+        pass.set_pipeline(&self.pipeline.render_pipeline);
+        pass.set_bind_group(
+            0,
+            &self
+                .pipeline
+                .bind_group(device, &self.uniforms_buffer, source_texture, &self.sampler),
+            &[],
+        );
+        pass.draw(0..4, 0..1);
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable, Default)]
+pub struct ColorAdjustUniforms {
+    brightness: f32,
+    contrast: f32,
+    saturation: f32,
+    temperature: f32,
+}
+
+impl From<ColorAdjust> for ColorAdjustUniforms {
+    fn from(value: ColorAdjust) -> Self {
+        Self {
+            brightness: value.brightness,
+            contrast: value.contrast,
+            saturation: value.saturation,
+            temperature: value.temperature,
+        }
+    }
+}
+
+pub struct ColorAdjustPipeline {
+    pub bind_group_layout: wgpu::BindGroupLayout,
+    pub render_pipeline: wgpu::RenderPipeline,
+}
+
+impl ColorAdjustPipeline {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("color-adjust Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Color Adjust Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/color-adjust.wgsl").into()),
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Color Adjust Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Color Adjust Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: wgpu::PipelineCompilationOptions {
+                    constants: &[],
+                    zero_initialize_workgroup_memory: false,
+                },
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: wgpu::TextureFormat::Rgba8UnormSrgb,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions {
+                    constants: &[],
+                    zero_initialize_workgroup_memory: false,
+                },
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: Some(wgpu::Face::Back),
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+        Self {
+            bind_group_layout,
+            render_pipeline,
+        }
+    }
+
+    pub fn bind_group(
+        &self,
+        device: &wgpu::Device,
+        uniform_buffer: &wgpu::Buffer,
+        texture_view: &wgpu::TextureView,
+        sampler: &wgpu::Sampler,
+    ) -> wgpu::BindGroup {
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("ColorAdjust Bind Group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(texture_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(sampler),
+                },
+            ],
+        })
+    }
+}
@@ -213,7 +213,8 @@ impl CursorLayer {
             let cursor_texture_size_aspect =
                 cursor_texture_size.width as f32 / cursor_texture_size.height as f32;
 
-            let screen_size = constants.options.screen_size;
+            let screen_size =
+                ProjectUniforms::get_screen_size(&constants.options, &uniforms.project);
             let cursor_size_percentage = if uniforms.cursor_size <= 0.0 {
                 100.0
             } else {
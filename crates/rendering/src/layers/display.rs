@@ -2,6 +2,7 @@ use cap_project::XY;
 
 use crate::{
     composite_frame::{CompositeVideoFramePipeline, CompositeVideoFrameUniforms},
+    lut::ColorLut,
     DecodedSegmentFrames,
 };
 
@@ -78,10 +79,11 @@ impl DisplayLayer {
         queue.write_buffer(&self.uniforms_buffer, 0, bytemuck::cast_slice(&[uniforms]));
     }
 
-    pub fn render(&self, pass: &mut wgpu::RenderPass<'_>) {
+    pub fn render(&self, pass: &mut wgpu::RenderPass<'_>, lut: &ColorLut) {
         if let Some(bind_group) = &self.bind_group {
             pass.set_pipeline(&self.pipeline.render_pipeline);
             pass.set_bind_group(0, bind_group, &[]);
+            pass.set_bind_group(1, lut.bind_group(), &[]);
             pass.draw(0..4, 0..1);
         }
     }
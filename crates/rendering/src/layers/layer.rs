@@ -0,0 +1,36 @@
+use cap_project::XY;
+
+use crate::{DecodedSegmentFrames, ProjectUniforms, RenderVideoConstants};
+
+/// Something drawn into each rendered frame. Every layer is prepared for the frame
+/// before any are drawn, so `prepare` is where GPU resources should be updated.
+///
+/// Custom layers can be added to `RendererLayers` with `with_layer`, eg. for logos or
+/// watermarks. They're prepared even while hidden by the scene, and are free to
+/// create their resources on the first `prepare` using `constants.device`.
+pub trait RenderLayer: Send {
+    fn prepare(
+        &mut self,
+        uniforms: &ProjectUniforms,
+        segment_frames: &DecodedSegmentFrames,
+        output_size: XY<u32>,
+        constants: &RenderVideoConstants,
+    );
+
+    /// Draw into `pass`, which targets the frame as composited by the layers beneath
+    fn render<'a>(&'a self, pass: &mut wgpu::RenderPass<'a>);
+}
+
+/// Where a custom layer is drawn among the built-in ones.
+/// Layers at the same position are drawn in the order they were added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayerPosition {
+    /// Over the background and its blur, under the display
+    AboveBackground,
+    /// Over the display and cursor, under the cameras
+    AboveDisplay,
+    /// Over the cameras. Color adjustments and the LUT are applied afterwards.
+    AboveCamera,
+    /// Over everything, including captions, with its colors left as they are
+    Top,
+}
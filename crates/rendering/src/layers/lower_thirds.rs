@@ -0,0 +1,383 @@
+use cap_project::{LowerThird, LowerThirdAnimation, XY};
+use glyphon::{
+    Attrs, Buffer, Cache, Color, Family, FontSystem, Metrics, Resolution, Shaping, SwashCache,
+    TextArea, TextAtlas, TextBounds, TextRenderer, Viewport, Weight,
+};
+use log::warn;
+use wgpu::{util::DeviceExt, Device, Queue};
+
+use super::captions::{
+    background_quad_pipeline, caption_y_position, load_bundled_fonts, wrapped_line_count,
+    QuadVertex,
+};
+use super::RenderLayer;
+use crate::{parse_color_component, DecodedSegmentFrames, ProjectUniforms, RenderVideoConstants};
+
+/// Subtitle size as a fraction of the title's
+const SUBTITLE_SCALE: f32 = 0.7;
+
+/// Widest the text may get before wrapping, as a fraction of the output width
+const MAX_TEXT_WIDTH: f32 = 0.6;
+
+/// Name cards from the project's `lower_thirds`, drawn over the bottom left of the frame
+pub struct LowerThirdsLayer {
+    /// Loaded on first use, since most projects have no lower thirds
+    font_system: Option<FontSystem>,
+    swash_cache: SwashCache,
+    text_atlas: TextAtlas,
+    text_renderer: TextRenderer,
+    viewport: Viewport,
+    text_buffer: Buffer,
+    subtitle_buffer: Buffer,
+    background_pipeline: wgpu::RenderPipeline,
+    background_vertex_buffer: wgpu::Buffer,
+    background_index_buffer: wgpu::Buffer,
+    visible: bool,
+}
+
+impl LowerThirdsLayer {
+    pub fn new(device: &Device, queue: &Queue) -> Self {
+        let cache = Cache::new(device);
+        let viewport = Viewport::new(device, &cache);
+        let mut text_atlas =
+            TextAtlas::new(device, queue, &cache, wgpu::TextureFormat::Rgba8UnormSrgb);
+        let text_renderer = TextRenderer::new(
+            &mut text_atlas,
+            device,
+            wgpu::MultisampleState::default(),
+            None,
+        );
+
+        let background_vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Lower Third Background Vertex Buffer"),
+            size: std::mem::size_of::<[QuadVertex; 4]>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let indices: [u16; 6] = [0, 1, 2, 2, 3, 0];
+        let background_index_buffer =
+            device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Lower Third Background Index Buffer"),
+                contents: bytemuck::cast_slice(&indices),
+                usage: wgpu::BufferUsages::INDEX,
+            });
+
+        let metrics = Metrics::new(36.0, 36.0 * 1.2);
+
+        Self {
+            font_system: None,
+            swash_cache: SwashCache::new(),
+            text_atlas,
+            text_renderer,
+            viewport,
+            text_buffer: Buffer::new_empty(metrics),
+            subtitle_buffer: Buffer::new_empty(metrics),
+            background_pipeline: background_quad_pipeline(device),
+            background_vertex_buffer,
+            background_index_buffer,
+            visible: false,
+        }
+    }
+}
+
+impl RenderLayer for LowerThirdsLayer {
+    fn prepare(
+        &mut self,
+        uniforms: &ProjectUniforms,
+        _segment_frames: &DecodedSegmentFrames,
+        output_size: XY<u32>,
+        constants: &RenderVideoConstants,
+    ) {
+        self.visible = false;
+
+        // Timed on the edited timeline, like captions
+        let time = uniforms.frame_time;
+        let Some(lower_third) = find_lower_third_at_time(time, &uniforms.project.lower_thirds)
+        else {
+            return;
+        };
+
+        let progress = lower_third_progress(time, lower_third);
+        if progress <= 0.0 {
+            return;
+        }
+
+        let style = &lower_third.style;
+        let (alpha, slide) = match style.animation {
+            LowerThirdAnimation::Fade => (progress, 0.0),
+            LowerThirdAnimation::Slide => (1.0, 1.0 - ease_out(progress)),
+        };
+
+        let (width, height) = (output_size.x as f32, output_size.y as f32);
+        let font_size = style.size as f32 * (height / 1080.0);
+        let subtitle_size = font_size * SUBTITLE_SCALE;
+        let padding = font_size * 0.4;
+
+        let family = match style.font.as_str() {
+            "System Serif" => Family::Serif,
+            "System Monospace" => Family::Monospace,
+            _ => Family::SansSerif,
+        };
+
+        let font_system = self.font_system.get_or_insert_with(|| {
+            let mut font_system = FontSystem::new();
+            load_bundled_fonts(&mut font_system);
+            font_system
+        });
+
+        let mut layout_text = |text: &str, size: f32, weight: Weight| {
+            let mut buffer = Buffer::new(font_system, Metrics::new(size, size * 1.2));
+            buffer.set_size(font_system, Some(width * MAX_TEXT_WIDTH), None);
+            buffer.set_wrap(font_system, glyphon::Wrap::Word);
+            buffer.set_text(
+                font_system,
+                text,
+                &Attrs::new().family(family).weight(weight),
+                Shaping::Advanced,
+            );
+
+            let lines = wrapped_line_count(&mut buffer, font_system);
+            let line_width = buffer
+                .layout_runs()
+                .map(|run| run.line_w)
+                .fold(0.0, f32::max);
+
+            (buffer, line_width, size * 1.2 * lines as f32)
+        };
+
+        let (text_buffer, text_width, text_height) =
+            layout_text(&lower_third.text, font_size, Weight::BOLD);
+        let (subtitle_buffer, subtitle_width, subtitle_height) = match lower_third
+            .subtitle
+            .as_deref()
+            .filter(|subtitle| !subtitle.is_empty())
+        {
+            Some(subtitle) => layout_text(subtitle, subtitle_size, Weight::NORMAL),
+            None => (
+                Buffer::new_empty(Metrics::new(subtitle_size, subtitle_size * 1.2)),
+                0.0,
+                0.0,
+            ),
+        };
+        self.text_buffer = text_buffer;
+        self.subtitle_buffer = subtitle_buffer;
+
+        let box_width = text_width.max(subtitle_width) + padding * 2.0;
+        let box_height = text_height + subtitle_height + padding * 2.0;
+
+        // Sliding starts with the box just off the left edge
+        let margin = width * 0.05;
+        let left = margin - slide * (margin + box_width);
+        let top = (lower_third_bottom(uniforms, height) - box_height).max(0.0);
+
+        let background_color = [
+            parse_color_component(&style.background_color, 0),
+            parse_color_component(&style.background_color, 1),
+            parse_color_component(&style.background_color, 2),
+            style.background_opacity as f32 / 100.0 * alpha,
+        ];
+        let (ndc_left, ndc_right) = (
+            left / width * 2.0 - 1.0,
+            (left + box_width) / width * 2.0 - 1.0,
+        );
+        let (ndc_top, ndc_bottom) = (
+            1.0 - top / height * 2.0,
+            1.0 - (top + box_height) / height * 2.0,
+        );
+        let vertices = [
+            [ndc_left, ndc_top],
+            [ndc_right, ndc_top],
+            [ndc_right, ndc_bottom],
+            [ndc_left, ndc_bottom],
+        ]
+        .map(|position| QuadVertex {
+            position,
+            color: background_color,
+        });
+        constants.queue.write_buffer(
+            &self.background_vertex_buffer,
+            0,
+            bytemuck::cast_slice(&vertices),
+        );
+
+        self.viewport.update(
+            &constants.queue,
+            Resolution {
+                width: output_size.x,
+                height: output_size.y,
+            },
+        );
+
+        let bounds = TextBounds {
+            left: 0,
+            top: 0,
+            right: output_size.x as i32,
+            bottom: output_size.y as i32,
+        };
+        let text_areas = [
+            TextArea {
+                buffer: &self.text_buffer,
+                left: left + padding,
+                top: top + padding,
+                scale: 1.0,
+                bounds,
+                default_color: hex_color(&style.color, alpha),
+                custom_glyphs: &[],
+            },
+            TextArea {
+                buffer: &self.subtitle_buffer,
+                left: left + padding,
+                top: top + padding + text_height,
+                scale: 1.0,
+                bounds,
+                default_color: hex_color(&style.subtitle_color, alpha),
+                custom_glyphs: &[],
+            },
+        ];
+
+        if let Err(e) = self.text_renderer.prepare(
+            &constants.device,
+            &constants.queue,
+            font_system,
+            &mut self.text_atlas,
+            &self.viewport,
+            text_areas,
+            &mut self.swash_cache,
+        ) {
+            warn!("Error preparing lower third text: {:?}", e);
+            return;
+        }
+
+        self.visible = true;
+    }
+
+    fn render<'a>(&'a self, pass: &mut wgpu::RenderPass<'a>) {
+        if !self.visible {
+            return;
+        }
+
+        pass.set_pipeline(&self.background_pipeline);
+        pass.set_vertex_buffer(0, self.background_vertex_buffer.slice(..));
+        pass.set_index_buffer(
+            self.background_index_buffer.slice(..),
+            wgpu::IndexFormat::Uint16,
+        );
+        pass.draw_indexed(0..6, 0, 0..1);
+
+        if let Err(e) = self
+            .text_renderer
+            .render(&self.text_atlas, &self.viewport, pass)
+        {
+            warn!("Error rendering lower third text: {:?}", e);
+        }
+    }
+}
+
+/// The lower third showing at `time`. The first listed wins where they overlap.
+pub fn find_lower_third_at_time(time: f32, lower_thirds: &[LowerThird]) -> Option<&LowerThird> {
+    lower_thirds
+        .iter()
+        .find(|lower_third| time >= lower_third.start && time < lower_third.end)
+}
+
+/// How far `lower_third` is into showing at `time`, from 0 while hidden to 1 once
+/// fully in, ramping over its animation duration at each end
+pub fn lower_third_progress(time: f32, lower_third: &LowerThird) -> f32 {
+    if time < lower_third.start || time >= lower_third.end {
+        return 0.0;
+    }
+
+    // Short lower thirds animate for at most half their length each way
+    let duration = lower_third
+        .style
+        .animation_duration
+        .min((lower_third.end - lower_third.start) / 2.0);
+    if duration <= 0.0 {
+        return 1.0;
+    }
+
+    let entering = (time - lower_third.start) / duration;
+    let leaving = (lower_third.end - time) / duration;
+
+    entering.min(leaving).clamp(0.0, 1.0)
+}
+
+/// Decelerates towards 1, so slides settle into place
+fn ease_out(t: f32) -> f32 {
+    1.0 - (1.0 - t).powi(3)
+}
+
+/// Bottom edge of the lower thirds, just above where bottom captions are anchored
+fn lower_third_bottom(uniforms: &ProjectUniforms, height: f32) -> f32 {
+    let caption_offset = uniforms
+        .project
+        .captions
+        .as_ref()
+        .map(|captions| &captions.settings)
+        .filter(|settings| {
+            settings.enabled && !matches!(settings.position.as_str(), "top" | "middle")
+        })
+        .map_or(0.0, |settings| settings.position_offset);
+
+    caption_y_position(2, caption_offset, height, 0.0) - height * 0.02
+}
+
+fn hex_color(hex: &str, alpha: f32) -> Color {
+    let component = |index| (parse_color_component(hex, index) * 255.0) as u8;
+    Color::rgba(
+        component(0),
+        component(1),
+        component(2),
+        (alpha * 255.0) as u8,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn animates_in_and_out_over_duration() {
+        let mut lower_third = LowerThird {
+            text: "Jane Doe".to_string(),
+            start: 2.0,
+            end: 6.0,
+            style: cap_project::LowerThirdStyle {
+                animation_duration: 0.5,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(lower_third_progress(1.9, &lower_third), 0.0);
+        assert_eq!(lower_third_progress(2.25, &lower_third), 0.5);
+        assert_eq!(lower_third_progress(4.0, &lower_third), 1.0);
+        assert_eq!(lower_third_progress(5.75, &lower_third), 0.5);
+        assert_eq!(lower_third_progress(6.0, &lower_third), 0.0);
+
+        // Short lower thirds spend half their time coming in and half going out
+        lower_third.end = 2.5;
+        assert_eq!(lower_third_progress(2.25, &lower_third), 1.0);
+
+        assert_eq!(ease_out(0.0), 0.0);
+        assert_eq!(ease_out(1.0), 1.0);
+        assert!(ease_out(0.5) > 0.5);
+    }
+
+    #[test]
+    fn first_overlapping_lower_third_wins() {
+        let lower_third = |text: &str, start: f32, end: f32| LowerThird {
+            text: text.to_string(),
+            start,
+            end,
+            ..Default::default()
+        };
+        let lower_thirds = [lower_third("a", 0.0, 5.0), lower_third("b", 3.0, 8.0)];
+
+        let text_at = |time| find_lower_third_at_time(time, &lower_thirds).map(|l| l.text.as_str());
+        assert_eq!(text_at(4.0), Some("a"));
+        assert_eq!(text_at(6.0), Some("b"));
+        assert_eq!(text_at(9.0), None);
+    }
+}
@@ -2,12 +2,18 @@ mod background;
 mod blur;
 mod camera;
 mod captions;
+mod color_adjust;
 mod cursor;
 mod display;
+mod layer;
+mod lower_thirds;
 
 pub use background::*;
 pub use blur::*;
 pub use camera::*;
 pub use captions::*;
+pub use color_adjust::*;
 pub use cursor::*;
 pub use display::*;
+pub use layer::*;
+pub use lower_thirds::*;
@@ -8,32 +8,42 @@ use core::f64;
 use cursor_interpolation::{interpolate_cursor, InterpolatedCursorPosition};
 use decoder::{spawn_decoder, AsyncVideoDecoderHandle};
 use frame_pipeline::finish_encoder;
-use futures::future::OptionFuture;
+use futures::future::{join_all, OptionFuture};
 use futures::FutureExt;
 use image::GenericImageView;
+use interpolation::FrameInterpolator;
 use layers::{
-    Background, BackgroundLayer, BlurLayer, CameraLayer, CaptionsLayer, CursorLayer, DisplayLayer,
+    Background, BackgroundLayer, BlurLayer, CameraLayer, CaptionsLayer, ColorAdjustLayer,
+    CursorLayer, DisplayLayer, LowerThirdsLayer,
 };
+use lut::ColorLut;
+use serde::Serialize;
 use specta::Type;
 use spring_mass_damper::SpringMassDamperSimulationConfig;
 use std::{collections::HashMap, sync::Arc};
 use std::{path::PathBuf, time::Instant};
 use tokio::sync::mpsc;
+use tracing::warn;
 
 mod composite_frame;
 mod coord;
 mod cursor_interpolation;
 pub mod decoder;
 mod frame_pipeline;
+mod interpolation;
 mod layers;
+pub mod lut;
 mod project_recordings;
 mod spring_mass_damper;
+mod title_card;
 mod zoom;
 
 pub use coord::*;
 pub use decoder::DecodedFrame;
 pub use frame_pipeline::RenderedFrame;
+pub use layers::{LayerPosition, RenderLayer};
 pub use project_recordings::{ProjectRecordingsMeta, SegmentRecordings};
+pub use title_card::generate_title_card;
 
 use zoom::*;
 
@@ -49,12 +59,15 @@ pub struct RenderOptions {
 pub struct RecordingSegmentDecoders {
     screen: AsyncVideoDecoderHandle,
     camera: Option<AsyncVideoDecoderHandle>,
+    additional_cameras: Vec<Option<AsyncVideoDecoderHandle>>,
     pub segment_offset: f64,
 }
 
 pub struct SegmentVideoPaths {
     pub display: PathBuf,
     pub camera: Option<PathBuf>,
+    /// Indexed by slot starting at slot 1, with `None` for slots that weren't in use
+    pub additional_cameras: Vec<Option<PathBuf>>,
 }
 
 impl RecordingSegmentDecoders {
@@ -77,7 +90,7 @@ impl RecordingSegmentDecoders {
             match &meta {
                 StudioRecordingMeta::SingleSegment { segment } => segment.display.fps,
                 StudioRecordingMeta::MultipleSegments { inner, .. } => {
-                    inner.segments[segment_i].display.fps
+                    inner.segments[segment_i].display()?.fps
                 }
             },
             match &meta {
@@ -86,7 +99,7 @@ impl RecordingSegmentDecoders {
                     let segment = &inner.segments[segment_i];
 
                     latest_start_time
-                        .zip(segment.display.start_time)
+                        .zip(segment.display()?.start_time)
                         .map(|(latest_start_time, display_time)| latest_start_time - display_time)
                         .unwrap_or(0.0)
                 }
@@ -123,9 +136,37 @@ impl RecordingSegmentDecoders {
         .await
         .transpose()?;
 
+        let mut additional_cameras = vec![];
+        for (i, path) in segment.additional_cameras.into_iter().enumerate() {
+            let StudioRecordingMeta::MultipleSegments { inner, .. } = &meta else {
+                break;
+            };
+            let (Some(path), Some(Some(camera))) =
+                (path, inner.segments[segment_i].additional_cameras.get(i))
+            else {
+                additional_cameras.push(None);
+                continue;
+            };
+
+            additional_cameras.push(Some(
+                spawn_decoder(
+                    "camera",
+                    recording_meta.project_path.join(path),
+                    camera.fps,
+                    latest_start_time
+                        .zip(camera.start_time)
+                        .map(|(latest_start_time, start_time)| latest_start_time - start_time)
+                        .unwrap_or(0.0),
+                )
+                .await
+                .map_err(|e| format!("Camera {}:{e}", i + 1))?,
+            ));
+        }
+
         Ok(Self {
             screen,
             camera,
+            additional_cameras,
             segment_offset: latest_start_time.unwrap_or(0.0),
         })
     }
@@ -135,18 +176,29 @@ impl RecordingSegmentDecoders {
         segment_time: f32,
         needs_camera: bool,
     ) -> Option<DecodedSegmentFrames> {
-        let (screen, camera) = tokio::join!(
+        let (screen, camera, additional_cameras) = tokio::join!(
             self.screen.get_frame(segment_time),
             OptionFuture::from(
                 needs_camera
                     .then(|| self.camera.as_ref().map(|d| d.get_frame(segment_time)))
                     .flatten()
-            )
+            ),
+            join_all(self.additional_cameras.iter().map(|d| {
+                OptionFuture::from(
+                    d.as_ref()
+                        .filter(|_| needs_camera)
+                        .map(|d| d.get_frame(segment_time)),
+                )
+            }))
         );
 
         Some(DecodedSegmentFrames {
             screen_frame: screen?,
             camera_frame: camera.flatten(),
+            additional_camera_frames: additional_cameras
+                .into_iter()
+                .map(Option::flatten)
+                .collect(),
             segment_time,
             recording_time: segment_time + self.segment_offset as f32,
         })
@@ -167,6 +219,8 @@ pub enum RenderingError {
     ChannelSendFrameFailed(#[from] mpsc::error::SendError<(RenderedFrame, u32)>),
     #[error("Failed to load image: {0}")]
     ImageLoadError(String),
+    #[error("Failed to generate title card: {0}")]
+    TitleCardFailed(String),
 }
 
 pub struct RenderSegment {
@@ -184,6 +238,7 @@ pub async fn render_video_to_channel(
     fps: u32,
     resolution_base: XY<u32>,
     recordings: &ProjectRecordingsMeta,
+    custom_layers: Vec<(LayerPosition, Box<dyn RenderLayer>)>,
 ) -> Result<(), RenderingError> {
     ffmpeg::init().unwrap();
 
@@ -199,6 +254,12 @@ pub async fn render_video_to_channel(
     let mut frame_renderer = FrameRenderer::new(&constants);
 
     let mut layers = RendererLayers::new(&constants.device, &constants.queue);
+    for (position, layer) in custom_layers {
+        layers.add_layer(position, layer);
+    }
+
+    // Interpolation for the timeline segment being rendered, if it's slowed down and has it enabled
+    let mut interpolator: Option<(usize, Option<FrameInterpolator>)> = None;
 
     loop {
         if frame_number >= total_frames {
@@ -230,11 +291,49 @@ pub async fn render_video_to_channel(
             Some(cap_project::SceneMode::Default) | None => !project.camera.hide,
         };
 
-        if let Some(segment_frames) = segment
+        if let Some(mut segment_frames) = segment
             .decoders
             .get_frames(segment_time as f32, needs_camera)
             .await
         {
+            if let Some((timeline_i, timeline_segment)) = project
+                .timeline
+                .as_ref()
+                .and_then(|t| t.get_segment_at_time(time))
+                .filter(|(_, s)| s.interpolate && s.timescale < 1.0)
+            {
+                if interpolator.as_ref().map(|(i, _)| *i) != Some(timeline_i) {
+                    let display = &recordings.segments[segment_i as usize].display;
+                    let new_interpolator = FrameInterpolator::new(
+                        display.width,
+                        display.height,
+                        display.fps,
+                        fps,
+                        timeline_segment.timescale,
+                        timeline_segment.start,
+                    )
+                    .map_err(|e| warn!("Failed to set up frame interpolation: {e}"))
+                    .ok();
+
+                    interpolator = Some((timeline_i, new_interpolator));
+                }
+
+                // Without an interpolated frame the decoded one is repeated as usual
+                if let Some((_, Some(interpolator))) = &mut interpolator {
+                    let output_frame = ((segment_time - timeline_segment.start)
+                        / timeline_segment.timescale
+                        * fps as f64)
+                        .round() as u32;
+
+                    if let Some(frame) = interpolator
+                        .get_frame(&segment.decoders.screen, output_frame)
+                        .await
+                    {
+                        segment_frames.screen_frame = frame;
+                    }
+                }
+            }
+
             let uniforms = ProjectUniforms::new(
                 &constants,
                 &project,
@@ -310,12 +409,22 @@ pub struct CursorTexture {
     hotspot: XY<f32>,
 }
 
+/// Whether frames are rendered on a hardware GPU or wgpu's software fallback adapter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Type)]
+pub enum RenderBackend {
+    Gpu,
+    Cpu,
+}
+
 pub struct RenderVideoConstants {
     pub _instance: wgpu::Instance,
     pub _adapter: wgpu::Adapter,
     pub queue: wgpu::Queue,
     pub device: wgpu::Device,
+    pub backend: RenderBackend,
     pub options: RenderOptions,
+    /// Frame sizes of the additional cameras, indexed by slot starting at slot 1
+    pub additional_camera_sizes: Vec<Option<XY<u32>>>,
     pub cursor_textures: HashMap<String, CursorTexture>,
     background_textures: std::sync::Arc<tokio::sync::RwLock<HashMap<String, wgpu::Texture>>>,
 }
@@ -333,18 +442,29 @@ impl RenderVideoConstants {
                 .as_ref()
                 .map(|c| XY::new(c.width, c.height)),
         };
+        let additional_camera_sizes = segments[0]
+            .additional_cameras
+            .iter()
+            .map(|c| c.as_ref().map(|c| XY::new(c.width, c.height)))
+            .collect();
 
         let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
-        let adapter = instance
-            .request_adapter(&wgpu::RequestAdapterOptions::default())
-            .await
-            .map_err(|_| RenderingError::NoAdapter)?;
-        let (device, queue) = adapter
-            .request_device(&wgpu::DeviceDescriptor {
-                required_features: wgpu::Features::MAPPABLE_PRIMARY_BUFFERS,
-                ..Default::default()
-            })
-            .await?;
+
+        // Headless machines and VMs often have no usable GPU, so fall back to
+        // wgpu's software adapter rather than refusing to render at all
+        let (adapter, device, queue) = match Self::request_device(&instance, false).await {
+            Ok(v) => v,
+            Err(e) => {
+                tracing::warn!("GPU unavailable ({e}), falling back to software rendering");
+                Self::request_device(&instance, true).await?
+            }
+        };
+
+        let backend = match adapter.get_info().device_type {
+            wgpu::DeviceType::Cpu => RenderBackend::Cpu,
+            _ => RenderBackend::Gpu,
+        };
+        tracing::info!("Rendering with {:?} backend", backend);
 
         let cursor_textures = Self::load_cursor_textures(&device, &queue, recording_meta, meta);
         let background_textures = Arc::new(tokio::sync::RwLock::new(HashMap::new()));
@@ -354,12 +474,38 @@ impl RenderVideoConstants {
             _adapter: adapter,
             device,
             queue,
+            backend,
             options,
+            additional_camera_sizes,
             cursor_textures,
             background_textures,
         })
     }
 
+    async fn request_device(
+        instance: &wgpu::Instance,
+        force_fallback_adapter: bool,
+    ) -> Result<(wgpu::Adapter, wgpu::Device, wgpu::Queue), RenderingError> {
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                force_fallback_adapter,
+                ..Default::default()
+            })
+            .await
+            .map_err(|_| RenderingError::NoAdapter)?;
+
+        // Software adapters may not support mappable primary buffers, and
+        // readback only needs MAP_READ | COPY_DST which is always available
+        let (device, queue) = adapter
+            .request_device(&wgpu::DeviceDescriptor {
+                required_features: adapter.features() & wgpu::Features::MAPPABLE_PRIMARY_BUFFERS,
+                ..Default::default()
+            })
+            .await?;
+
+        Ok((adapter, device, queue))
+    }
+
     fn load_cursor_textures(
         device: &wgpu::Device,
         queue: &wgpu::Queue,
@@ -452,11 +598,15 @@ pub struct ProjectUniforms {
     pub cursor_size: f32,
     display: CompositeVideoFrameUniforms,
     camera: Option<CompositeVideoFrameUniforms>,
+    /// Uniforms for each additional camera slot, `None` where that camera is hidden
+    additional_cameras: Vec<Option<CompositeVideoFrameUniforms>>,
     interpolated_cursor: Option<InterpolatedCursorPosition>,
     pub project: ProjectConfiguration,
     pub zoom: InterpolatedZoom,
     pub resolution_base: XY<u32>,
     pub scene_mode: Option<cap_project::SceneMode>,
+    /// Where the frame is on the edited timeline, in seconds
+    pub frame_time: f32,
 }
 
 #[derive(Debug, Clone)]
@@ -479,13 +629,15 @@ impl ProjectUniforms {
     fn get_crop(options: &RenderOptions, project: &ProjectConfiguration) -> Crop {
         project.background.crop.as_ref().cloned().unwrap_or(Crop {
             position: XY { x: 0, y: 0 },
-            size: XY {
-                x: options.screen_size.x,
-                y: options.screen_size.y,
-            },
+            size: Self::get_screen_size(options, project),
         })
     }
 
+    /// Size of the screen once rotated, which is the space crops are defined in
+    pub fn get_screen_size(options: &RenderOptions, project: &ProjectConfiguration) -> XY<u32> {
+        project.oriented_size(options.screen_size)
+    }
+
     fn get_padding(options: &RenderOptions, project: &ProjectConfiguration) -> f64 {
         let crop = Self::get_crop(options, project);
 
@@ -566,6 +718,15 @@ impl ProjectUniforms {
         project: &ProjectConfiguration,
         resolution_base: XY<u32>,
     ) -> Coord<FrameSpace> {
+        Self::display_bounds(options, project, resolution_base).0
+    }
+
+    /// The display's offset and size within the output frame
+    fn display_bounds(
+        options: &RenderOptions,
+        project: &ProjectConfiguration,
+        resolution_base: XY<u32>,
+    ) -> (Coord<FrameSpace>, Coord<FrameSpace>) {
         let output_size = Self::get_output_size(options, project, resolution_base);
         let output_size = XY::new(output_size.0 as f64, output_size.1 as f64);
 
@@ -603,11 +764,19 @@ impl ProjectUniforms {
 
         let target_offset = (output_size - target_size) / 2.0;
 
-        Coord::new(if is_height_constrained {
-            XY::new(target_offset.x, padding)
+        let offset = if is_height_constrained {
+            // Only the space left beside the padding is used, so offsets don't squash the display
+            let free_width = (output_size.x - target_size.x - 2.0 * padding).max(0.0);
+            let offset_x = project.display_offset_x.clamp(-1.0, 1.0);
+            XY::new(
+                padding.min(target_offset.x) + free_width * (1.0 + offset_x) / 2.0,
+                padding,
+            )
         } else {
             XY::new(padding, target_offset.y)
-        })
+        };
+
+        (Coord::new(offset), Coord::new(target_size))
     }
 
     pub fn display_size(
@@ -615,14 +784,106 @@ impl ProjectUniforms {
         project: &ProjectConfiguration,
         resolution_base: XY<u32>,
     ) -> Coord<FrameSpace> {
-        let output_size = Self::get_output_size(options, project, resolution_base);
-        let output_size = XY::new(output_size.0 as f64, output_size.1 as f64);
+        Self::display_bounds(options, project, resolution_base).1
+    }
 
-        let display_offset = Self::display_offset(options, project, resolution_base);
+    fn camera_uniforms(
+        camera: &cap_project::Camera,
+        camera_size: XY<u32>,
+        output_size: (u32, u32),
+        zoom: &InterpolatedZoom,
+        motion_blur_amount: f32,
+    ) -> CompositeVideoFrameUniforms {
+        let output_size = [output_size.0 as f32, output_size.1 as f32];
+        let frame_size = [camera_size.x as f32, camera_size.y as f32];
+        let min_axis = output_size[0].min(output_size[1]);
+
+        // Calculate camera size based on zoom
+        let base_size = camera.size / 100.0;
+        let zoom_size = camera
+            .zoom_size
+            .unwrap_or(cap_project::Camera::default_zoom_size())
+            / 100.0;
+
+        let zoomed_size =
+            (zoom.t as f32) * zoom_size * base_size + (1.0 - zoom.t as f32) * base_size;
+
+        let aspect = frame_size[0] / frame_size[1];
+        let size = match camera.shape {
+            CameraShape::Source => {
+                if aspect >= 1.0 {
+                    [
+                        (min_axis * zoomed_size + CAMERA_PADDING) * aspect,
+                        min_axis * zoomed_size + CAMERA_PADDING,
+                    ]
+                } else {
+                    [
+                        min_axis * zoomed_size + CAMERA_PADDING,
+                        (min_axis * zoomed_size + CAMERA_PADDING) / aspect,
+                    ]
+                }
+            }
+            CameraShape::Square => [
+                min_axis * zoomed_size + CAMERA_PADDING,
+                min_axis * zoomed_size + CAMERA_PADDING,
+            ],
+        };
 
-        let end = Coord::new(output_size) - display_offset;
+        let position = {
+            let x = match &camera.position.x {
+                CameraXPosition::Left => CAMERA_PADDING,
+                CameraXPosition::Center => output_size[0] / 2.0 - (size[0]) / 2.0,
+                CameraXPosition::Right => output_size[0] - CAMERA_PADDING - size[0],
+            };
+            let y = match &camera.position.y {
+                CameraYPosition::Top => CAMERA_PADDING,
+                CameraYPosition::Bottom => output_size[1] - size[1] - CAMERA_PADDING,
+            };
 
-        end - display_offset
+            [x, y]
+        };
+
+        let target_bounds = [
+            position[0],
+            position[1],
+            position[0] + size[0],
+            position[1] + size[1],
+        ];
+
+        // Calculate camera motion blur based on zoom transition
+        let camera_motion_blur = 0.0;
+
+        CompositeVideoFrameUniforms {
+            output_size,
+            frame_size,
+            crop_bounds: match camera.shape {
+                CameraShape::Source => [0.0, 0.0, frame_size[0], frame_size[1]],
+                CameraShape::Square => [
+                    (frame_size[0] - frame_size[1]) / 2.0,
+                    0.0,
+                    frame_size[0] - (frame_size[0] - frame_size[1]) / 2.0,
+                    frame_size[1],
+                ],
+            },
+            target_bounds,
+            target_size: [
+                target_bounds[2] - target_bounds[0],
+                target_bounds[3] - target_bounds[1],
+            ],
+            rounding_px: camera.rounding / 100.0 * 0.5 * size[0].min(size[1]),
+            mirror_x: if camera.mirror { 1.0 } else { 0.0 },
+            velocity_uv: [0.0, 0.0],
+            motion_blur_amount,
+            camera_motion_blur_amount: camera_motion_blur,
+            shadow: camera.shadow,
+            shadow_size: camera.advanced_shadow.as_ref().map_or(50.0, |s| s.size),
+            shadow_opacity: camera.advanced_shadow.as_ref().map_or(18.0, |s| s.opacity),
+            shadow_blur: camera.advanced_shadow.as_ref().map_or(50.0, |s| s.blur),
+            rotation: 0.0,
+            flip_x: 0.0,
+            flip_y: 0.0,
+            _padding: [0.0; 1],
+        }
     }
 
     pub fn new(
@@ -674,13 +935,15 @@ impl ProjectUniforms {
                 }),
             )
             .as_ref()
-            .map(|i| i.position)
+            // Zooming happens within the crop, so focus on where the cursor is inside it
+            .map(|i| i.position.to_cropped_display_uv_space(options, project))
             .unwrap_or_else(|| Coord::new(XY::new(0.5, 0.5))),
         );
 
         let display = {
             let output_size = XY::new(output_size.0 as f64, output_size.1 as f64);
-            let size = [options.screen_size.x as f32, options.screen_size.y as f32];
+            let screen_size = Self::get_screen_size(options, project);
+            let size = [screen_size.x as f32, screen_size.y as f32];
 
             let crop_start = Coord::<RawDisplaySpace>::new(XY::new(
                 crop.position.x as f64,
@@ -739,7 +1002,10 @@ impl ProjectUniforms {
                     .advanced_shadow
                     .as_ref()
                     .map_or(50.0, |s| s.blur),
-                _padding: [0.0; 3],
+                rotation: project.rotation.quarter_turns() as f32,
+                flip_x: if project.flip_horizontal { 1.0 } else { 0.0 },
+                flip_y: if project.flip_vertical { 1.0 } else { 0.0 },
+                _padding: [0.0; 1],
             }
         };
 
@@ -747,118 +1013,45 @@ impl ProjectUniforms {
             .camera_size
             .filter(|_| !project.camera.hide)
             .map(|camera_size| {
-                let output_size = [output_size.0 as f32, output_size.1 as f32];
-                let frame_size = [camera_size.x as f32, camera_size.y as f32];
-                let min_axis = output_size[0].min(output_size[1]);
-
-                // Calculate camera size based on zoom
-                let base_size = project.camera.size / 100.0;
-                let zoom_size = project
-                    .camera
-                    .zoom_size
-                    .unwrap_or(cap_project::Camera::default_zoom_size())
-                    / 100.0;
-
-                let zoomed_size =
-                    (zoom.t as f32) * zoom_size * base_size + (1.0 - zoom.t as f32) * base_size;
-
-                let aspect = frame_size[0] / frame_size[1];
-                let size = match project.camera.shape {
-                    CameraShape::Source => {
-                        if aspect >= 1.0 {
-                            [
-                                (min_axis * zoomed_size + CAMERA_PADDING) * aspect,
-                                min_axis * zoomed_size + CAMERA_PADDING,
-                            ]
-                        } else {
-                            [
-                                min_axis * zoomed_size + CAMERA_PADDING,
-                                (min_axis * zoomed_size + CAMERA_PADDING) / aspect,
-                            ]
-                        }
-                    }
-                    CameraShape::Square => [
-                        min_axis * zoomed_size + CAMERA_PADDING,
-                        min_axis * zoomed_size + CAMERA_PADDING,
-                    ],
-                };
-
-                let position = {
-                    let x = match &project.camera.position.x {
-                        CameraXPosition::Left => CAMERA_PADDING,
-                        CameraXPosition::Center => output_size[0] / 2.0 - (size[0]) / 2.0,
-                        CameraXPosition::Right => output_size[0] - CAMERA_PADDING - size[0],
-                    };
-                    let y = match &project.camera.position.y {
-                        CameraYPosition::Top => CAMERA_PADDING,
-                        CameraYPosition::Bottom => output_size[1] - size[1] - CAMERA_PADDING,
-                    };
-
-                    [x, y]
-                };
-
-                let target_bounds = [
-                    position[0],
-                    position[1],
-                    position[0] + size[0],
-                    position[1] + size[1],
-                ];
-
-                // Calculate camera motion blur based on zoom transition
-                let camera_motion_blur = 0.0;
-
-                CompositeVideoFrameUniforms {
+                Self::camera_uniforms(
+                    &project.camera,
+                    camera_size,
                     output_size,
-                    frame_size,
-                    crop_bounds: match project.camera.shape {
-                        CameraShape::Source => [0.0, 0.0, frame_size[0], frame_size[1]],
-                        CameraShape::Square => [
-                            (frame_size[0] - frame_size[1]) / 2.0,
-                            0.0,
-                            frame_size[0] - (frame_size[0] - frame_size[1]) / 2.0,
-                            frame_size[1],
-                        ],
-                    },
-                    target_bounds,
-                    target_size: [
-                        target_bounds[2] - target_bounds[0],
-                        target_bounds[3] - target_bounds[1],
-                    ],
-                    rounding_px: project.camera.rounding / 100.0 * 0.5 * size[0].min(size[1]),
-                    mirror_x: if project.camera.mirror { 1.0 } else { 0.0 },
-                    velocity_uv: [0.0, 0.0],
+                    &zoom,
                     motion_blur_amount,
-                    camera_motion_blur_amount: camera_motion_blur,
-                    shadow: project.camera.shadow,
-                    shadow_size: project
-                        .camera
-                        .advanced_shadow
-                        .as_ref()
-                        .map_or(50.0, |s| s.size),
-                    shadow_opacity: project
-                        .camera
-                        .advanced_shadow
-                        .as_ref()
-                        .map_or(18.0, |s| s.opacity),
-                    shadow_blur: project
-                        .camera
-                        .advanced_shadow
-                        .as_ref()
-                        .map_or(50.0, |s| s.blur),
-                    _padding: [0.0; 3],
-                }
+                )
             });
 
+        let additional_cameras = constants
+            .additional_camera_sizes
+            .iter()
+            .enumerate()
+            .map(|(i, camera_size)| {
+                let camera = project.camera_for_slot(i + 1);
+                camera_size.filter(|_| !camera.hide).map(|camera_size| {
+                    Self::camera_uniforms(
+                        &camera,
+                        camera_size,
+                        output_size,
+                        &zoom,
+                        motion_blur_amount,
+                    )
+                })
+            })
+            .collect();
+
         Self {
             output_size,
             cursor_size: project.cursor.size as f32,
             resolution_base,
             display,
             camera,
+            additional_cameras,
             project: project.clone(),
             zoom,
             interpolated_cursor,
             scene_mode,
+            frame_time,
         }
     }
 }
@@ -866,6 +1059,8 @@ impl ProjectUniforms {
 pub struct DecodedSegmentFrames {
     pub screen_frame: DecodedFrame,
     pub camera_frame: Option<DecodedFrame>,
+    /// Frames for each additional camera slot, in slot order
+    pub additional_camera_frames: Vec<Option<DecodedFrame>>,
     pub segment_time: f32,
     pub recording_time: f32,
 }
@@ -922,9 +1117,14 @@ pub struct RendererLayers {
     pub(crate) display: DisplayLayer,
     pub(crate) cursor: CursorLayer,
     pub(crate) camera: CameraLayer,
+    pub(crate) additional_cameras: Vec<CameraLayer>,
     pub(crate) captions: CaptionsLayer,
+    pub(crate) lower_thirds: LowerThirdsLayer,
+    pub(crate) color_adjust: ColorAdjustLayer,
+    pub(crate) lut: ColorLut,
     pub(crate) camera_enabled: bool,
     pub(crate) display_enabled: bool,
+    custom_layers: Vec<(LayerPosition, Box<dyn RenderLayer>)>,
 }
 
 impl RendererLayers {
@@ -935,12 +1135,31 @@ impl RendererLayers {
             display: DisplayLayer::new(device),
             cursor: CursorLayer::new(device),
             camera: CameraLayer::new(device),
+            additional_cameras: vec![],
             captions: CaptionsLayer::new(device, queue),
+            lower_thirds: LowerThirdsLayer::new(device, queue),
+            color_adjust: ColorAdjustLayer::new(device),
+            lut: ColorLut::new(device, queue),
             camera_enabled: false,
             display_enabled: true,
+            custom_layers: vec![],
         }
     }
 
+    /// Draw `layer` into every frame at `position`, over any custom layers already there
+    pub fn with_layer(
+        mut self,
+        position: LayerPosition,
+        layer: impl RenderLayer + 'static,
+    ) -> Self {
+        self.add_layer(position, Box::new(layer));
+        self
+    }
+
+    pub fn add_layer(&mut self, position: LayerPosition, layer: Box<dyn RenderLayer>) {
+        self.custom_layers.push((position, layer));
+    }
+
     pub async fn prepare(
         &mut self,
         constants: &RenderVideoConstants,
@@ -960,6 +1179,12 @@ impl RendererLayers {
             self.background_blur.prepare(&constants.queue, uniforms);
         }
 
+        self.lut.update(
+            &constants.device,
+            &constants.queue,
+            uniforms.project.lut.as_deref(),
+        );
+
         // Determine what to render based on scene mode
         self.display_enabled = match &uniforms.scene_mode {
             Some(cap_project::SceneMode::CameraOnly) => false,
@@ -1007,6 +1232,45 @@ impl RendererLayers {
             }
         }
 
+        while self.additional_cameras.len() < constants.additional_camera_sizes.len() {
+            self.additional_cameras
+                .push(CameraLayer::new(&constants.device));
+        }
+
+        // Each additional slot is hidden by its own settings rather than the primary camera's
+        let additional_cameras_enabled = !matches!(
+            uniforms.scene_mode,
+            Some(cap_project::SceneMode::HideCamera)
+        );
+
+        for (i, layer) in self.additional_cameras.iter_mut().enumerate() {
+            let camera_frame = segment_frames
+                .additional_camera_frames
+                .get(i)
+                .and_then(Option::as_ref);
+            let camera_uniforms = uniforms.additional_cameras.get(i).copied().flatten();
+
+            layer.enabled = false;
+
+            if let (true, Some(Some(camera_size)), Some(camera_frame), Some(camera_uniforms)) = (
+                additional_cameras_enabled,
+                constants.additional_camera_sizes.get(i),
+                camera_frame,
+                camera_uniforms,
+            ) {
+                layer.prepare(
+                    &constants.device,
+                    &constants.queue,
+                    camera_uniforms,
+                    *camera_size,
+                    camera_frame,
+                );
+                layer.enabled = true;
+            }
+        }
+
+        self.color_adjust.prepare(&constants.queue, uniforms);
+
         if let Some(captions) = &uniforms.project.captions {
             self.captions.prepare(
                 uniforms,
@@ -1016,6 +1280,22 @@ impl RendererLayers {
             );
         }
 
+        self.lower_thirds.prepare(
+            uniforms,
+            segment_frames,
+            uniforms.resolution_base,
+            constants,
+        );
+
+        for (_, layer) in &mut self.custom_layers {
+            layer.prepare(
+                uniforms,
+                segment_frames,
+                uniforms.resolution_base,
+                constants,
+            );
+        }
+
         Ok(())
     }
 
@@ -1044,6 +1324,23 @@ impl RendererLayers {
             };
         }
 
+        macro_rules! render_custom_layers {
+            ($position:expr) => {
+                let mut layers = self
+                    .custom_layers
+                    .iter()
+                    .filter(|(position, _)| *position == $position)
+                    .peekable();
+
+                if layers.peek().is_some() {
+                    let mut pass = render_pass!(session.current_texture_view(), wgpu::LoadOp::Load);
+                    for (_, layer) in layers {
+                        layer.render(&mut pass);
+                    }
+                }
+            };
+        }
+
         {
             let mut pass = render_pass!(
                 session.current_texture_view(),
@@ -1060,9 +1357,11 @@ impl RendererLayers {
             session.swap_textures();
         }
 
+        render_custom_layers!(LayerPosition::AboveBackground);
+
         if self.display_enabled {
             let mut pass = render_pass!(session.current_texture_view(), wgpu::LoadOp::Load);
-            self.display.render(&mut pass);
+            self.display.render(&mut pass, &self.lut);
         }
 
         if self.display_enabled {
@@ -1070,15 +1369,39 @@ impl RendererLayers {
             self.cursor.render(&mut pass);
         }
 
+        render_custom_layers!(LayerPosition::AboveDisplay);
+
         if self.camera_enabled {
             let mut pass = render_pass!(session.current_texture_view(), wgpu::LoadOp::Load);
-            self.camera.render(&mut pass);
+            self.camera.render(&mut pass, &self.lut);
+        }
+
+        // Additional cameras are composited over the primary one in slot order
+        if self.additional_cameras.iter().any(|l| l.enabled) {
+            let mut pass = render_pass!(session.current_texture_view(), wgpu::LoadOp::Load);
+            for layer in self.additional_cameras.iter().filter(|l| l.enabled) {
+                layer.render(&mut pass, &self.lut);
+            }
+        }
+
+        render_custom_layers!(LayerPosition::AboveCamera);
+
+        // Captions and lower thirds are drawn afterwards so they keep their configured colors
+        if self.color_adjust.enabled {
+            let mut pass = render_pass!(session.other_texture_view(), wgpu::LoadOp::Load);
+            self.color_adjust
+                .render(&mut pass, device, session.current_texture_view());
+
+            session.swap_textures();
         }
 
         {
             let mut pass = render_pass!(session.current_texture_view(), wgpu::LoadOp::Load);
             self.captions.render(&mut pass);
+            self.lower_thirds.render(&mut pass);
         }
+
+        render_custom_layers!(LayerPosition::Top);
     }
 }
 
@@ -1236,12 +1559,21 @@ pub fn create_shader_render_pipeline(
     device: &wgpu::Device,
     bind_group_layout: &wgpu::BindGroupLayout,
     shader: wgpu::ShaderModuleDescriptor,
+) -> wgpu::RenderPipeline {
+    create_shader_render_pipeline_with_layouts(device, &[bind_group_layout], shader)
+}
+
+/// Like `create_shader_render_pipeline`, for shaders that use more than one bind group
+pub fn create_shader_render_pipeline_with_layouts(
+    device: &wgpu::Device,
+    bind_group_layouts: &[&wgpu::BindGroupLayout],
+    shader: wgpu::ShaderModuleDescriptor,
 ) -> wgpu::RenderPipeline {
     let shader = device.create_shader_module(shader);
 
     let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
         label: Some("Render Pipeline Layout"),
-        bind_group_layouts: &[bind_group_layout],
+        bind_group_layouts,
         push_constant_ranges: &[],
     });
 
@@ -0,0 +1,285 @@
+use std::path::{Path, PathBuf};
+
+use tracing::warn;
+
+/// LUT_3D_SIZE allowed by the .cube spec
+const MAX_LUT_SIZE: usize = 256;
+
+/// A 3D color lookup table parsed from a `.cube` file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lut3d {
+    pub size: usize,
+    /// RGB entries with red changing fastest, then green, then blue
+    pub entries: Vec<[f32; 3]>,
+}
+
+impl Lut3d {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read LUT {}: {e}", path.display()))?;
+
+        Self::parse(&text).map_err(|e| format!("Invalid LUT {}: {e}", path.display()))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut size = None;
+        let mut entries = vec![];
+
+        for (i, line) in text.lines().enumerate() {
+            let line_number = i + 1;
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut parts = line.split_whitespace();
+            let keyword = parts.next().unwrap_or_default();
+
+            match keyword {
+                "TITLE" => {}
+                "LUT_1D_SIZE" => return Err("1D LUTs are not supported".to_string()),
+                "LUT_3D_SIZE" => {
+                    let value = parts
+                        .next()
+                        .and_then(|v| v.parse::<usize>().ok())
+                        .filter(|v| (2..=MAX_LUT_SIZE).contains(v))
+                        .ok_or_else(|| {
+                            format!(
+                                "Line {line_number}: LUT_3D_SIZE must be between 2 and {MAX_LUT_SIZE}"
+                            )
+                        })?;
+                    size = Some(value);
+                }
+                "DOMAIN_MIN" | "DOMAIN_MAX" => {
+                    let expected = if keyword == "DOMAIN_MIN" { 0.0 } else { 1.0 };
+                    let values = parse_floats(parts, line_number)?;
+                    if values.iter().any(|v| *v != expected) {
+                        return Err(format!(
+                            "Line {line_number}: only the default 0-1 input domain is supported"
+                        ));
+                    }
+                }
+                _ if keyword.starts_with(|c: char| c.is_ascii_alphabetic()) => {
+                    // Unknown keywords are vendor extensions and safe to skip
+                }
+                _ => {
+                    let values = parse_floats(line.split_whitespace(), line_number)?;
+                    entries.push([values[0], values[1], values[2]]);
+                }
+            }
+        }
+
+        let size = size.ok_or_else(|| "Missing LUT_3D_SIZE".to_string())?;
+        let expected = size * size * size;
+        if entries.len() != expected {
+            return Err(format!(
+                "Expected {expected} entries for a size {size} LUT, found {}",
+                entries.len()
+            ));
+        }
+
+        Ok(Self { size, entries })
+    }
+
+    /// Entries as RGBA8 texels, clamping values outside 0-1
+    fn to_rgba8(&self) -> Vec<u8> {
+        self.entries
+            .iter()
+            .flat_map(|[r, g, b]| {
+                let to_u8 = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+                [to_u8(*r), to_u8(*g), to_u8(*b), 255]
+            })
+            .collect()
+    }
+}
+
+fn parse_floats<'a>(
+    parts: impl Iterator<Item = &'a str>,
+    line_number: usize,
+) -> Result<[f32; 3], String> {
+    let values = parts
+        .map(|v| v.parse::<f32>().ok().filter(|v| v.is_finite()))
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| format!("Line {line_number}: expected numbers"))?;
+
+    values
+        .try_into()
+        .map_err(|_| format!("Line {line_number}: expected 3 values"))
+}
+
+/// The LUT applied to the screen and camera layers. Holds a 1x1x1 placeholder when unset,
+/// which the shader treats as no LUT.
+pub struct ColorLut {
+    path: Option<PathBuf>,
+    bind_group_layout: wgpu::BindGroupLayout,
+    bind_group: wgpu::BindGroup,
+}
+
+impl ColorLut {
+    pub fn new(device: &wgpu::Device, queue: &wgpu::Queue) -> Self {
+        let bind_group_layout = Self::bind_group_layout(device);
+        let bind_group = Self::create_bind_group(device, queue, &bind_group_layout, None);
+
+        Self {
+            path: None,
+            bind_group_layout,
+            bind_group,
+        }
+    }
+
+    pub fn bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("ColorLut Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D3,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        })
+    }
+
+    /// Loads the LUT at `path` if it differs from the current one.
+    /// A LUT that fails to load is logged and rendered as if unset.
+    pub fn update(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, path: Option<&Path>) {
+        if self.path.as_deref() == path {
+            return;
+        }
+
+        self.path = path.map(Path::to_path_buf);
+
+        let lut = path.and_then(|path| Lut3d::load(path).map_err(|e| warn!("{e}")).ok());
+
+        self.bind_group =
+            Self::create_bind_group(device, queue, &self.bind_group_layout, lut.as_ref());
+    }
+
+    pub fn bind_group(&self) -> &wgpu::BindGroup {
+        &self.bind_group
+    }
+
+    fn create_bind_group(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        layout: &wgpu::BindGroupLayout,
+        lut: Option<&Lut3d>,
+    ) -> wgpu::BindGroup {
+        let (size, data) = match lut {
+            Some(lut) => (lut.size as u32, lut.to_rgba8()),
+            None => (1, vec![0, 0, 0, 255]),
+        };
+
+        let extent = wgpu::Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: size,
+        };
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("ColorLut Texture"),
+            size: extent,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D3,
+            // LUT values are already gamma encoded, so they mustn't be converted on sampling
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+
+        queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            &data,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(size * 4),
+                rows_per_image: Some(size),
+            },
+            extent,
+        );
+
+        let view = texture.create_view(&Default::default());
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("ColorLut Bind Group"),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&sampler),
+                },
+            ],
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const IDENTITY: &str = "TITLE \"Identity\"
+# comment
+LUT_3D_SIZE 2
+DOMAIN_MIN 0 0 0
+DOMAIN_MAX 1 1 1
+0 0 0
+1 0 0
+0 1 0
+1 1 0
+0 0 1
+1 0 1
+0 1 1
+1 1 1
+";
+
+    #[test]
+    fn parses_cube() {
+        let lut = Lut3d::parse(IDENTITY).unwrap();
+
+        assert_eq!(lut.size, 2);
+        assert_eq!(lut.entries.len(), 8);
+        assert_eq!(lut.entries[1], [1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn rejects_malformed_cubes() {
+        // Missing an entry
+        assert!(Lut3d::parse(IDENTITY.trim_end().rsplit_once('\n').unwrap().0).is_err());
+        // Too many values on a line
+        assert!(Lut3d::parse(&IDENTITY.replace("0 1 1\n", "0 1 1 1\n")).is_err());
+        // Not a number
+        assert!(Lut3d::parse(&IDENTITY.replace("0 1 1", "0 x 1")).is_err());
+        assert!(Lut3d::parse(&IDENTITY.replace("LUT_3D_SIZE 2", "LUT_3D_SIZE 100000")).is_err());
+        assert!(Lut3d::parse("0 0 0\n").is_err());
+    }
+}
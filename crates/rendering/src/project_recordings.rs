@@ -3,7 +3,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use cap_project::{AudioMeta, StudioRecordingMeta, VideoMeta};
+use cap_project::{AudioMeta, FrameTimestamps, StudioRecordingMeta, VideoMeta};
 use serde::Serialize;
 use specta::Type;
 
@@ -50,6 +50,21 @@ impl Video {
     pub fn fps(&self) -> u32 {
         self.fps
     }
+
+    /// Uses the captured frame timestamps for duration, as the container duration
+    /// assumes uniform frame spacing and drifts when frames were dropped
+    pub fn with_frame_timestamps(mut self, timestamps: &FrameTimestamps) -> Self {
+        if let Some(duration) = timestamps.duration(self.fps) {
+            let dropped = timestamps.dropped_frames(self.fps);
+            if dropped > 0 {
+                tracing::debug!("{dropped} frames were dropped during capture");
+            }
+
+            self.duration = duration;
+        }
+
+        self
+    }
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Type)]
@@ -113,6 +128,7 @@ impl ProjectRecordingsMeta {
                 vec![SegmentRecordings {
                     display,
                     camera,
+                    additional_cameras: vec![],
                     mic,
                     system_audio: None,
                 }]
@@ -146,7 +162,22 @@ impl ProjectRecordingsMeta {
 
                     let load_video = |meta: &VideoMeta| {
                         ensure_start_time(meta.start_time).and_then(|start_time| {
-                            Video::new(meta.path.to_path(recording_path), start_time)
+                            let video = Video::new(meta.path.to_path(recording_path), start_time)?;
+
+                            Ok(match &meta.frame_timestamps {
+                                Some(path) => {
+                                    match FrameTimestamps::load_from_file(
+                                        &path.to_path(recording_path),
+                                    ) {
+                                        Ok(timestamps) => video.with_frame_timestamps(&timestamps),
+                                        Err(e) => {
+                                            tracing::warn!("{e}");
+                                            video
+                                        }
+                                    }
+                                }
+                                None => video,
+                            })
                         })
                     };
 
@@ -157,10 +188,20 @@ impl ProjectRecordingsMeta {
                     };
 
                     Ok::<_, String>(SegmentRecordings {
-                        display: load_video(&s.display).map_err(|e| format!("video / {e}"))?,
+                        display: load_video(s.display()?).map_err(|e| format!("video / {e}"))?,
                         camera: Option::map(s.camera.as_ref(), load_video)
                             .transpose()
                             .map_err(|e| format!("camera / {e}"))?,
+                        additional_cameras: s
+                            .additional_cameras
+                            .iter()
+                            .enumerate()
+                            .map(|(i, camera)| {
+                                Option::map(camera.as_ref(), load_video)
+                                    .transpose()
+                                    .map_err(|e| format!("camera {} / {e}", i + 1))
+                            })
+                            .collect::<Result<_, _>>()?,
                         mic: Option::map(s.mic.as_ref(), load_audio)
                             .transpose()
                             .map_err(|e| format!("mic / {e}"))?,
@@ -190,6 +231,8 @@ impl ProjectRecordingsMeta {
 pub struct SegmentRecordings {
     pub display: Video,
     pub camera: Option<Video>,
+    /// Indexed by slot starting at slot 1, with `None` for slots that weren't in use
+    pub additional_cameras: Vec<Option<Video>>,
     pub mic: Option<Audio>,
     pub system_audio: Option<Audio>,
 }
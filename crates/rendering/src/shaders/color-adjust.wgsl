@@ -0,0 +1,55 @@
+struct Uniforms {
+    brightness: f32,
+    contrast: f32,
+    saturation: f32,
+    temperature: f32,
+};
+
+@group(0) @binding(0) var<uniform> u: Uniforms;
+@group(0) @binding(1) var t_frame: texture_2d<f32>;
+@group(0) @binding(2) var s_frame: sampler;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) tex_coords: vec2<f32>,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) in_vertex_index: u32) -> VertexOutput {
+    var out: VertexOutput;
+    let x = f32(i32(in_vertex_index & 1u) * 4 - 1);
+    let y = f32(i32(in_vertex_index & 2u) * 2 - 1);
+    out.tex_coords = vec2<f32>(x * 0.5 + 0.5, 1.0 - (y * 0.5 + 0.5));
+    out.clip_position = vec4<f32>(x, y, 0.0, 1.0);
+    return out;
+}
+
+@fragment
+fn fs_main(@location(0) tex_coords: vec2<f32>) -> @location(0) vec4<f32> {
+    let sampled = textureSample(t_frame, s_frame, tex_coords);
+
+    // Adjustments are made on gamma encoded values so they feel perceptually even
+    var color = linear_to_srgb(sampled.rgb);
+
+    color = color + u.brightness * 0.5;
+    color = (color - 0.5) * (1.0 + u.contrast) + 0.5;
+
+    let luma = dot(color, vec3<f32>(0.2126, 0.7152, 0.0722));
+    color = mix(vec3<f32>(luma), color, 1.0 + u.saturation);
+
+    color = color + vec3<f32>(u.temperature, 0.0, -u.temperature) * 0.1;
+
+    return vec4<f32>(srgb_to_linear(clamp(color, vec3<f32>(0.0), vec3<f32>(1.0))), sampled.a);
+}
+
+fn linear_to_srgb(c: vec3<f32>) -> vec3<f32> {
+    let low = c * 12.92;
+    let high = 1.055 * pow(c, vec3<f32>(1.0 / 2.4)) - 0.055;
+    return select(high, low, c <= vec3<f32>(0.0031308));
+}
+
+fn srgb_to_linear(c: vec3<f32>) -> vec3<f32> {
+    let low = c / 12.92;
+    let high = pow((c + 0.055) / 1.055, vec3<f32>(2.4));
+    return select(high, low, c <= vec3<f32>(0.04045));
+}
@@ -13,11 +13,16 @@ struct Uniforms {
     shadow_size: f32,
     shadow_opacity: f32,
     shadow_blur: f32,
+    rotation: f32,
+    flip_x: f32,
+    flip_y: f32,
 };
 
 @group(0) @binding(0) var<uniform> uniforms: Uniforms;
 @group(0) @binding(1) var frame_texture: texture_2d<f32>;
 @group(0) @binding(2) var frame_sampler: sampler;
+@group(1) @binding(0) var lut_texture: texture_3d<f32>;
+@group(1) @binding(1) var lut_sampler: sampler;
 
 struct VertexOutput {
     @builtin(position) position: vec4<f32>,
@@ -158,12 +163,62 @@ fn sample_texture(uv: vec2<f32>, crop_bounds_uv: vec4<f32>) -> vec4<f32> {
         }
 
         let cropped_uv = sample_uv * (crop_bounds_uv.zw - crop_bounds_uv.xy) + crop_bounds_uv.xy;
-        return vec4(textureSample(frame_texture, frame_sampler, cropped_uv).rgb, 1.0);
+        let frame_uv = unorient_uv(cropped_uv);
+        return vec4(apply_lut(textureSample(frame_texture, frame_sampler, frame_uv).rgb), 1.0);
     }
 
     return vec4(0.0);
 }
 
+// Maps a position on the rotated and flipped frame back to the texture it came from
+fn unorient_uv(uv: vec2<f32>) -> vec2<f32> {
+    var flipped = uv;
+    if uniforms.flip_x != 0.0 {
+        flipped.x = 1.0 - flipped.x;
+    }
+    if uniforms.flip_y != 0.0 {
+        flipped.y = 1.0 - flipped.y;
+    }
+
+    let turns = u32(uniforms.rotation + 0.5) % 4u;
+    if turns == 1u {
+        return vec2<f32>(flipped.y, 1.0 - flipped.x);
+    } else if turns == 2u {
+        return vec2<f32>(1.0 - flipped.x, 1.0 - flipped.y);
+    } else if turns == 3u {
+        return vec2<f32>(1.0 - flipped.y, flipped.x);
+    }
+
+    return flipped;
+}
+
+// A 1x1x1 LUT is the placeholder for no LUT
+fn apply_lut(color: vec3<f32>) -> vec3<f32> {
+    let size = f32(textureDimensions(lut_texture).x);
+    if size < 2.0 {
+        return color;
+    }
+
+    // .cube LUTs map gamma encoded values, while the frame is sampled as linear
+    let encoded = linear_to_srgb(clamp(color, vec3<f32>(0.0), vec3<f32>(1.0)));
+    // Sample at texel centers so 0 and 1 land on the first and last entries
+    let uvw = encoded * ((size - 1.0) / size) + 0.5 / size;
+    let mapped = textureSampleLevel(lut_texture, lut_sampler, uvw, 0.0).rgb;
+    return srgb_to_linear(mapped);
+}
+
+fn linear_to_srgb(c: vec3<f32>) -> vec3<f32> {
+    let low = c * 12.92;
+    let high = 1.055 * pow(c, vec3<f32>(1.0 / 2.4)) - 0.055;
+    return select(high, low, c <= vec3<f32>(0.0031308));
+}
+
+fn srgb_to_linear(c: vec3<f32>) -> vec3<f32> {
+    let low = c / 12.92;
+    let high = pow((c + 0.055) / 1.055, vec3<f32>(2.4));
+    return select(high, low, c <= vec3<f32>(0.04045));
+}
+
 fn apply_rounded_corners(current_color: vec4<f32>, target_uv: vec2<f32>) -> vec4<f32> {
     let target_coord = abs(target_uv * uniforms.target_size - uniforms.target_size / 2.0);
     let rounding_point = uniforms.target_size / 2.0 - uniforms.rounding_px;
@@ -0,0 +1,284 @@
+use std::io::Cursor;
+
+use cap_project::{CaptionSettings, XY};
+use futures_intrusive::channel::shared::oneshot_channel;
+use glyphon::{
+    cosmic_text::Align, Attrs, Buffer, Cache, Color, Family, FontSystem, Metrics, Resolution,
+    Shaping, Style, SwashCache, TextArea, TextAtlas, TextBounds, TextRenderer, Viewport, Weight,
+};
+use wgpu::util::DeviceExt;
+
+use crate::{
+    frame_pipeline::padded_bytes_per_row,
+    layers::{background_quad_pipeline, load_bundled_fonts, wrapped_line_count, QuadVertex},
+    parse_color_component, srgb_to_linear, RenderVideoConstants, RenderingError,
+};
+
+/// Subtitle size as a fraction of the title's
+const SUBTITLE_SCALE: f32 = 0.6;
+
+/// Widest the text may get before wrapping, as a fraction of the card's width
+const MAX_TEXT_WIDTH: f32 = 0.8;
+
+/// Render `text`, and `subtitle` beneath it, centered on a `background` colored card,
+/// eg. for intros and outros. `style` supplies the font, color, size and bold/italic
+/// options like it does for captions, with `style.size` being the title's size on a 1080p card.
+/// Returns the card as PNG bytes.
+pub async fn generate_title_card(
+    text: &str,
+    subtitle: Option<&str>,
+    size: XY<u32>,
+    background: &str,
+    style: &CaptionSettings,
+) -> Result<Vec<u8>, RenderingError> {
+    if size.x == 0 || size.y == 0 {
+        return Err(RenderingError::TitleCardFailed("Invalid size".to_string()));
+    }
+
+    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+    let (_adapter, device, queue) =
+        match RenderVideoConstants::request_device(&instance, false).await {
+            Ok(v) => v,
+            Err(_) => RenderVideoConstants::request_device(&instance, true).await?,
+        };
+
+    let (width, height) = (size.x as f32, size.y as f32);
+    let font_size = style.size as f32 * (height / 1080.0);
+    let subtitle_size = font_size * SUBTITLE_SCALE;
+
+    let family = match style.font.as_str() {
+        "System Serif" => Family::Serif,
+        "System Monospace" => Family::Monospace,
+        _ => Family::SansSerif,
+    };
+    let mut attrs = Attrs::new().family(family).color(hex_color(&style.color));
+    if style.bold {
+        attrs = attrs.weight(Weight::BOLD);
+    }
+    if style.italic {
+        attrs = attrs.style(Style::Italic);
+    }
+
+    let mut font_system = FontSystem::new();
+    load_bundled_fonts(&mut font_system);
+
+    let mut layout_text = |text: &str, size: f32, attrs: &Attrs| {
+        let mut buffer = Buffer::new(&mut font_system, Metrics::new(size, size * 1.2));
+        buffer.set_size(&mut font_system, Some(width * MAX_TEXT_WIDTH), None);
+        buffer.set_wrap(&mut font_system, glyphon::Wrap::Word);
+        buffer.set_text(&mut font_system, text, attrs, Shaping::Advanced);
+        for line in buffer.lines.iter_mut() {
+            line.set_align(Some(Align::Center));
+        }
+
+        let lines = wrapped_line_count(&mut buffer, &mut font_system);
+        (buffer, size * 1.2 * lines as f32)
+    };
+
+    let (text_buffer, text_height) = layout_text(text, font_size, &attrs);
+    // Subtitles are never bold, so they read as secondary to the title
+    let subtitle = subtitle
+        .filter(|subtitle| !subtitle.is_empty())
+        .map(|subtitle| {
+            layout_text(
+                subtitle,
+                subtitle_size,
+                &attrs.clone().weight(Weight::NORMAL),
+            )
+        });
+
+    let gap = subtitle.as_ref().map_or(0.0, |_| subtitle_size * 0.5);
+    let block_height = text_height + gap + subtitle.as_ref().map_or(0.0, |(_, h)| *h);
+    let text_left = width * (1.0 - MAX_TEXT_WIDTH) / 2.0;
+    let text_top = ((height - block_height) / 2.0).max(0.0);
+
+    let bounds = TextBounds {
+        left: 0,
+        top: 0,
+        right: size.x as i32,
+        bottom: size.y as i32,
+    };
+    let mut text_areas = vec![TextArea {
+        buffer: &text_buffer,
+        left: text_left,
+        top: text_top,
+        scale: 1.0,
+        bounds,
+        default_color: hex_color(&style.color),
+        custom_glyphs: &[],
+    }];
+    if let Some((subtitle_buffer, _)) = &subtitle {
+        text_areas.push(TextArea {
+            buffer: subtitle_buffer,
+            left: text_left,
+            top: text_top + text_height + gap,
+            scale: 1.0,
+            bounds,
+            default_color: hex_color(&style.color),
+            custom_glyphs: &[],
+        });
+    }
+
+    let cache = Cache::new(&device);
+    let mut viewport = Viewport::new(&device, &cache);
+    viewport.update(
+        &queue,
+        Resolution {
+            width: size.x,
+            height: size.y,
+        },
+    );
+    let mut text_atlas =
+        TextAtlas::new(&device, &queue, &cache, wgpu::TextureFormat::Rgba8UnormSrgb);
+    let mut text_renderer = TextRenderer::new(
+        &mut text_atlas,
+        &device,
+        wgpu::MultisampleState::default(),
+        None,
+    );
+    text_renderer
+        .prepare(
+            &device,
+            &queue,
+            &mut font_system,
+            &mut text_atlas,
+            &viewport,
+            text_areas,
+            &mut SwashCache::new(),
+        )
+        .map_err(|e| RenderingError::TitleCardFailed(e.to_string()))?;
+
+    // The background is a quad over the whole card, drawn like the caption background.
+    // It's converted to linear like project background colors, so it comes out as given.
+    let background_component =
+        |index| srgb_to_linear((parse_color_component(background, index) * 255.0) as u16);
+    let background_color = [
+        background_component(0),
+        background_component(1),
+        background_component(2),
+        1.0,
+    ];
+    let vertices =
+        [[-1.0, 1.0], [1.0, 1.0], [1.0, -1.0], [-1.0, -1.0]].map(|position| QuadVertex {
+            position,
+            color: background_color,
+        });
+    let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("Title Card Background Vertex Buffer"),
+        contents: bytemuck::cast_slice(&vertices),
+        usage: wgpu::BufferUsages::VERTEX,
+    });
+    let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("Title Card Background Index Buffer"),
+        contents: bytemuck::cast_slice(&[0u16, 1, 2, 2, 3, 0]),
+        usage: wgpu::BufferUsages::INDEX,
+    });
+    let background_pipeline = background_quad_pipeline(&device);
+
+    let texture_size = wgpu::Extent3d {
+        width: size.x,
+        height: size.y,
+        depth_or_array_layers: 1,
+    };
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Title Card Texture"),
+        size: texture_size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8UnormSrgb,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+    let padded_bytes_per_row = padded_bytes_per_row((size.x, size.y));
+    let output_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Title Card Output Buffer"),
+        size: (padded_bytes_per_row * size.y) as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Title Card Encoder"),
+    });
+
+    {
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Title Card Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        pass.set_pipeline(&background_pipeline);
+        pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+        pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+        pass.draw_indexed(0..6, 0, 0..1);
+
+        text_renderer
+            .render(&text_atlas, &viewport, &mut pass)
+            .map_err(|e| RenderingError::TitleCardFailed(e.to_string()))?;
+    }
+
+    encoder.copy_texture_to_buffer(
+        wgpu::TexelCopyTextureInfo {
+            texture: &texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        wgpu::TexelCopyBufferInfo {
+            buffer: &output_buffer,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_bytes_per_row),
+                rows_per_image: Some(size.y),
+            },
+        },
+        texture_size,
+    );
+    queue.submit(std::iter::once(encoder.finish()));
+
+    let buffer_slice = output_buffer.slice(..);
+    let (tx, rx) = oneshot_channel();
+    buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
+        tx.send(result).ok();
+    });
+    device.poll(wgpu::PollType::Wait);
+
+    rx.receive()
+        .await
+        .ok_or(RenderingError::BufferMapWaitingFailed)??;
+
+    let rgba = buffer_slice
+        .get_mapped_range()
+        .chunks(padded_bytes_per_row as usize)
+        .flat_map(|row| row[..size.x as usize * 4].iter().copied())
+        .collect::<Vec<_>>();
+    output_buffer.unmap();
+
+    let image = image::RgbaImage::from_raw(size.x, size.y, rgba)
+        .ok_or_else(|| RenderingError::TitleCardFailed("Invalid size".to_string()))?;
+
+    let mut png = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+        .map_err(|e| RenderingError::TitleCardFailed(e.to_string()))?;
+
+    Ok(png)
+}
+
+fn hex_color(hex: &str) -> Color {
+    let component = |index| (parse_color_component(hex, index) * 255.0) as u8;
+    Color::rgb(component(0), component(1), component(2))
+}
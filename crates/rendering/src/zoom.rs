@@ -1,6 +1,6 @@
 use cap_project::{ZoomSegment, XY};
 
-use crate::{Coord, RawDisplayUVSpace};
+use crate::{Coord, CroppedDisplayUVSpace};
 
 pub const ZOOM_DURATION: f64 = 1.0;
 // Added constant for cursor smoothing
@@ -54,7 +54,10 @@ pub struct SegmentBounds {
 }
 
 impl SegmentBounds {
-    fn from_segment(segment: &ZoomSegment, interpolated_cursor: Coord<RawDisplayUVSpace>) -> Self {
+    fn from_segment(
+        segment: &ZoomSegment,
+        interpolated_cursor: Coord<CroppedDisplayUVSpace>,
+    ) -> Self {
         let position = match segment.mode {
             cap_project::ZoomMode::Auto => (interpolated_cursor.x, interpolated_cursor.y),
             cap_project::ZoomMode::Manual { x, y } => (x as f64, y as f64),
@@ -92,7 +95,7 @@ pub struct InterpolatedZoom {
 }
 
 impl InterpolatedZoom {
-    pub fn new(cursor: SegmentsCursor, interpolated_cursor: Coord<RawDisplayUVSpace>) -> Self {
+    pub fn new(cursor: SegmentsCursor, interpolated_cursor: Coord<CroppedDisplayUVSpace>) -> Self {
         let ease_in = bezier_easing::bezier_easing(0.1, 0.0, 0.3, 1.0).unwrap();
         let ease_out = bezier_easing::bezier_easing(0.5, 0.0, 0.5, 1.0).unwrap();
 
@@ -106,7 +109,7 @@ impl InterpolatedZoom {
 
     pub(self) fn new_with_easing(
         cursor: SegmentsCursor,
-        interpolated_cursor: Coord<RawDisplayUVSpace>,
+        interpolated_cursor: Coord<CroppedDisplayUVSpace>,
         ease_in: impl Fn(f32) -> f32,
         ease_out: impl Fn(f32) -> f32,
     ) -> InterpolatedZoom {
@@ -19,6 +19,18 @@ const arch =
   process.env.RUST_TARGET_TRIPLE?.split("-")[0] ??
   (process.arch === "arm64" ? "aarch64" : "x86_64");
 
+// Caption fallbacks for scripts system fonts often lack, bundled as Tauri resources
+const CAPTION_FONTS_DIR = path.join(
+  __root,
+  "apps/desktop/src-tauri/assets/fonts"
+);
+const CAPTION_FONTS = {
+  "NotoSansCJK-Regular.ttc":
+    "https://github.com/notofonts/noto-cjk/raw/Sans2.004/Sans/OTC/NotoSansCJK-Regular.ttc",
+  "NotoNaskhArabic-Regular.ttf":
+    "https://github.com/notofonts/notofonts.github.io/raw/main/fonts/NotoNaskhArabic/hinted/ttf/NotoNaskhArabic-Regular.ttf",
+};
+
 const BASE_CARGO_TOML = `[env]
 FFMPEG_DIR = { relative = true, force = true, value = "target/native-deps" }
 `;
@@ -162,6 +174,8 @@ async function main() {
     )}"\n`;
   }
 
+  await downloadCaptionFonts();
+
   await fs.mkdir(path.join(__root, ".cargo"), { recursive: true });
   await fs.writeFile(
     path.join(__root, ".cargo/config.toml"),
@@ -171,6 +185,24 @@ async function main() {
 
 main();
 
+async function downloadCaptionFonts() {
+  await fs.mkdir(CAPTION_FONTS_DIR, { recursive: true });
+
+  for (const [name, url] of Object.entries(CAPTION_FONTS)) {
+    const fontPath = path.join(CAPTION_FONTS_DIR, name);
+    if (await fileExists(fontPath)) {
+      console.log(`Using cached ${name}`);
+      continue;
+    }
+
+    const response = await fetch(url);
+    if (!response.ok)
+      throw new Error(`Failed to download ${name}: ${response.status}`);
+    await fs.writeFile(fontPath, Buffer.from(await response.arrayBuffer()));
+    console.log(`Downloaded ${name}`);
+  }
+}
+
 async function trimMacOSFramework(frameworkDir) {
   const headersDir = path.join(frameworkDir, "Headers");
   const librariesDir = path.join(frameworkDir, "Libraries");